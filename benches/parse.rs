@@ -0,0 +1,115 @@
+//! Parsing full-size result pages, for both layouts.
+//!
+//! Run with `cargo bench --bench parse`.
+//!
+//! Most of the time goes to building the document with html5ever; extracting
+//! the fields takes about a tenth of it. Trimming text as it is collected
+//! and reserving the entries up front cut the allocations made while
+//! extracting from `take_full.html` from 977 to 900, out of about 8,200 for
+//! the whole parse. Timings moved within run-to-run noise.
+//!
+//! `first_definition` compares collecting all 116 definitions of
+//! `take_full.html` with stopping after the first, as
+//! `YdtClient::translate_first` does. Both take about 5 to 7 ms here and
+//! the difference stays within noise, as building the document dominates.
+//!
+//! `sections` takes the phonetics, definitions and Collins examples of
+//! `take_full.html`. Parsing the page once per section (`parse_each`, 13 to
+//! 15 ms here) takes two to three times as long as asking one `ParsedPage`
+//! for all three (`parsed_page`, 4 to 6 ms), which is on par with the full
+//! parse with Collins senses (`one_shot`, about 7 ms).
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const TAKE_HTML: &str = include_str!("../tests/fixtures/take_full.html");
+const XUEXI_HTML: &str = include_str!("../tests/fixtures/xuexi_full.html");
+
+fn parse_translation_from_html(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_translation_from_html");
+    group.bench_function("english_chinese", |b| {
+        b.iter(|| ydt::parse_translation_from_html(black_box("take"), black_box(TAKE_HTML)))
+    });
+    group.bench_function("chinese_english", |b| {
+        b.iter(|| ydt::parse_translation_from_html(black_box("学习"), black_box(XUEXI_HTML)))
+    });
+    group.finish();
+}
+
+fn first_definition(c: &mut Criterion) {
+    let selectors = ydt::SelectorConfig::default().compile().unwrap();
+    let first = ydt::TranslationOptions {
+        max_definitions: Some(1),
+        ..ydt::TranslationOptions::default()
+    };
+    let mut group = c.benchmark_group("first_definition");
+    group.bench_function("all_definitions", |b| {
+        b.iter(|| {
+            ydt::parse_translation_with_selectors(
+                black_box("take"),
+                black_box(TAKE_HTML),
+                &selectors,
+            )
+        })
+    });
+    group.bench_function("first_only", |b| {
+        b.iter(|| {
+            ydt::parse_translation_with_options(
+                black_box("take"),
+                black_box(TAKE_HTML),
+                &selectors,
+                &first,
+            )
+        })
+    });
+    group.finish();
+}
+
+fn sections(c: &mut Criterion) {
+    let selectors = ydt::SelectorConfig::default().compile().unwrap();
+    let collins = ydt::TranslationOptions {
+        include_collins: Some(true),
+        ..ydt::TranslationOptions::default()
+    };
+    let mut group = c.benchmark_group("sections");
+    group.bench_function("one_shot", |b| {
+        b.iter(|| {
+            ydt::parse_translation_with_options(
+                black_box("take"),
+                black_box(TAKE_HTML),
+                &selectors,
+                &collins,
+            )
+        })
+    });
+    group.bench_function("parse_each", |b| {
+        b.iter(|| {
+            let html = black_box(TAKE_HTML);
+            (
+                ydt::parse_phonetics(html),
+                ydt::parse_definitions(html),
+                ydt::ParsedPage::parse(html).examples().len(),
+            )
+        })
+    });
+    group.bench_function("parsed_page", |b| {
+        b.iter(|| {
+            let page = ydt::ParsedPage::parse(black_box(TAKE_HTML));
+            (
+                page.phonetics().len(),
+                page.definitions().len(),
+                page.examples().len(),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    parse_translation_from_html,
+    first_definition,
+    sections
+);
+criterion_main!(benches);
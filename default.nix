@@ -9,6 +9,7 @@ pkgs.rustPlatform.buildRustPackage {
   cargoLock = {
     lockFile = ./Cargo.lock;
   };
+  nativeBuildInputs = [ pkgs.installShellFiles ];
   outputs = [
     "out"
     "doc"
@@ -29,6 +30,11 @@ pkgs.rustPlatform.buildRustPackage {
     fi
     mkdir -p "$doc/share/doc/$pname"
     cp -r "$docdir/." "$doc/share/doc/$pname/"
+
+    installShellCompletion --cmd ydt \
+      --bash <($out/bin/ydt completions bash) \
+      --fish <($out/bin/ydt completions fish) \
+      --zsh <($out/bin/ydt completions zsh)
   '';
 
   meta = with pkgs.lib; {
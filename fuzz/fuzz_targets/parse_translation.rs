@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary bytes, decoded the way response bodies are, through the parsers of
+// both layouts and of the mobile site, and through rendering.
+fuzz_target!(|data: &[u8]| {
+    let html = String::from_utf8_lossy(data);
+    for word in ["hello", "你好"] {
+        let _ = ydt::parse_translation_from_html(word, &html);
+        let _ = ydt::parse_mobile_html(word, &html);
+    }
+});
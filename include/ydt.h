@@ -0,0 +1,33 @@
+#ifndef YDT_H
+#define YDT_H
+
+/* Generated by cbindgen from src/ffi.rs; regenerate with `just ffi`. */
+
+// Look `word` up and return the translation as a JSON object, such as
+// `{"word":"hello","phonetics":[{"label":"英","text":"/həˈləʊ/"}],"entries":[{"pos":"int.","meaning":"你好"}]}`.
+//
+// The client is configured from the `YDT_*` environment variables, as with
+// [`YdtClient::from_env`]. Returns NULL on failure; see [`ydt_last_error`].
+//
+// # Safety
+//
+// `word` must be NULL or point to a NUL-terminated string valid for reads.
+char *ydt_translate(const char *word);
+
+// Details of the last failed call on this thread, as a JSON object with the
+// fields of [`ErrorReport`](crate::ErrorReport), or NULL when the last call
+// succeeded.
+//
+// Besides the kinds of [`YdtError`], `kind` is `invalid_argument` for bad
+// input and `panic` for a caught panic. The string must be released with
+// [`ydt_free_string`].
+char *ydt_last_error(void);
+
+// Release a string returned by `ydt`. NULL is ignored.
+//
+// # Safety
+//
+// `s` must be NULL or a string returned by `ydt` that was not released yet.
+void ydt_free_string(char *s);
+
+#endif  /* YDT_H */
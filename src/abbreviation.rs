@@ -0,0 +1,36 @@
+//! The abbreviation block of result pages, listing what an all-caps query such
+//! as `NASA` stands for.
+
+use scraper::Html;
+
+use crate::first_text;
+use crate::selectors::Selectors;
+use crate::translation::{Entry, EntrySource};
+
+/// Parse at most `max` expansions into `abbr.` entries, each meaning the
+/// spelled-out words followed by their Chinese gloss.
+pub(crate) fn parse_abbreviations(
+    document: &Html,
+    selectors: &Selectors,
+    max: usize,
+) -> Vec<Entry> {
+    document
+        .select(&selectors.abbreviation)
+        .filter_map(|expansion| {
+            let full = first_text(expansion, &selectors.abbreviation_full);
+            let gloss = first_text(expansion, &selectors.abbreviation_gloss);
+            let meaning = match (full.is_empty(), gloss.is_empty()) {
+                (true, true) => return None,
+                (false, false) => format!("{full} {gloss}"),
+                (false, true) => full,
+                (true, false) => gloss,
+            };
+            Some(Entry {
+                pos: "abbr.".to_string(),
+                meaning,
+                source: EntrySource::Concise,
+            })
+        })
+        .take(max)
+        .collect()
+}
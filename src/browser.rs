@@ -0,0 +1,52 @@
+//! Opening result pages in the web browser, for `ydt open`.
+//!
+//! Kept apart from `main.rs`, with the browser behind [`Launcher`], so that
+//! `tests/open.rs` can check what would be opened without starting one.
+
+use url::Url;
+use ydt::{YdtClient, YdtError};
+
+/// Opens URLs.
+pub trait Launcher {
+    fn launch(&mut self, url: &Url) -> Result<(), String>;
+}
+
+/// The system's default browser.
+#[cfg(feature = "open")]
+pub struct System;
+
+#[cfg(feature = "open")]
+impl Launcher for System {
+    fn launch(&mut self, url: &Url) -> Result<(), String> {
+        open::that_detached(url.as_str()).map_err(|err| format!("Failed to open {url}: {err}"))
+    }
+}
+
+#[cfg(not(feature = "open"))]
+pub const UNSUPPORTED: &str = "`ydt open` needs ydt built with the `open` feature";
+
+/// Why a result page was not opened.
+#[derive(Debug)]
+pub enum OpenError {
+    Lookup(YdtError),
+    /// The word was found elsewhere than on Youdao's pages, e.g. in the local
+    /// dictionary.
+    NoPage,
+    Launch(String),
+}
+
+/// Look `word` up and open the page its translation was read from, the
+/// [`source_url`](ydt::Translation::source_url), returning it.
+///
+/// Looking the word up first, rather than composing the URL, links the page
+/// of the corrected word with auto-correction and the page redirected to.
+pub fn open_word(
+    client: &YdtClient,
+    word: &str,
+    launcher: &mut impl Launcher,
+) -> Result<Url, OpenError> {
+    let translation = client.translate(word).map_err(OpenError::Lookup)?;
+    let url = translation.source_url.ok_or(OpenError::NoPage)?;
+    launcher.launch(&url).map_err(OpenError::Launch)?;
+    Ok(url)
+}
@@ -0,0 +1,69 @@
+//! What this build of the crate is: its version, features and defaults.
+
+use crate::query::DEFAULT_BASE_URL;
+
+/// User agent sent to Youdao unless configured otherwise.
+pub(crate) const PROJECT_USER_AGENT: &str = concat!(
+    "ydt/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/yushengyangchem/ydt)"
+);
+
+/// The crate's features, in the order of `Cargo.toml`, and whether each is
+/// enabled in this build.
+const FEATURES: [(&str, bool); 10] = [
+    ("http", cfg!(feature = "http")),
+    ("rustls-tls", cfg!(feature = "rustls-tls")),
+    ("native-tls", cfg!(feature = "native-tls")),
+    ("clipboard", cfg!(feature = "clipboard")),
+    ("open", cfg!(feature = "open")),
+    ("ffi", cfg!(feature = "ffi")),
+    ("server", cfg!(feature = "server")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("serde", cfg!(feature = "serde")),
+    ("test-util", cfg!(feature = "test-util")),
+];
+
+/// The version of the crate, the features it was built with and the
+/// defaults it uses, returned by [`build_info`].
+///
+/// With the `serde` feature it implements `Serialize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct BuildInfo {
+    /// Version of the crate, e.g. `0.5.0`.
+    pub version: &'static str,
+    /// Result page endpoint used unless configured otherwise,
+    /// [`DEFAULT_BASE_URL`].
+    pub default_base_url: &'static str,
+    /// Enabled features, in the order of `Cargo.toml`, e.g. `["http",
+    /// "rustls-tls"]`.
+    pub features: Vec<&'static str>,
+    /// User agent sent unless configured otherwise, `ydt/<version>`.
+    pub default_user_agent: &'static str,
+}
+
+/// Describe this build of the crate, e.g. for logging which version a
+/// service runs.
+///
+/// # Examples
+///
+/// ```
+/// let info = ydt::build_info();
+/// assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+/// assert_eq!(info.default_base_url, "https://www.youdao.com/result");
+/// assert!(info.default_user_agent.starts_with("ydt/"));
+/// ```
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        default_base_url: DEFAULT_BASE_URL,
+        features: FEATURES
+            .into_iter()
+            .filter(|&(_, enabled)| enabled)
+            .map(|(name, _)| name)
+            .collect(),
+        default_user_agent: PROJECT_USER_AGENT,
+    }
+}
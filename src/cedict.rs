@@ -0,0 +1,244 @@
+//! A local [CC-CEDICT](https://cc-cedict.org/) Chinese-English dictionary, for
+//! offline use and as a last resort when Youdao has nothing.
+//!
+//! ```
+//! use ydt::cedict::Cedict;
+//! use ydt::TranslationSource;
+//!
+//! let dictionary = Cedict::parse("學習 学习 [xue2 xi2] /to learn/to study/\n");
+//! let translation = dictionary.lookup("学习", "en").unwrap();
+//! assert_eq!(translation.phonetics[0].text, "[xue2 xi2]");
+//! assert_eq!(translation.entries[1].meaning, "to study");
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{
+    contains_cjk_ideograph, Direction, Entry, EntrySource, Layout, Phonetic, Translation,
+    TranslationSource, YdtError,
+};
+
+/// One line of a CC-CEDICT file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CedictEntry {
+    /// Headword in traditional characters.
+    pub traditional: String,
+    /// Headword in simplified characters.
+    pub simplified: String,
+    /// Pinyin with tone numbers, such as `xue2 xi2`.
+    pub pinyin: String,
+    /// English definitions.
+    pub glosses: Vec<String>,
+}
+
+/// Parse a dictionary line of the form `傳統 传统 [chuan2 tong3] /tradition/convention/`.
+///
+/// Returns `None` when the line is not an entry, such as a comment or a
+/// malformed line.
+///
+/// # Examples
+///
+/// ```
+/// let entry = ydt::cedict::parse_line("學習 学习 [xue2 xi2] /to learn/to study/").unwrap();
+/// assert_eq!(entry.traditional, "學習");
+/// assert_eq!(entry.glosses, ["to learn", "to study"]);
+/// assert!(ydt::cedict::parse_line("# CC-CEDICT").is_none());
+/// ```
+pub fn parse_line(line: &str) -> Option<CedictEntry> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    let (traditional, rest) = line.split_once(' ')?;
+    let (simplified, rest) = rest.trim_start().split_once(' ')?;
+    let (pinyin, rest) = rest.trim_start().strip_prefix('[')?.split_once(']')?;
+    let glosses: Vec<String> = rest
+        .trim()
+        .strip_prefix('/')?
+        .strip_suffix('/')?
+        .split('/')
+        .map(str::trim)
+        .filter(|gloss| !gloss.is_empty())
+        .map(str::to_string)
+        .collect();
+    if glosses.is_empty() || pinyin.trim().is_empty() {
+        return None;
+    }
+    Some(CedictEntry {
+        traditional: traditional.to_string(),
+        simplified: simplified.to_string(),
+        pinyin: pinyin.trim().to_string(),
+        glosses,
+    })
+}
+
+/// A CC-CEDICT dictionary held in memory, indexed by both simplified and
+/// traditional headwords.
+///
+/// As a [`TranslationSource`] it only answers Chinese words; see
+/// [`YdtClientBuilder::cedict_path`](crate::YdtClientBuilder::cedict_path).
+#[derive(Debug, Clone, Default)]
+pub struct Cedict {
+    entries: Vec<CedictEntry>,
+    index: HashMap<String, Vec<usize>>,
+    skipped: usize,
+}
+
+impl Cedict {
+    /// Read and parse the dictionary at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::ReadDictionary`] when the file cannot be read.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, YdtError> {
+        Self::read(path.as_ref()).map_err(YdtError::ReadDictionary)
+    }
+
+    fn read(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)
+            .map(|text| Self::parse(&text))
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))
+    }
+
+    /// Parse the contents of a dictionary file.
+    ///
+    /// Comments and blank lines are ignored; malformed lines are skipped and
+    /// counted in [`skipped`](Self::skipped).
+    pub fn parse(text: &str) -> Self {
+        let mut dictionary = Self::default();
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let Some(entry) = parse_line(trimmed) else {
+                log::debug!("skipping malformed CC-CEDICT line {line:?}");
+                dictionary.skipped += 1;
+                continue;
+            };
+            let id = dictionary.entries.len();
+            dictionary
+                .index
+                .entry(entry.simplified.clone())
+                .or_default()
+                .push(id);
+            if entry.traditional != entry.simplified {
+                dictionary
+                    .index
+                    .entry(entry.traditional.clone())
+                    .or_default()
+                    .push(id);
+            }
+            dictionary.entries.push(entry);
+        }
+        if dictionary.skipped > 0 {
+            log::warn!("skipped {} malformed CC-CEDICT lines", dictionary.skipped);
+        }
+        dictionary
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the dictionary has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of malformed lines skipped while parsing.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Entries whose simplified or traditional headword is `word`, in file
+    /// order.
+    pub fn get(&self, word: &str) -> impl Iterator<Item = &CedictEntry> {
+        self.index
+            .get(word)
+            .into_iter()
+            .flatten()
+            .map(|&id| &self.entries[id])
+    }
+}
+
+impl TranslationSource for Cedict {
+    fn name(&self) -> &str {
+        "cedict"
+    }
+
+    /// Look up a Chinese `word`; other words have no results. Each reading
+    /// becomes a [`Phonetic`] labelled `拼音`, and each gloss an [`Entry`].
+    fn lookup(&self, word: &str, _lang: &str) -> Result<Translation, YdtError> {
+        let mut translation = Translation {
+            word: word.to_string(),
+            ..Translation::default()
+        };
+        if !contains_cjk_ideograph(word) {
+            return Ok(translation);
+        }
+        for entry in self.get(word) {
+            let text = format!("[{}]", entry.pinyin);
+            if !translation
+                .phonetics
+                .iter()
+                .any(|phonetic| phonetic.text == text)
+            {
+                translation.phonetics.push(Phonetic {
+                    label: "拼音".to_string(),
+                    text,
+                });
+            }
+            translation
+                .entries
+                .extend(entry.glosses.iter().map(|gloss| Entry {
+                    pos: String::new(),
+                    meaning: gloss.clone(),
+                    source: EntrySource::Cedict,
+                }));
+        }
+        if !translation.is_empty() {
+            translation.layout = Some(Layout::ChineseEnglish);
+            translation.direction = Some(Direction::ZhToEn);
+        }
+        Ok(translation)
+    }
+}
+
+#[cfg(feature = "http")]
+/// The dictionary at `path` as a source, or one failing every lookup with the
+/// error from reading it.
+pub(crate) fn load_source(path: &Path) -> Box<dyn TranslationSource> {
+    match Cedict::read(path) {
+        Ok(dictionary) => Box::new(dictionary),
+        Err(err) => Box::new(Unreadable {
+            kind: err.kind(),
+            message: err.to_string(),
+        }),
+    }
+}
+
+#[cfg(feature = "http")]
+/// A dictionary that could not be read.
+struct Unreadable {
+    kind: io::ErrorKind,
+    message: String,
+}
+
+#[cfg(feature = "http")]
+impl TranslationSource for Unreadable {
+    fn name(&self) -> &str {
+        "cedict"
+    }
+
+    fn lookup(&self, _word: &str, _lang: &str) -> Result<Translation, YdtError> {
+        Err(YdtError::ReadDictionary(io::Error::new(
+            self.kind,
+            self.message.clone(),
+        )))
+    }
+}
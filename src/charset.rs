@@ -0,0 +1,121 @@
+//! Decoding response bodies whose charset may be missing or wrong.
+
+use std::fmt;
+
+use encoding_rs::{Encoding, UTF_8};
+
+/// Number of leading bytes searched for a `<meta charset>` declaration, as in
+/// the HTML encoding sniffing algorithm.
+const META_SNIFF_LEN: usize = 1024;
+
+/// How the charset of a response body was determined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CharsetSource {
+    /// The `charset` parameter of the `Content-Type` header.
+    Header,
+    /// A `<meta charset>` or `<meta http-equiv="Content-Type">` declaration near
+    /// the start of the page.
+    Meta,
+    /// No usable declaration; the body was valid UTF-8.
+    #[default]
+    Utf8,
+    /// Nothing decoded cleanly, so invalid UTF-8 was replaced with `U+FFFD`.
+    Lossy,
+}
+
+impl fmt::Display for CharsetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Header => f.write_str("header"),
+            Self::Meta => f.write_str("meta"),
+            Self::Utf8 => f.write_str("utf-8"),
+            Self::Lossy => f.write_str("lossy"),
+        }
+    }
+}
+
+pub(crate) struct Decoded {
+    pub(crate) text: String,
+    /// Name of the encoding used, such as `UTF-8` or `GBK`.
+    pub(crate) charset: &'static str,
+    pub(crate) source: CharsetSource,
+}
+
+/// Decode `bytes` with the charset from `content_type`, then with the one
+/// declared in the page, then as UTF-8, using the first that decodes without
+/// errors. A declaration that fails to decode the body is taken to be wrong.
+///
+/// As a last resort invalid UTF-8 is replaced, unless that would replace most
+/// of the body, which then most likely is not text at all; that fails with a
+/// description of the problem.
+pub(crate) fn decode(bytes: &[u8], content_type: Option<&str>) -> Result<Decoded, String> {
+    let declared = [
+        (content_type.and_then(header_charset), CharsetSource::Header),
+        (meta_charset(bytes), CharsetSource::Meta),
+        (Some(UTF_8), CharsetSource::Utf8),
+    ];
+    for (encoding, source) in declared {
+        let Some(encoding) = encoding else { continue };
+        match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+            Some(text) => {
+                return Ok(Decoded {
+                    text: text.into_owned(),
+                    charset: encoding.name(),
+                    source,
+                })
+            }
+            None => log::debug!("response body is not valid {}", encoding.name()),
+        }
+    }
+
+    let text = String::from_utf8_lossy(bytes).into_owned();
+    let replaced = text.chars().filter(|&ch| ch == '\u{fffd}').count();
+    if replaced * 2 > text.chars().count() {
+        return Err(format!(
+            "{replaced} of {} characters are not valid UTF-8",
+            text.chars().count()
+        ));
+    }
+    log::warn!("response body is not valid UTF-8; replaced {replaced} invalid sequences");
+    Ok(Decoded {
+        text,
+        charset: UTF_8.name(),
+        source: CharsetSource::Lossy,
+    })
+}
+
+/// The encoding named by the `charset` parameter of a `Content-Type` value.
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+/// The encoding declared by a `<meta>` tag in the first bytes of a page.
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head =
+        String::from_utf8_lossy(&bytes[..bytes.len().min(META_SNIFF_LEN)]).to_ascii_lowercase();
+    head.match_indices("<meta").find_map(|(start, _)| {
+        let tag = &head[start..];
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+        let value = tag[tag.find("charset")? + "charset".len()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .trim_start()
+            .trim_start_matches(['"', '\'']);
+        let end = value
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || "-_.:".contains(ch)))
+            .unwrap_or(value.len());
+        let encoding = Encoding::for_label(&value.as_bytes()[..end])?;
+        // A page cannot declare itself UTF-16 in ASCII.
+        if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+            Some(UTF_8)
+        } else {
+            Some(encoding)
+        }
+    })
+}
@@ -0,0 +1,84 @@
+//! Stopping batch lookups once the server keeps refusing them.
+
+use std::sync::{Mutex, MutexGuard};
+
+use log::warn;
+use reqwest::StatusCode;
+
+use crate::{Translation, YdtError};
+
+/// Opens after a number of consecutive failures that suggest the server is
+/// blocking or throttling us, and stays open until reset.
+pub(crate) struct CircuitBreaker {
+    /// Failures that open the breaker; 0 never opens it.
+    threshold: usize,
+    failures: Mutex<usize>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            failures: Mutex::new(0),
+        }
+    }
+
+    fn failures(&self) -> MutexGuard<'_, usize> {
+        match self.failures.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// The error the lookup of `word` fails with while the breaker is open.
+    pub(crate) fn check(&self, word: &str) -> Result<(), YdtError> {
+        if self.threshold > 0 && *self.failures() >= self.threshold {
+            Err(YdtError::CircuitOpen {
+                word: word.to_string(),
+                failures: self.threshold,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Count a lookup's outcome; any other outcome than a blocking failure
+    /// starts the count over.
+    pub(crate) fn record(&self, result: &Result<Translation, YdtError>) {
+        let mut failures = self.failures();
+        if self.threshold == 0 || *failures >= self.threshold {
+            return;
+        }
+        match result {
+            Err(err) if is_blocking_failure(err) => {
+                *failures += 1;
+                if *failures == self.threshold {
+                    warn!(
+                        "skipping remaining lookups after {} consecutive failures",
+                        self.threshold
+                    );
+                }
+            }
+            _ => *failures = 0,
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        *self.failures() = 0;
+    }
+}
+
+/// Whether `err` suggests that further requests will fail too: a rate limit,
+/// `403 Forbidden` or a server error.
+fn is_blocking_failure(err: &YdtError) -> bool {
+    match err {
+        YdtError::RateLimited { .. } => true,
+        YdtError::HttpStatus { status, .. } => {
+            *status == StatusCode::FORBIDDEN || status.is_server_error()
+        }
+        YdtError::AllSourcesFailed { errors, .. } => {
+            !errors.is_empty() && errors.iter().all(|(_, err)| is_blocking_failure(err))
+        }
+        _ => false,
+    }
+}
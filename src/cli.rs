@@ -0,0 +1,389 @@
+//! Command-line arguments of the `ydt` binary.
+//!
+//! Kept apart from `main.rs` so that `tests/cli.rs` can check parsing without
+//! running lookups.
+
+use std::ffi::OsString;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::{ArgAction, ArgGroup, Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use ydt::{Accent, Backend, ColorChoice, OutputFormat};
+
+const AFTER_HELP: &str =
+    "Without WORD or --clip, words are read from stdin, one per line, or with --stdio \
+JSON requests, one per line.
+
+Exit status: 0 on success, 1 when nothing was found, 2 on network, HTTP and other \
+failures, 3 on usage errors and 4 when Youdao refused the request or rate-limited it.";
+
+/// A simple Youdao dict tool.
+#[derive(Debug, Parser)]
+#[command(
+    name = "ydt",
+    version,
+    disable_version_flag = true,
+    args_conflicts_with_subcommands = true,
+    after_help = AFTER_HELP
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub translate: TranslateArgs,
+    /// Print version; with --verbose, also the features, base URL and user agent.
+    #[arg(short = 'V', long)]
+    pub version: bool,
+}
+
+impl Cli {
+    /// Parse `args` like [`Parser::try_parse_from`], also accepting the flags
+    /// that apply to subcommands before the subcommand, as in
+    /// `ydt --timeout 5 suggest hel`, which `args_conflicts_with_subcommands`
+    /// would reject.
+    pub fn try_parse_args<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let mut args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        move_global_flags(&Self::command(), &mut args);
+        Self::try_parse_from(args)
+    }
+}
+
+/// Move the `global` flags before the subcommand in `args`, if there is one,
+/// to right after it, leaving the other flags for clap to reject.
+fn move_global_flags(command: &clap::Command, args: &mut Vec<OsString>) {
+    let mut global: Vec<Range<usize>> = Vec::new();
+    let mut index = 1;
+    while let Some(arg) = args.get(index).and_then(|arg| arg.to_str()) {
+        let (flag, takes_value) = if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (long, false),
+            };
+            match command.get_arguments().find(|a| a.get_long() == Some(name)) {
+                Some(flag) => (flag, !value && flag.get_action().takes_values()),
+                None => return,
+            }
+        } else if let Some(short) = arg.strip_prefix('-').and_then(|s| s.chars().next()) {
+            match command
+                .get_arguments()
+                .find(|a| a.get_short() == Some(short))
+            {
+                Some(flag) => (flag, arg.len() == 2 && flag.get_action().takes_values()),
+                None => return,
+            }
+        } else {
+            if command.find_subcommand(arg).is_some() {
+                let moved: Vec<_> = global
+                    .iter()
+                    .flat_map(|range| args[range.clone()].to_vec())
+                    .collect();
+                for range in global.into_iter().rev() {
+                    args.drain(range);
+                }
+                let after = index - moved.len() + 1;
+                args.splice(after..after, moved);
+            }
+            return;
+        };
+        let end = index + 1 + usize::from(takes_value);
+        if flag.is_global_set() {
+            global.push(index..end);
+        }
+        index = end;
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List completions for a prefix with short glosses.
+    Suggest {
+        /// Number of completions to list.
+        #[arg(long, value_name = "N", default_value_t = 10, value_parser = positive)]
+        limit: usize,
+        /// Start of the words to list.
+        #[arg(value_parser = word)]
+        prefix: String,
+    },
+    /// Look up two words and show them side by side.
+    Diff {
+        /// Width to fit the two columns in, instead of the terminal's.
+        #[arg(long, value_name = "N")]
+        width: Option<usize>,
+        /// First word.
+        #[arg(value_parser = word)]
+        first: String,
+        /// Second word.
+        #[arg(value_parser = word)]
+        second: String,
+    },
+    /// Open the result page of a word in the web browser (needs the `open`
+    /// feature).
+    Open {
+        /// Word to look up.
+        #[arg(value_parser = word)]
+        word: String,
+    },
+    /// Answer lookups over HTTP with JSON (needs the `server` feature).
+    Serve {
+        /// Address to listen on.
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on; 0 picks a free one.
+        #[arg(long, value_name = "N", default_value_t = 8080)]
+        port: u16,
+    },
+    /// Check the network, Youdao, the parser and local files, for bug reports.
+    Doctor {
+        /// Print the results as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Locate or create the config file.
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Print a completion script for SHELL.
+    #[command(hide = true)]
+    Completions { shell: Shell },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print where the config file is looked up.
+    Path,
+    /// Write a commented template.
+    Init,
+}
+
+/// Arguments for looking words up, the default without a subcommand.
+#[derive(Debug, Args)]
+#[command(group = ArgGroup::new("input").args(["word", "clip"]))]
+pub struct TranslateArgs {
+    /// Word to look up; read from stdin when left out.
+    #[arg(value_parser = typed_word)]
+    pub word: Option<String>,
+    /// Look up the clipboard contents instead of WORD.
+    #[arg(long, conflicts_with = "stdio")]
+    pub clip: bool,
+    /// Copy the result to the clipboard after printing it.
+    #[arg(long, requires = "input", conflicts_with = "raw_html")]
+    pub copy: bool,
+    /// Look up the words listed in FILE, one per line; `#` starts a comment
+    /// and a suffix such as `@fr` sets the language of a word.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["input", "stdio", "watch_clipboard"]
+    )]
+    pub from: Option<PathBuf>,
+    /// Look up text whenever it is copied, until interrupted.
+    #[arg(long, conflicts_with_all = ["input", "stdio", "raw_html", "dump_on_empty"])]
+    pub watch_clipboard: bool,
+    /// With --watch-clipboard, skip text longer than N characters [default: 50].
+    #[arg(long, value_name = "N", value_parser = positive)]
+    pub max_len: Option<usize>,
+
+    /// Print only the phonetics.
+    #[arg(long, conflicts_with = "no_phonetic")]
+    pub phonetic_only: bool,
+    /// Print only the definitions.
+    #[arg(long)]
+    pub no_phonetic: bool,
+    /// Only show this accent's phonetics [possible values: uk, us].
+    #[arg(long, value_name = "ACCENT")]
+    pub accent: Option<Accent>,
+    /// Print phonetics in ASCII (X-SAMPA), for consoles without IPA symbols.
+    #[arg(long)]
+    pub ascii_phonetics: bool,
+    /// Tag each definition with where it comes from, e.g. [mobile].
+    #[arg(long)]
+    pub show_source: bool,
+    /// Label accents, directions and idiom fields in English, e.g. UK for 英;
+    /// the default on terminals with a non-Chinese locale.
+    #[arg(long)]
+    pub english_labels: bool,
+    /// Follow each result with the link to its page on Youdao.
+    #[arg(long)]
+    pub show_link: bool,
+    /// Print everything on a single line, truncated to the width.
+    #[arg(long)]
+    pub one_line: bool,
+    /// Width to wrap or truncate to, instead of the terminal's.
+    #[arg(long, value_name = "N")]
+    pub width: Option<usize>,
+    /// Print definitions as raw lines instead of wrapping them.
+    #[arg(long)]
+    pub no_wrap: bool,
+    /// Output format [possible values: plain, tsv, ndjson].
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+    /// When to color output [possible values: auto, always, never].
+    #[arg(long, value_name = "WHEN")]
+    pub color: Option<ColorChoice>,
+    /// Print errors to stderr as JSON objects; implied by --format ndjson.
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+    /// Print a header row before TSV output.
+    #[arg(long)]
+    pub header: bool,
+    /// Also show a detailed section of the page [possible values: collins].
+    #[arg(long, value_name = "SECTION")]
+    pub detail: Vec<Detail>,
+    /// Most senses shown with --detail collins.
+    #[arg(long, value_name = "N", value_parser = positive)]
+    pub max_senses: Option<usize>,
+    /// Also show words derived from the same root (同根词).
+    #[arg(long)]
+    pub related: bool,
+    /// Only show senses containing TEXT; repeat to show those containing any.
+    #[arg(long, value_name = "TEXT")]
+    pub filter: Vec<String>,
+
+    /// Number of words looked up in parallel when reading stdin.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = ydt::DEFAULT_CONCURRENCY,
+        value_parser = positive
+    )]
+    pub concurrency: usize,
+    /// Skip the rest of a batch after N consecutive failures (0 never skips).
+    #[arg(long, value_name = "N")]
+    pub abort_after: Option<usize>,
+    /// Print batch results as they complete instead of in input order.
+    #[arg(long)]
+    pub unordered: bool,
+    /// Hide the progress line of batch lookups.
+    #[arg(long)]
+    pub quiet: bool,
+    /// Print fetch details and log to stderr; repeat for more detail.
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Fail instead of printing partial results of unrecognized pages.
+    #[arg(long)]
+    pub strict: bool,
+    /// Show results in every direction, e.g. both English and pinyin readings
+    /// of `ha`.
+    #[arg(long)]
+    pub auto_both: bool,
+    /// Look up Traditional Chinese words as written, without converting them
+    /// to Simplified Chinese.
+    #[arg(long)]
+    pub no_convert: bool,
+    /// Look up Youdao's first suggested correction of a misspelled word.
+    #[arg(long)]
+    pub auto_correct: bool,
+    /// Print the fetched result page instead of parsing it.
+    #[arg(long, requires = "input")]
+    pub raw_html: bool,
+    /// Print what looking WORD up would request, without sending anything.
+    #[arg(long, requires = "input", conflicts_with_all = ["raw_html", "sentence"])]
+    pub dry_run: bool,
+    /// With --dry-run, print the requests as JSON.
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+    /// Save result pages that yield no results to DIR.
+    #[arg(long, value_name = "DIR", requires = "input")]
+    pub dump_on_empty: Option<PathBuf>,
+    /// Save every fetched result page to DIR as <WORD>.html.
+    #[arg(long, value_name = "DIR", conflicts_with = "offline")]
+    pub record: Option<PathBuf>,
+    /// Machine-translate WORD as a sentence.
+    #[arg(long, requires = "input")]
+    pub sentence: bool,
+    /// Where words are looked up [possible values: auto, scrape, api].
+    #[arg(long, value_name = "BACKEND")]
+    pub backend: Option<Backend>,
+    /// Do not retry on Youdao's mobile site.
+    #[arg(long)]
+    pub no_mobile_fallback: bool,
+    /// Look Chinese words up in a local CC-CEDICT file.
+    #[arg(long, value_name = "PATH")]
+    pub cedict: Option<PathBuf>,
+    /// Only use the local dictionary, without requests.
+    #[arg(long, conflicts_with_all = ["raw_html", "sentence"])]
+    pub offline: bool,
+    /// Do not keep cookies between runs.
+    #[arg(long, global = true)]
+    pub no_cookie_file: bool,
+    /// Answer newline-delimited JSON requests from stdin.
+    #[arg(long, conflicts_with = "word")]
+    pub stdio: bool,
+
+    /// Timeout of each request, in seconds.
+    #[arg(long, global = true, value_name = "SECS", value_parser = seconds)]
+    pub timeout: Option<Duration>,
+    /// Time allowed for a whole lookup, across fallbacks and retries, or for
+    /// any other request such as of suggestions, in seconds.
+    #[arg(long, global = true, value_name = "SECS", value_parser = seconds)]
+    pub deadline: Option<Duration>,
+    /// Retries after network and server errors.
+    #[arg(long, global = true, value_name = "N")]
+    pub retries: Option<u32>,
+    /// Proxy to send requests through.
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+    /// Always send this user agent.
+    #[arg(long, global = true, value_name = "UA")]
+    pub user_agent: Option<String>,
+    /// Largest response read, in bytes.
+    #[arg(long, global = true, value_name = "BYTES", value_parser = positive)]
+    pub max_body_size: Option<usize>,
+}
+
+/// Detailed section of the result page, parsed only when asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// The Collins dictionary section.
+    Collins,
+}
+
+impl FromStr for Detail {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "collins" => Ok(Self::Collins),
+            other => Err(format!("unknown detail {other:?}, expected collins")),
+        }
+    }
+}
+
+/// A word or prefix, as given to [`ydt::normalize_word`].
+fn word(arg: &str) -> Result<String, String> {
+    ydt::normalize_word(arg, ydt::DEFAULT_MAX_WORD_LEN).map_err(|err| err.to_string())
+}
+
+/// A word or sentence checked with [`ydt::normalize_word`] but kept as typed
+/// apart from whitespace, so that what normalizing it changes can be
+/// reported. Whether it is short enough for a word is checked once it is
+/// known not to be a sentence.
+fn typed_word(arg: &str) -> Result<String, String> {
+    ydt::normalize_word(arg, ydt::DEFAULT_MAX_SENTENCE_LEN).map_err(|err| err.to_string())?;
+    Ok(arg.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn positive(arg: &str) -> Result<usize, String> {
+    match arg.parse() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(value) => Ok(value),
+        Err(err) => Err(format!("{err}")),
+    }
+}
+
+fn seconds(arg: &str) -> Result<Duration, String> {
+    arg.parse::<f64>()
+        .ok()
+        .filter(|secs| *secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| "must be a positive number of seconds".to_string())
+}
@@ -0,0 +1,2747 @@
+//! Fetching result pages and the reusable [`YdtClient`].
+
+use log::{debug, info, warn};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT_ENCODING, ACCEPT_LANGUAGE, CONTENT_ENCODING, CONTENT_TYPE,
+    LOCATION, RETRY_AFTER, USER_AGENT,
+};
+use reqwest::redirect;
+use reqwest::Proxy;
+use reqwest::StatusCode;
+use reqwest::Url;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use url::Host;
+
+use crate::build_info::PROJECT_USER_AGENT;
+use crate::cedict;
+use crate::charset::{self, CharsetSource, Decoded};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::compression::{self, Failure};
+use crate::config::Config;
+use crate::cookies::CookieJar;
+use crate::mobile::parse_mobile_html;
+use crate::openapi::{self, signed_request_url, Credentials, DEFAULT_API_URL};
+use crate::probe::{self, Probe};
+use crate::pronunciation::{
+    audio_url, Pronunciation, PronunciationItem, PronunciationPack, DEFAULT_AUDIO_URL,
+};
+use crate::query::{
+    mobile_result_url, result_url, DEFAULT_BASE_URL, DEFAULT_LANG, DEFAULT_MOBILE_URL,
+};
+use crate::rate_limit::{parse_retry_after, Cooldown, RateLimiter};
+use crate::result_cache::ResultCache;
+use crate::selectors::{default_selectors, InvalidSelector, SelectorConfig, Selectors};
+use crate::sentence::{parse_sentence_with_selectors, DEFAULT_MAX_SENTENCE_LEN};
+use crate::suggest::{parse_suggestions, suggest_url, Suggestion, DEFAULT_SUGGEST_URL};
+use crate::{
+    is_number, normalize_word, parse_translation_with_options, to_simplified, Accent,
+    LookupStrategy, ParseWarning, Translation, TranslationOptions, TranslationSource, YdtError,
+    DEFAULT_MAX_WORD_LEN,
+};
+
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36";
+/// `Accept-Language` header sent unless configured otherwise, asking for the
+/// Chinese labels and headings the parser was written against.
+pub const DEFAULT_ACCEPT_LANGUAGE: &str = "zh-CN,zh;q=0.9,en;q=0.8";
+
+/// Timeout for a single HTTP request unless configured otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed for a whole lookup, across sources, user agents and retries,
+/// unless configured otherwise.
+pub const DEFAULT_DEADLINE: Duration = Duration::from_secs(15);
+
+/// Largest response body read, in bytes, unless configured otherwise.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// Number of redirects followed per request unless configured otherwise.
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Idle connections kept open to each host unless configured otherwise.
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 2;
+
+/// How long an idle connection is kept open unless configured otherwise.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest `Retry-After` waited out before retrying; longer ones fail with
+/// [`YdtError::RateLimited`].
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// Cooldown after a rate limit without a `Retry-After` header.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Clients shared by every fetcher with the default client settings, so that
+/// consecutive lookups reuse connections instead of opening new ones.
+static PROJECT_CLIENT: OnceLock<Client> = OnceLock::new();
+static BROWSER_CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Clients built by one fetcher whose settings differ from the defaults,
+/// by user agent, so that its lookups reuse connections too.
+///
+/// Clones of the fetcher start without clients, as do fetchers whose client
+/// settings were changed.
+#[derive(Default)]
+struct ClientCache(Mutex<Vec<(String, Client)>>);
+
+impl ClientCache {
+    fn get_or_build(
+        &self,
+        user_agent: &str,
+        build: impl FnOnce() -> Result<Client, YdtError>,
+    ) -> Result<Client, YdtError> {
+        let mut clients = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        match clients.iter().find(|(agent, _)| agent == user_agent) {
+            Some((_, client)) => Ok(client.clone()),
+            None => {
+                let client = build()?;
+                clients.push((user_agent.to_string(), client.clone()));
+                Ok(client)
+            }
+        }
+    }
+}
+
+impl Clone for ClientCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for ClientCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClientCache")
+    }
+}
+
+/// A response and how it was obtained.
+struct Delivered {
+    response: Response,
+    user_agent: UserAgentKind,
+    redirects: usize,
+    /// Time from sending the request to receiving the response headers.
+    time_to_headers: Duration,
+}
+
+/// Time left for one lookup, shared by its sources, user agents and retries.
+struct Deadline {
+    limit: Duration,
+    end: Cell<Instant>,
+}
+
+impl Deadline {
+    /// Start a deadline of `limit` from now, or `None` for no deadline.
+    fn start(limit: Duration) -> Option<Self> {
+        let end = Instant::now()
+            .checked_add(limit)
+            .filter(|_| !limit.is_zero())?;
+        Some(Self {
+            limit,
+            end: Cell::new(end),
+        })
+    }
+
+    /// Leave `wait` out of the time taken, e.g. a wait for the request
+    /// interval.
+    fn extend(&self, wait: Duration) {
+        if let Some(end) = self.end.get().checked_add(wait) {
+            self.end.set(end);
+        }
+    }
+
+    /// The time left, or [`YdtError::DeadlineExceeded`] during `tier` when
+    /// there is none.
+    fn remaining(&self, word: &str, tier: impl FnOnce() -> String) -> Result<Duration, YdtError> {
+        match self.end.get().checked_duration_since(Instant::now()) {
+            Some(left) if !left.is_zero() => Ok(left),
+            _ => Err(self.exceeded(word, tier())),
+        }
+    }
+
+    fn is_past(&self) -> bool {
+        Instant::now() >= self.end.get()
+    }
+
+    fn exceeded(&self, word: &str, tier: String) -> YdtError {
+        YdtError::DeadlineExceeded {
+            word: word.to_string(),
+            deadline: self.limit,
+            tier,
+        }
+    }
+}
+
+/// Name an attempt at a request for [`YdtError::DeadlineExceeded`], e.g.
+/// `request with the browser user agent, retry 1`.
+fn attempt_tier(stage: &str, attempt: u32) -> String {
+    match attempt {
+        0 => stage.to_string(),
+        _ => format!("{stage}, retry {attempt}"),
+    }
+}
+
+impl Delivered {
+    fn ensure_success(self, word: &str) -> Result<Self, YdtError> {
+        let status = self.response.status();
+        if status.is_success() {
+            Ok(self)
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = self
+                .response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| parse_retry_after(value, SystemTime::now()));
+            Err(YdtError::RateLimited {
+                word: word.to_string(),
+                retry_after,
+            })
+        } else {
+            Err(YdtError::HttpStatus {
+                word: word.to_string(),
+                url: self.response.url().to_string(),
+                status,
+            })
+        }
+    }
+}
+
+/// Buffer that refuses writes beyond `limit` bytes in total.
+struct LimitedBuffer {
+    bytes: Vec<u8>,
+    limit: usize,
+    received: usize,
+}
+
+impl io::Write for LimitedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.received = self.received.saturating_add(buf.len());
+        if self.received > self.limit {
+            return Err(io::Error::other("response body too large"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Read, decompress and decode the response body for `word`; see
+/// [`compression::decompress`] and [`charset::decode`]. The `Content-Encoding`
+/// the body was decompressed from is returned along with it.
+///
+/// The body is read in chunks and reading stops, dropping the connection, as
+/// soon as more than `limit` bytes have arrived. The decompressed body is held
+/// to the same limit.
+fn read_body(
+    response: Response,
+    word: &str,
+    limit: usize,
+) -> Result<(Decoded, Option<&'static str>), YdtError> {
+    let url = response.url().to_string();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let (bytes, encoding) = read_bytes(response, word, limit)?;
+    let decoded =
+        charset::decode(&bytes, content_type.as_deref()).map_err(|message| YdtError::Decode {
+            word: word.to_string(),
+            url,
+            message,
+        })?;
+    Ok((decoded, encoding))
+}
+
+/// Read and decompress the response body for `word`, as [`read_body`] does
+/// without decoding it as text.
+fn read_bytes(
+    mut response: Response,
+    word: &str,
+    limit: usize,
+) -> Result<(Vec<u8>, Option<&'static str>), YdtError> {
+    let url = response.url().to_string();
+    let content_encoding = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let mut buffer = LimitedBuffer {
+        bytes: Vec::new(),
+        limit,
+        received: 0,
+    };
+    if let Err(err) = response.copy_to(&mut buffer) {
+        if buffer.received > limit {
+            return Err(YdtError::ResponseTooLarge {
+                word: word.to_string(),
+                url,
+                limit,
+                received: buffer.received,
+            });
+        }
+        return Err(YdtError::ReadResponse {
+            word: word.to_string(),
+            url,
+            source: err,
+        });
+    }
+    match compression::decompress(buffer.bytes, content_encoding.as_deref(), limit) {
+        Ok(body) => Ok((body.bytes, body.encoding)),
+        Err(Failure::Compressed(encoding)) => Err(YdtError::CompressedBody {
+            word: word.to_string(),
+            url,
+            encoding,
+        }),
+        Err(Failure::Corrupt(err)) => Err(YdtError::Decode {
+            word: word.to_string(),
+            url,
+            message: format!("invalid compressed body: {err}"),
+        }),
+        Err(Failure::TooLarge) => Err(YdtError::Decode {
+            word: word.to_string(),
+            url,
+            message: format!("decompressed body is larger than {limit} bytes"),
+        }),
+    }
+}
+
+/// Whether a redirect from `from` to `to` stays on the same site, taking the last
+/// two labels of a domain as the site, so that `www.youdao.com` may redirect to
+/// `m.youdao.com`.
+fn same_site(from: &Url, to: &Url) -> bool {
+    fn site(domain: &str) -> &str {
+        let mut dots = domain.trim_end_matches('.').rmatch_indices('.');
+        match (dots.next(), dots.next()) {
+            (Some(_), Some((i, _))) => &domain[i + 1..],
+            _ => domain,
+        }
+    }
+    match (from.host(), to.host()) {
+        (Some(Host::Domain(from)), Some(Host::Domain(to))) => {
+            site(from).eq_ignore_ascii_case(site(to))
+        }
+        (from, to) => from == to,
+    }
+}
+
+/// Where `response` redirects to, or `None` when it is not a redirect or has
+/// no usable `Location` header, as `reqwest` would treat it.
+fn redirect_target(response: &Response) -> Option<Url> {
+    let redirects = matches!(
+        response.status(),
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    );
+    if !redirects {
+        return None;
+    }
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    response.url().join(location).ok()
+}
+
+/// Turn the error of a request to `url` for `word` into [`YdtError::Fetch`].
+fn request_error(err: reqwest::Error, word: &str, url: &Url) -> YdtError {
+    YdtError::Fetch {
+        word: word.to_string(),
+        url: url.to_string(),
+        source: err,
+    }
+}
+
+/// Number of consecutive rate limits, `403 Forbidden` responses or server
+/// errors after which batch lookups skip the remaining words, unless configured
+/// otherwise.
+pub const DEFAULT_ABORT_AFTER: usize = 5;
+
+/// Number of translations [`YdtClient::translate_cached`] keeps unless
+/// configured otherwise.
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// Number of parallel requests used by batch lookups unless configured otherwise.
+///
+/// Kept small to stay polite to Youdao.
+pub const DEFAULT_CONCURRENCY: usize = 2;
+
+/// Order in which concurrent batch lookups report their results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResultOrder {
+    /// Report results in the order of the input words.
+    #[default]
+    Input,
+    /// Report results as soon as each lookup completes.
+    Completion,
+}
+
+/// Progress of a batch lookup, reported by
+/// [`YdtClient::translate_many_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent<'a> {
+    /// The lookup of `word` finished.
+    Completed {
+        /// The word whose lookup finished.
+        word: &'a str,
+        /// Number of finished lookups, including this one.
+        completed: usize,
+        /// Number of finished lookups that failed.
+        failed: usize,
+        /// Total number of words in the batch.
+        total: usize,
+    },
+    /// All lookups finished.
+    Finished {
+        /// Number of finished lookups.
+        completed: usize,
+        /// Number of finished lookups that failed.
+        failed: usize,
+    },
+}
+
+/// Which user agents [`HttpFetcher`] sends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum UaStrategy {
+    /// Send the project user agent, retrying with a browser user agent when the
+    /// request fails or is rejected with `403`. Rate limits (`429`) are not
+    /// retried this way, as they apply whatever the user agent.
+    #[default]
+    ProjectThenBrowser,
+    /// Always send a browser user agent.
+    BrowserOnly,
+    /// Always send the given user agent.
+    Custom(String),
+}
+
+/// User agent a request was sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAgentKind {
+    /// The project user agent, `ydt/<version>`.
+    Project,
+    /// The browser user agent.
+    Browser,
+    /// A user agent given with [`UaStrategy::Custom`].
+    Custom,
+}
+
+impl fmt::Display for UserAgentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Project => f.write_str("project"),
+            Self::Browser => f.write_str("browser"),
+            Self::Custom => f.write_str("custom"),
+        }
+    }
+}
+
+/// Where [`YdtClient`] looks words up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Use the official API when credentials are configured, falling back to
+    /// scraping when it fails or finds nothing; otherwise scrape.
+    #[default]
+    Auto,
+    /// Always scrape the result pages.
+    Scrape,
+    /// Always use the official API; see [`YdtClientBuilder::api_credentials`].
+    Api,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    /// Parse `auto`, `scrape` or `api`, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "scrape" => Ok(Self::Scrape),
+            "api" => Ok(Self::Api),
+            _ => Err(format!(
+                "unknown backend `{s}`, expected `auto`, `scrape` or `api`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Scrape => f.write_str("scrape"),
+            Self::Api => f.write_str("api"),
+        }
+    }
+}
+
+/// Site a result page was fetched from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageSource {
+    /// The desktop result page, or a page from a custom [`Fetcher`].
+    #[default]
+    Desktop,
+    /// The mobile result page; see [`YdtClientBuilder::mobile_fallback`].
+    Mobile,
+    /// A JSON response from the official API; see [`Backend`].
+    Api,
+    /// A [`TranslationSource`] added with [`YdtClientBuilder::source`].
+    Custom,
+}
+
+impl fmt::Display for PageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Desktop => f.write_str("desktop"),
+            Self::Mobile => f.write_str("mobile"),
+            Self::Api => f.write_str("api"),
+            Self::Custom => f.write_str("custom"),
+        }
+    }
+}
+
+/// Details about how a page was fetched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchInfo {
+    /// HTTP status of the response that returned the page.
+    pub status: StatusCode,
+    /// Time taken to fetch the page, including retries.
+    pub elapsed: Duration,
+    /// User agent of the request that returned the page, or `None` when the
+    /// fetcher does not send HTTP requests.
+    pub user_agent: Option<UserAgentKind>,
+    /// URL the page was served from after following redirects, or `None` when the
+    /// fetcher does not send HTTP requests.
+    pub final_url: Option<Url>,
+    /// Whether the page was served from a cache instead of being requested.
+    pub from_cache: bool,
+    /// Site the page was fetched from.
+    pub source: PageSource,
+    /// Number of redirects followed to reach [`final_url`](Self::final_url).
+    pub redirects: usize,
+    /// Name of the encoding the page was decoded with, such as `UTF-8` or
+    /// `GBK`.
+    pub charset: &'static str,
+    /// How [`charset`](Self::charset) was determined.
+    pub charset_source: CharsetSource,
+    /// `Content-Encoding` the page was sent with and decompressed from,
+    /// `gzip` or `br`, or `None` when it was sent uncompressed.
+    pub content_encoding: Option<&'static str>,
+    /// Time from sending the request that returned the page to receiving its
+    /// response headers, or `None` when the fetcher does not send HTTP
+    /// requests. This includes connecting, so it drops when a pooled
+    /// connection is reused.
+    pub time_to_headers: Option<Duration>,
+    /// The [`request_id`](TranslationOptions::request_id) of the lookup, or
+    /// `None` when it was given none.
+    pub request_id: Option<String>,
+}
+
+impl FetchInfo {
+    /// Details of a page from `source` that took `elapsed` to produce without
+    /// sending HTTP requests: a `200 OK` status in UTF-8.
+    pub(crate) fn local(source: PageSource, elapsed: Duration) -> Self {
+        Self {
+            status: StatusCode::OK,
+            elapsed,
+            user_agent: None,
+            final_url: None,
+            from_cache: false,
+            source,
+            redirects: 0,
+            charset: "UTF-8",
+            charset_source: CharsetSource::Utf8,
+            content_encoding: None,
+            time_to_headers: None,
+            request_id: None,
+        }
+    }
+}
+
+/// What looking a word up would request, from [`YdtClient::plan`], without
+/// sending anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestPlan {
+    /// The word as it would be requested: normalized, and converted to
+    /// Simplified Chinese unless that is disabled.
+    pub word: String,
+    /// A request per source, in the order the sources are tried.
+    pub requests: Vec<PlannedRequest>,
+    /// Timeout of each request.
+    pub timeout: Duration,
+    /// Time allowed for the whole lookup, or `None` for no limit.
+    pub deadline: Option<Duration>,
+    /// Retries after network errors, server errors and short rate limits.
+    pub retries: u32,
+    /// Proxy requests are sent through.
+    pub proxy: Option<String>,
+    /// User agent a request is sent again with when its own is rejected; see
+    /// [`UaStrategy::ProjectThenBrowser`].
+    pub fallback_user_agent: Option<String>,
+}
+
+/// A request of a [`RequestPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlannedRequest {
+    /// Name of the source, e.g. `desktop`, `mobile` or `api`.
+    pub source: String,
+    /// URL of the `GET` request, or `None` when the source sends no HTTP
+    /// request itself, as with a custom [`Fetcher`] or [`TranslationSource`].
+    pub url: Option<String>,
+    /// Headers of the request, with lowercase names such as `user-agent`;
+    /// empty without a URL.
+    pub headers: Vec<(String, String)>,
+    /// Whether the next source is tried quietly when this one fails, as with
+    /// the API under [`Backend::Auto`].
+    pub optional: bool,
+}
+
+/// Source of Youdao result page HTML.
+///
+/// [`HttpFetcher`] is the default; other implementations can serve canned pages,
+/// e.g. in tests.
+pub trait Fetcher: Send + Sync {
+    /// Fetch the result page for `word`.
+    fn fetch(&self, word: &str) -> Result<String, YdtError>;
+
+    /// Fetch the result page for `word` along with details about the request.
+    ///
+    /// The default implementation times [`fetch`](Self::fetch) and reports a
+    /// `200 OK` status without HTTP details.
+    fn fetch_with_info(&self, word: &str) -> Result<(String, FetchInfo), YdtError> {
+        let start = Instant::now();
+        let html = self.fetch(word)?;
+        let info = FetchInfo::local(PageSource::Desktop, start.elapsed());
+        Ok((html, info))
+    }
+}
+
+impl<F: Fetcher + ?Sized> Fetcher for Arc<F> {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        (**self).fetch(word)
+    }
+
+    fn fetch_with_info(&self, word: &str) -> Result<(String, FetchInfo), YdtError> {
+        (**self).fetch_with_info(word)
+    }
+}
+
+/// [`Fetcher`] that requests pages from Youdao over HTTP.
+///
+/// By default, requests are sent with the project user agent first, falling back to
+/// a browser user agent when the project one is rejected; see [`UaStrategy`].
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    timeout: Duration,
+    retries: u32,
+    proxy: Option<String>,
+    base_url: String,
+    mobile_url: String,
+    suggest_url: String,
+    audio_url: String,
+    api_url: String,
+    credentials: Option<Credentials>,
+    ua_strategy: UaStrategy,
+    lang: String,
+    accept_language: String,
+    max_body_size: usize,
+    max_redirects: usize,
+    pool_max_idle_per_host: usize,
+    pool_idle_timeout: Duration,
+    tcp_keepalive: Option<Duration>,
+    /// Cookies shared by all requests, and by clones of the fetcher.
+    cookie_jar: Option<Arc<CookieJar>>,
+    cooldown: Option<Cooldown>,
+    clients: ClientCache,
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            retries: 0,
+            proxy: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            mobile_url: DEFAULT_MOBILE_URL.to_string(),
+            suggest_url: DEFAULT_SUGGEST_URL.to_string(),
+            audio_url: DEFAULT_AUDIO_URL.to_string(),
+            api_url: DEFAULT_API_URL.to_string(),
+            credentials: None,
+            ua_strategy: UaStrategy::default(),
+            lang: DEFAULT_LANG.to_string(),
+            accept_language: DEFAULT_ACCEPT_LANGUAGE.to_string(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            tcp_keepalive: None,
+            cookie_jar: None,
+            cooldown: None,
+            clients: ClientCache::default(),
+        }
+    }
+}
+
+impl HttpFetcher {
+    /// Request result pages from `url` instead of [`DEFAULT_BASE_URL`].
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Request mobile result pages from `url` instead of [`DEFAULT_MOBILE_URL`].
+    pub fn mobile_url(mut self, url: impl Into<String>) -> Self {
+        self.mobile_url = url.into();
+        self
+    }
+
+    /// Request suggestions from `url` instead of [`DEFAULT_SUGGEST_URL`].
+    pub fn suggest_url(mut self, url: impl Into<String>) -> Self {
+        self.suggest_url = url.into();
+        self
+    }
+
+    /// Request recordings from `url` instead of [`DEFAULT_AUDIO_URL`].
+    pub fn audio_url(mut self, url: impl Into<String>) -> Self {
+        self.audio_url = url.into();
+        self
+    }
+
+    /// Send API requests to `url` instead of [`DEFAULT_API_URL`].
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = url.into();
+        self
+    }
+
+    /// Sign API requests with `credentials`.
+    pub fn api_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Always send `user_agent`; shorthand for [`UaStrategy::Custom`].
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.ua_strategy = UaStrategy::Custom(user_agent.into());
+        self
+    }
+
+    /// Choose which user agents to send. Defaults to
+    /// [`UaStrategy::ProjectThenBrowser`].
+    pub fn ua_strategy(mut self, strategy: UaStrategy) -> Self {
+        self.ua_strategy = strategy;
+        self
+    }
+
+    /// Set the `lang` query parameter. Defaults to [`DEFAULT_LANG`].
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = lang.into();
+        self
+    }
+
+    /// Send `value` as the `Accept-Language` header. Defaults to
+    /// [`DEFAULT_ACCEPT_LANGUAGE`]; an empty value sends no header.
+    pub fn accept_language(mut self, value: impl Into<String>) -> Self {
+        self.accept_language = value.into();
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Set the timeout for each HTTP request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Retry up to `retries` times after network errors and server errors (5xx).
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Send all requests through the proxy at `url`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Fail with [`YdtError::ResponseTooLarge`] instead of reading response
+    /// bodies larger than `bytes`. Defaults to [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Keep cookies set by Youdao and send them with later requests made through
+    /// this fetcher or its clones. Disabled by default.
+    pub fn cookies(mut self, enabled: bool) -> Self {
+        self.cookie_jar = enabled.then(Arc::default);
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Keep cookies as with [`cookies`](Self::cookies), loading them from the
+    /// JSON file at `path` now and saving them back after successful requests.
+    ///
+    /// Only persistent cookies are saved, in a file readable only by the user.
+    /// A missing or corrupt file is replaced.
+    pub fn cookie_store_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cookie_jar = Some(Arc::new(CookieJar::load(path.into())));
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Record rate limits in the file at `path`, and fail with
+    /// [`YdtError::RateLimited`] without sending requests until they end.
+    ///
+    /// This lets a rate limit outlive the process, as each run of a
+    /// command-line tool would otherwise try again straight away. Rate limits
+    /// without a `Retry-After` header last a minute.
+    pub fn cooldown_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cooldown = Some(Cooldown::new(path.into()));
+        self
+    }
+
+    /// Follow at most `max` redirects per request. Defaults to
+    /// [`DEFAULT_MAX_REDIRECTS`].
+    ///
+    /// Exceeding the limit, a redirect loop or a redirect to another site fails
+    /// with [`YdtError::UnexpectedRedirect`].
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    /// Fail with [`YdtError::UnexpectedRedirect`] instead of following any
+    /// redirect; shorthand for `max_redirects(0)`.
+    pub fn no_redirects(self) -> Self {
+        self.max_redirects(0)
+    }
+
+    /// Keep at most `max` idle connections open to each host for later
+    /// requests. Defaults to [`DEFAULT_POOL_MAX_IDLE_PER_HOST`]; `0` opens a
+    /// new connection for every request.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Close connections left idle for longer than `timeout`. Defaults to
+    /// [`DEFAULT_POOL_IDLE_TIMEOUT`].
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = timeout;
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// Send TCP keep-alive probes on open connections every `interval`.
+    /// Defaults to the interval chosen by `reqwest`.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self.clients = ClientCache::default();
+        self
+    }
+
+    /// The HTTP client for requests with `user_agent`, built on first use and
+    /// shared with other fetchers when the settings allow it.
+    fn client(&self, user_agent: &str) -> Result<Client, YdtError> {
+        let shared = match user_agent {
+            PROJECT_USER_AGENT => Some(&PROJECT_CLIENT),
+            BROWSER_USER_AGENT => Some(&BROWSER_CLIENT),
+            _ => None,
+        };
+        let Some(shared) = shared.filter(|_| self.has_default_client_settings()) else {
+            return self
+                .clients
+                .get_or_build(user_agent, || self.build_client(user_agent));
+        };
+        let client = match shared.get() {
+            Some(shared) => shared,
+            None => {
+                let built = self.build_client(user_agent)?;
+                // Another thread may have got there first, in which case its
+                // client is used and this one dropped.
+                shared.get_or_init(|| built)
+            }
+        };
+        Ok(client.clone())
+    }
+
+    /// Whether the settings baked into an HTTP client are the defaults, so that
+    /// a shared client can be used.
+    fn has_default_client_settings(&self) -> bool {
+        self.timeout == DEFAULT_TIMEOUT
+            && self.proxy.is_none()
+            && self.accept_language == DEFAULT_ACCEPT_LANGUAGE
+            && self.pool_max_idle_per_host == DEFAULT_POOL_MAX_IDLE_PER_HOST
+            && self.pool_idle_timeout == DEFAULT_POOL_IDLE_TIMEOUT
+            && self.tcp_keepalive.is_none()
+            && self.cookie_jar.is_none()
+    }
+
+    /// Build an HTTP client that leaves redirects to
+    /// [`send_with_ua`](Self::send_with_ua).
+    fn build_client(&self, user_agent: &str) -> Result<Client, YdtError> {
+        let mut builder = Client::builder()
+            .user_agent(user_agent)
+            .timeout(self.timeout)
+            .redirect(redirect::Policy::none())
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout);
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(jar) = &self.cookie_jar {
+            builder = builder.cookie_provider(Arc::clone(jar));
+        }
+        builder = builder.default_headers(self.default_headers());
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy).map_err(YdtError::CreateHttpClient)?);
+        }
+        builder.build().map_err(YdtError::CreateHttpClient)
+    }
+
+    /// Headers sent with every request besides `User-Agent`.
+    fn default_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::from_iter([(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static(compression::ACCEPT_ENCODING),
+        )]);
+        if !self.accept_language.is_empty() {
+            match HeaderValue::from_str(&self.accept_language) {
+                Ok(value) => {
+                    headers.insert(ACCEPT_LANGUAGE, value);
+                }
+                Err(_) => warn!(
+                    "not sending invalid Accept-Language header {:?}",
+                    self.accept_language
+                ),
+            }
+        }
+        headers
+    }
+
+    /// User agent requests are first sent with.
+    fn first_user_agent(&self) -> (&str, UserAgentKind) {
+        match &self.ua_strategy {
+            UaStrategy::ProjectThenBrowser => (PROJECT_USER_AGENT, UserAgentKind::Project),
+            UaStrategy::BrowserOnly => (BROWSER_USER_AGENT, UserAgentKind::Browser),
+            UaStrategy::Custom(user_agent) => (user_agent.as_str(), UserAgentKind::Custom),
+        }
+    }
+
+    /// The request [`fetch_page_with_info`](Self::fetch_page_with_info) would
+    /// send first for `word`.
+    fn plan_request(
+        &self,
+        word: &str,
+        source: PageSource,
+        lang: &str,
+        optional: bool,
+    ) -> Result<PlannedRequest, YdtError> {
+        let url = self.page_url(word, source, lang)?;
+        let (user_agent, _) = self.first_user_agent();
+        let mut headers = vec![(USER_AGENT.to_string(), user_agent.to_string())];
+        for (name, value) in &self.default_headers() {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            headers.push((name.to_string(), value.into_owned()));
+        }
+        Ok(PlannedRequest {
+            source: source.to_string(),
+            url: Some(url.to_string()),
+            headers,
+            optional,
+        })
+    }
+
+    fn send_with_ua(
+        &self,
+        url: &Url,
+        word: &str,
+        user_agent: &str,
+        kind: UserAgentKind,
+        deadline: Option<&Deadline>,
+        attempt: u32,
+    ) -> Result<Delivered, YdtError> {
+        let tier = || attempt_tier(&format!("request with the {kind} user agent"), attempt);
+        let client = self.client(user_agent)?;
+        debug!("GET {url} with {kind} user agent");
+        let start = Instant::now();
+        // Redirects are followed here rather than by `reqwest`, so that each
+        // request counts its own even when requests run concurrently.
+        let mut visited = Vec::new();
+        let mut target = url.clone();
+        let result = loop {
+            let mut request = client.get(target.clone());
+            if let Some(deadline) = deadline {
+                // The request may not outlast the lookup.
+                let left = deadline.remaining(word, tier)?;
+                if left < self.timeout {
+                    request = request.timeout(left);
+                }
+            }
+            let response = match request.send() {
+                Ok(response) => response,
+                Err(err) => break Err(request_error(err, word, url)),
+            };
+            let Some(next) = redirect_target(&response) else {
+                break Ok(response);
+            };
+            visited.push(target);
+            let rejected = visited.len() > self.max_redirects
+                || visited.contains(&next)
+                || !same_site(&visited[0], &next);
+            if rejected {
+                break Err(YdtError::UnexpectedRedirect {
+                    word: word.to_string(),
+                    url: url.to_string(),
+                    to: next.to_string(),
+                });
+            }
+            debug!("{} redirect to {next}", response.status());
+            target = next;
+        };
+        match &result {
+            Ok(resp) => debug!(
+                "{} from {} in {:.0?}",
+                resp.status(),
+                resp.url(),
+                start.elapsed()
+            ),
+            Err(err) => debug!(
+                "request for {word:?} failed after {:.0?}: {err}",
+                start.elapsed()
+            ),
+        }
+        if let Some(deadline) = deadline.filter(|deadline| result.is_err() && deadline.is_past()) {
+            return Err(deadline.exceeded(word, tier()));
+        }
+        Ok(Delivered {
+            response: result?,
+            user_agent: kind,
+            redirects: visited.len(),
+            time_to_headers: start.elapsed(),
+        })
+    }
+
+    fn fetch_with_fallback(
+        &self,
+        url: &Url,
+        word: &str,
+        deadline: Option<&Deadline>,
+        attempt: u32,
+    ) -> Result<Delivered, YdtError> {
+        let (user_agent, kind) = self.first_user_agent();
+        let result = self.send_with_ua(url, word, user_agent, kind, deadline, attempt);
+        if self.ua_strategy == UaStrategy::ProjectThenBrowser {
+            // A rate limit applies to the address whatever the user agent, so
+            // it is left to the `Retry-After` handling.
+            let rejected = match &result {
+                Ok(delivered) => delivered.response.status() == StatusCode::FORBIDDEN,
+                Err(YdtError::DeadlineExceeded { .. }) => false,
+                Err(_) => true,
+            };
+            if rejected {
+                match &result {
+                    Ok(delivered) => warn!(
+                        "project user agent rejected with {} for {word:?}; retrying with browser user agent",
+                        delivered.response.status()
+                    ),
+                    Err(err) => warn!(
+                        "request for {word:?} failed ({err}); retrying with browser user agent"
+                    ),
+                }
+                let fallback = self.send_with_ua(
+                    url,
+                    word,
+                    BROWSER_USER_AGENT,
+                    UserAgentKind::Browser,
+                    deadline,
+                    attempt,
+                )?;
+                return fallback.ensure_success(word);
+            }
+        }
+        result?.ensure_success(word)
+    }
+
+    /// Request `url` for `word`, applying the user agent strategy and retries.
+    ///
+    /// Rate limits with a short `Retry-After` are waited out as long as retries
+    /// remain; others start the cooldown, if any. Neither requests nor waits
+    /// run past `deadline`.
+    fn fetch_retrying<T>(
+        &self,
+        url: &Url,
+        word: &str,
+        deadline: Option<&Deadline>,
+        read: impl Fn(Delivered) -> Result<T, YdtError>,
+    ) -> Result<T, YdtError> {
+        if let Some(remaining) = self.cooldown.as_ref().and_then(Cooldown::remaining) {
+            debug!("not requesting {word:?} during rate limit cooldown");
+            return Err(YdtError::RateLimited {
+                word: word.to_string(),
+                retry_after: Some(remaining),
+            });
+        }
+        // Fail with the deadline rather than wait past it before the next retry.
+        let wait_within_deadline = |wait: Duration, retry: u32| match deadline {
+            Some(deadline) => {
+                let tier = || format!("wait before retry {retry}");
+                if wait >= deadline.remaining(word, tier)? {
+                    return Err(deadline.exceeded(word, tier()));
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        };
+        let mut attempt = 0;
+        loop {
+            let mut result = self
+                .fetch_with_fallback(url, word, deadline, attempt)
+                .and_then(&read);
+            if let (Ok(_), Some(jar)) = (&result, &self.cookie_jar) {
+                jar.save();
+            }
+            if let Some(deadline) = deadline.filter(|deadline| deadline.is_past()) {
+                // A body cut short by the deadline fails to read.
+                if matches!(&result, Err(err) if !matches!(err, YdtError::DeadlineExceeded { .. }))
+                {
+                    result = Err(deadline.exceeded(word, attempt_tier("response body", attempt)));
+                }
+            }
+            match result {
+                Err(err @ YdtError::DeadlineExceeded { .. }) => return Err(err),
+                Err(YdtError::RateLimited { retry_after, .. }) => {
+                    let wait = retry_after.unwrap_or(RETRY_BACKOFF * (attempt + 1));
+                    if attempt < self.retries && wait <= MAX_RETRY_AFTER {
+                        wait_within_deadline(wait, attempt + 1)?;
+                        attempt += 1;
+                        warn!(
+                            "lookup of {word:?} rate limited; retry {attempt} of {} in {wait:.0?}",
+                            self.retries
+                        );
+                        thread::sleep(wait);
+                        continue;
+                    }
+                    if let Some(cooldown) = &self.cooldown {
+                        let duration = retry_after.unwrap_or(DEFAULT_COOLDOWN);
+                        if let Err(err) = cooldown.start(duration) {
+                            warn!("failed to record rate limit cooldown: {err}");
+                        }
+                    }
+                    return Err(YdtError::RateLimited {
+                        word: word.to_string(),
+                        retry_after,
+                    });
+                }
+                Err(err) if attempt < self.retries && err.is_retryable() => {
+                    wait_within_deadline(RETRY_BACKOFF * (attempt + 1), attempt + 1)?;
+                    attempt += 1;
+                    warn!(
+                        "lookup of {word:?} failed ({err}); retry {attempt} of {}",
+                        self.retries
+                    );
+                    thread::sleep(RETRY_BACKOFF * attempt);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl HttpFetcher {
+    fn page_url(&self, word: &str, source: PageSource, lang: &str) -> Result<Url, YdtError> {
+        match source {
+            PageSource::Desktop => result_url(&self.base_url, word, lang),
+            PageSource::Mobile => mobile_result_url(&self.mobile_url, word, lang),
+            PageSource::Api => match &self.credentials {
+                Some(credentials) => signed_request_url(&self.api_url, credentials, word),
+                None => Err(YdtError::MissingApiCredentials),
+            },
+            PageSource::Custom => unreachable!("custom sources are not fetched over HTTP"),
+        }
+    }
+
+    fn fetch_page(
+        &self,
+        word: &str,
+        source: PageSource,
+        deadline: Option<&Deadline>,
+    ) -> Result<String, YdtError> {
+        let url = self.page_url(word, source, &self.lang)?;
+        self.fetch_retrying(&url, word, deadline, |delivered| {
+            read_body(delivered.response, word, self.max_body_size).map(|(body, _)| body.text)
+        })
+    }
+
+    fn fetch_page_with_info(
+        &self,
+        word: &str,
+        source: PageSource,
+        lang: &str,
+        deadline: Option<&Deadline>,
+    ) -> Result<(String, FetchInfo), YdtError> {
+        let start = Instant::now();
+        let url = self.page_url(word, source, lang)?;
+        self.fetch_retrying(&url, word, deadline, |delivered| {
+            let status = delivered.response.status();
+            let final_url = delivered.response.url().clone();
+            let (body, content_encoding) = read_body(delivered.response, word, self.max_body_size)?;
+            let info = FetchInfo {
+                status,
+                elapsed: start.elapsed(),
+                user_agent: Some(delivered.user_agent),
+                final_url: Some(final_url),
+                from_cache: false,
+                source,
+                redirects: delivered.redirects,
+                charset: body.charset,
+                charset_source: body.source,
+                content_encoding,
+                time_to_headers: Some(delivered.time_to_headers),
+                request_id: None,
+            };
+            Ok((body.text, info))
+        })
+    }
+
+    fn probe(&self) -> Result<Probe, YdtError> {
+        let endpoint = Url::parse(&self.base_url).map_err(YdtError::BuildRequestUrl)?;
+        let proxy = self
+            .proxy
+            .as_deref()
+            .map(Url::parse)
+            .transpose()
+            .map_err(YdtError::BuildRequestUrl)?;
+        let user_agent = match &self.ua_strategy {
+            UaStrategy::ProjectThenBrowser => PROJECT_USER_AGENT,
+            UaStrategy::BrowserOnly => BROWSER_USER_AGENT,
+            UaStrategy::Custom(user_agent) => user_agent.as_str(),
+        };
+        let client = self.client(user_agent)?;
+        Ok(probe::run(&endpoint, proxy.as_ref(), self.timeout, &client))
+    }
+
+    /// Request the raw suggestion payload for `prefix`.
+    fn fetch_suggestions(
+        &self,
+        prefix: &str,
+        limit: usize,
+        deadline: Option<&Deadline>,
+    ) -> Result<String, YdtError> {
+        let url = suggest_url(&self.suggest_url, prefix, limit, &self.lang)?;
+        self.fetch_retrying(&url, prefix, deadline, |delivered| {
+            read_body(delivered.response, prefix, self.max_body_size).map(|(body, _)| body.text)
+        })
+    }
+
+    /// Request the recording of `word` in `accent`.
+    fn fetch_audio(
+        &self,
+        word: &str,
+        accent: Accent,
+        deadline: Option<&Deadline>,
+    ) -> Result<Vec<u8>, YdtError> {
+        let url = audio_url(&self.audio_url, word, accent)?;
+        self.fetch_retrying(&url, word, deadline, |delivered| {
+            read_bytes(delivered.response, word, self.max_body_size).map(|(bytes, _)| bytes)
+        })
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        self.fetch_page(word, PageSource::Desktop, None)
+    }
+
+    fn fetch_with_info(&self, word: &str) -> Result<(String, FetchInfo), YdtError> {
+        self.fetch_page_with_info(word, PageSource::Desktop, &self.lang, None)
+    }
+}
+
+/// A place the client looks words up, in the order they are tried.
+enum Source {
+    /// The official API; optional when it is only preferred by
+    /// [`Backend::Auto`], so that its failures fall through to scraping.
+    Api {
+        optional: bool,
+    },
+    Desktop,
+    Mobile,
+    Custom(Box<dyn TranslationSource>),
+}
+
+impl Source {
+    fn name(&self) -> &str {
+        match self {
+            Self::Api { .. } => "api",
+            Self::Desktop => "desktop",
+            Self::Mobile => "mobile",
+            Self::Custom(source) => source.name(),
+        }
+    }
+
+    fn is_optional(&self) -> bool {
+        matches!(self, Self::Api { optional: true })
+    }
+}
+
+/// Errors that would recur with every source, so trying the next one is
+/// pointless.
+fn is_configuration_error(err: &YdtError) -> bool {
+    matches!(
+        err,
+        YdtError::ParseCssSelector { .. }
+            | YdtError::CreateHttpClient(_)
+            | YdtError::BuildRequestUrl(_)
+            | YdtError::ReadDictionary(_)
+    )
+}
+
+type WaitCallback = Box<dyn Fn(Duration) + Send + Sync>;
+type PageCallback = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Client for looking up translations.
+///
+/// A client is `Sync`, so it can be shared between threads; settings such as the
+/// minimum request interval apply to all lookups made through it.
+pub struct YdtClient {
+    /// Custom fetcher of result pages; `http` fetches them when `None`.
+    fetcher: Option<Box<dyn Fetcher>>,
+    /// HTTP settings for the suggestion endpoint, used even with a custom fetcher.
+    http: HttpFetcher,
+    rate_limiter: Option<RateLimiter>,
+    on_wait: Option<WaitCallback>,
+    on_page: Option<PageCallback>,
+    breaker: CircuitBreaker,
+    cache: ResultCache,
+    strict: bool,
+    auto_correct: bool,
+    convert_traditional: bool,
+    skip_numbers: bool,
+    sources: Vec<Source>,
+    max_word_len: usize,
+    max_sentence_len: usize,
+    /// Time allowed for each lookup; see [`YdtClientBuilder::deadline`].
+    deadline: Duration,
+    /// Custom selectors, or the error from compiling them.
+    selectors: Option<Result<Selectors, InvalidSelector>>,
+    options: TranslationOptions,
+}
+
+impl Default for YdtClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for [`YdtClient`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let client = ydt::YdtClient::builder()
+///     .min_request_interval(Duration::from_millis(500))
+///     .build();
+/// # drop(client);
+/// ```
+#[derive(Default)]
+pub struct YdtClientBuilder {
+    fetcher: Option<Box<dyn Fetcher>>,
+    http: HttpFetcher,
+    min_request_interval: Option<Duration>,
+    on_wait: Option<WaitCallback>,
+    on_page: Option<PageCallback>,
+    abort_after: Option<usize>,
+    cache_capacity: Option<usize>,
+    strict: bool,
+    auto_correct: bool,
+    convert_traditional: bool,
+    skip_numbers: bool,
+    mobile_fallback: Option<bool>,
+    backend: Backend,
+    sources: Vec<Box<dyn TranslationSource>>,
+    cedict_path: Option<PathBuf>,
+    offline: bool,
+    max_word_len: Option<usize>,
+    max_sentence_len: Option<usize>,
+    deadline: Option<Duration>,
+    selectors: Option<SelectorConfig>,
+    options: TranslationOptions,
+}
+
+impl YdtClientBuilder {
+    /// Set the timeout for each HTTP request. Defaults to [`DEFAULT_TIMEOUT`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.timeout(timeout);
+        self
+    }
+
+    /// Give up on a lookup after `deadline`, across all of its
+    /// [sources](Self::source), user agents and [retries](Self::retries), with
+    /// [`YdtError::DeadlineExceeded`]. Defaults to [`DEFAULT_DEADLINE`]; zero
+    /// sets no deadline.
+    ///
+    /// Each request is given at most the time left, even when that is shorter
+    /// than its [`timeout`](Self::timeout), and retries that would wait past
+    /// the deadline are not made. Time held back by the [minimum request
+    /// interval](Self::min_request_interval) does not count. A custom
+    /// [`fetcher`](Self::fetcher) is not interrupted, but no source is tried
+    /// after it overran the deadline.
+    ///
+    /// Other requests, such as those of
+    /// [`fetch_html`](YdtClient::fetch_html),
+    /// [`translate_sentence`](YdtClient::translate_sentence),
+    /// [`suggest`](YdtClient::suggest) and the recordings of
+    /// [`pronunciation_pack`](YdtClient::pronunciation_pack), are each given
+    /// the same time across their retries.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Retry up to `retries` times after network errors and server errors (5xx).
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.http = self.http.retries(retries);
+        self
+    }
+
+    /// Send all requests through the proxy at `url`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.proxy(url);
+        self
+    }
+
+    /// Fail with [`YdtError::ResponseTooLarge`] instead of reading response
+    /// bodies larger than `bytes`. Defaults to [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.http = self.http.max_body_size(bytes);
+        self
+    }
+
+    /// Request result pages from `url` instead of [`DEFAULT_BASE_URL`].
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.base_url(url);
+        self
+    }
+
+    /// Request mobile result pages from `url` instead of [`DEFAULT_MOBILE_URL`].
+    pub fn mobile_url(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.mobile_url(url);
+        self
+    }
+
+    /// Request suggestions from `url` instead of [`DEFAULT_SUGGEST_URL`].
+    pub fn suggest_url(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.suggest_url(url);
+        self
+    }
+
+    /// Request recordings from `url` instead of [`DEFAULT_AUDIO_URL`].
+    pub fn audio_url(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.audio_url(url);
+        self
+    }
+
+    /// Send API requests to `url` instead of [`DEFAULT_API_URL`].
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.http = self.http.api_url(url);
+        self
+    }
+
+    /// Look words up with the official Youdao text translation API, signing
+    /// requests with `credentials`.
+    ///
+    /// With the default [`Backend::Auto`], the API is preferred and result pages
+    /// are scraped only when it fails or finds nothing.
+    pub fn api_credentials(mut self, credentials: Credentials) -> Self {
+        self.http = self.http.api_credentials(credentials);
+        self
+    }
+
+    /// Choose between the official API and scraping. Defaults to
+    /// [`Backend::Auto`].
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Always send `user_agent`; shorthand for [`UaStrategy::Custom`].
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.http = self.http.user_agent(user_agent);
+        self
+    }
+
+    /// Choose which user agents to send. Defaults to
+    /// [`UaStrategy::ProjectThenBrowser`].
+    pub fn ua_strategy(mut self, strategy: UaStrategy) -> Self {
+        self.http = self.http.ua_strategy(strategy);
+        self
+    }
+
+    /// Set the `lang` query parameter. Defaults to [`DEFAULT_LANG`].
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.http = self.http.lang(lang);
+        self
+    }
+
+    /// Send `value` as the `Accept-Language` header, e.g. `en` for English
+    /// section names in raw pages. Defaults to [`DEFAULT_ACCEPT_LANGUAGE`]; an
+    /// empty value sends no header.
+    pub fn accept_language(mut self, value: impl Into<String>) -> Self {
+        self.http = self.http.accept_language(value);
+        self
+    }
+
+    /// Keep cookies set by Youdao in a jar belonging to the client and send them
+    /// with its later lookups. Disabled by default.
+    pub fn cookies(mut self, enabled: bool) -> Self {
+        self.http = self.http.cookies(enabled);
+        self
+    }
+
+    /// Keep cookies across clients and processes in the JSON file at `path`;
+    /// see [`HttpFetcher::cookie_store_path`].
+    pub fn cookie_store_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.http = self.http.cookie_store_path(path);
+        self
+    }
+
+    /// Keep rate limits across clients and processes in the file at `path`;
+    /// see [`HttpFetcher::cooldown_path`].
+    pub fn cooldown_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.http = self.http.cooldown_path(path);
+        self
+    }
+
+    /// Follow at most `max` redirects per request. Defaults to
+    /// [`DEFAULT_MAX_REDIRECTS`].
+    ///
+    /// Exceeding the limit, a redirect loop or a redirect to another site fails
+    /// with [`YdtError::UnexpectedRedirect`].
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.http = self.http.max_redirects(max);
+        self
+    }
+
+    /// Fail with [`YdtError::UnexpectedRedirect`] instead of following any
+    /// redirect; shorthand for `max_redirects(0)`.
+    pub fn no_redirects(mut self) -> Self {
+        self.http = self.http.no_redirects();
+        self
+    }
+
+    /// Keep at most `max` idle connections open to each host; see
+    /// [`HttpFetcher::pool_max_idle_per_host`].
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http = self.http.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Close connections left idle for longer than `timeout`; see
+    /// [`HttpFetcher::pool_idle_timeout`].
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Send TCP keep-alive probes every `interval`; see
+    /// [`HttpFetcher::tcp_keepalive`].
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.http = self.http.tcp_keepalive(interval);
+        self
+    }
+
+    /// Start from the settings in the `YDT_*` environment variables.
+    ///
+    /// See [`Config::from_env`] for the variables read. Builder methods called
+    /// afterwards take precedence over the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::InvalidConfig`] when a variable holds a malformed value.
+    pub fn from_env() -> Result<Self, YdtError> {
+        Ok(Self::default().config(&Config::from_env()?))
+    }
+
+    /// Apply the HTTP and selector settings from `config` that are set.
+    pub fn config(mut self, config: &Config) -> Self {
+        if let Some(timeout) = config.timeout {
+            self = self.timeout(timeout);
+        }
+        if let Some(deadline) = config.deadline {
+            self = self.deadline(deadline);
+        }
+        if let Some(retries) = config.retries {
+            self = self.retries(retries);
+        }
+        if let Some(proxy) = &config.proxy {
+            self = self.proxy(proxy.clone());
+        }
+        if let Some(failures) = config.abort_after {
+            self = self.abort_after(failures);
+        }
+        if let Some(bytes) = config.max_body_size {
+            self = self.max_body_size(bytes);
+        }
+        if let Some(capacity) = config.cache_capacity {
+            self = self.cache_capacity(capacity);
+        }
+        if let Some(base_url) = &config.base_url {
+            self = self.base_url(base_url.clone());
+        }
+        if let Some(user_agent) = &config.user_agent {
+            self = self.user_agent(user_agent.clone());
+        }
+        if let Some(lang) = &config.lang {
+            self = self.lang(lang.clone());
+        }
+        if let Some(accept_language) = &config.accept_language {
+            self = self.accept_language(accept_language.clone());
+        }
+        if let Some(mobile_url) = &config.mobile_url {
+            self = self.mobile_url(mobile_url.clone());
+        }
+        if let Some(mobile_fallback) = config.mobile_fallback {
+            self = self.mobile_fallback(mobile_fallback);
+        }
+        if let Some(skip_numbers) = config.skip_numbers {
+            self = self.skip_numbers(skip_numbers);
+        }
+        if let Some(suggest_url) = &config.suggest_url {
+            self = self.suggest_url(suggest_url.clone());
+        }
+        if let (Some(app_key), Some(app_secret)) = (&config.app_key, &config.app_secret) {
+            self = self.api_credentials(Credentials::new(app_key.clone(), app_secret.clone()));
+        }
+        if let Some(backend) = config.backend {
+            self = self.backend(backend);
+        }
+        if let Some(path) = &config.cedict {
+            self = self.cedict_path(path.clone());
+        }
+        if let Some(selectors) = &config.selectors {
+            self = self.selectors(selectors.clone());
+        }
+        self
+    }
+
+    /// Obtain result pages from `fetcher` instead of Youdao over HTTP.
+    ///
+    /// HTTP settings such as [`timeout`](Self::timeout) do not apply to a custom
+    /// fetcher.
+    pub fn fetcher(mut self, fetcher: impl Fetcher + 'static) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    /// Wait at least `interval` between the start of consecutive requests.
+    ///
+    /// The limit is shared by all threads using the client.
+    pub fn min_request_interval(mut self, interval: Duration) -> Self {
+        self.min_request_interval = Some(interval);
+        self
+    }
+
+    /// Call `callback` with the delay whenever a request is held back by
+    /// [`min_request_interval`](Self::min_request_interval).
+    pub fn on_rate_limit_wait(
+        mut self,
+        callback: impl Fn(Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_wait = Some(Box::new(callback));
+        self
+    }
+
+    /// Call `callback` with the word and page whenever a result page is
+    /// fetched, before it is parsed, e.g. to keep pages for tests or bug
+    /// reports.
+    ///
+    /// Only pages of the desktop site or custom [`fetcher`](Self::fetcher) are
+    /// passed, not those of the mobile site or the API.
+    pub fn on_page(mut self, callback: impl Fn(&str, &str) + Send + Sync + 'static) -> Self {
+        self.on_page = Some(Box::new(callback));
+        self
+    }
+
+    /// Skip the remaining words of batch lookups, such as
+    /// [`translate_many`](YdtClient::translate_many), after `failures`
+    /// consecutive rate limits, `403 Forbidden` responses or server errors.
+    /// Defaults to [`DEFAULT_ABORT_AFTER`]; 0 never skips.
+    ///
+    /// Skipped words fail with [`YdtError::CircuitOpen`] without being looked
+    /// up, until [`YdtClient::reset_circuit_breaker`] is called.
+    pub fn abort_after(mut self, failures: usize) -> Self {
+        self.abort_after = Some(failures);
+        self
+    }
+
+    /// Keep up to `capacity` translations for
+    /// [`translate_cached`](YdtClient::translate_cached). Defaults to
+    /// [`DEFAULT_CACHE_CAPACITY`]; 0 keeps none.
+    pub fn cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Fail lookups with [`YdtError::UnrecognizedLayout`] when the result page is
+    /// only partially recognized, instead of returning what could be parsed.
+    ///
+    /// See [`parse_translation_with_warnings`](crate::parse_translation_with_warnings).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// When a page has no results but suggests corrections, look up the first
+    /// suggestion instead. At most one correction is made per lookup, and the
+    /// original word is recorded in [`Translation::corrected_from`].
+    pub fn auto_correct(mut self, auto_correct: bool) -> Self {
+        self.auto_correct = auto_correct;
+        self
+    }
+
+    /// Look words written in Traditional Chinese up in Simplified Chinese,
+    /// e.g. 學習 as 学习, which often has better results. Characters are
+    /// converted one at a time with [`to_simplified`](crate::to_simplified), and
+    /// the word as given is recorded in [`Translation::converted_from`].
+    pub fn convert_traditional(mut self, convert_traditional: bool) -> Self {
+        self.convert_traditional = convert_traditional;
+        self
+    }
+
+    /// Fail lookups of numbers such as `42` or `3.14` with
+    /// [`YdtError::NumberQuery`] without making a request, as the dictionary
+    /// has no translations for them; see [`is_number`](crate::is_number).
+    pub fn skip_numbers(mut self, skip_numbers: bool) -> Self {
+        self.skip_numbers = skip_numbers;
+        self
+    }
+
+    /// When the desktop result page fails or yields nothing, look the word up on
+    /// Youdao's mobile site, whose simpler layout changes less often; see
+    /// [`parse_mobile_html`](crate::parse_mobile_html).
+    ///
+    /// Enabled by default unless a custom [`fetcher`](Self::fetcher) is set. Pages
+    /// that only suggest corrections are not retried.
+    pub fn mobile_fallback(mut self, mobile_fallback: bool) -> Self {
+        self.mobile_fallback = Some(mobile_fallback);
+        self
+    }
+
+    /// Look words up in `source` when the built-in sources find nothing.
+    ///
+    /// Sources are tried in order until one finds results or suggestions: the
+    /// API when enabled (see [`backend`](Self::backend)), the desktop result page
+    /// or custom [`fetcher`](Self::fetcher), the [mobile
+    /// site](Self::mobile_fallback), then the sources added here in the order
+    /// they were added. When every source fails, the errors are collected into
+    /// [`YdtError::AllSourcesFailed`].
+    pub fn source(mut self, source: impl TranslationSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Load the [CC-CEDICT](crate::cedict) dictionary at `path` when the client
+    /// is built and look Chinese words up in it after every other source.
+    ///
+    /// A dictionary that cannot be read makes lookups reaching it fail with
+    /// [`YdtError::ReadDictionary`].
+    pub fn cedict_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cedict_path = Some(path.into());
+        self
+    }
+
+    /// Only look words up in the [sources](Self::source) and
+    /// [dictionary](Self::cedict_path) added to the builder, without accessing
+    /// the network.
+    ///
+    /// This applies to [`YdtClient::translate`] and its variants; other methods
+    /// still make requests.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Reject words longer than `max` characters with [`YdtError::WordTooLong`].
+    /// Defaults to [`DEFAULT_MAX_WORD_LEN`].
+    pub fn max_word_len(mut self, max: usize) -> Self {
+        self.max_word_len = Some(max);
+        self
+    }
+
+    /// Reject sentences longer than `max` characters given to
+    /// [`YdtClient::translate_sentence`] with [`YdtError::WordTooLong`].
+    /// Defaults to [`DEFAULT_MAX_SENTENCE_LEN`].
+    pub fn max_sentence_len(mut self, max: usize) -> Self {
+        self.max_sentence_len = Some(max);
+        self
+    }
+
+    /// Locate results on the page with `selectors` instead of the built-in ones.
+    ///
+    /// Invalid selectors make every lookup fail with
+    /// [`YdtError::ParseCssSelector`].
+    pub fn selectors(mut self, selectors: SelectorConfig) -> Self {
+        self.selectors = Some(selectors);
+        self
+    }
+
+    /// Options for every lookup, such as parsing the Collins dictionary section
+    /// of result pages; see [`YdtClient::translate_with`] to override them for
+    /// a single lookup. Optional sections are only parsed from desktop result
+    /// pages.
+    pub fn translation_options(mut self, options: TranslationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Build the client.
+    pub fn build(self) -> YdtClient {
+        let mobile_fallback = self.mobile_fallback.unwrap_or(self.fetcher.is_none());
+        let mut sources = Vec::new();
+        match self.backend {
+            _ if self.offline => {}
+            Backend::Auto if self.fetcher.is_none() && self.http.credentials.is_some() => {
+                sources.push(Source::Api { optional: true });
+            }
+            Backend::Api => sources.push(Source::Api { optional: false }),
+            _ => {}
+        }
+        if self.backend != Backend::Api && !self.offline {
+            sources.push(Source::Desktop);
+            if mobile_fallback {
+                sources.push(Source::Mobile);
+            }
+        }
+        sources.extend(self.sources.into_iter().map(Source::Custom));
+        if let Some(path) = &self.cedict_path {
+            sources.push(Source::Custom(cedict::load_source(path)));
+        }
+        YdtClient {
+            fetcher: self.fetcher,
+            http: self.http,
+            rate_limiter: self
+                .min_request_interval
+                .filter(|interval| !interval.is_zero())
+                .map(RateLimiter::new),
+            on_wait: self.on_wait,
+            on_page: self.on_page,
+            breaker: CircuitBreaker::new(self.abort_after.unwrap_or(DEFAULT_ABORT_AFTER)),
+            cache: ResultCache::new(self.cache_capacity.unwrap_or(DEFAULT_CACHE_CAPACITY)),
+            strict: self.strict,
+            auto_correct: self.auto_correct,
+            convert_traditional: self.convert_traditional,
+            skip_numbers: self.skip_numbers,
+            sources,
+            max_word_len: self.max_word_len.unwrap_or(DEFAULT_MAX_WORD_LEN),
+            max_sentence_len: self.max_sentence_len.unwrap_or(DEFAULT_MAX_SENTENCE_LEN),
+            deadline: self.deadline.unwrap_or(DEFAULT_DEADLINE),
+            selectors: self.selectors.map(|selectors| selectors.try_compile()),
+            options: self.options,
+        }
+    }
+}
+
+impl YdtClient {
+    /// Create a client that fetches pages from Youdao over HTTP.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Create a client that obtains result pages from `fetcher`.
+    pub fn with_fetcher(fetcher: impl Fetcher + 'static) -> Self {
+        Self::builder().fetcher(fetcher).build()
+    }
+
+    /// Create a client configured from the `YDT_*` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::InvalidConfig`] when a variable holds a malformed value.
+    pub fn from_env() -> Result<Self, YdtError> {
+        Ok(YdtClientBuilder::from_env()?.build())
+    }
+
+    /// Start building a client with custom settings.
+    pub fn builder() -> YdtClientBuilder {
+        YdtClientBuilder::default()
+    }
+
+    /// The `lang` query parameter sent with requests; see
+    /// [`YdtClientBuilder::lang`].
+    pub fn lang(&self) -> &str {
+        &self.http.lang
+    }
+
+    /// Wait for the request interval, returning how long that took.
+    fn wait_for_rate_limit(&self) -> Duration {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return Duration::ZERO;
+        };
+        let wait = rate_limiter.acquire();
+        if let Some(on_wait) = self.on_wait.as_ref().filter(|_| !wait.is_zero()) {
+            on_wait(wait);
+        }
+        wait
+    }
+
+    fn normalize(&self, word: &str) -> Result<String, YdtError> {
+        normalize_word(word, self.max_word_len)
+    }
+
+    /// Wait for the request interval, then start the deadline of a request
+    /// that is not a dictionary lookup, such as for suggestions.
+    fn start_request(&self) -> Option<Deadline> {
+        self.wait_for_rate_limit();
+        Deadline::start(self.deadline)
+    }
+
+    fn fetch_normalized(&self, word: &str) -> Result<String, YdtError> {
+        let deadline = self.start_request();
+        match &self.fetcher {
+            Some(fetcher) => fetcher.fetch(word),
+            None => self
+                .http
+                .fetch_page(word, PageSource::Desktop, deadline.as_ref()),
+        }
+    }
+
+    /// Check step by step whether the result page endpoint can be reached:
+    /// resolve its host, open a TCP connection and send it a `HEAD` request.
+    ///
+    /// The checks use the client's HTTP settings, such as the proxy and
+    /// timeout, even when the client uses a custom [`Fetcher`]. Failed steps
+    /// are reported in the returned [`Probe`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when the endpoint or proxy URL is invalid or the
+    /// HTTP client cannot be created.
+    pub fn probe(&self) -> Result<Probe, YdtError> {
+        self.http.probe()
+    }
+
+    /// What looking `word` up with `options` would request, source by
+    /// source, without sending anything.
+    ///
+    /// The plan covers the first request to each source; retries and the
+    /// fallback user agent reuse its URL, and looking up a suggestion instead
+    /// with [auto-correction](YdtClientBuilder::auto_correct) is left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected, as it would be by
+    /// [`translate_with`](Self::translate_with), or a request URL cannot be
+    /// built.
+    pub fn plan(&self, word: &str, options: &TranslationOptions) -> Result<RequestPlan, YdtError> {
+        let word = self.normalize(word)?;
+        if self.skip_numbers && is_number(&word) {
+            return Err(YdtError::NumberQuery { word });
+        }
+        let word = if self.convert_traditional {
+            to_simplified(&word)
+        } else {
+            word
+        };
+        let lang = options.lang.as_deref().or(self.options.lang.as_deref());
+        let lang = lang.unwrap_or(&self.http.lang);
+        let without_request = |source: &str| PlannedRequest {
+            source: source.to_string(),
+            url: None,
+            headers: Vec::new(),
+            optional: false,
+        };
+        let requests = self
+            .sources
+            .iter()
+            .map(|source| match source {
+                Source::Api { optional } => {
+                    self.http
+                        .plan_request(&word, PageSource::Api, lang, *optional)
+                }
+                Source::Desktop if self.fetcher.is_some() => Ok(without_request(source.name())),
+                Source::Desktop => self
+                    .http
+                    .plan_request(&word, PageSource::Desktop, lang, false),
+                Source::Mobile => self
+                    .http
+                    .plan_request(&word, PageSource::Mobile, lang, false),
+                Source::Custom(_) => Ok(without_request(source.name())),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(RequestPlan {
+            word,
+            requests,
+            timeout: self.http.timeout,
+            deadline: Some(self.deadline).filter(|deadline| !deadline.is_zero()),
+            retries: self.http.retries,
+            proxy: self.http.proxy.clone(),
+            fallback_user_agent: (self.http.ua_strategy == UaStrategy::ProjectThenBrowser)
+                .then(|| BROWSER_USER_AGENT.to_string()),
+        })
+    }
+
+    /// Fetch the raw result page for `word` without parsing it.
+    ///
+    /// The page is requested the same way as for [`translate`](Self::translate),
+    /// honoring the user agent strategy, timeout, retries and request interval.
+    /// The page is decoded with the charset from the `Content-Type` header or
+    /// the page itself, falling back to UTF-8 with invalid bytes replaced by
+    /// `U+FFFD`; see [`CharsetSource`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected by [`normalize_word`], the
+    /// request fails or Youdao answers with an error status.
+    pub fn fetch_html(&self, word: &str) -> Result<String, YdtError> {
+        self.fetch_normalized(&self.normalize(word)?)
+    }
+
+    /// List up to `limit` completions for `prefix` from Youdao's suggestion
+    /// endpoint.
+    ///
+    /// The endpoint is always requested over HTTP with the client's user agent,
+    /// timeout, proxy, retry and request interval settings, even when the client
+    /// uses a custom [`Fetcher`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `prefix` is rejected by [`normalize_word`], the
+    /// request fails, or the response is not a suggestion payload
+    /// ([`YdtError::InvalidSuggestPayload`]).
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<Suggestion>, YdtError> {
+        let prefix = self.normalize(prefix)?;
+        let deadline = self.start_request();
+        let body = self
+            .http
+            .fetch_suggestions(&prefix, limit, deadline.as_ref())?;
+        let mut suggestions = parse_suggestions(&body)?;
+        suggestions.truncate(limit);
+        Ok(suggestions)
+    }
+
+    /// Gather the phonetics and recordings of `word` in each accent.
+    ///
+    /// The result page is looked up once for the phonetics, as by
+    /// [`translate`](Self::translate), while the recordings are requested from
+    /// the audio endpoint on other threads at the same time. Like
+    /// [`suggest`](Self::suggest), the audio endpoint is always requested
+    /// over HTTP, even when the client uses a custom [`Fetcher`].
+    ///
+    /// A part that fails is left empty and listed in
+    /// [`PronunciationPack::errors`]; a page without phonetics for an accent
+    /// is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] only when `word` is rejected by
+    /// [`normalize_word`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "http")] {
+    /// use ydt::{Accent, YdtClient};
+    ///
+    /// let pack = YdtClient::new().pronunciation_pack("hello")?;
+    /// if let Some(audio) = &pack.get(Accent::Us).audio {
+    ///     std::fs::write("hello.mp3", audio)?;
+    /// }
+    /// for (item, err) in &pack.errors {
+    ///     eprintln!("no {item}: {err}");
+    /// }
+    /// # }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn pronunciation_pack(&self, word: &str) -> Result<PronunciationPack, YdtError> {
+        let word = self.normalize(word)?;
+        let fetch_audio = |accent| {
+            let deadline = self.start_request();
+            self.http.fetch_audio(&word, accent, deadline.as_ref())
+        };
+        let (translation, uk_audio, us_audio) = thread::scope(|scope| {
+            let uk = scope.spawn(|| fetch_audio(Accent::Uk));
+            let us = scope.spawn(|| fetch_audio(Accent::Us));
+            let translation = self.translate(&word);
+            let join = |handle: thread::ScopedJoinHandle<'_, _>| match handle.join() {
+                Ok(result) => result,
+                Err(panic) => panic::resume_unwind(panic),
+            };
+            (translation, join(uk), join(us))
+        });
+
+        let mut pack = PronunciationPack {
+            word,
+            uk: Pronunciation::default(),
+            us: Pronunciation::default(),
+            errors: Vec::new(),
+        };
+        match translation {
+            Ok(translation) => {
+                pack.uk.phonetic = translation.phonetic(Accent::Uk).map(str::to_string);
+                pack.us.phonetic = translation.phonetic(Accent::Us).map(str::to_string);
+            }
+            Err(err) => pack.errors.push((PronunciationItem::Phonetics, err)),
+        }
+        for (accent, audio) in [(Accent::Uk, uk_audio), (Accent::Us, us_audio)] {
+            match audio {
+                Ok(bytes) if bytes.is_empty() => {}
+                Ok(bytes) => {
+                    let slot = match accent {
+                        Accent::Uk => &mut pack.uk.audio,
+                        Accent::Us => &mut pack.us.audio,
+                    };
+                    *slot = Some(bytes);
+                }
+                Err(err) => pack.errors.push((PronunciationItem::Audio(accent), err)),
+            }
+        }
+        Ok(pack)
+    }
+
+    /// Machine-translate a sentence, English to Chinese or Chinese to English.
+    ///
+    /// Youdao picks the direction from the script of `text`, the same way as for
+    /// word lookups. Returns `None` when the page has no machine translation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `text` is rejected by [`normalize_word`], with
+    /// the [sentence limit](YdtClientBuilder::max_sentence_len) rather than the
+    /// word limit, the request fails or Youdao answers with an error status.
+    pub fn translate_sentence(&self, text: &str) -> Result<Option<String>, YdtError> {
+        let text = normalize_word(text, self.max_sentence_len)?;
+        let html = self.fetch_normalized(&text)?;
+        Ok(parse_sentence_with_selectors(&html, self.selectors()?))
+    }
+
+    fn selectors(&self) -> Result<&Selectors, YdtError> {
+        match &self.selectors {
+            None => Ok(default_selectors()),
+            Some(Ok(selectors)) => Ok(selectors),
+            Some(Err(err)) => Err(err.clone().into()),
+        }
+    }
+
+    fn parse(
+        &self,
+        word: &str,
+        html: &str,
+        options: &TranslationOptions,
+    ) -> Result<Translation, YdtError> {
+        let (translation, mut warnings) =
+            parse_translation_with_options(word, html, self.selectors()?, options)?;
+        // Long values are read whole; only missing parts mean a changed layout.
+        warnings.retain(|warning| !matches!(warning, ParseWarning::FieldTooLong { .. }));
+        if self.strict && !warnings.is_empty() {
+            return Err(YdtError::UnrecognizedLayout {
+                word: word.to_string(),
+                warnings,
+            });
+        }
+        Ok(translation)
+    }
+
+    /// Look `word` up, tagging the lookup with the request ID of `options`.
+    fn lookup(
+        &self,
+        word: &str,
+        options: &TranslationOptions,
+    ) -> Result<(Translation, String, FetchInfo), YdtError> {
+        let deadline = Deadline::start(self.deadline);
+        let deadline = deadline.as_ref();
+        let Some(id) = &options.request_id else {
+            return self.lookup_corrected(word, options, deadline);
+        };
+        debug!("request {id}: looking up {word:?}");
+        match self.lookup_corrected(word, options, deadline) {
+            Ok((translation, html, mut info)) => {
+                info.request_id = Some(id.clone());
+                Ok((translation, html, info))
+            }
+            Err(err) => {
+                debug!("request {id}: lookup of {word:?} failed: {err}");
+                Err(err)
+            }
+        }
+    }
+
+    /// Normalize `word` and look it up, re-querying the first suggestion when
+    /// auto-correction applies.
+    fn lookup_corrected(
+        &self,
+        word: &str,
+        options: &TranslationOptions,
+        deadline: Option<&Deadline>,
+    ) -> Result<(Translation, String, FetchInfo), YdtError> {
+        let original = word;
+        let word = self.normalize(word)?;
+        if word != original.split_whitespace().collect::<Vec<_>>().join(" ") {
+            info!("looking up {original:?} as {word:?}");
+        }
+        if self.skip_numbers && is_number(&word) {
+            return Err(YdtError::NumberQuery { word });
+        }
+        let query = if self.convert_traditional {
+            to_simplified(&word)
+        } else {
+            word.clone()
+        };
+        if query != word {
+            log::debug!("looking up {word:?} as {query:?}");
+        }
+        let (mut translation, html, info) = self.lookup_normalized(&query, options, deadline)?;
+        if query != word {
+            translation.converted_from = Some(word.clone());
+        }
+        if self.auto_correct && translation.is_empty() {
+            if let Some(suggestion) = translation.suggestions.first() {
+                log::debug!("{word:?} has no results; looking up {suggestion:?} instead");
+                let corrected = self.normalize(suggestion)?;
+                let (mut translation, html, info) =
+                    self.lookup_normalized(&corrected, options, deadline)?;
+                translation.corrected_from = Some(word);
+                return Ok((translation, html, info));
+            }
+        }
+        Ok((translation, html, info))
+    }
+
+    /// Try the sources in order until one finds results or suggestions.
+    ///
+    /// When none does, the outcome of the first source that is not optional is
+    /// returned, unless every source failed: then a lone error is returned as is
+    /// and several are collected into [`YdtError::AllSourcesFailed`]. Once
+    /// `deadline` passes, no further source is tried and the lookup fails with
+    /// [`YdtError::DeadlineExceeded`], unless a source found no results.
+    fn lookup_normalized(
+        &self,
+        word: &str,
+        options: &TranslationOptions,
+        deadline: Option<&Deadline>,
+    ) -> Result<(Translation, String, FetchInfo), YdtError> {
+        let mut first_empty = None;
+        let mut answered_after_error = false;
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            let name = source.name();
+            if let Some(deadline) = deadline.filter(|deadline| deadline.is_past()) {
+                return match first_empty {
+                    Some(empty) => Ok(empty),
+                    None => Err(deadline.exceeded(word, format!("{name} source"))),
+                };
+            }
+            match self.lookup_in(source, word, options, deadline) {
+                Err(YdtError::DeadlineExceeded {
+                    word,
+                    deadline,
+                    tier,
+                }) => {
+                    return match first_empty {
+                        Some(empty) => Ok(empty),
+                        None => Err(YdtError::DeadlineExceeded {
+                            word,
+                            deadline,
+                            tier: format!("{name} source ({tier})"),
+                        }),
+                    };
+                }
+                Ok(found) if !found.0.is_empty() || !found.0.suggestions.is_empty() => {
+                    if first_empty.is_some() || !errors.is_empty() {
+                        info!("{word:?} found by the {name} source");
+                    }
+                    return Ok(found);
+                }
+                Err(err) if is_configuration_error(&err) => return Err(err),
+                Ok(_) if source.is_optional() => info!("{name} source has no results for {word:?}"),
+                Err(err) if source.is_optional() => {
+                    warn!("{name} source failed for {word:?}: {err}")
+                }
+                Ok(empty) => {
+                    if first_empty.is_none() && errors.is_empty() {
+                        first_empty = Some(empty);
+                    } else if first_empty.is_none() {
+                        answered_after_error = true;
+                    }
+                }
+                Err(err) => {
+                    debug!("{name} source failed for {word:?}: {err}");
+                    errors.push((name.to_string(), err));
+                }
+            }
+        }
+        if let Some(empty) = first_empty {
+            return Ok(empty);
+        }
+        if answered_after_error || errors.len() == 1 {
+            // The first source's error is more telling than the empty results of
+            // the sources after it.
+            return Err(errors.swap_remove(0).1);
+        }
+        Err(YdtError::AllSourcesFailed {
+            word: word.to_string(),
+            errors,
+        })
+    }
+
+    fn lookup_in(
+        &self,
+        source: &Source,
+        word: &str,
+        options: &TranslationOptions,
+        deadline: Option<&Deadline>,
+    ) -> Result<(Translation, String, FetchInfo), YdtError> {
+        let lang = options.lang.as_deref().unwrap_or(&self.http.lang);
+        let (mut translation, page, info) = match source {
+            Source::Api { .. } => {
+                let (json, info) =
+                    self.fetch_page_with_info(word, PageSource::Api, lang, deadline)?;
+                let mut translation = openapi::parse_response(word, &json)?;
+                // The request URL carries the signed credentials; link the
+                // result page instead.
+                translation.source_url =
+                    Some(self.http.page_url(word, PageSource::Desktop, lang)?);
+                (translation, json, info)
+            }
+            Source::Desktop => {
+                let (html, info) =
+                    self.fetch_page_with_info(word, PageSource::Desktop, lang, deadline)?;
+                if let Some(on_page) = &self.on_page {
+                    on_page(word, &html);
+                }
+                // Parsing applies the options already.
+                let mut translation = self.parse(word, &html, options)?;
+                translation.source_url = info.final_url.clone();
+                return Ok((translation, html, info));
+            }
+            Source::Mobile => {
+                let (html, info) =
+                    self.fetch_page_with_info(word, PageSource::Mobile, lang, deadline)?;
+                let mut translation = parse_mobile_html(word, &html)?;
+                translation.source_url = info.final_url.clone();
+                (translation, html, info)
+            }
+            Source::Custom(source) => {
+                let start = Instant::now();
+                let translation = source.lookup(word, lang)?;
+                let info = FetchInfo::local(PageSource::Custom, start.elapsed());
+                (translation, String::new(), info)
+            }
+        };
+        options.apply(&mut translation);
+        Ok((translation, page, info))
+    }
+
+    fn fetch_page_with_info(
+        &self,
+        word: &str,
+        source: PageSource,
+        lang: &str,
+        deadline: Option<&Deadline>,
+    ) -> Result<(String, FetchInfo), YdtError> {
+        let wait = self.wait_for_rate_limit();
+        if let Some(deadline) = deadline {
+            deadline.extend(wait);
+        }
+        match (&self.fetcher, source) {
+            (Some(fetcher), PageSource::Desktop) => fetcher.fetch_with_info(word),
+            _ => self.http.fetch_page_with_info(word, source, lang, deadline),
+        }
+    }
+
+    /// Fetch and parse the translation for `word`.
+    ///
+    /// Surrounding whitespace is trimmed and internal runs of whitespace are
+    /// collapsed before the request is made; see [`normalize_word`]. The word is
+    /// looked up in each source in turn until one finds it; see
+    /// [`YdtClientBuilder::source`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected, or fetching the page or
+    /// parsing it fails.
+    pub fn translate(&self, word: &str) -> Result<Translation, YdtError> {
+        self.lookup(word, &self.options)
+            .map(|(translation, _, _)| translation)
+    }
+
+    /// Like [`translate`](Self::translate), with `options` taking precedence
+    /// over the [options the client was built
+    /// with](YdtClientBuilder::translation_options) for this lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected, or fetching the page or
+    /// parsing it fails.
+    pub fn translate_with(
+        &self,
+        word: &str,
+        options: &TranslationOptions,
+    ) -> Result<Translation, YdtError> {
+        let options = self.options.clone().merge(options.clone());
+        self.lookup(word, &options)
+            .map(|(translation, _, _)| translation)
+    }
+
+    /// The meaning of the first definition of `word`, such as `你好；喂` for
+    /// `hello`, or `None` when it has no definitions.
+    ///
+    /// Only the first definition is collected from the page and no optional
+    /// sections are parsed, which makes this cheaper than
+    /// [`translate`](Self::translate) when nothing else is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected, or fetching the page or
+    /// parsing it fails.
+    pub fn translate_first(&self, word: &str) -> Result<Option<String>, YdtError> {
+        let options = self.options.clone().merge(TranslationOptions {
+            include_collins: Some(false),
+            include_related: Some(false),
+            max_definitions: Some(1),
+            group_by_pos: Some(false),
+            strategy: Some(LookupStrategy::Auto),
+            ..TranslationOptions::default()
+        });
+        let (translation, _, _) = self.lookup(word, &options)?;
+        Ok(translation
+            .entries
+            .into_iter()
+            .next()
+            .map(|entry| entry.meaning))
+    }
+
+    /// Like [`translate`](Self::translate), but also return the page the
+    /// translation was parsed from, e.g. to keep it when nothing was found.
+    ///
+    /// The page is empty when the translation came from a custom
+    /// [`TranslationSource`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when fetching the page or parsing it fails.
+    pub fn translate_with_html(&self, word: &str) -> Result<(Translation, String), YdtError> {
+        self.lookup(word, &self.options)
+            .map(|(translation, html, _)| (translation, html))
+    }
+
+    /// Like [`translate`](Self::translate), but also return details about how the
+    /// page was fetched.
+    ///
+    /// Time spent waiting for the [minimum request
+    /// interval](YdtClientBuilder::min_request_interval) is not included in
+    /// [`FetchInfo::elapsed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when fetching the page or parsing it fails.
+    pub fn translate_detailed(&self, word: &str) -> Result<(Translation, FetchInfo), YdtError> {
+        self.lookup(word, &self.options)
+            .map(|(translation, _, info)| (translation, info))
+    }
+
+    /// Like [`translate`](Self::translate), but also return both the page the
+    /// translation was parsed from, as [`translate_with_html`] does, and
+    /// details about how it was fetched, as [`translate_detailed`] does.
+    ///
+    /// [`translate_with_html`]: Self::translate_with_html
+    /// [`translate_detailed`]: Self::translate_detailed
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when fetching the page or parsing it fails.
+    pub fn translate_with_page(
+        &self,
+        word: &str,
+    ) -> Result<(Translation, String, FetchInfo), YdtError> {
+        self.lookup(word, &self.options)
+    }
+
+    /// Let batch lookups skipped after repeated failures proceed again; see
+    /// [`YdtClientBuilder::abort_after`].
+    pub fn reset_circuit_breaker(&self) {
+        self.breaker.reset();
+    }
+
+    /// Like [`translate`](Self::translate), keeping the translation in memory
+    /// and answering later calls for the same word with it, shared rather
+    /// than copied.
+    ///
+    /// Up to [`cache_capacity`](YdtClientBuilder::cache_capacity)
+    /// translations are kept, dropping the one cached first when full.
+    /// Failed lookups are not kept. A word looked up by several threads at
+    /// once may be fetched more than once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError`] when `word` is rejected, or fetching the page or
+    /// parsing it fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # struct Pages;
+    /// # impl ydt::Fetcher for Pages {
+    /// #     fn fetch(&self, word: &str) -> Result<String, ydt::YdtError> {
+    /// #         Ok(format!(r#"<li class="word-exp-ce mcols-layout"><a class="point">{word}</a></li>"#))
+    /// #     }
+    /// # }
+    /// use std::sync::Arc;
+    ///
+    /// let client = ydt::YdtClient::with_fetcher(Pages);
+    /// let first = client.translate_cached("你好")?;
+    /// let second = client.translate_cached(" 你好 ")?;
+    /// assert!(Arc::ptr_eq(&first, &second));
+    /// # Ok::<(), ydt::YdtError>(())
+    /// ```
+    pub fn translate_cached(&self, word: &str) -> Result<Arc<Translation>, YdtError> {
+        let key = normalize_word(word, self.max_word_len)?;
+        if let Some(translation) = self.cache.get(&key) {
+            return Ok(translation);
+        }
+        let translation = Arc::new(self.translate(&key)?);
+        self.cache.insert(key, Arc::clone(&translation));
+        Ok(translation)
+    }
+
+    /// Forget the translations kept by
+    /// [`translate_cached`](Self::translate_cached).
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Look up a word of a batch with `options`, unless the circuit breaker is
+    /// open.
+    fn translate_in_batch(
+        &self,
+        word: &str,
+        options: &TranslationOptions,
+    ) -> Result<Translation, YdtError> {
+        self.breaker.check(word)?;
+        let result = self.translate_with(word, options);
+        self.breaker.record(&result);
+        result
+    }
+
+    /// Look up each word in turn, passing every result to `on_result` as soon as it
+    /// is available.
+    ///
+    /// Words are consumed lazily, so `words` may be a stream such as lines of stdin.
+    /// After repeated failures the remaining words are skipped; see
+    /// [`YdtClientBuilder::abort_after`].
+    pub fn translate_each<I, S, F>(&self, words: I, mut on_result: F)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(&str, Result<Translation, YdtError>),
+    {
+        for word in words {
+            let word = word.as_ref();
+            on_result(
+                word,
+                self.translate_in_batch(word, &TranslationOptions::default()),
+            );
+        }
+    }
+
+    /// Look up words using up to `concurrency` parallel requests, passing every
+    /// result to `on_result` on the calling thread.
+    ///
+    /// With [`ResultOrder::Input`], a result is reported once all results for
+    /// earlier words have been reported; with [`ResultOrder::Completion`], results
+    /// are reported as soon as they are available. As with
+    /// [`translate_each`](Self::translate_each), the remaining words are skipped
+    /// after repeated failures.
+    pub fn translate_each_concurrent<I, S, F>(
+        &self,
+        words: I,
+        concurrency: usize,
+        order: ResultOrder,
+        mut on_result: F,
+    ) where
+        I: IntoIterator<Item = S>,
+        I::IntoIter: Send,
+        S: AsRef<str> + Send,
+        F: FnMut(&str, Result<Translation, YdtError>),
+    {
+        let words = words
+            .into_iter()
+            .map(|word| (word, TranslationOptions::default()));
+        self.translate_each_concurrent_with(words, concurrency, order, |word, _, result| {
+            on_result(word, result);
+        });
+    }
+
+    /// Like [`translate_each_concurrent`](Self::translate_each_concurrent),
+    /// with options of each word taking precedence over the [options the
+    /// client was built with](YdtClientBuilder::translation_options), as with
+    /// [`translate_with`](Self::translate_with). `on_result` is passed the
+    /// options of the word too.
+    pub fn translate_each_concurrent_with<I, S, F>(
+        &self,
+        words: I,
+        concurrency: usize,
+        order: ResultOrder,
+        mut on_result: F,
+    ) where
+        I: IntoIterator<Item = (S, TranslationOptions)>,
+        I::IntoIter: Send,
+        S: AsRef<str> + Send,
+        F: FnMut(&str, &TranslationOptions, Result<Translation, YdtError>),
+    {
+        let words = Mutex::new(words.into_iter().enumerate());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..concurrency.max(1) {
+                let sender = sender.clone();
+                let words = &words;
+                scope.spawn(move || loop {
+                    let next = words.lock().map(|mut words| words.next());
+                    let Ok(Some((index, (word, options)))) = next else {
+                        break;
+                    };
+                    let result = self.translate_in_batch(word.as_ref(), &options);
+                    if sender.send((index, (word, options), result)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut pending = BTreeMap::new();
+            let mut next_index = 0;
+            for (index, (word, options), result) in receiver {
+                if order == ResultOrder::Completion {
+                    on_result(word.as_ref(), &options, result);
+                    continue;
+                }
+                pending.insert(index, (word, options, result));
+                while let Some((word, options, result)) = pending.remove(&next_index) {
+                    on_result(word.as_ref(), &options, result);
+                    next_index += 1;
+                }
+            }
+        });
+    }
+
+    /// Look up `words` using up to `concurrency` parallel requests.
+    ///
+    /// Results are returned in the same order as `words`.
+    pub fn translate_many<S>(
+        &self,
+        words: &[S],
+        concurrency: usize,
+    ) -> Vec<Result<Translation, YdtError>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        let mut results = Vec::with_capacity(words.len());
+        self.translate_each_concurrent(
+            words.iter().map(AsRef::as_ref),
+            concurrency,
+            ResultOrder::Input,
+            |_, result| results.push(result),
+        );
+        results
+    }
+
+    /// Like [`translate_many`](Self::translate_many), but stop starting lookups
+    /// once `cancel` is set, e.g. from a Ctrl-C handler.
+    ///
+    /// Lookups already in progress are finished, so the results are those of a
+    /// prefix of `words`: the words from `results.len()` on were not looked up.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// let cancel = AtomicBool::new(false);
+    /// let words = ["hello", "world"];
+    /// let results = ydt::YdtClient::new().translate_many_with_cancel(&words, 2, &cancel);
+    /// println!("{} of {} words looked up", results.len(), words.len());
+    /// ```
+    pub fn translate_many_with_cancel<S>(
+        &self,
+        words: &[S],
+        concurrency: usize,
+        cancel: &AtomicBool,
+    ) -> Vec<Result<Translation, YdtError>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        let mut results = Vec::with_capacity(words.len());
+        self.translate_each_concurrent(
+            words
+                .iter()
+                .map(AsRef::as_ref)
+                .take_while(|_| !cancel.load(Ordering::SeqCst)),
+            concurrency,
+            ResultOrder::Input,
+            |_, result| results.push(result),
+        );
+        results
+    }
+
+    /// Like [`translate_many`](Self::translate_many), reporting a [`ProgressEvent`]
+    /// to `progress` after each lookup and once all lookups have finished.
+    pub fn translate_many_with_progress<S, P>(
+        &self,
+        words: &[S],
+        concurrency: usize,
+        mut progress: P,
+    ) -> Vec<Result<Translation, YdtError>>
+    where
+        S: AsRef<str> + Sync,
+        P: FnMut(ProgressEvent<'_>),
+    {
+        let total = words.len();
+        let mut results = Vec::with_capacity(total);
+        let mut failed = 0;
+        self.translate_each_concurrent(
+            words.iter().map(AsRef::as_ref),
+            concurrency,
+            ResultOrder::Input,
+            |word, result| {
+                if result.is_err() {
+                    failed += 1;
+                }
+                results.push(result);
+                progress(ProgressEvent::Completed {
+                    word,
+                    completed: results.len(),
+                    failed,
+                    total,
+                });
+            },
+        );
+        progress(ProgressEvent::Finished {
+            completed: results.len(),
+            failed,
+        });
+        results
+    }
+}
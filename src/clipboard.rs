@@ -0,0 +1,64 @@
+//! The system clipboard, for `--clip`, `--copy` and `--watch-clipboard`.
+//!
+//! Without the `clipboard` feature both directions fail with an error saying
+//! so, as they do in sessions without a clipboard, e.g. over SSH.
+
+#[cfg(feature = "clipboard")]
+use crate::watch::ClipboardSource;
+
+/// The text on the clipboard.
+#[cfg(feature = "clipboard")]
+pub fn read() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|err| format!("Failed to read the clipboard: {err}"))
+}
+
+/// Replace the clipboard contents with `text`.
+///
+/// On X11 the contents are served by this process, so they are handed to a
+/// clipboard manager when it exits and lost without one.
+#[cfg(feature = "clipboard")]
+pub fn write(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|err| format!("Failed to copy to the clipboard: {err}"))
+}
+
+/// The system clipboard, kept open while it is watched.
+#[cfg(feature = "clipboard")]
+pub struct System(arboard::Clipboard);
+
+#[cfg(feature = "clipboard")]
+impl System {
+    pub fn open() -> Result<Self, String> {
+        arboard::Clipboard::new()
+            .map(Self)
+            .map_err(|err| format!("Failed to open the clipboard: {err}"))
+    }
+}
+
+#[cfg(feature = "clipboard")]
+impl ClipboardSource for System {
+    fn text(&mut self) -> Result<String, String> {
+        match self.0.get_text() {
+            Ok(text) => Ok(text),
+            // Images and other contents without text.
+            Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+            Err(err) => Err(format!("Failed to read the clipboard: {err}")),
+        }
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn read() -> Result<String, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn write(_text: &str) -> Result<(), String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub const UNSUPPORTED: &str = "ydt was built without clipboard support (the `clipboard` feature)";
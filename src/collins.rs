@@ -0,0 +1,53 @@
+//! The Collins dictionary section of result pages: numbered senses with
+//! English and Chinese definitions and bilingual examples.
+
+use scraper::Html;
+
+use crate::selectors::Selectors;
+use crate::translation::{CollinsSense, Example, ParseWarning};
+use crate::{first_text, MAX_PARSED_ITEMS};
+
+/// Parse up to `max` senses, skipping those without an English definition.
+pub(crate) fn parse_collins(
+    document: &Html,
+    selectors: &Selectors,
+    max: usize,
+    warnings: &mut Vec<ParseWarning>,
+) -> Vec<CollinsSense> {
+    let mut senses = Vec::new();
+    for (i, sense) in document
+        .select(&selectors.collins_sense)
+        .take(MAX_PARSED_ITEMS)
+        .enumerate()
+    {
+        if senses.len() == max {
+            break;
+        }
+        let en_def = first_text(sense, &selectors.collins_en_def);
+        if en_def.is_empty() {
+            warnings.push(ParseWarning::CollinsDefinitionMissing);
+            continue;
+        }
+        let number = first_text(sense, &selectors.collins_number)
+            .trim_end_matches('.')
+            .parse()
+            .unwrap_or(i + 1);
+        let examples = sense
+            .select(&selectors.example)
+            .take(MAX_PARSED_ITEMS)
+            .map(|example| Example {
+                en: first_text(example, &selectors.example_en),
+                zh: first_text(example, &selectors.example_zh),
+            })
+            .filter(|example| !example.en.is_empty())
+            .collect();
+        senses.push(CollinsSense {
+            number,
+            pos: first_text(sense, &selectors.collins_pos),
+            en_def,
+            zh_def: first_text(sense, &selectors.collins_zh_def),
+            examples,
+        });
+    }
+    senses
+}
@@ -0,0 +1,90 @@
+//! Decompressing response bodies, and recognizing bodies that arrive
+//! compressed without saying so.
+
+use std::io::{self, Read};
+
+use flate2::read::GzDecoder;
+
+/// Value of the `Accept-Encoding` header sent with every request: the
+/// encodings [`decompress`] decodes.
+pub(crate) const ACCEPT_ENCODING: &str = "gzip, br";
+
+/// Size of the buffer the Brotli decoder reads through.
+const BROTLI_BUFFER_LEN: usize = 4096;
+
+/// Why a body could not be decompressed.
+#[derive(Debug)]
+pub(crate) enum Failure {
+    /// The body is compressed with an encoding that is not decoded, named
+    /// either by the `Content-Encoding` header or by its leading bytes.
+    Compressed(String),
+    /// The body did not decode as the encoding it was sent with.
+    Corrupt(io::Error),
+    /// The decompressed body is larger than the limit.
+    TooLarge,
+}
+
+/// A decompressed body, with the `Content-Encoding` it was sent with.
+pub(crate) struct Decompressed {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) encoding: Option<&'static str>,
+}
+
+/// Decompress `bytes` sent with the `Content-Encoding` `encoding`, keeping
+/// at most `limit` bytes of the result.
+///
+/// Bodies that still start with the magic bytes of gzip or zstd afterwards,
+/// usually because a proxy dropped the header, are reported as
+/// [`Failure::Compressed`] rather than decoded as text. Brotli streams have
+/// no such signature, so they cannot be recognized without the header.
+pub(crate) fn decompress(
+    bytes: Vec<u8>,
+    encoding: Option<&str>,
+    limit: usize,
+) -> Result<Decompressed, Failure> {
+    let encoding = encoding
+        .map(str::trim)
+        .filter(|encoding| !encoding.is_empty() && !encoding.eq_ignore_ascii_case("identity"));
+    let (bytes, encoding) = match encoding {
+        None => (bytes, None),
+        Some(encoding) if encoding.eq_ignore_ascii_case("gzip") => (
+            read_limited(GzDecoder::new(&bytes[..]), limit)?,
+            Some("gzip"),
+        ),
+        Some(encoding) if encoding.eq_ignore_ascii_case("br") => (
+            read_limited(
+                brotli_decompressor::Decompressor::new(&bytes[..], BROTLI_BUFFER_LEN),
+                limit,
+            )?,
+            Some("br"),
+        ),
+        Some(encoding) => return Err(Failure::Compressed(encoding.to_ascii_lowercase())),
+    };
+    if let Some(sniffed) = sniff(&bytes) {
+        return Err(Failure::Compressed(sniffed.to_string()));
+    }
+    Ok(Decompressed { bytes, encoding })
+}
+
+/// The compression format `bytes` start with the signature of.
+fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        Some("gzip")
+    } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Some("zstd")
+    } else {
+        None
+    }
+}
+
+fn read_limited(reader: impl Read, limit: usize) -> Result<Vec<u8>, Failure> {
+    let mut bytes = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(Failure::Corrupt)?;
+    if bytes.len() > limit {
+        return Err(Failure::TooLarge);
+    }
+    Ok(bytes)
+}
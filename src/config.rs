@@ -0,0 +1,612 @@
+//! Configuration file and environment variable support.
+//!
+//! The configuration file is TOML. For the command-line tool, values from the file
+//! override built-in defaults, `YDT_*` environment variables override the file,
+//! and command-line flags override everything; see [`Config::merge`].
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use reqwest::header::HeaderValue;
+use toml::{Table, Value};
+use url::Url;
+
+use crate::{Accent, Backend, ColorChoice, OutputFormat, SelectorConfig, YdtError};
+
+/// Commented configuration template written by `ydt config init`.
+pub const TEMPLATE: &str = r#"# ydt configuration file.
+# Command-line flags take precedence over values set here.
+
+# Timeout for each HTTP request, in seconds.
+# timeout = 10
+
+# Time allowed for a whole lookup, across fallbacks and retries, in seconds.
+# deadline = 15
+
+# Number of retries after network errors and server errors.
+# retries = 0
+
+# Skip the rest of a batch after this many consecutive rate limits, 403s or
+# server errors; 0 never skips.
+# abort_after = 5
+
+# Proxy for all requests.
+# proxy = "http://127.0.0.1:8080"
+
+# Largest response body read, in bytes.
+# max_body_size = 5242880
+
+# Only show phonetics for this accent: "uk" or "us".
+# accent = "us"
+
+# Default output format: "plain", "tsv" or "ndjson".
+# format = "plain"
+
+# When to color output: "auto" for terminals only, "always" or "never".
+# color = "auto"
+
+# Translations a client keeps in memory for repeated lookups of the same word;
+# 0 keeps none.
+# cache_capacity = 256
+
+# Longest definition, gloss or web translation shown whole, in characters;
+# longer ones are cut short with "…" in text output but kept whole in JSON.
+# max_field_len = 500
+
+# Machine-translate input with at least this many words instead of looking it
+# up in the dictionary; 0 only does so for punctuated sentences.
+# sentence_threshold = 4
+
+# Result page endpoint.
+# base_url = "https://www.youdao.com/result"
+
+# User agent sent instead of the project one.
+# user_agent = "my-tool/1.0"
+
+# Value of the `lang` query parameter.
+# lang = "en"
+
+# Accept-Language header; "" sends none.
+# accept_language = "zh-CN,zh;q=0.9,en;q=0.8"
+
+# Mobile result page endpoint, tried when the result page yields nothing.
+# mobile_url = "https://m.youdao.com/dict"
+
+# Set to false to never fall back to the mobile site.
+# mobile_fallback = true
+
+# Set to false to not keep cookies in ~/.cache/ydt/cookies.json between runs.
+# cookie_file = true
+
+# Set to true to skip numbers such as 42 without a request, as the dictionary
+# has no translations for them.
+# skip_numbers = false
+
+# Credentials for the official Youdao text translation API. When both are
+# set, the API is preferred over scraping result pages.
+# app_key = "your-app-key"
+# app_secret = "your-app-secret"
+
+# Where words are looked up: "auto", "scrape" or "api".
+# backend = "auto"
+
+# Suggestion endpoint used by `ydt suggest`.
+# suggest_url = "https://dict.youdao.com/suggest"
+
+# CC-CEDICT dictionary file, consulted for Chinese words when Youdao has no
+# results and used by `--offline`.
+# cedict = "/usr/share/cedict/cedict_ts.u8"
+
+# CSS selectors used to find results on the page, in case Youdao's markup
+# changes. Unset selectors keep their defaults.
+# [selectors]
+# trans_container = "div.trans-container"
+# phone = "div.per-phone"
+# phone_label = "span"
+# phonetic = "span.phonetic"
+# word_exp = "li.word-exp"
+# pos = "span.pos"
+# trans = "span.trans"
+# word_exp_ce = "li.word-exp-ce.mcols-layout"
+# point = "a.point"
+# headword = "div.word-head div.title"
+# lemma_note = "div.word-head .tips"
+# frequency = "div.word-head .star"
+# suggestion = "div.typo-rel a.title"
+# sentence = "div.fanyi .trans-content"
+# collins_sense = "div.collins li.mcols-layout"
+# collins_number = "span.index"
+# collins_pos = ".collins-def span.pos"
+# collins_en_def = "span.def-en"
+# collins_zh_def = "span.def-ch"
+# example = "div.exam"
+# example_en = "div.sen-eng"
+# example_zh = "div.sen-ch"
+# related_group = "div.rel_word .wordGroup"
+# related_pos = ".contentTitle"
+# related_word = "a.search-js"
+# pinyin_candidate = "li.pinyin-candidate"
+# candidate_pinyin = "span.pinyin"
+# candidate_gloss = "p.word-exp_tran"
+# idiom = "div.idiom"
+# idiom_pinyin = ".idiom-pinyin"
+# idiom_explanation = ".idiom-explain"
+# idiom_source = ".idiom-source"
+# idiom_example = ".idiom-example"
+# abbreviation = "div.abbr li"
+# abbreviation_full = ".abbr-full"
+# abbreviation_gloss = ".abbr-trans"
+# conversion = "div.num-conversion li"
+# web_translation = "div#webTrans .wt-container .title"
+"#;
+
+/// Settings loaded from a configuration file or from command-line flags.
+///
+/// Every field is optional; unset fields fall back to the next layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// Timeout for each HTTP request.
+    pub timeout: Option<Duration>,
+    /// Time allowed for a whole lookup.
+    pub deadline: Option<Duration>,
+    /// Number of retries after transient failures.
+    pub retries: Option<u32>,
+    /// Consecutive failures after which batch lookups are skipped.
+    pub abort_after: Option<usize>,
+    /// Proxy URL for all requests.
+    pub proxy: Option<String>,
+    /// Largest response body read, in bytes.
+    pub max_body_size: Option<usize>,
+    /// Only show phonetics for this accent.
+    pub accent: Option<Accent>,
+    /// Default output format.
+    pub format: Option<OutputFormat>,
+    /// When to color output.
+    pub color: Option<ColorChoice>,
+    /// Number of translations kept for repeated lookups; see
+    /// [`YdtClientBuilder::cache_capacity`](crate::YdtClientBuilder::cache_capacity).
+    pub cache_capacity: Option<usize>,
+    /// Number of words from which input is machine-translated as a sentence.
+    pub sentence_threshold: Option<usize>,
+    /// Longest value rendered whole; see
+    /// [`TranslationOptions::max_field_len`](crate::TranslationOptions::max_field_len).
+    pub max_field_len: Option<usize>,
+    /// Result page endpoint.
+    pub base_url: Option<String>,
+    /// User agent sent instead of the project one.
+    pub user_agent: Option<String>,
+    /// Value of the `lang` query parameter.
+    pub lang: Option<String>,
+    /// `Accept-Language` header.
+    pub accept_language: Option<String>,
+    /// Mobile result page endpoint.
+    pub mobile_url: Option<String>,
+    /// Whether to fall back to the mobile site.
+    pub mobile_fallback: Option<bool>,
+    /// Whether the command-line tool keeps cookies in a file between runs.
+    pub cookie_file: Option<bool>,
+    /// Whether to skip numbers without a request.
+    pub skip_numbers: Option<bool>,
+    /// Suggestion endpoint.
+    pub suggest_url: Option<String>,
+    /// App key for the official API.
+    pub app_key: Option<String>,
+    /// App secret for the official API.
+    pub app_secret: Option<String>,
+    /// Where words are looked up.
+    pub backend: Option<Backend>,
+    /// CC-CEDICT dictionary file.
+    pub cedict: Option<PathBuf>,
+    /// CSS selectors, from the `[selectors]` table.
+    pub selectors: Option<SelectorConfig>,
+}
+
+fn invalid(key: &str, message: impl Into<String>) -> YdtError {
+    YdtError::ConfigFile {
+        key: Some(key.to_string()),
+        message: message.into(),
+    }
+}
+
+/// The positive number of seconds `value` of `key` holds.
+fn seconds(key: &str, value: &Value) -> Result<Duration, YdtError> {
+    let secs = match value {
+        Value::Integer(secs) => *secs as f64,
+        Value::Float(secs) => *secs,
+        _ => return Err(invalid(key, "expected a number of seconds")),
+    };
+    if secs.is_nan() || secs <= 0.0 {
+        return Err(invalid(key, "expected a positive number of seconds"));
+    }
+    Duration::try_from_secs_f64(secs).map_err(|_| invalid(key, "too many seconds"))
+}
+
+fn secs_to_duration(secs: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|_| secs > 0.0)
+}
+
+fn is_valid_url(url: &str) -> bool {
+    Url::parse(url).is_ok()
+}
+
+fn is_valid_lang(lang: &str) -> bool {
+    !lang.is_empty()
+        && lang
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+}
+
+/// Parse the `[selectors]` table; unset selectors keep their defaults.
+fn parse_selectors(table: &Table) -> Result<SelectorConfig, YdtError> {
+    let mut selectors = SelectorConfig::default();
+    for (name, value) in table {
+        let key = format!("selectors.{name}");
+        let field = match name.as_str() {
+            "trans_container" => &mut selectors.trans_container,
+            "phone" => &mut selectors.phone,
+            "phone_label" => &mut selectors.phone_label,
+            "phonetic" => &mut selectors.phonetic,
+            "word_exp" => &mut selectors.word_exp,
+            "pos" => &mut selectors.pos,
+            "trans" => &mut selectors.trans,
+            "word_exp_ce" => &mut selectors.word_exp_ce,
+            "point" => &mut selectors.point,
+            "headword" => &mut selectors.headword,
+            "lemma_note" => &mut selectors.lemma_note,
+            "frequency" => &mut selectors.frequency,
+            "suggestion" => &mut selectors.suggestion,
+            "sentence" => &mut selectors.sentence,
+            "collins_sense" => &mut selectors.collins_sense,
+            "collins_number" => &mut selectors.collins_number,
+            "collins_pos" => &mut selectors.collins_pos,
+            "collins_en_def" => &mut selectors.collins_en_def,
+            "collins_zh_def" => &mut selectors.collins_zh_def,
+            "example" => &mut selectors.example,
+            "example_en" => &mut selectors.example_en,
+            "example_zh" => &mut selectors.example_zh,
+            "related_group" => &mut selectors.related_group,
+            "related_pos" => &mut selectors.related_pos,
+            "related_word" => &mut selectors.related_word,
+            "pinyin_candidate" => &mut selectors.pinyin_candidate,
+            "candidate_pinyin" => &mut selectors.candidate_pinyin,
+            "candidate_gloss" => &mut selectors.candidate_gloss,
+            "idiom" => &mut selectors.idiom,
+            "idiom_pinyin" => &mut selectors.idiom_pinyin,
+            "idiom_explanation" => &mut selectors.idiom_explanation,
+            "idiom_source" => &mut selectors.idiom_source,
+            "idiom_example" => &mut selectors.idiom_example,
+            "abbreviation" => &mut selectors.abbreviation,
+            "abbreviation_full" => &mut selectors.abbreviation_full,
+            "abbreviation_gloss" => &mut selectors.abbreviation_gloss,
+            "conversion" => &mut selectors.conversion,
+            "web_translation" => &mut selectors.web_translation,
+            _ => return Err(invalid(&key, "unknown key")),
+        };
+        *field = expect_str(&key, value)?.to_string();
+    }
+    Ok(selectors)
+}
+
+fn expect_str<'a>(key: &str, value: &'a Value) -> Result<&'a str, YdtError> {
+    value.as_str().ok_or_else(|| {
+        invalid(
+            key,
+            format!("expected a string, found {}", value.type_str()),
+        )
+    })
+}
+
+impl Config {
+    /// Default location of the configuration file.
+    ///
+    /// This is `$XDG_CONFIG_HOME/ydt/config.toml` (falling back to
+    /// `~/.config/ydt/config.toml`), or `%APPDATA%\ydt\config.toml` on Windows.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = if cfg!(windows) {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+        base.map(|base| base.join("ydt").join("config.toml"))
+    }
+
+    /// Load the configuration file at `path`.
+    ///
+    /// A missing file yields an empty configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::ReadConfig`] when the file cannot be read and
+    /// [`YdtError::ConfigFile`] when it is invalid.
+    pub fn load(path: &Path) -> Result<Self, YdtError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(YdtError::ReadConfig(err)),
+        }
+    }
+
+    /// Parse a configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::ConfigFile`] naming the offending key when a value has
+    /// the wrong type or an unknown key is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let config = ydt::config::Config::from_toml("timeout = 5\naccent = \"us\"").unwrap();
+    /// assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+    /// assert_eq!(config.accent, Some(ydt::Accent::Us));
+    ///
+    /// let err = ydt::config::Config::from_toml("retries = -1").unwrap_err();
+    /// assert!(err.to_string().contains("`retries`"));
+    /// ```
+    pub fn from_toml(text: &str) -> Result<Self, YdtError> {
+        let table: Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| YdtError::ConfigFile {
+                key: None,
+                message: err.message().to_string(),
+            })?;
+
+        let mut config = Self::default();
+        for (key, value) in &table {
+            match key.as_str() {
+                "timeout" => config.timeout = Some(seconds(key, value)?),
+                "deadline" => config.deadline = Some(seconds(key, value)?),
+                "retries" => {
+                    let retries = value
+                        .as_integer()
+                        .and_then(|retries| u32::try_from(retries).ok())
+                        .ok_or_else(|| invalid(key, "expected a non-negative integer"))?;
+                    config.retries = Some(retries);
+                }
+                "abort_after" => {
+                    let failures = value
+                        .as_integer()
+                        .and_then(|failures| usize::try_from(failures).ok())
+                        .ok_or_else(|| invalid(key, "expected a non-negative integer"))?;
+                    config.abort_after = Some(failures);
+                }
+                "max_body_size" => {
+                    let bytes = value
+                        .as_integer()
+                        .and_then(|bytes| usize::try_from(bytes).ok())
+                        .filter(|bytes| *bytes > 0)
+                        .ok_or_else(|| invalid(key, "expected a positive number of bytes"))?;
+                    config.max_body_size = Some(bytes);
+                }
+                "cache_capacity" => {
+                    let capacity = value
+                        .as_integer()
+                        .and_then(|capacity| usize::try_from(capacity).ok())
+                        .ok_or_else(|| invalid(key, "expected a non-negative integer"))?;
+                    config.cache_capacity = Some(capacity);
+                }
+                "sentence_threshold" => {
+                    let threshold = value
+                        .as_integer()
+                        .and_then(|threshold| usize::try_from(threshold).ok())
+                        .ok_or_else(|| invalid(key, "expected a non-negative integer"))?;
+                    config.sentence_threshold = Some(threshold);
+                }
+                "max_field_len" => {
+                    let len = value
+                        .as_integer()
+                        .and_then(|len| usize::try_from(len).ok())
+                        .filter(|len| *len > 0)
+                        .ok_or_else(|| invalid(key, "expected a positive number of characters"))?;
+                    config.max_field_len = Some(len);
+                }
+                "proxy" | "base_url" | "mobile_url" | "suggest_url" => {
+                    let url = expect_str(key, value)?;
+                    Url::parse(url).map_err(|err| invalid(key, err.to_string()))?;
+                    let url = Some(url.to_string());
+                    match key.as_str() {
+                        "proxy" => config.proxy = url,
+                        "base_url" => config.base_url = url,
+                        "mobile_url" => config.mobile_url = url,
+                        _ => config.suggest_url = url,
+                    }
+                }
+                "mobile_fallback" | "cookie_file" | "skip_numbers" => {
+                    let enabled = Some(
+                        value
+                            .as_bool()
+                            .ok_or_else(|| invalid(key, "expected `true` or `false`"))?,
+                    );
+                    match key.as_str() {
+                        "mobile_fallback" => config.mobile_fallback = enabled,
+                        "cookie_file" => config.cookie_file = enabled,
+                        _ => config.skip_numbers = enabled,
+                    }
+                }
+                "user_agent" => config.user_agent = Some(expect_str(key, value)?.to_string()),
+                "accept_language" => {
+                    let accept_language = expect_str(key, value)?;
+                    HeaderValue::from_str(accept_language)
+                        .map_err(|_| invalid(key, "expected a valid header value"))?;
+                    config.accept_language = Some(accept_language.to_string());
+                }
+                "app_key" => config.app_key = Some(expect_str(key, value)?.to_string()),
+                "app_secret" => config.app_secret = Some(expect_str(key, value)?.to_string()),
+                "cedict" => config.cedict = Some(PathBuf::from(expect_str(key, value)?)),
+                "backend" => {
+                    config.backend = Some(
+                        expect_str(key, value)?
+                            .parse()
+                            .map_err(|msg: String| invalid(key, msg))?,
+                    );
+                }
+                "lang" => {
+                    let lang = expect_str(key, value)?;
+                    if !is_valid_lang(lang) {
+                        return Err(invalid(key, "expected a language code such as `en`"));
+                    }
+                    config.lang = Some(lang.to_string());
+                }
+                "accent" => {
+                    config.accent = Some(
+                        expect_str(key, value)?
+                            .parse()
+                            .map_err(|msg: String| invalid(key, msg))?,
+                    );
+                }
+                "format" => {
+                    config.format = Some(
+                        expect_str(key, value)?
+                            .parse()
+                            .map_err(|msg: String| invalid(key, msg))?,
+                    );
+                }
+                "color" => {
+                    config.color = Some(
+                        expect_str(key, value)?
+                            .parse()
+                            .map_err(|msg: String| invalid(key, msg))?,
+                    );
+                }
+                "selectors" => {
+                    let table = value
+                        .as_table()
+                        .ok_or_else(|| invalid(key, "expected a table"))?;
+                    config.selectors = Some(parse_selectors(table)?);
+                }
+                _ => return Err(invalid(key, "unknown key")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Layer `overrides` on top of `self`: every field set in `overrides` wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::config::Config;
+    ///
+    /// let file = Config::from_toml("retries = 2\nformat = \"tsv\"").unwrap();
+    /// let flags = Config { retries: Some(5), ..Config::default() };
+    /// let merged = file.merge(flags);
+    /// assert_eq!(merged.retries, Some(5));
+    /// assert_eq!(merged.format, Some(ydt::OutputFormat::Tsv));
+    /// ```
+    pub fn merge(self, overrides: Config) -> Config {
+        Config {
+            timeout: overrides.timeout.or(self.timeout),
+            deadline: overrides.deadline.or(self.deadline),
+            retries: overrides.retries.or(self.retries),
+            abort_after: overrides.abort_after.or(self.abort_after),
+            proxy: overrides.proxy.or(self.proxy),
+            max_body_size: overrides.max_body_size.or(self.max_body_size),
+            accent: overrides.accent.or(self.accent),
+            format: overrides.format.or(self.format),
+            color: overrides.color.or(self.color),
+            cache_capacity: overrides.cache_capacity.or(self.cache_capacity),
+            sentence_threshold: overrides.sentence_threshold.or(self.sentence_threshold),
+            max_field_len: overrides.max_field_len.or(self.max_field_len),
+            base_url: overrides.base_url.or(self.base_url),
+            user_agent: overrides.user_agent.or(self.user_agent),
+            lang: overrides.lang.or(self.lang),
+            accept_language: overrides.accept_language.or(self.accept_language),
+            mobile_url: overrides.mobile_url.or(self.mobile_url),
+            mobile_fallback: overrides.mobile_fallback.or(self.mobile_fallback),
+            cookie_file: overrides.cookie_file.or(self.cookie_file),
+            skip_numbers: overrides.skip_numbers.or(self.skip_numbers),
+            suggest_url: overrides.suggest_url.or(self.suggest_url),
+            app_key: overrides.app_key.or(self.app_key),
+            app_secret: overrides.app_secret.or(self.app_secret),
+            backend: overrides.backend.or(self.backend),
+            cedict: overrides.cedict.or(self.cedict),
+            selectors: overrides.selectors.or(self.selectors),
+        }
+    }
+
+    /// Read settings from environment variables.
+    ///
+    /// | Variable          | Setting                          |
+    /// |-------------------|----------------------------------|
+    /// | `YDT_TIMEOUT`     | request timeout, in seconds      |
+    /// | `YDT_RETRIES`     | retries after transient failures |
+    /// | `YDT_PROXY`       | proxy URL                        |
+    /// | `YDT_BASE_URL`    | result page endpoint             |
+    /// | `YDT_USER_AGENT`  | user agent                       |
+    /// | `YDT_LANG`        | `lang` query parameter           |
+    /// | `YDT_MOBILE_URL`  | mobile result page endpoint      |
+    /// | `YDT_SUGGEST_URL` | suggestion endpoint              |
+    /// | `YDT_APP_KEY`     | app key for the official API     |
+    /// | `YDT_APP_SECRET`  | app secret for the official API  |
+    ///
+    /// Unset and empty variables are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::InvalidConfig`] naming the variable when a value is
+    /// malformed.
+    pub fn from_env() -> Result<Self, YdtError> {
+        fn var(name: &str) -> Option<String> {
+            env::var(name).ok().filter(|value| !value.is_empty())
+        }
+        fn check<T>(
+            name: &str,
+            value: String,
+            parse: impl FnOnce(&str) -> Option<T>,
+        ) -> Result<T, YdtError> {
+            parse(&value).ok_or(YdtError::InvalidConfig {
+                var: name.to_string(),
+                value,
+            })
+        }
+
+        let mut config = Self::default();
+        if let Some(value) = var("YDT_TIMEOUT") {
+            config.timeout = Some(check("YDT_TIMEOUT", value, |value| {
+                value.parse().ok().and_then(secs_to_duration)
+            })?);
+        }
+        if let Some(value) = var("YDT_RETRIES") {
+            config.retries = Some(check("YDT_RETRIES", value, |value| value.parse().ok())?);
+        }
+        if let Some(value) = var("YDT_PROXY") {
+            config.proxy = Some(check("YDT_PROXY", value, |value| {
+                is_valid_url(value).then(|| value.to_string())
+            })?);
+        }
+        if let Some(value) = var("YDT_BASE_URL") {
+            config.base_url = Some(check("YDT_BASE_URL", value, |value| {
+                is_valid_url(value).then(|| value.to_string())
+            })?);
+        }
+        config.user_agent = var("YDT_USER_AGENT");
+        if let Some(value) = var("YDT_LANG") {
+            config.lang = Some(check("YDT_LANG", value, |value| {
+                is_valid_lang(value).then(|| value.to_string())
+            })?);
+        }
+        if let Some(value) = var("YDT_MOBILE_URL") {
+            config.mobile_url = Some(check("YDT_MOBILE_URL", value, |value| {
+                is_valid_url(value).then(|| value.to_string())
+            })?);
+        }
+        config.app_key = var("YDT_APP_KEY");
+        config.app_secret = var("YDT_APP_SECRET");
+        if let Some(value) = var("YDT_SUGGEST_URL") {
+            config.suggest_url = Some(check("YDT_SUGGEST_URL", value, |value| {
+                is_valid_url(value).then(|| value.to_string())
+            })?);
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,37 @@
+//! Console setup at startup.
+//!
+//! The legacy Windows console shows IPA symbols and Chinese as mojibake unless
+//! its output code page is UTF-8, and prints escape sequences literally unless
+//! virtual terminal processing is on. Elsewhere there is nothing to set up.
+
+/// Switch the console to UTF-8 output and enable escape sequences on stdout
+/// and stderr.
+///
+/// Failures, e.g. when output is redirected or on consoles predating Windows
+/// 10, are ignored: output still works, only possibly garbled;
+/// `--ascii-phonetics` avoids the IPA symbols.
+#[cfg(windows)]
+pub fn setup() {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, SetConsoleOutputCP,
+        ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    const CP_UTF8: u32 = 65001;
+
+    // SAFETY: these calls take no pointers other than `mode`, which outlives
+    // them, and report failure through their return values.
+    unsafe {
+        SetConsoleOutputCP(CP_UTF8);
+        for std_handle in [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE] {
+            let handle = GetStdHandle(std_handle);
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) != 0 {
+                SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub fn setup() {}
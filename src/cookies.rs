@@ -0,0 +1,140 @@
+//! Cookies kept across requests and, optionally, across processes in a file.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use cookie_store::{CookieStore, RawCookie};
+use log::{debug, warn};
+use reqwest::header::HeaderValue;
+use url::Url;
+
+/// A cookie jar for reqwest that can be saved to and loaded from a JSON file.
+#[derive(Debug, Default)]
+pub(crate) struct CookieJar {
+    store: Mutex<CookieStore>,
+    /// File the cookies are saved to, if any.
+    path: Option<PathBuf>,
+    /// Whether cookies were set since the jar was loaded or last saved.
+    changed: AtomicBool,
+    /// Held while saving, so that lookups finishing together on several
+    /// threads do not write the file at the same time.
+    saving: Mutex<()>,
+}
+
+impl CookieJar {
+    /// A jar holding the cookies saved at `path` and saving them back there.
+    ///
+    /// A missing file yields an empty jar, as does a corrupt one, which is
+    /// replaced on the next save.
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let store = match File::open(&path) {
+            Ok(file) => {
+                cookie_store::serde::json::load(BufReader::new(file)).unwrap_or_else(|err| {
+                    warn!(
+                        "discarding unreadable cookie file {}: {err}",
+                        path.display()
+                    );
+                    CookieStore::default()
+                })
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => CookieStore::default(),
+            Err(err) => {
+                warn!("failed to read cookie file {}: {err}", path.display());
+                CookieStore::default()
+            }
+        };
+        Self {
+            store: Mutex::new(store),
+            path: Some(path),
+            changed: AtomicBool::new(false),
+            saving: Mutex::default(),
+        }
+    }
+
+    fn store(&self) -> MutexGuard<'_, CookieStore> {
+        lock(&self.store)
+    }
+
+    /// Save the cookies to the jar's file if any were set since the last save.
+    ///
+    /// Only persistent cookies are saved. Failures are logged, since losing
+    /// cookies only makes the next run look like a new visitor.
+    pub(crate) fn save(&self) {
+        let Some(path) = &self.path else { return };
+        let _saving = lock(&self.saving);
+        if !self.changed.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let mut json = Vec::new();
+        let store = self.store();
+        if let Err(err) = cookie_store::serde::json::save(&store, &mut json) {
+            warn!("failed to serialize cookies: {err}");
+            return;
+        }
+        drop(store);
+        match write_private(path, &json) {
+            Ok(()) => debug!("saved cookies to {}", path.display()),
+            Err(err) => warn!("failed to save cookies to {}: {err}", path.display()),
+        }
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| RawCookie::parse(value.to_string()).ok());
+        self.store().store_response_cookies(cookies, url);
+        self.changed.store(true, Ordering::Relaxed);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let header = self
+            .store()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if header.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+/// Lock `mutex`, recovering it from a thread that panicked while holding it:
+/// cookies are only ever replaced whole.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Replace the file at `path` with `contents`, readable only by the user.
+fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Named after the process, so that processes saving together each write
+    // their own file and the last rename wins.
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".{}.tmp", process::id()));
+    let temp = PathBuf::from(temp);
+    // Created afresh so that the mode applies.
+    let _ = fs::remove_file(&temp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&temp, path)
+}
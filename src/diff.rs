@@ -0,0 +1,138 @@
+//! `ydt diff`: two lookups side by side, for telling near-synonyms apart.
+//!
+//! Kept apart from `main.rs` so that `tests/diff.rs` can check the layout
+//! without running lookups.
+
+use ydt::{display_width, wrap_to_width, ResultKind, Translation, YdtError};
+
+/// Between the two columns.
+const GUTTER: &str = " │ ";
+
+/// Narrowest column worth showing side by side; below it, the lookups are
+/// stacked instead.
+pub const MIN_COLUMN_WIDTH: usize = 20;
+
+/// A looked-up word and the result shown for it.
+pub struct Side<'a> {
+    pub word: &'a str,
+    pub result: &'a Result<Translation, YdtError>,
+}
+
+impl Side<'_> {
+    /// The phonetics on one line, e.g. `英 /əˈfekt/ 美 /əˈfekt/`.
+    fn phonetics(&self) -> String {
+        let Ok(translation) = self.result else {
+            return String::new();
+        };
+        translation
+            .phonetics
+            .iter()
+            .map(|phonetic| format!("{} {}", phonetic.label, phonetic.text))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// One line per definition and pinyin candidate, or why there are none.
+    fn definitions(&self) -> Vec<String> {
+        let translation = match self.result {
+            Ok(translation) => translation,
+            Err(err) => return vec![err.to_string()],
+        };
+        let lines: Vec<_> = translation
+            .entries
+            .iter()
+            .map(|entry| {
+                if entry.pos.is_empty() {
+                    entry.meaning.clone()
+                } else {
+                    format!("{} {}", entry.pos, entry.meaning)
+                }
+            })
+            .chain(translation.candidates.iter().map(|candidate| {
+                [&candidate.hanzi, &candidate.pinyin, &candidate.gloss]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }))
+            .chain(translation.conversions.iter().cloned())
+            .chain(
+                (translation.result_kind() == ResultKind::WebOnly)
+                    .then(|| format!("(web) {}", translation.web_translations.join("；"))),
+            )
+            .collect();
+        if !lines.is_empty() {
+            lines
+        } else if translation.suggestions.is_empty() {
+            vec!["No results.".to_string()]
+        } else {
+            vec![format!(
+                "Did you mean: {}?",
+                translation.suggestions.join(", ")
+            )]
+        }
+    }
+}
+
+/// Render `left` and `right` in two columns filling `width`, with their
+/// headwords, phonetics and definitions on the same rows.
+///
+/// Definitions are paired in page order and wrapped within their column.
+/// When the columns would be narrower than [`MIN_COLUMN_WIDTH`], the two
+/// lookups are stacked instead, separated by a blank line.
+pub fn render(left: &Side, right: &Side, width: usize) -> String {
+    let column = width.saturating_sub(display_width(GUTTER)) / 2;
+    if column < MIN_COLUMN_WIDTH {
+        return [left, right]
+            .map(|side| render_stacked(side, width))
+            .join("\n\n");
+    }
+
+    let mut rows = Vec::new();
+    push_row(&mut rows, left.word, right.word, column);
+    let (left_phonetics, right_phonetics) = (left.phonetics(), right.phonetics());
+    if !left_phonetics.is_empty() || !right_phonetics.is_empty() {
+        push_row(&mut rows, &left_phonetics, &right_phonetics, column);
+    }
+    let (left_lines, right_lines) = (left.definitions(), right.definitions());
+    for i in 0..left_lines.len().max(right_lines.len()) {
+        let cell = |lines: &[String]| lines.get(i).cloned().unwrap_or_default();
+        push_row(&mut rows, &cell(&left_lines), &cell(&right_lines), column);
+    }
+    rows.join("\n")
+}
+
+/// Push the rows of one pair of cells, each wrapped to `column` columns; the
+/// shorter cell is padded with blank lines.
+fn push_row(rows: &mut Vec<String>, left: &str, right: &str, column: usize) {
+    let left = wrap_cell(left, column);
+    let right = wrap_cell(right, column);
+    for i in 0..left.len().max(right.len()) {
+        let left = left.get(i).map_or("", String::as_str);
+        let right = right.get(i).map_or("", String::as_str);
+        let padding = column.saturating_sub(display_width(left));
+        let row = format!("{left}{}{GUTTER}{right}", " ".repeat(padding));
+        rows.push(row.trim_end().to_string());
+    }
+}
+
+fn wrap_cell(text: &str, width: usize) -> Vec<String> {
+    if text.is_empty() {
+        Vec::new()
+    } else {
+        wrap_to_width(text, width)
+    }
+}
+
+/// One lookup on its own: the headword, the phonetics and the definitions,
+/// wrapped at `width`.
+fn render_stacked(side: &Side, width: usize) -> String {
+    let mut lines = vec![side.word.to_string()];
+    let phonetics = side.phonetics();
+    lines.extend(wrap_cell(&phonetics, width));
+    for definition in side.definitions() {
+        lines.extend(wrap_cell(&definition, width));
+    }
+    lines.join("\n")
+}
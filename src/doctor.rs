@@ -0,0 +1,218 @@
+//! `ydt doctor`: checks telling apart a broken network, proxy, block by Youdao
+//! or parser, for bug reports.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde_json::{json, Value};
+use ydt::config::Config;
+use ydt::{build_info, build_info_to_json, YdtClient, YdtError};
+
+/// Word looked up to check fetching and parsing; its page has phonetics and
+/// definitions.
+const PROBE_WORD: &str = "hello";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// Not run because a check it depends on failed.
+    Skip,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            detail: detail.into(),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl ToString) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            detail: detail.to_string(),
+        }
+    }
+
+    fn skip(name: &'static str) -> Self {
+        Self {
+            name,
+            status: Status::Skip,
+            detail: String::new(),
+        }
+    }
+}
+
+fn check_config(path: Option<&Path>, config: &Result<Config, YdtError>) -> Check {
+    match (path, config) {
+        (_, Err(err)) => Check::fail("config", err),
+        (Some(path), Ok(_)) if path.exists() => Check::pass("config", path.display().to_string()),
+        (Some(path), Ok(_)) => Check::pass("config", format!("{} (not found)", path.display())),
+        (None, Ok(_)) => Check::pass("config", "no config directory"),
+    }
+}
+
+/// Check that `dir` can be created and written to.
+fn check_cache_dir(dir: Option<&Path>) -> Check {
+    let Some(dir) = dir else {
+        return Check::fail("cache", "Could not determine the cache directory");
+    };
+    let probe = dir.join(format!(".doctor-{}", process::id()));
+    let written = fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&probe, b""))
+        .and_then(|()| fs::remove_file(&probe));
+    match written {
+        Ok(()) => Check::pass("cache", dir.display().to_string()),
+        Err(err) => Check::fail("cache", format!("{}: {err}", dir.display())),
+    }
+}
+
+/// Check name resolution, connectivity and TLS, returning whether all passed.
+fn check_network(client: &YdtClient, checks: &mut Vec<Check>) -> bool {
+    let probe = match client.probe() {
+        Ok(probe) => probe,
+        Err(err) => {
+            checks.extend([
+                Check::fail("dns", err),
+                Check::skip("connect"),
+                Check::skip("http"),
+            ]);
+            return false;
+        }
+    };
+    let host = &probe.host;
+    checks.push(match &probe.resolved {
+        Ok(addrs) => {
+            let addrs: Vec<_> = addrs.iter().map(ToString::to_string).collect();
+            Check::pass("dns", format!("{host}: {}", addrs.join(", ")))
+        }
+        Err(err) => Check::fail("dns", format!("{host}: {err}")),
+    });
+    checks.push(match &probe.connected {
+        Some(Ok(elapsed)) => Check::pass("connect", format!("{host} in {elapsed:.0?}")),
+        Some(Err(err)) => Check::fail("connect", format!("{host}: {err}")),
+        None => Check::skip("connect"),
+    });
+    checks.push(match &probe.responded {
+        Some(Ok(status)) => Check::pass("http", status.to_string()),
+        Some(Err(err)) => Check::fail("http", err),
+        None => Check::skip("http"),
+    });
+    probe.is_ok()
+}
+
+/// Look up [`PROBE_WORD`] and check that the parser found its phonetics and
+/// definitions.
+fn check_lookup(client: &YdtClient, checks: &mut Vec<Check>) {
+    let translation = match client.translate(PROBE_WORD) {
+        Ok(translation) if !translation.is_empty() => translation,
+        Ok(_) => {
+            checks.extend([
+                Check::fail("lookup", format!("No results for {PROBE_WORD:?}")),
+                Check::skip("parser"),
+            ]);
+            return;
+        }
+        Err(err) => {
+            checks.extend([Check::fail("lookup", err), Check::skip("parser")]);
+            return;
+        }
+    };
+    checks.push(Check::pass("lookup", PROBE_WORD));
+    let found = format!(
+        "{} phonetics, {} definitions",
+        translation.phonetics.len(),
+        translation.entries.len()
+    );
+    if translation.phonetics.is_empty() || translation.entries.is_empty() {
+        checks.push(Check::fail(
+            "parser",
+            format!("expected phonetics and definitions, found {found}"),
+        ));
+    } else {
+        checks.push(Check::pass("parser", found));
+    }
+}
+
+fn run_checks(
+    config_path: Option<PathBuf>,
+    config: Result<Config, YdtError>,
+    cache_dir: Option<PathBuf>,
+) -> Vec<Check> {
+    let mut checks = vec![
+        check_config(config_path.as_deref(), &config),
+        check_cache_dir(cache_dir.as_deref()),
+    ];
+    let mut builder = YdtClient::builder().config(&config.unwrap_or_default());
+    if let Some(dir) = &cache_dir {
+        builder = builder.cooldown_path(dir.join("cooldown"));
+    }
+    let client = builder.build();
+    if check_network(&client, &mut checks) {
+        check_lookup(&client, &mut checks);
+    } else {
+        checks.extend([Check::skip("lookup"), Check::skip("parser")]);
+    }
+    checks
+}
+
+fn to_json(checks: &[Check]) -> Value {
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "build": build_info_to_json(&build_info()),
+        "ok": checks.iter().all(|check| check.status != Status::Fail),
+        "checks": checks
+            .iter()
+            .map(|check| json!({
+                "name": check.name,
+                "status": check.status.as_str(),
+                "detail": check.detail,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Run every check and print one line per check, or a JSON report with
+/// `json`. Returns whether all checks that ran passed.
+pub fn run(
+    config_path: Option<PathBuf>,
+    config: Result<Config, YdtError>,
+    cache_dir: Option<PathBuf>,
+    json: bool,
+) -> bool {
+    let checks = run_checks(config_path, config, cache_dir);
+    if json {
+        println!("{}", to_json(&checks));
+    } else {
+        for check in &checks {
+            let line = format!(
+                "{:<4}  {:<7}  {}",
+                check.status.as_str(),
+                check.name,
+                check.detail
+            );
+            println!("{}", line.trim_end());
+        }
+    }
+    checks.iter().all(|check| check.status != Status::Fail)
+}
@@ -0,0 +1,121 @@
+//! C interface, for embedding `ydt` in programs not written in Rust.
+//!
+//! Build the library with `cargo rustc --lib --release --features ffi --crate-type cdylib`
+//! (or `staticlib`) and include `include/ydt.h`.
+//!
+//! Strings passed in are borrowed and must be NUL-terminated UTF-8. Strings
+//! returned are owned by the caller and must be released with
+//! [`ydt_free_string`]. Panics never cross the boundary; they are reported as
+//! errors.
+//!
+//! ```c
+//! char *json = ydt_translate("hello");
+//! if (json == NULL) {
+//!     char *error = ydt_last_error();
+//!     fprintf(stderr, "%s\n", error);
+//!     ydt_free_string(error);
+//! } else {
+//!     puts(json);
+//!     ydt_free_string(json);
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use serde_json::{json, Value};
+
+use crate::render::error_json;
+use crate::{translation_to_json, YdtClient};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Look `word` up and return the translation as a JSON object, such as
+/// `{"word":"hello","phonetics":[{"label":"英","text":"/həˈləʊ/"}],"entries":[{"pos":"int.","meaning":"你好"}]}`.
+///
+/// The client is configured from the `YDT_*` environment variables, as with
+/// [`YdtClient::from_env`]. Returns NULL on failure; see [`ydt_last_error`].
+///
+/// # Safety
+///
+/// `word` must be NULL or point to a NUL-terminated string valid for reads.
+#[no_mangle]
+pub unsafe extern "C" fn ydt_translate(word: *const c_char) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if word.is_null() {
+            return Err(invalid_argument("word is NULL"));
+        }
+        // SAFETY: the caller guarantees a NUL-terminated string.
+        let word = unsafe { CStr::from_ptr(word) }
+            .to_str()
+            .map_err(|_| invalid_argument("word is not valid UTF-8"))?;
+        YdtClient::from_env()
+            .and_then(|client| client.translate(word))
+            .map(|translation| translation_to_json(&translation).to_string())
+            .map_err(|err| error_json(&err))
+    }));
+    let result = result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(json!({ "kind": "panic", "message": message }))
+    });
+    match result {
+        Ok(json) => {
+            set_last_error(None);
+            into_c_string(json)
+        }
+        Err(error) => {
+            set_last_error(Some(error.to_string()));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Details of the last failed call on this thread, as a JSON object with the
+/// fields of [`ErrorReport`](crate::ErrorReport), or NULL when the last call
+/// succeeded.
+///
+/// Besides the kinds of [`YdtError`](crate::YdtError), `kind` is
+/// `invalid_argument` for bad input and `panic` for a caught panic. The string
+/// must be released with [`ydt_free_string`].
+#[no_mangle]
+pub extern "C" fn ydt_last_error() -> *mut c_char {
+    LAST_ERROR
+        .with(|last| last.borrow().clone())
+        .map_or(ptr::null_mut(), into_c_string)
+}
+
+/// Release a string returned by `ydt`. NULL is ignored.
+///
+/// # Safety
+///
+/// `s` must be NULL or a string returned by `ydt` that was not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn ydt_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+fn invalid_argument(message: &str) -> Value {
+    json!({ "kind": "invalid_argument", "message": message })
+}
+
+fn set_last_error(error: Option<String>) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = error);
+}
+
+/// JSON has no raw NUL bytes, since serde_json escapes control characters.
+fn into_c_string(json: String) -> *mut c_char {
+    CString::new(json)
+        .expect("JSON contains no NUL bytes")
+        .into_raw()
+}
@@ -0,0 +1,27 @@
+//! The idiom explanation block (成语) of Chinese result pages.
+
+use scraper::Html;
+
+use crate::first_text;
+use crate::selectors::Selectors;
+use crate::translation::{Idiom, ParseWarning};
+
+/// Parse the idiom block, if the page has one with an explanation.
+pub(crate) fn parse_idiom(
+    document: &Html,
+    selectors: &Selectors,
+    warnings: &mut Vec<ParseWarning>,
+) -> Option<Idiom> {
+    let block = document.select(&selectors.idiom).next()?;
+    let idiom = Idiom {
+        pinyin: first_text(block, &selectors.idiom_pinyin),
+        explanation: first_text(block, &selectors.idiom_explanation),
+        source: first_text(block, &selectors.idiom_source),
+        example: first_text(block, &selectors.idiom_example),
+    };
+    if idiom.explanation.is_empty() {
+        warnings.push(ParseWarning::IdiomExplanationMissing);
+        return None;
+    }
+    Some(idiom)
+}
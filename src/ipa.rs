@@ -0,0 +1,67 @@
+//! ASCII transliteration of IPA transcriptions, for terminals that cannot show
+//! IPA symbols.
+
+/// X-SAMPA equivalents of the IPA symbols found in English transcriptions.
+///
+/// Symbols missing here, such as tie bars and other diacritics, are dropped.
+const TABLE: &[(char, &str)] = &[
+    ('ˈ', "\""),
+    ('ˌ', "%"),
+    ('ː', ":"),
+    ('ˑ', ":\\"),
+    ('ə', "@"),
+    ('ɚ', "@`"),
+    ('ɝ', "3`"),
+    ('ɜ', "3"),
+    ('ɐ', "6"),
+    ('ʌ', "V"),
+    ('æ', "{"),
+    ('ɑ', "A"),
+    ('ɒ', "Q"),
+    ('ɔ', "O"),
+    ('ɪ', "I"),
+    ('ᵻ', "1"),
+    ('ɨ', "1"),
+    ('ʊ', "U"),
+    ('ɛ', "E"),
+    ('ɵ', "8"),
+    ('θ', "T"),
+    ('ð', "D"),
+    ('ʃ', "S"),
+    ('ʒ', "Z"),
+    ('ʧ', "tS"),
+    ('ʤ', "dZ"),
+    ('ŋ', "N"),
+    ('ɡ', "g"),
+    ('ɹ', "r\\"),
+    ('ɾ', "4"),
+    ('ɫ', "5"),
+    ('ʔ', "?"),
+    ('ʍ', "W"),
+    ('ɣ', "G"),
+    ('χ', "X"),
+    ('ç', "C"),
+    ('ɲ', "J"),
+];
+
+/// Transliterate an IPA transcription such as `/həˈləʊ/` to ASCII
+/// ([X-SAMPA](https://en.wikipedia.org/wiki/X-SAMPA)), dropping symbols that
+/// have no equivalent.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::ipa_to_ascii("/həˈləʊ/"), "/h@\"l@U/");
+/// assert_eq!(ydt::ipa_to_ascii("/θɪŋk/"), "/TINk/");
+/// ```
+pub fn ipa_to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch.is_ascii() {
+            out.push(ch);
+        } else if let Some((_, ascii)) = TABLE.iter().find(|(ipa, _)| *ipa == ch) {
+            out.push_str(ascii);
+        }
+    }
+    out
+}
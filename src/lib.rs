@@ -1,72 +1,727 @@
 //! `ydt` library API.
 //!
 //! This crate provides a simple way to fetch and parse translations from Youdao.
+//!
+//! Requests, retries, user agent fallbacks and empty results are reported through
+//! the [`log`](https://docs.rs/log) facade; nothing is printed unless the
+//! application installs a logger.
+//!
+//! Fetching needs the default `http` feature. Without it, as with
+//! `default-features = false`, the crate only parses pages fetched some other
+//! way, leaving out the HTTP client and its dependencies:
+//!
+//! ```toml
+//! [dependencies]
+//! ydt = { version = "0.5", default-features = false }
+//! ```
+//!
+//! Requests use rustls, or with the `native-tls` feature the platform's TLS
+//! library; exactly one of `rustls-tls` and `native-tls` must be enabled.
+//!
+//! The parsers also build for `wasm32-unknown-unknown` with the `http` feature
+//! off; the `wasm` feature adds [`translate_async`], which fetches through
+//! the browser.
+//!
+//! # Threads
+//!
+//! Everything can be used from several threads at once. A [`YdtClient`] is
+//! `Send + Sync`: share one by reference or in an `Arc` rather than building
+//! one per thread, so that its [minimum request
+//! interval](YdtClientBuilder::min_request_interval), [circuit
+//! breaker](YdtClientBuilder::abort_after), connections and cookies apply to
+//! all lookups made through it. The built-in CSS selectors are compiled once
+//! for the whole process, by whichever lookup needs them first. Functions
+//! such as [`translate`] build a new client for each call, so they share only
+//! the connection pool.
+//!
+//! ```
+//! # #[cfg(feature = "http")] {
+//! use std::thread;
+//!
+//! struct Pages;
+//!
+//! impl ydt::Fetcher for Pages {
+//!     fn fetch(&self, word: &str) -> Result<String, ydt::YdtError> {
+//!         Ok(format!(r#"<li class="word-exp-ce mcols-layout"><a class="point">{word}</a></li>"#))
+//!     }
+//! }
+//!
+//! let client = ydt::YdtClient::with_fetcher(Pages);
+//! thread::scope(|scope| {
+//!     for word in ["你好", "世界"] {
+//!         let client = &client;
+//!         scope.spawn(move || client.translate(word).unwrap());
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! [`YdtClient::translate_many`] spreads a list of words over a number of
+//! threads itself.
 
-use reqwest::blocking::{Client, Response};
-use reqwest::StatusCode;
-use reqwest::Url;
-use scraper::{Html, Selector};
+#[cfg(all(feature = "rustls-tls", feature = "native-tls"))]
+compile_error!(
+    "features `rustls-tls` and `native-tls` are mutually exclusive; \
+     use `default-features = false` to select `native-tls`"
+);
+#[cfg(all(
+    feature = "http",
+    not(any(feature = "rustls-tls", feature = "native-tls"))
+))]
+compile_error!("feature `http` needs a TLS backend: enable `rustls-tls` or `native-tls`");
+
+use scraper::{ElementRef, Html, Selector};
 use std::error::Error;
 use std::fmt;
-use std::sync::OnceLock;
 use std::time::Duration;
 
-const PROJECT_USER_AGENT: &str = concat!(
-    "ydt/",
-    env!("CARGO_PKG_VERSION"),
-    " (+https://github.com/yushengyangchem/ydt)"
-);
-const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36";
-const YOUDAO_RESULT_URL: &str = "https://www.youdao.com/result";
-
-static WORD_EXP_CE_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static POINT_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static TRANS_CONTAINER_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static PHONE_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static SPAN_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static PHONETIC_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static WORD_EXP_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static POS_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
-static TRANS_SELECTOR: OnceLock<Result<Selector, YdtError>> = OnceLock::new();
+mod abbreviation;
+mod build_info;
+pub mod cedict;
+#[cfg(feature = "http")]
+mod charset;
+#[cfg(feature = "http")]
+mod circuit_breaker;
+#[cfg(feature = "http")]
+mod client;
+mod collins;
+#[cfg(feature = "http")]
+mod compression;
+#[cfg(feature = "http")]
+pub mod config;
+#[cfg(feature = "http")]
+mod cookies;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod idiom;
+mod ipa;
+mod mobile;
+pub mod openapi;
+mod page;
+mod pinyin;
+#[cfg(feature = "http")]
+mod probe;
+#[cfg(feature = "http")]
+mod pronunciation;
+mod query;
+#[cfg(feature = "http")]
+mod rate_limit;
+mod related;
+mod render;
+#[cfg(feature = "http")]
+mod result_cache;
+mod selectors;
+mod sentence;
+#[cfg(feature = "server")]
+pub mod server;
+mod source;
+#[cfg(feature = "http")]
+mod suggest;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod text;
+mod traditional;
+mod translation;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "http")]
+pub use client::{
+    Backend, FetchInfo, Fetcher, HttpFetcher, PageSource, PlannedRequest, ProgressEvent,
+    RequestPlan, ResultOrder, UaStrategy, UserAgentKind, YdtClient, YdtClientBuilder,
+    DEFAULT_ABORT_AFTER, DEFAULT_ACCEPT_LANGUAGE, DEFAULT_CACHE_CAPACITY, DEFAULT_CONCURRENCY,
+    DEFAULT_DEADLINE, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS, DEFAULT_POOL_IDLE_TIMEOUT,
+    DEFAULT_POOL_MAX_IDLE_PER_HOST, DEFAULT_TIMEOUT,
+};
+
+pub use build_info::{build_info, BuildInfo};
+#[cfg(feature = "http")]
+pub use charset::CharsetSource;
+pub use http::StatusCode;
+pub use ipa::ipa_to_ascii;
+pub use mobile::parse_mobile_html;
+pub use page::{parse_ce_candidates, parse_definitions, parse_phonetics, ParsedPage};
+pub use pinyin::looks_like_pinyin;
+#[cfg(feature = "http")]
+pub use probe::Probe;
+#[cfg(feature = "http")]
+pub use pronunciation::{Pronunciation, PronunciationItem, PronunciationPack, DEFAULT_AUDIO_URL};
+pub use query::{
+    build_result_url, is_number, normalize_word, DEFAULT_BASE_URL, DEFAULT_LANG,
+    DEFAULT_MAX_WORD_LEN, DEFAULT_MOBILE_URL,
+};
+pub use render::{
+    build_info_to_json, display_width, error_to_json, render_conversion_notice,
+    render_lemma_notice, render_ndjson_line, render_one_line, render_one_line_labeled,
+    render_pretty, render_stable, render_translation, render_tsv_row, translation_to_json,
+    truncate_to_width, wrap_to_width, ColorChoice, Labels, OutputFormat, RenderOptions, Sections,
+    TSV_HEADER,
+};
+pub use selectors::{SelectorConfig, Selectors};
+pub use sentence::{
+    looks_like_sentence, parse_sentence_translation, DEFAULT_MAX_SENTENCE_LEN,
+    DEFAULT_SENTENCE_THRESHOLD,
+};
+pub use source::TranslationSource;
+#[cfg(feature = "http")]
+pub use suggest::{Suggestion, DEFAULT_SUGGEST_URL};
+pub use traditional::{to_simplified, to_traditional};
+pub use translation::{
+    Accent, Candidate, CollinsSense, Direction, Entry, EntrySource, Example, Idiom, Layout,
+    LookupStrategy, ParseWarning, Phonetic, RelatedGroup, RelatedWord, ResultKind, Translation,
+    TranslationOptions, DEFAULT_MAX_FIELD_LEN,
+};
+#[cfg(feature = "wasm")]
+pub use wasm::{fetch_html_async, translate_async};
 
 #[derive(Debug)]
+#[non_exhaustive]
 /// Error type returned by `ydt` public APIs.
+///
+/// Errors from looking a word up name the word, and those from a request the
+/// URL, so that failures in a batch can be told apart.
 pub enum YdtError {
+    #[cfg(feature = "http")]
     CreateHttpClient(reqwest::Error),
     BuildRequestUrl(url::ParseError),
-    FetchTranslation(reqwest::Error),
-    HttpStatus(StatusCode),
-    ReadResponse(reqwest::Error),
-    ParseCssSelector(&'static str),
+    #[cfg(any(feature = "http", feature = "wasm"))]
+    Fetch {
+        word: String,
+        url: String,
+        source: reqwest::Error,
+    },
+    HttpStatus {
+        word: String,
+        url: String,
+        status: StatusCode,
+    },
+    #[cfg(any(feature = "http", feature = "wasm"))]
+    ReadResponse {
+        word: String,
+        url: String,
+        source: reqwest::Error,
+    },
+    ParseCssSelector {
+        css: String,
+        message: String,
+    },
+    ReadConfig(std::io::Error),
+    ConfigFile {
+        key: Option<String>,
+        message: String,
+    },
+    InvalidConfig {
+        var: String,
+        value: String,
+    },
+    UnrecognizedLayout {
+        word: String,
+        warnings: Vec<ParseWarning>,
+    },
+    EmptyWord,
+    WordTooLong {
+        len: usize,
+        max: usize,
+    },
+    UnsupportedCharacter {
+        word: String,
+        ch: char,
+    },
+    InvalidSuggestPayload(String),
+    ApiError {
+        word: String,
+        code: u32,
+        msg: String,
+    },
+    InvalidApiResponse {
+        word: String,
+        message: String,
+    },
+    MissingApiCredentials,
+    AllSourcesFailed {
+        word: String,
+        errors: Vec<(String, YdtError)>,
+    },
+    ReadDictionary(std::io::Error),
+    ResponseTooLarge {
+        word: String,
+        url: String,
+        limit: usize,
+        received: usize,
+    },
+    Decode {
+        word: String,
+        url: String,
+        message: String,
+    },
+    CompressedBody {
+        word: String,
+        url: String,
+        encoding: String,
+    },
+    UnexpectedRedirect {
+        word: String,
+        url: String,
+        to: String,
+    },
+    RateLimited {
+        word: String,
+        retry_after: Option<Duration>,
+    },
+    CircuitOpen {
+        word: String,
+        failures: usize,
+    },
+    NumberQuery {
+        word: String,
+    },
+    DeadlineExceeded {
+        word: String,
+        deadline: Duration,
+        tier: String,
+    },
 }
 
 impl fmt::Display for YdtError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "http")]
             Self::CreateHttpClient(err) => write!(f, "Failed to create HTTP client: {err}"),
             Self::BuildRequestUrl(err) => write!(f, "Failed to build request URL: {err}"),
-            Self::FetchTranslation(err) => write!(f, "Failed to fetch translation: {err}"),
-            Self::HttpStatus(status) => write!(f, "Request failed with status: {status}"),
-            Self::ReadResponse(err) => write!(f, "Failed to read response: {err}"),
-            Self::ParseCssSelector(css) => write!(f, "Failed to parse CSS selector: {css}"),
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { word, source, .. } => {
+                write!(f, "Failed to fetch translation for {word:?}: {source}")
+            }
+            Self::HttpStatus { word, status, .. } => {
+                write!(f, "Request for {word:?} failed with status: {status}")
+            }
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::ReadResponse { word, source, .. } => {
+                write!(f, "Failed to read response for {word:?}: {source}")
+            }
+            Self::ParseCssSelector { css, message } => {
+                write!(f, "Failed to parse CSS selector `{css}`: {message}")
+            }
+            Self::ReadConfig(err) => write!(f, "Failed to read config file: {err}"),
+            Self::ConfigFile {
+                key: Some(key),
+                message,
+            } => write!(f, "Invalid config value for `{key}`: {message}"),
+            Self::ConfigFile { key: None, message } => {
+                write!(f, "Invalid config file: {message}")
+            }
+            Self::InvalidConfig { var, value } => {
+                write!(f, "Invalid value for environment variable {var}: {value:?}")
+            }
+            Self::EmptyWord => f.write_str("Word to translate is empty"),
+            Self::WordTooLong { len, max } => write!(
+                f,
+                "Word to translate is too long: {len} characters (at most {max} allowed)"
+            ),
+            Self::UnsupportedCharacter { word, ch } => {
+                write!(
+                    f,
+                    "Word to translate {word:?} contains unsupported character {ch:?}"
+                )
+            }
+            Self::InvalidSuggestPayload(message) => {
+                write!(f, "Invalid suggestion response: {message}")
+            }
+            Self::ApiError { word, code, msg } => {
+                write!(f, "Youdao API error {code} for {word:?}: {msg}")
+            }
+            Self::InvalidApiResponse { word, message } => {
+                write!(f, "Invalid Youdao API response for {word:?}: {message}")
+            }
+            Self::MissingApiCredentials => {
+                f.write_str("The Youdao API backend requires an app key and secret")
+            }
+            Self::ReadDictionary(err) => write!(f, "Failed to read dictionary: {err}"),
+            Self::UnexpectedRedirect { word, to, .. } => {
+                write!(f, "Unexpected redirect for {word:?} to {to}")
+            }
+            Self::CircuitOpen { word, failures } => {
+                write!(f, "Skipped {word:?} after {failures} consecutive failures")
+            }
+            Self::NumberQuery { word } => {
+                write!(f, "Skipped {word:?}: numbers are not translated")
+            }
+            Self::DeadlineExceeded {
+                word,
+                deadline,
+                tier,
+            } => write!(
+                f,
+                "Lookup of {word:?} took longer than {deadline:.0?}; stopped during the {tier}"
+            ),
+            Self::RateLimited {
+                word,
+                retry_after: None,
+            } => write!(f, "Rate limited by the server for {word:?}"),
+            Self::RateLimited {
+                word,
+                retry_after: Some(wait),
+            } => write!(
+                f,
+                "Rate limited by the server for {word:?}; try again in {}s",
+                whole_secs(*wait)
+            ),
+            Self::Decode { word, message, .. } => {
+                write!(f, "Failed to decode response body for {word:?}: {message}")
+            }
+            Self::CompressedBody { word, encoding, .. } => write!(
+                f,
+                "Response body for {word:?} is {encoding}-compressed and could not be decoded; \
+                 only gzip and br are supported, and a proxy that drops the Content-Encoding \
+                 header also leaves bodies compressed"
+            ),
+            Self::ResponseTooLarge {
+                word,
+                limit,
+                received,
+                ..
+            } => write!(
+                f,
+                "Response body for {word:?} is larger than {limit} bytes ({received} bytes received)"
+            ),
+            Self::AllSourcesFailed { word, errors } => {
+                write!(f, "All sources failed for {word:?}: ")?;
+                for (i, (name, err)) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{name}: {err}")?;
+                }
+                Ok(())
+            }
+            Self::UnrecognizedLayout { word, warnings } => {
+                write!(f, "Unrecognized result page layout for {word:?}: ")?;
+                for (i, warning) in warnings.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{warning}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
+impl YdtError {
+    /// The word whose lookup failed, for errors that name it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let err = ydt::normalize_word("a\u{7}b", 100).unwrap_err();
+    /// assert_eq!(err.word(), Some("a\u{7}b"));
+    /// assert_eq!(ydt::YdtError::EmptyWord.word(), None);
+    /// ```
+    pub fn word(&self) -> Option<&str> {
+        match self {
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { word, .. } | Self::ReadResponse { word, .. } => Some(word),
+            Self::HttpStatus { word, .. }
+            | Self::UnrecognizedLayout { word, .. }
+            | Self::UnsupportedCharacter { word, .. }
+            | Self::ApiError { word, .. }
+            | Self::InvalidApiResponse { word, .. }
+            | Self::AllSourcesFailed { word, .. }
+            | Self::ResponseTooLarge { word, .. }
+            | Self::Decode { word, .. }
+            | Self::CompressedBody { word, .. }
+            | Self::UnexpectedRedirect { word, .. }
+            | Self::RateLimited { word, .. }
+            | Self::CircuitOpen { word, .. }
+            | Self::NumberQuery { word }
+            | Self::DeadlineExceeded { word, .. } => Some(word),
+            _ => None,
+        }
+    }
+
+    /// Whether trying again later may succeed: failed requests, rate limits,
+    /// server errors, lookups past their deadline and lookups skipped after
+    /// repeated failures.
+    ///
+    /// A lookup that failed in every source is retryable when any of the
+    /// failures is.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { .. } | Self::ReadResponse { .. } => true,
+            Self::RateLimited { .. } | Self::CircuitOpen { .. } => true,
+            Self::DeadlineExceeded { .. } => true,
+            Self::HttpStatus { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::REQUEST_TIMEOUT
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::AllSourcesFailed { errors, .. } => {
+                errors.iter().any(|(_, err)| err.is_retryable())
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether no response was received or it could not be read, such as when
+    /// the connection failed or timed out, or the lookup ran past its
+    /// deadline.
+    ///
+    /// A lookup that failed in every source is a network error when all of
+    /// the failures are, and there was at least one.
+    pub fn is_network(&self) -> bool {
+        match self {
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { .. } | Self::ReadResponse { .. } => true,
+            Self::DeadlineExceeded { .. } => true,
+            Self::AllSourcesFailed { errors, .. } => {
+                !errors.is_empty() && errors.iter().all(|(_, err)| err.is_network())
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the server answered `404 Not Found` or `410 Gone`, or the word
+    /// was a number the client was set to skip.
+    ///
+    /// Words Youdao does not know are not errors but empty translations; see
+    /// [`Translation::is_empty`]. A lookup that failed in every source is not
+    /// found when all of the failures are, and there was at least one.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Self::HttpStatus { status, .. } => {
+                *status == StatusCode::NOT_FOUND || *status == StatusCode::GONE
+            }
+            Self::AllSourcesFailed { errors, .. } => {
+                !errors.is_empty() && errors.iter().all(|(_, err)| err.is_not_found())
+            }
+            Self::NumberQuery { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether Youdao refused to answer: a rate limit, `403 Forbidden`, or a
+    /// lookup skipped after repeated such failures.
+    ///
+    /// A lookup that failed in every source is blocked when any of the
+    /// failures is.
+    pub fn is_blocked(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::CircuitOpen { .. } => true,
+            Self::HttpStatus { status, .. } => {
+                *status == StatusCode::FORBIDDEN || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::AllSourcesFailed { errors, .. } => errors.iter().any(|(_, err)| err.is_blocked()),
+            _ => false,
+        }
+    }
+
+    /// The HTTP status the server answered with, for errors caused by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::StatusCode;
+    ///
+    /// let err = ydt::YdtError::RateLimited {
+    ///     word: "hello".to_string(),
+    ///     retry_after: None,
+    /// };
+    /// assert_eq!(err.status_code(), Some(StatusCode::TOO_MANY_REQUESTS));
+    /// assert_eq!(ydt::YdtError::EmptyWord.status_code(), None);
+    /// ```
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            Self::HttpStatus { status, .. } => Some(*status),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+
+    /// How long the server asked to wait before trying again, for rate limits
+    /// that said.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let err = ydt::YdtError::RateLimited {
+    ///     word: "hello".to_string(),
+    ///     retry_after: Some(Duration::from_secs(30)),
+    /// };
+    /// assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+    /// assert_eq!(ydt::YdtError::EmptyWord.retry_after(), None);
+    /// ```
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Name of the variant in snake case, e.g. `http_status`.
+    ///
+    /// Kinds are stable: they are not renamed between releases, so scripts
+    /// and error reports can match on them. The README lists them all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(ydt::YdtError::EmptyWord.kind(), "empty_word");
+    /// ```
+    pub fn kind(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "http")]
+            Self::CreateHttpClient(_) => "create_http_client",
+            Self::BuildRequestUrl(_) => "build_request_url",
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { .. } => "fetch",
+            Self::HttpStatus { .. } => "http_status",
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::ReadResponse { .. } => "read_response",
+            Self::ParseCssSelector { .. } => "parse_css_selector",
+            Self::ReadConfig(_) => "read_config",
+            Self::ConfigFile { .. } => "config_file",
+            Self::InvalidConfig { .. } => "invalid_config",
+            Self::UnrecognizedLayout { .. } => "unrecognized_layout",
+            Self::EmptyWord => "empty_word",
+            Self::WordTooLong { .. } => "word_too_long",
+            Self::UnsupportedCharacter { .. } => "unsupported_character",
+            Self::InvalidSuggestPayload(_) => "invalid_suggest_payload",
+            Self::ApiError { .. } => "api_error",
+            Self::InvalidApiResponse { .. } => "invalid_api_response",
+            Self::MissingApiCredentials => "missing_api_credentials",
+            Self::AllSourcesFailed { .. } => "all_sources_failed",
+            Self::ReadDictionary(_) => "read_dictionary",
+            Self::ResponseTooLarge { .. } => "response_too_large",
+            Self::Decode { .. } => "decode",
+            Self::CompressedBody { .. } => "compressed_body",
+            Self::UnexpectedRedirect { .. } => "unexpected_redirect",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::CircuitOpen { .. } => "circuit_open",
+            Self::NumberQuery { .. } => "number_query",
+            Self::DeadlineExceeded { .. } => "deadline_exceeded",
+        }
+    }
+
+    /// An owned summary of the error, which unlike the error itself can be
+    /// cloned and compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::{ErrorReport, StatusCode, YdtError};
+    ///
+    /// let err = YdtError::HttpStatus {
+    ///     word: "hello".to_string(),
+    ///     url: "https://www.youdao.com/result?word=hello&lang=en".to_string(),
+    ///     status: StatusCode::NOT_FOUND,
+    /// };
+    /// let report = err.to_owned_report();
+    /// assert_eq!(
+    ///     report,
+    ///     ErrorReport {
+    ///         kind: "http_status".to_string(),
+    ///         message: "Request for \"hello\" failed with status: 404 Not Found".to_string(),
+    ///         word: Some("hello".to_string()),
+    ///         status: Some(404),
+    ///         retry_after_secs: None,
+    ///     }
+    /// );
+    ///
+    /// // With the `serde` feature, reports can be serialized.
+    /// # #[cfg(feature = "serde")]
+    /// assert_eq!(
+    ///     serde_json::to_string(&report).unwrap(),
+    ///     r#"{"kind":"http_status","message":"Request for \"hello\" failed with status: 404 Not Found","word":"hello","status":404}"#
+    /// );
+    /// ```
+    pub fn to_owned_report(&self) -> ErrorReport {
+        ErrorReport {
+            kind: self.kind().to_string(),
+            message: self.to_string(),
+            word: self.word().map(str::to_string),
+            status: self.status_code().map(|status| status.as_u16()),
+            retry_after_secs: self.retry_after().map(whole_secs),
+        }
+    }
+}
+
+/// Summary of a [`YdtError`], made with [`YdtError::to_owned_report`].
+///
+/// With the `serde` feature it implements `Serialize` and `Deserialize`,
+/// leaving out unset fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorReport {
+    /// Name of the error variant in snake case, as from [`YdtError::kind`].
+    pub kind: String,
+    /// The error message, as displayed.
+    pub message: String,
+    /// The word whose lookup failed, for errors that name it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub word: Option<String>,
+    /// The HTTP status code the server answered with, for errors caused by one.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub status: Option<u16>,
+    /// Seconds the server asked to wait before trying again, for rate limits
+    /// that said.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub retry_after_secs: Option<u64>,
+}
+
 impl Error for YdtError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            #[cfg(feature = "http")]
             Self::CreateHttpClient(err) => Some(err),
             Self::BuildRequestUrl(err) => Some(err),
-            Self::FetchTranslation(err) => Some(err),
-            Self::ReadResponse(err) => Some(err),
-            Self::HttpStatus(_) => None,
-            Self::ParseCssSelector(_) => None,
+            #[cfg(any(feature = "http", feature = "wasm"))]
+            Self::Fetch { source, .. } | Self::ReadResponse { source, .. } => Some(source),
+            Self::ReadConfig(err) => Some(err),
+            Self::ReadDictionary(err) => Some(err),
+            Self::HttpStatus { .. } => None,
+            Self::ParseCssSelector { .. } => None,
+            Self::ConfigFile { .. } => None,
+            Self::InvalidConfig { .. } => None,
+            Self::UnrecognizedLayout { .. } => None,
+            Self::EmptyWord => None,
+            Self::WordTooLong { .. } => None,
+            Self::UnsupportedCharacter { .. } => None,
+            Self::InvalidSuggestPayload(_) => None,
+            Self::ApiError { .. } => None,
+            Self::InvalidApiResponse { .. } => None,
+            Self::MissingApiCredentials => None,
+            Self::AllSourcesFailed { .. } => None,
+            Self::ResponseTooLarge { .. } => None,
+            Self::Decode { .. } => None,
+            Self::CompressedBody { .. } => None,
+            Self::UnexpectedRedirect { .. } => None,
+            Self::RateLimited { .. } => None,
+            Self::CircuitOpen { .. } => None,
+            Self::NumberQuery { .. } => None,
+            Self::DeadlineExceeded { .. } => None,
         }
     }
 }
 
-fn contains_cjk_ideograph(text: &str) -> bool {
+/// `wait` in whole seconds, rounded up so that waiting that long is enough.
+pub(crate) fn whole_secs(wait: Duration) -> u64 {
+    wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+}
+
+pub(crate) fn contains_cjk_ideograph(text: &str) -> bool {
     text.chars().any(|ch| {
         ('\u{3400}'..='\u{4DBF}').contains(&ch)
             || ('\u{4E00}'..='\u{9FFF}').contains(&ch)
@@ -81,59 +736,146 @@ fn contains_cjk_ideograph(text: &str) -> bool {
     })
 }
 
-fn build_client(user_agent: &str) -> Result<Client, YdtError> {
-    Client::builder()
-        .user_agent(user_agent)
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(YdtError::CreateHttpClient)
+/// Most phonetics, definitions or suggestions taken from a page, so that
+/// adversarial pages cannot produce huge results.
+pub const MAX_PARSED_ITEMS: usize = 256;
+
+/// Longest text kept for a single phonetic, definition or suggestion, in bytes.
+pub const MAX_FIELD_LEN: usize = 4096;
+
+/// Longest page parsed, in bytes; anything after it is ignored.
+pub const MAX_HTML_LEN: usize = 16 * 1024 * 1024;
+
+/// Deepest nesting of elements parsed; pages are cut where they nest deeper,
+/// since parsing time grows with the square of the depth.
+pub const MAX_NESTING_DEPTH: usize = 512;
+
+/// Elements without content, which never nest.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Length of the start of `html` whose elements nest at most
+/// [`MAX_NESTING_DEPTH`] deep, estimated from its start and end tags.
+fn nesting_limit(html: &str) -> usize {
+    let mut depth = 0usize;
+    let mut pos = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let tag = &html[start + 1..];
+        let Some(tag_len) = tag.find('>') else { break };
+        pos = start + 1 + tag_len + 1;
+        if tag.starts_with("!--") {
+            pos = html[start..]
+                .find("-->")
+                .map_or(html.len(), |end| start + end + 3);
+        } else if tag.starts_with('/') {
+            depth = depth.saturating_sub(1);
+        } else if tag.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            let name_len = tag
+                .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '-')
+                .unwrap_or(tag.len());
+            let name = &tag[..name_len];
+            if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+                // Raw text, which may contain `<` of its own.
+                let close = format!("</{name}");
+                pos = find_ignore_ascii_case(&html[pos..], &close)
+                    .map_or(html.len(), |end| pos + end);
+            } else if !VOID_ELEMENTS
+                .iter()
+                .any(|void| name.eq_ignore_ascii_case(void))
+                && !tag[..tag_len].ends_with('/')
+            {
+                depth += 1;
+                if depth > MAX_NESTING_DEPTH {
+                    return start;
+                }
+            }
+        }
+    }
+    html.len()
 }
 
-fn send_with_ua(word: &str, user_agent: &str) -> Result<Response, YdtError> {
-    let client = build_client(user_agent)?;
-    let url = Url::parse_with_params(YOUDAO_RESULT_URL, &[("word", word), ("lang", "en")])
-        .map_err(YdtError::BuildRequestUrl)?;
-    client.get(url).send().map_err(YdtError::FetchTranslation)
+/// Offset of the first `needle` in `haystack`, ignoring ASCII case.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
 }
 
-fn ensure_success_response(response: Response) -> Result<Response, YdtError> {
-    let status = response.status();
-    if status.is_success() {
-        Ok(response)
-    } else {
-        Err(YdtError::HttpStatus(status))
+/// `text` cut to at most `max` bytes at a character boundary.
+pub(crate) fn truncate_bytes(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
     }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
 }
 
-fn fetch_with_fallback(word: &str) -> Result<Response, YdtError> {
-    match send_with_ua(word, PROJECT_USER_AGENT) {
-        Ok(resp) => {
-            let status = resp.status();
-            if status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS {
-                let fallback_resp = send_with_ua(word, BROWSER_USER_AGENT)?;
-                ensure_success_response(fallback_resp)
-            } else {
-                ensure_success_response(resp)
-            }
-        }
-        Err(_) => {
-            let fallback_resp = send_with_ua(word, BROWSER_USER_AGENT)?;
-            ensure_success_response(fallback_resp)
-        }
-    }
+/// `text` cut to [`MAX_FIELD_LEN`].
+pub(crate) fn capped(mut text: String) -> String {
+    let len = truncate_bytes(&text, MAX_FIELD_LEN).len();
+    text.truncate(len);
+    text
 }
 
-fn cached_selector(
-    cache: &'static OnceLock<Result<Selector, YdtError>>,
-    css: &'static str,
-) -> Result<&'static Selector, YdtError> {
-    match cache.get_or_init(|| Selector::parse(css).map_err(|_| YdtError::ParseCssSelector(css))) {
-        Ok(selector) => Ok(selector),
-        Err(_) => Err(YdtError::ParseCssSelector(css)),
+/// The text of `element` [normalized](text::normalize) and cut to
+/// [`MAX_FIELD_LEN`].
+pub(crate) fn trimmed_text(element: ElementRef<'_>) -> String {
+    capped(text::normalize(&element.text().collect::<String>()))
+}
+
+/// The [trimmed text](trimmed_text) of the first element in `parent` matching
+/// `selector`, or an empty string.
+pub(crate) fn first_text(parent: ElementRef<'_>, selector: &Selector) -> String {
+    parent
+        .select(selector)
+        .next()
+        .map(trimmed_text)
+        .unwrap_or_default()
+}
+
+/// The elements of `document` matching `selector` that are not inside
+/// another match, so that panes nested in a container of the same class are
+/// read once as part of it.
+pub(crate) fn outermost<'a>(
+    document: &'a Html,
+    selector: &'a Selector,
+) -> impl Iterator<Item = ElementRef<'a>> + 'a {
+    document.select(selector).filter(move |element| {
+        !element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|parent| selector.matches(&parent))
+    })
+}
+
+/// Parse the part of `html` within [`MAX_HTML_LEN`] and
+/// [`MAX_NESTING_DEPTH`].
+pub(crate) fn parse_document(html: &str) -> Html {
+    let page = truncate_bytes(html, MAX_HTML_LEN);
+    let page = &page[..nesting_limit(page)];
+    if page.len() < html.len() {
+        log::warn!(
+            "only parsing the first {} of {} bytes of the page",
+            page.len(),
+            html.len()
+        );
     }
+    Html::parse_document(page)
 }
 
-/// Parse translation text from a Youdao result HTML fragment.
+/// Parse a Youdao result HTML fragment into a structured [`Translation`].
+///
+/// Pages are parsed up to [`MAX_HTML_LEN`] bytes or [`MAX_NESTING_DEPTH`]
+/// nested elements, and at most
+/// [`MAX_PARSED_ITEMS`] phonetics, definitions and suggestions of at most
+/// [`MAX_FIELD_LEN`] bytes each are kept.
 ///
 /// This function does not perform network I/O.
 ///
@@ -153,83 +895,558 @@ fn cached_selector(
 ///   </li>
 /// </div>
 /// "#;
-/// let out = ydt::parse_translation_from_html("hello", html).unwrap();
-/// assert_eq!(out, "英 /həˈləʊ/\nint.: 你好");
+/// let translation = ydt::parse_translation("hello", html).unwrap();
+/// assert_eq!(translation.phonetics[0].text, "/həˈləʊ/");
+/// assert_eq!(translation.entries[0].meaning, "你好");
 /// ```
-pub fn parse_translation_from_html(word: &str, html: &str) -> Result<String, YdtError> {
-    let document = Html::parse_document(html);
-    let mut translations = Vec::new();
-    let mut phonetics = Vec::new();
+pub fn parse_translation(word: &str, html: &str) -> Result<Translation, YdtError> {
+    parse_translation_with_warnings(word, html).map(|(translation, _)| translation)
+}
 
-    if contains_cjk_ideograph(word) {
-        let word_exp_selector =
-            cached_selector(&WORD_EXP_CE_SELECTOR, "li.word-exp-ce.mcols-layout")?;
-        let point_selector = cached_selector(&POINT_SELECTOR, "a.point")?;
+/// Like [`parse_translation`], but also report parts of the page that were
+/// found yet yielded nothing.
+///
+/// Warnings are also logged at the `warn` level. A client built with
+/// [`YdtClientBuilder::strict`] turns them into errors.
+///
+/// # Examples
+///
+/// ```
+/// use ydt::ParseWarning;
+///
+/// let html = r#"
+/// <div class="trans-container"></div>
+/// <div class="trans-container"><ul><li>你好</li></ul></div>
+/// "#;
+/// let (translation, warnings) = ydt::parse_translation_with_warnings("hello", html).unwrap();
+/// assert!(translation.is_empty());
+/// assert_eq!(warnings, [ParseWarning::DefinitionsMissing]);
+/// ```
+pub fn parse_translation_with_warnings(
+    word: &str,
+    html: &str,
+) -> Result<(Translation, Vec<ParseWarning>), YdtError> {
+    parse_translation_with_selectors(word, html, selectors::default_selectors())
+}
 
-        for exp in document.select(word_exp_selector) {
-            if let Some(word_text) = exp.select(point_selector).next() {
-                translations.push(word_text.text().collect::<String>());
-            }
-        }
+/// Like [`parse_translation_with_warnings`], locating results with custom
+/// [`Selectors`].
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div class="trans-container"></div>
+/// <div class="trans-container">
+///   <li class="entry"><span class="pos">int.</span><span class="trans">你好</span></li>
+/// </div>
+/// "#;
+/// let selectors = ydt::SelectorConfig {
+///     word_exp: "li.entry".to_string(),
+///     ..ydt::SelectorConfig::default()
+/// }
+/// .compile()
+/// .unwrap();
+/// let (translation, _) = ydt::parse_translation_with_selectors("hello", html, &selectors).unwrap();
+/// assert_eq!(translation.entries[0].meaning, "你好");
+/// ```
+pub fn parse_translation_with_selectors(
+    word: &str,
+    html: &str,
+    selectors: &Selectors,
+) -> Result<(Translation, Vec<ParseWarning>), YdtError> {
+    parse_translation_with_options(word, html, selectors, &TranslationOptions::default())
+}
+
+/// Like [`parse_translation_with_selectors`], also parsing the optional
+/// sections requested in `options` and leaving out what they exclude, such as
+/// the phonetics of other accents. [`TranslationOptions::lang`] is ignored, as
+/// the page has been fetched already.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div class="trans-container"></div>
+/// <div class="trans-container">
+///   <li class="word-exp"><span class="pos">v.</span><span class="trans">拿</span></li>
+/// </div>
+/// <div class="collins"><ul><li class="mcols-layout">
+///   <span class="index">1.</span>
+///   <p class="collins-def">
+///     <span class="pos">VERB</span>
+///     <span class="def-en">If you take something, you hold it.</span>
+///     <span class="def-ch">拿</span>
+///   </p>
+/// </li></ul></div>
+/// "#;
+/// let options = ydt::TranslationOptions {
+///     include_collins: Some(true),
+///     ..Default::default()
+/// };
+/// let selectors = ydt::SelectorConfig::default().compile().unwrap();
+/// let (translation, _) =
+///     ydt::parse_translation_with_options("take", html, &selectors, &options).unwrap();
+/// assert_eq!(translation.collins[0].pos, "VERB");
+/// ```
+pub fn parse_translation_with_options(
+    word: &str,
+    html: &str,
+    selectors: &Selectors,
+    options: &TranslationOptions,
+) -> Result<(Translation, Vec<ParseWarning>), YdtError> {
+    let page = ParsedPage::with_selectors(html, selectors);
+    let document = page.document();
+    // Mixed-script queries such as "C语言" may be served either layout, so the
+    // other layouts are tried when the expected one yields nothing. Queries
+    // such as "xuexi" may be pinyin, answered with candidate Chinese words.
+    let mut layouts = if contains_cjk_ideograph(word) {
+        vec![Layout::ChineseEnglish, Layout::EnglishChinese]
     } else {
-        let trans_container_selector =
-            cached_selector(&TRANS_CONTAINER_SELECTOR, "div.trans-container")?;
-        let phone_selector = cached_selector(&PHONE_SELECTOR, "div.per-phone")?;
-        let span_selector = cached_selector(&SPAN_SELECTOR, "span")?;
-        let phonetic_selector = cached_selector(&PHONETIC_SELECTOR, "span.phonetic")?;
-        let word_exp_selector = cached_selector(&WORD_EXP_SELECTOR, "li.word-exp")?;
-        let pos_selector = cached_selector(&POS_SELECTOR, "span.pos")?;
-        let trans_selector = cached_selector(&TRANS_SELECTOR, "span.trans")?;
-
-        if let Some(container) = document.select(trans_container_selector).nth(0) {
-            for phone_div in container.select(phone_selector) {
-                if let Some(label) = phone_div.select(span_selector).next() {
-                    let label_text = label.text().collect::<String>().trim().to_string();
-                    if let Some(phonetic) = phone_div.select(phonetic_selector).next() {
-                        let phonetic_text = phonetic.text().collect::<String>().trim().to_string();
-                        phonetics.push(format!("{} {}", label_text, phonetic_text));
-                    }
-                }
+        vec![Layout::EnglishChinese, Layout::ChineseEnglish]
+    };
+    let both = options.strategy == Some(LookupStrategy::Both)
+        && !word.is_empty()
+        && word.chars().all(|ch| ch.is_ascii_alphabetic() || ch == ' ');
+    if both || looks_like_pinyin(word) {
+        layouts.push(Layout::Pinyin);
+    }
+    // Only the definitions kept are collected, unless grouping needs them all.
+    let max_definitions = match options.group_by_pos {
+        Some(true) => MAX_PARSED_ITEMS,
+        _ => options
+            .max_definitions
+            .map_or(MAX_PARSED_ITEMS, |max| max.min(MAX_PARSED_ITEMS)),
+    };
+    let mut parsed = layouts.into_iter().map(|layout| {
+        let parsed = parse_layout(document, selectors, word, layout, max_definitions);
+        (layout, parsed)
+    });
+    let (_, (mut translation, mut warnings)) = parsed.next().unwrap();
+    if translation.is_empty() {
+        match parsed.find(|(_, (other, _))| !other.is_empty()) {
+            Some((layout, other)) => {
+                log::debug!("{word:?} parsed with the {layout:?} layout");
+                (translation, warnings) = other;
+            }
+            None => {
+                translation.layout = None;
+                translation.direction = None;
             }
         }
+    }
+    if both {
+        // Layouts before the one kept yielded nothing.
+        translation.other_directions = parsed
+            .map(|(_, (other, _))| other)
+            .filter(|other| !other.is_empty())
+            .collect();
+    }
 
-        if let Some(container) = document.select(trans_container_selector).nth(1) {
-            for exp in container.select(word_exp_selector) {
-                if let (Some(pos), Some(trans)) = (
-                    exp.select(pos_selector).next(),
-                    exp.select(trans_selector).next(),
-                ) {
-                    let pos_text = pos.text().collect::<String>().trim().to_string();
-                    let trans_text = trans.text().collect::<String>().trim().to_string();
-                    translations.push(format!("{}: {}", pos_text, trans_text));
-                }
-            }
+    parse_headword(document, selectors, &mut translation);
+    parse_frequency(document, selectors, &mut translation, &mut warnings);
+    translation.idiom = idiom::parse_idiom(document, selectors, &mut warnings);
+    if options.include_collins == Some(true) {
+        translation.collins = collins::parse_collins(
+            document,
+            selectors,
+            options.max_senses.unwrap_or(MAX_PARSED_ITEMS),
+            &mut warnings,
+        );
+    }
+    if options.include_related == Some(true) {
+        translation.related = related::parse_related(document, selectors);
+    }
+    translation.suggestions = page.suggestions().to_vec();
+    translation.conversions = document
+        .select(&selectors.conversion)
+        .map(trimmed_text)
+        .filter(|conversion| !conversion.is_empty())
+        .take(MAX_PARSED_ITEMS)
+        .collect();
+    translation.web_translations = document
+        .select(&selectors.web_translation)
+        .map(trimmed_text)
+        .filter(|web| !web.is_empty())
+        .take(MAX_PARSED_ITEMS)
+        .collect();
+    options.apply(&mut translation);
+    if let Some(max) = translation.display_limit {
+        for (field, len) in translation.long_fields(max) {
+            warnings.push(ParseWarning::FieldTooLong {
+                field: field.to_string(),
+                len,
+                max,
+            });
         }
     }
 
-    if phonetics.is_empty() && translations.is_empty() {
-        Ok("No results.".to_string())
-    } else {
-        let phonetics_str = phonetics.join(" ");
-        let translations_str = translations.join("\n");
-        if phonetics_str.is_empty() {
-            Ok(translations_str)
-        } else if translations_str.is_empty() {
-            Ok(phonetics_str)
+    for warning in &warnings {
+        log::warn!("{word:?}: {warning}");
+    }
+    if translation.is_empty() {
+        log::warn!("no phonetics or definitions found on the page for {word:?}");
+    }
+    Ok((translation, warnings))
+}
+
+/// The words suggested for a misspelled query.
+pub(crate) fn parse_suggestions(document: &Html, selectors: &Selectors) -> Vec<String> {
+    document
+        .select(&selectors.suggestion)
+        .map(trimmed_text)
+        .filter(|suggestion| !suggestion.is_empty())
+        .take(MAX_PARSED_ITEMS)
+        .collect()
+}
+
+fn parse_layout(
+    document: &Html,
+    selectors: &Selectors,
+    word: &str,
+    layout: Layout,
+    max_definitions: usize,
+) -> (Translation, Vec<ParseWarning>) {
+    let mut translation = Translation {
+        word: word.to_string(),
+        layout: Some(layout),
+        direction: Some(layout.into()),
+        ..Translation::default()
+    };
+    let mut warnings = Vec::new();
+    match layout {
+        Layout::ChineseEnglish => parse_chinese_english(
+            document,
+            selectors,
+            max_definitions,
+            &mut translation,
+            &mut warnings,
+        ),
+        Layout::EnglishChinese => parse_english_chinese(
+            document,
+            selectors,
+            max_definitions,
+            &mut translation,
+            &mut warnings,
+        ),
+        Layout::Pinyin => parse_pinyin(
+            document,
+            selectors,
+            max_definitions,
+            &mut translation,
+            &mut warnings,
+        ),
+    }
+    (translation, warnings)
+}
+
+fn parse_headword(document: &Html, selectors: &Selectors, translation: &mut Translation) {
+    let Some(headword) = document
+        .select(&selectors.headword)
+        .next()
+        .map(trimmed_text)
+        .filter(|headword| !headword.is_empty())
+    else {
+        return;
+    };
+    if !headword.eq_ignore_ascii_case(&translation.word) {
+        translation.lemma_note = document
+            .select(&selectors.lemma_note)
+            .next()
+            .map(|note| inflection_name(trimmed_text(note)))
+            .filter(|note| !note.is_empty());
+    }
+    translation.headword = Some(headword);
+}
+
+/// Read the number of stars from the `star<N>` class of the frequency rating,
+/// clamping it to 1 to 5.
+fn parse_frequency(
+    document: &Html,
+    selectors: &Selectors,
+    translation: &mut Translation,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    let Some(stars) = document.select(&selectors.frequency).find_map(|rating| {
+        rating
+            .value()
+            .classes()
+            .find_map(|class| class.strip_prefix("star")?.parse::<u32>().ok())
+    }) else {
+        return;
+    };
+    let clamped = stars.clamp(1, 5);
+    if clamped != stars {
+        warnings.push(ParseWarning::FrequencyOutOfRange { stars });
+    }
+    translation.frequency = Some(clamped as u8);
+}
+
+/// Extract `现在分词` from a note such as `running 是 run 的现在分词`, keeping
+/// notes in other forms whole.
+fn inflection_name(note: String) -> String {
+    note.split_once('是')
+        .and_then(|(_, rest)| rest.split_once('的'))
+        .map(|(_, name)| name.trim().trim_end_matches(['。', '.']).to_string())
+        .unwrap_or(note)
+}
+
+fn parse_chinese_english(
+    document: &Html,
+    selectors: &Selectors,
+    max_definitions: usize,
+    translation: &mut Translation,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    translation.entries = ce_entries(document, selectors, max_definitions, warnings);
+}
+
+/// The English words given for a Chinese query, as definitions without a
+/// part of speech.
+pub(crate) fn ce_entries(
+    document: &Html,
+    selectors: &Selectors,
+    max_definitions: usize,
+    warnings: &mut Vec<ParseWarning>,
+) -> Vec<Entry> {
+    let exps = document
+        .select(&selectors.word_exp_ce)
+        .take(max_definitions)
+        .collect::<Vec<_>>();
+    let mut entries = Vec::with_capacity(exps.len());
+    for exp in exps {
+        if let Some(word_text) = exp.select(&selectors.point).next() {
+            entries.push(Entry {
+                pos: String::new(),
+                meaning: trimmed_text(word_text),
+                source: EntrySource::Concise,
+            });
+        } else {
+            warnings.push(ParseWarning::CandidateTextMissing);
+        }
+    }
+    entries
+}
+
+fn parse_pinyin(
+    document: &Html,
+    selectors: &Selectors,
+    max_definitions: usize,
+    translation: &mut Translation,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    for candidate in document
+        .select(&selectors.pinyin_candidate)
+        .take(max_definitions)
+    {
+        let hanzi = first_text(candidate, &selectors.point);
+        if hanzi.is_empty() {
+            warnings.push(ParseWarning::CandidateTextMissing);
+            continue;
+        }
+        translation.candidates.push(Candidate {
+            hanzi,
+            pinyin: first_text(candidate, &selectors.candidate_pinyin),
+            gloss: first_text(candidate, &selectors.candidate_gloss),
+        });
+    }
+}
+
+fn parse_english_chinese(
+    document: &Html,
+    selectors: &Selectors,
+    max_definitions: usize,
+    translation: &mut Translation,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    translation.phonetics = ec_phonetics(document, selectors, warnings);
+    translation.entries = ec_entries(document, selectors, max_definitions, warnings);
+}
+
+/// The phonetics of an English query, from the first result container.
+pub(crate) fn ec_phonetics(
+    document: &Html,
+    selectors: &Selectors,
+    warnings: &mut Vec<ParseWarning>,
+) -> Vec<Phonetic> {
+    let Some(container) = outermost(document, &selectors.trans_container).next() else {
+        return Vec::new();
+    };
+    let phone_divs = container
+        .select(&selectors.phone)
+        .take(MAX_PARSED_ITEMS)
+        .collect::<Vec<_>>();
+    let mut phonetics = Vec::with_capacity(phone_divs.len());
+    for phone_div in phone_divs {
+        if let Some(label) = phone_div.select(&selectors.phone_label).next() {
+            let label_text = trimmed_text(label);
+            if let Some(phonetic) = phone_div.select(&selectors.phonetic).next() {
+                phonetics.push(Phonetic {
+                    label: label_text,
+                    text: trimmed_text(phonetic),
+                });
+            } else {
+                warnings.push(ParseWarning::PhoneticTextMissing { label: label_text });
+            }
         } else {
-            Ok(format!("{}\n{}", phonetics_str, translations_str))
+            warnings.push(ParseWarning::PhoneticLabelMissing);
+        }
+    }
+    phonetics
+}
+
+/// The definitions of an English query, from the second result container,
+/// followed by what an all-caps query stands for.
+pub(crate) fn ec_entries(
+    document: &Html,
+    selectors: &Selectors,
+    max_definitions: usize,
+    warnings: &mut Vec<ParseWarning>,
+) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    if let Some(container) = outermost(document, &selectors.trans_container).nth(1) {
+        let mut exps = container
+            .select(&selectors.word_exp)
+            .take(MAX_PARSED_ITEMS)
+            .peekable();
+        if exps.peek().is_none() && max_definitions > 0 {
+            warnings.push(ParseWarning::DefinitionsMissing);
+        }
+        for exp in exps {
+            if entries.len() >= max_definitions {
+                break;
+            }
+            let pos = exp.select(&selectors.pos).next();
+            let trans = exp.select(&selectors.trans).next();
+            let meaning = match trans {
+                Some(trans) => trimmed_text(trans),
+                // Glosses of phrases and abbreviations may be all of the
+                // definition, without a part of speech.
+                None => text_outside(exp, pos),
+            };
+            if meaning.is_empty() {
+                warnings.push(ParseWarning::DefinitionEmpty);
+                continue;
+            }
+            let entry = Entry {
+                pos: pos.map(trimmed_text).unwrap_or_default(),
+                meaning,
+                source: EntrySource::Concise,
+            };
+            // Panes nested in the container repeat some definitions.
+            if entries.contains(&entry) {
+                continue;
+            }
+            if pos.is_some() && trans.is_none() {
+                warnings.push(ParseWarning::DefinitionIncomplete);
+            }
+            entries.push(entry);
         }
     }
+
+    // All-caps queries such as "NASA" may list what they stand for instead.
+    let max_abbreviations = max_definitions.saturating_sub(entries.len());
+    entries.extend(abbreviation::parse_abbreviations(
+        document,
+        selectors,
+        max_abbreviations,
+    ));
+    entries
+}
+
+/// The [normalized](text::normalize) text of `element` outside `skipped`.
+fn text_outside(element: ElementRef<'_>, skipped: Option<ElementRef<'_>>) -> String {
+    let text = element
+        .descendants()
+        .filter(|node| {
+            skipped.is_none_or(|skipped| !node.ancestors().any(|parent| parent == *skipped))
+        })
+        .filter_map(|node| node.value().as_text().map(|text| &**text))
+        .collect::<String>();
+    capped(text::normalize(&text))
+}
+
+/// Parse translation text from a Youdao result HTML fragment.
+///
+/// This function does not perform network I/O.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div class="trans-container">
+///   <div class="per-phone">
+///     <span>英</span><span class="phonetic">/həˈləʊ/</span>
+///   </div>
+/// </div>
+/// <div class="trans-container">
+///   <li class="word-exp">
+///     <span class="pos">int.</span>
+///     <span class="trans">你好</span>
+///   </li>
+/// </div>
+/// "#;
+/// let out = ydt::parse_translation_from_html("hello", html).unwrap();
+/// assert_eq!(out, "英 /həˈləʊ/\nint.: 你好");
+/// ```
+pub fn parse_translation_from_html(word: &str, html: &str) -> Result<String, YdtError> {
+    let translation = parse_translation(word, html)?;
+    Ok(render_translation(&translation, &RenderOptions::default()))
+}
+
+#[cfg(feature = "http")]
+/// Fetch and parse the translation for a word from Youdao.
+///
+/// Calls share their HTTP clients, so consecutive lookups reuse connections.
+///
+/// # Errors
+///
+/// Returns [`YdtError`] when the word is empty or too long (see [`normalize_word`]),
+/// or when request building, HTTP request, HTTP status validation, response reading,
+/// or selector parsing fails.
+pub fn translate(word: &str) -> Result<Translation, YdtError> {
+    YdtClient::new().translate(word)
 }
 
+#[cfg(feature = "http")]
+/// Fetch the raw Youdao result page for a word without parsing it.
+///
+/// # Errors
+///
+/// Returns [`YdtError`] when request building, HTTP request, HTTP status validation
+/// or response reading fails.
+pub fn fetch_html(word: &str) -> Result<String, YdtError> {
+    YdtClient::new().fetch_html(word)
+}
+
+#[cfg(feature = "http")]
+/// Fetch and parse the translation for a word, also returning the page it was
+/// parsed from.
+///
+/// # Errors
+///
+/// Returns [`YdtError`] under the same conditions as [`translate`].
+pub fn translate_with_html(word: &str) -> Result<(Translation, String), YdtError> {
+    YdtClient::new().translate_with_html(word)
+}
+
+#[cfg(feature = "http")]
 /// Fetch translation for a word from Youdao and return normalized display text.
 ///
 /// # Errors
 ///
-/// Returns [`YdtError`] when request building, HTTP request, HTTP status validation,
-/// response reading, or selector parsing fails.
+/// Returns [`YdtError`] under the same conditions as [`translate`].
 pub fn get_translation(word: &str) -> Result<String, YdtError> {
-    let response = fetch_with_fallback(word)?;
-    let html = response.text().map_err(YdtError::ReadResponse)?;
-    parse_translation_from_html(word, &html)
+    get_translation_with(word, &RenderOptions::default())
+}
+
+#[cfg(feature = "http")]
+/// Like [`get_translation`], rendering the result with the given [`RenderOptions`].
+///
+/// # Errors
+///
+/// Returns [`YdtError`] under the same conditions as [`get_translation`].
+pub fn get_translation_with(word: &str, options: &RenderOptions) -> Result<String, YdtError> {
+    let translation = translate(word)?;
+    Ok(render_translation(&translation, options))
 }
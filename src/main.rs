@@ -1,17 +1,1651 @@
+// Only `UNSUPPORTED` is used without the `open` feature.
+#[cfg_attr(not(feature = "open"), allow(dead_code))]
+mod browser;
+mod cli;
+mod clipboard;
+mod console;
+mod diff;
+mod doctor;
+#[cfg(feature = "clipboard")]
+mod watch;
+mod word_file;
+
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+#[cfg(feature = "clipboard")]
+use std::time::Instant;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Please provide a word to translate");
-        process::exit(2);
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use cli::{Cli, Command, ConfigAction, Detail, TranslateArgs};
+use log::LevelFilter;
+use serde_json::{json, Value};
+use terminal_size::{terminal_size, Width};
+use ydt::config::{self, Config};
+use ydt::{
+    is_number, FetchInfo, Labels, LookupStrategy, OutputFormat, RenderOptions, RequestPlan,
+    ResultOrder, Sections, Translation, TranslationOptions, YdtClient, YdtError,
+};
+
+const EXIT_NO_RESULTS: i32 = 1;
+/// Network and HTTP failures, and any other error.
+const EXIT_FAILURE: i32 = 2;
+const EXIT_USAGE: i32 = 3;
+/// Rate limits and refused requests.
+const EXIT_BLOCKED: i32 = 4;
+/// Batches stopped with Ctrl-C, as for a process killed by SIGINT.
+const EXIT_INTERRUPTED: i32 = 130;
+
+const DEFAULT_WIDTH: usize = 120;
+const DEFAULT_SUGGEST_LIMIT: usize = 10;
+
+/// How often a batch waiting for stdin checks whether Ctrl-C was pressed.
+const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+struct Args {
+    word: Option<String>,
+    /// Look up the clipboard contents; see `clip_word`.
+    clip: bool,
+    /// Copy the printed result to the clipboard.
+    copy: bool,
+    /// Word list to look up; see `run_word_file`.
+    from: Option<PathBuf>,
+    /// Look up text as it is copied; see `run_watch`.
+    watch_clipboard: bool,
+    /// Longest copied text looked up by `run_watch`, if not the default.
+    #[cfg(feature = "clipboard")]
+    max_len: Option<usize>,
+    sections: Sections,
+    ascii_phonetics: bool,
+    /// Tag definitions with where they come from.
+    show_source: bool,
+    /// Follow results with the link to their page.
+    show_link: bool,
+    /// Label results in English regardless of the locale; see `Args::labels`.
+    english_labels: bool,
+    one_line: bool,
+    width: Option<usize>,
+    no_wrap: bool,
+    header: bool,
+    /// Print errors as JSON, from `--json-errors`; see `Args::json_errors`.
+    json_errors: bool,
+    concurrency: usize,
+    order: ResultOrder,
+    quiet: bool,
+    raw_html: bool,
+    /// Print the requests of a lookup instead; see `run_dry_run`.
+    dry_run: bool,
+    /// Print them as JSON.
+    json: bool,
+    strict: bool,
+    auto_correct: bool,
+    /// Look Traditional Chinese words up in Simplified Chinese.
+    convert_traditional: bool,
+    /// Machine-translate WORD as a sentence regardless of its length.
+    sentence: bool,
+    /// Only look words up in the local dictionary.
+    offline: bool,
+    /// Answer JSON requests read from stdin; see `run_stdio`.
+    stdio: bool,
+    /// Directory to save result pages that yield no results to.
+    dump_on_empty: Option<PathBuf>,
+    /// Directory to save every fetched result page to.
+    record: Option<PathBuf>,
+    /// Only keep senses containing any of these, from `--filter`.
+    filters: Vec<String>,
+    /// Optional sections of result pages to parse, from `--detail`.
+    translation_options: TranslationOptions,
+    /// Number of `-v` flags: fetch details and info logs, then debug and trace logs.
+    verbosity: u8,
+    /// Settings given on the command line, layered over the config file.
+    config: Config,
+    terminals: Terminals,
+}
+
+impl Args {
+    fn format(&self) -> OutputFormat {
+        self.config.format.unwrap_or_default()
+    }
+
+    /// Whether to print errors as JSON objects rather than prose, as scripts
+    /// reading JSON output expect.
+    fn json_errors(&self) -> bool {
+        self.json_errors || self.format() == OutputFormat::Ndjson
+    }
+
+    /// Language of the labels around results: English with `--english-labels`
+    /// or, on terminals, when the locale names a language other than Chinese.
+    /// Output for pipes keeps the labels of the page whatever the locale.
+    fn labels(&self) -> Labels {
+        if self.english_labels {
+            return Labels::English;
+        }
+        if !self.terminals.stdout {
+            return Labels::Chinese;
+        }
+        // The first variable set decides, as for other programs.
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|var| env::var(var).ok().filter(|locale| !locale.is_empty()))
+            .and_then(|locale| Labels::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Whether to print plain output with fixed line positions for scripts:
+    /// stdout is not a terminal and no format or layout was asked for.
+    fn stable_layout(&self) -> bool {
+        !self.terminals.stdout && self.config.format.is_none() && !self.one_line
+    }
+}
+
+/// Which of stdout and stderr are terminals, checked once at startup.
+///
+/// Anything meant for terminals only, such as wrapping, escape sequences or
+/// the progress line, is left out of pipes and files.
+#[derive(Debug, Clone, Copy)]
+struct Terminals {
+    stdout: bool,
+    stderr: bool,
+}
+
+impl Terminals {
+    fn detect() -> Self {
+        Self {
+            stdout: io::stdout().is_terminal(),
+            stderr: io::stderr().is_terminal(),
+        }
+    }
+}
+
+impl From<TranslateArgs> for Args {
+    fn from(args: TranslateArgs) -> Self {
+        let sections = if args.phonetic_only {
+            Sections::PhoneticsOnly
+        } else if args.no_phonetic {
+            Sections::DefinitionsOnly
+        } else {
+            Sections::All
+        };
+        let config = Config {
+            timeout: args.timeout,
+            deadline: args.deadline,
+            retries: args.retries,
+            abort_after: args.abort_after,
+            proxy: args.proxy,
+            max_body_size: args.max_body_size,
+            accent: args.accent,
+            format: args.format,
+            color: args.color,
+            user_agent: args.user_agent,
+            mobile_fallback: args.no_mobile_fallback.then_some(false),
+            cookie_file: args.no_cookie_file.then_some(false),
+            backend: args.backend,
+            cedict: args.cedict,
+            ..Config::default()
+        };
+        Self {
+            word: args.word,
+            clip: args.clip,
+            copy: args.copy,
+            from: args.from,
+            watch_clipboard: args.watch_clipboard,
+            #[cfg(feature = "clipboard")]
+            max_len: args.max_len,
+            sections,
+            ascii_phonetics: args.ascii_phonetics,
+            show_source: args.show_source,
+            show_link: args.show_link,
+            english_labels: args.english_labels,
+            one_line: args.one_line,
+            width: args.width,
+            no_wrap: args.no_wrap,
+            header: args.header,
+            json_errors: args.json_errors,
+            concurrency: args.concurrency,
+            order: if args.unordered {
+                ResultOrder::Completion
+            } else {
+                ResultOrder::Input
+            },
+            quiet: args.quiet,
+            raw_html: args.raw_html,
+            dry_run: args.dry_run,
+            json: args.json,
+            strict: args.strict,
+            auto_correct: args.auto_correct,
+            convert_traditional: !args.no_convert,
+            sentence: args.sentence,
+            offline: args.offline,
+            stdio: args.stdio,
+            dump_on_empty: args.dump_on_empty,
+            record: args.record,
+            filters: args.filter,
+            translation_options: TranslationOptions {
+                include_collins: args.detail.contains(&Detail::Collins).then_some(true),
+                max_senses: args.max_senses,
+                include_related: args.related.then_some(true),
+                strategy: args.auto_both.then_some(LookupStrategy::Both),
+                ..TranslationOptions::default()
+            },
+            verbosity: args.verbose,
+            config,
+            terminals: Terminals::detect(),
+        }
+    }
+}
+
+/// Print `err` and exit, with `EXIT_USAGE` unless it is `--help`.
+fn exit_with(err: clap::Error) -> ! {
+    let _ = err.print();
+    process::exit(if err.use_stderr() { EXIT_USAGE } else { 0 })
+}
+
+/// Exit with a usage error for `message`, as for arguments clap rejects.
+fn usage_error(kind: ErrorKind, message: &str) -> ! {
+    exit_with(Cli::command().error(kind, message))
+}
+
+fn terminal_width() -> usize {
+    match terminal_size() {
+        Some((Width(width), _)) => usize::from(width),
+        None => DEFAULT_WIDTH,
+    }
+}
+
+fn select_sections(mut translation: Translation, args: &Args) -> Translation {
+    match args.sections {
+        Sections::All => {}
+        Sections::PhoneticsOnly => {
+            translation.entries.clear();
+            translation.candidates.clear();
+            translation.conversions.clear();
+            translation.web_translations.clear();
+            translation.collins.clear();
+            translation.related.clear();
+            translation.idiom = None;
+        }
+        Sections::DefinitionsOnly => translation.phonetics.clear(),
     }
-    match ydt::get_translation(&args[1]) {
-        Ok(text) => println!("{text}"),
+    if args.ascii_phonetics {
+        for phonetic in &mut translation.phonetics {
+            *phonetic = phonetic.to_ascii();
+        }
+    }
+    translation
+}
+
+/// `translation` with only the senses matching `--filter`, if given.
+fn filter_senses(translation: Translation, args: &Args) -> Translation {
+    if args.filters.is_empty() {
+        return translation;
+    }
+    let filters: Vec<&str> = args.filters.iter().map(String::as_str).collect();
+    translation.filtered(&filters)
+}
+
+/// Number of senses `--filter` chooses from, in every direction.
+fn sense_count(translation: &Translation) -> usize {
+    translation.entries.len()
+        + translation.candidates.len()
+        + translation.conversions.len()
+        + translation.web_translations.len()
+        + translation.collins.len()
+        + translation
+            .related
+            .iter()
+            .map(|group| group.words.len())
+            .sum::<usize>()
+        + usize::from(translation.idiom.is_some())
+        + translation
+            .other_directions
+            .iter()
+            .map(sense_count)
+            .sum::<usize>()
+}
+
+fn render(args: &Args, translation: &Translation) -> String {
+    let options = RenderOptions {
+        sections: args.sections,
+        show_source: args.show_source,
+        color: args
+            .config
+            .color
+            .unwrap_or_default()
+            .enabled(args.terminals.stdout),
+        labels: args.labels(),
+    };
+    if args.one_line {
+        let width = args.width.unwrap_or_else(terminal_width);
+        return ydt::render_one_line_labeled(translation, width, options.labels);
+    }
+    let notices = [
+        ydt::render_conversion_notice(translation),
+        ydt::render_lemma_notice(translation),
+    ];
+    if args.stable_layout() {
+        // Keep the notices out of the way of line positions.
+        for notice in notices.iter().flatten() {
+            eprintln!("{notice}");
+        }
+        let body = ydt::render_stable(translation, &options);
+        return with_link(args, translation, body);
+    }
+    let body = if !args.no_wrap && args.terminals.stdout {
+        let width = args.width.unwrap_or_else(terminal_width);
+        ydt::render_pretty(translation, &options, width)
+    } else {
+        ydt::render_translation(translation, &options)
+    };
+    let mut lines: Vec<_> = notices.into_iter().flatten().collect();
+    lines.push(body);
+    with_link(args, translation, lines.join("\n"))
+}
+
+/// `body` followed by the link to the result page with `--show-link`, dimmed
+/// on terminals.
+fn with_link(args: &Args, translation: &Translation, body: String) -> String {
+    match &translation.source_url {
+        Some(url) if args.show_link && args.terminals.stdout => {
+            format!("{body}\n\x1b[2m{url}\x1b[0m")
+        }
+        Some(url) if args.show_link => format!("{body}\n{url}"),
+        _ => body,
+    }
+}
+
+fn lookup(client: &YdtClient, args: &Args, word: &str) -> Result<Translation, YdtError> {
+    client
+        .translate(word)
+        .map(|translation| select_sections(translation, args))
+}
+
+fn render_fetch_info(info: &FetchInfo) -> String {
+    let mut lines = vec![
+        format!("status: {}", info.status),
+        format!("elapsed: {:.0?}", info.elapsed),
+    ];
+    if let Some(time_to_headers) = info.time_to_headers {
+        lines.push(format!("time to headers: {time_to_headers:.0?}"));
+    }
+    if let Some(user_agent) = info.user_agent {
+        lines.push(format!("user agent: {user_agent}"));
+    }
+    if let Some(url) = &info.final_url {
+        lines.push(format!("url: {url}"));
+        lines.push(format!("redirects: {}", info.redirects));
+    }
+    lines.push(format!("from cache: {}", info.from_cache));
+    lines.push(format!("source: {}", info.source));
+    lines.push(format!(
+        "charset: {} ({})",
+        info.charset, info.charset_source
+    ));
+    if let Some(encoding) = info.content_encoding {
+        lines.push(format!("content encoding: {encoding}"));
+    }
+    lines.join("\n")
+}
+
+/// Exit status for a failed lookup.
+fn exit_code(err: &YdtError) -> i32 {
+    if err.is_not_found() {
+        EXIT_NO_RESULTS
+    } else if err.is_blocked() {
+        EXIT_BLOCKED
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+/// Print `err` to stderr, as `{"error": {...}}` with `--json-errors`.
+///
+/// `word` names the lookup in plain output when the error itself does not.
+fn print_error(args: &Args, word: Option<&str>, err: &YdtError) {
+    if args.json_errors() {
+        let mut error = ydt::error_to_json(err);
+        if let (None, Some(word)) = (err.word(), word) {
+            error["word"] = json!(word);
+        }
+        eprintln!("{}", json!({ "error": error }));
+        return;
+    }
+    match (err.word(), word) {
+        (None, Some(word)) => eprintln!("{word}: {err}"),
+        _ => eprintln!("{err}"),
+    }
+}
+
+/// Print a failure that is not a `YdtError` to stderr, as `print_error` does,
+/// with `kind` naming it in JSON.
+fn print_failure(args: &Args, kind: &str, word: Option<&str>, message: &str) {
+    if !args.json_errors() {
+        eprintln!("{message}");
+        return;
+    }
+    let mut error = json!({ "kind": kind });
+    if let Some(word) = word {
+        error["word"] = json!(word);
+    }
+    error["message"] = json!(message);
+    eprintln!("{}", json!({ "error": error }));
+}
+
+fn run_suggest(client: &YdtClient, args: &Args, prefix: &str, limit: usize) -> i32 {
+    let suggestions = match client.suggest(prefix, limit) {
+        Ok(suggestions) => suggestions,
         Err(err) => {
-            eprintln!("{err}");
-            process::exit(1);
+            print_error(args, Some(prefix), &err);
+            return exit_code(&err);
+        }
+    };
+    if suggestions.is_empty() {
+        let message = format!("No suggestions found for: {prefix}");
+        print_failure(args, "no_suggestions", Some(prefix), &message);
+        return EXIT_NO_RESULTS;
+    }
+    let width = suggestions
+        .iter()
+        .map(|suggestion| ydt::display_width(&suggestion.word))
+        .max()
+        .unwrap_or(0);
+    for suggestion in &suggestions {
+        if suggestion.gloss.is_empty() {
+            println!("{}", suggestion.word);
+        } else {
+            let padding = width - ydt::display_width(&suggestion.word);
+            println!(
+                "{}{}  {}",
+                suggestion.word,
+                " ".repeat(padding),
+                suggestion.gloss
+            );
+        }
+    }
+    0
+}
+
+/// Look up both `words` at once and print them side by side; a failed
+/// lookup is shown in its column without hiding the other, and reported on
+/// stderr.
+fn run_diff(client: &YdtClient, args: &Args, words: [&str; 2], width: usize) -> i32 {
+    let results = client.translate_many(&words, words.len());
+    let [left, right] = [0, 1].map(|i| diff::Side {
+        word: words[i],
+        result: &results[i],
+    });
+    println!("{}", diff::render(&left, &right, width));
+    for (word, result) in words.iter().zip(&results) {
+        if let Err(err) = result {
+            print_error(args, Some(word), err);
         }
     }
+    match results.iter().find_map(|result| result.as_ref().err()) {
+        Some(err) => exit_code(err),
+        None if results.iter().flatten().any(Translation::is_empty) => EXIT_NO_RESULTS,
+        None => 0,
+    }
+}
+
+/// Open the result page of `word` in the browser.
+#[cfg(feature = "open")]
+fn run_open(client: &YdtClient, args: &Args, word: &str) -> i32 {
+    match browser::open_word(client, word, &mut browser::System) {
+        Ok(url) => {
+            eprintln!("Opened {url}");
+            0
+        }
+        Err(browser::OpenError::Lookup(err)) => {
+            print_error(args, Some(word), &err);
+            exit_code(&err)
+        }
+        Err(browser::OpenError::NoPage) => {
+            let message = format!("{word}: no result page to open; it was not looked up on Youdao");
+            print_failure(args, "no_page", Some(word), &message);
+            EXIT_FAILURE
+        }
+        Err(browser::OpenError::Launch(msg)) => {
+            print_failure(args, "open_browser", Some(word), &msg);
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(not(feature = "open"))]
+fn run_open(_client: &YdtClient, _args: &Args, _word: &str) -> i32 {
+    usage_error(ErrorKind::InvalidSubcommand, browser::UNSUPPORTED)
+}
+
+#[cfg(feature = "server")]
+fn run_serve(client: YdtClient, host: &str, port: u16) -> i32 {
+    // Log each request to stderr.
+    env_logger::Builder::new()
+        .filter_level(LevelFilter::Warn)
+        .filter_module("ydt::server", LevelFilter::Info)
+        .init();
+    match ydt::server::Server::bind((host, port), client) {
+        Ok(server) => {
+            eprintln!("Listening on http://{}", server.local_addr());
+            server.run();
+            0
+        }
+        Err(err) => {
+            eprintln!("Failed to listen on {host}:{port}: {err}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(not(feature = "server"))]
+fn run_serve(_client: YdtClient, _host: &str, _port: u16) -> i32 {
+    usage_error(
+        ErrorKind::InvalidSubcommand,
+        "`ydt serve` needs ydt built with the `server` feature",
+    )
+}
+
+fn run_raw_html(client: &YdtClient, args: &Args, word: &str) -> i32 {
+    match client.fetch_html(word) {
+        Ok(html) => {
+            print!("{html}");
+            0
+        }
+        Err(err) => {
+            print_error(args, None, &err);
+            exit_code(&err)
+        }
+    }
+}
+
+/// Print what looking `word` up would request, without sending anything.
+fn run_dry_run(client: &YdtClient, args: &Args, word: &str) -> i32 {
+    match client.plan(word, &args.translation_options) {
+        Ok(plan) if args.json => {
+            println!("{}", plan_to_json(&plan));
+            0
+        }
+        Ok(plan) => {
+            println!("{}", render_plan(&plan));
+            0
+        }
+        Err(err) => {
+            print_error(args, None, &err);
+            exit_code(&err)
+        }
+    }
+}
+
+fn render_plan(plan: &RequestPlan) -> String {
+    let mut lines = vec![format!("word: {}", plan.word)];
+    for (i, request) in plan.requests.iter().enumerate() {
+        let optional = if request.optional { " (optional)" } else { "" };
+        match &request.url {
+            Some(url) => lines.push(format!(
+                "{}. {}{optional}: GET {url}",
+                i + 1,
+                request.source
+            )),
+            None => lines.push(format!(
+                "{}. {}{optional}: no HTTP request",
+                i + 1,
+                request.source
+            )),
+        }
+        for (name, value) in &request.headers {
+            lines.push(format!("   {name}: {value}"));
+        }
+    }
+    if let Some(user_agent) = &plan.fallback_user_agent {
+        lines.push(format!("fallback user agent: {user_agent}"));
+    }
+    lines.push(format!("timeout: {:.0?}", plan.timeout));
+    match plan.deadline {
+        Some(deadline) => lines.push(format!("deadline: {deadline:.0?}")),
+        None => lines.push("deadline: none".to_string()),
+    }
+    lines.push(format!("retries: {}", plan.retries));
+    lines.push(format!(
+        "proxy: {}",
+        plan.proxy.as_deref().unwrap_or("none")
+    ));
+    lines.join("\n")
+}
+
+fn plan_to_json(plan: &RequestPlan) -> Value {
+    json!({
+        "word": plan.word,
+        "requests": plan
+            .requests
+            .iter()
+            .map(|request| {
+                json!({
+                    "source": request.source,
+                    "url": request.url,
+                    "headers": request
+                        .headers
+                        .iter()
+                        .map(|(name, value)| (name.clone(), json!(value)))
+                        .collect::<serde_json::Map<_, _>>(),
+                    "optional": request.optional,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "fallback_user_agent": plan.fallback_user_agent,
+        "timeout_secs": plan.timeout.as_secs_f64(),
+        "deadline_secs": plan.deadline.map(|deadline| deadline.as_secs_f64()),
+        "retries": plan.retries,
+        "proxy": plan.proxy,
+    })
+}
+
+/// `word` as a file name, with characters other than letters and digits
+/// replaced.
+fn file_stem(word: &str) -> String {
+    word.chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { '_' })
+        .collect()
+}
+
+/// Save `html` to a new timestamped file in `dir` and return its path.
+fn dump_html(dir: &Path, word: &str, html: &str) -> io::Result<PathBuf> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}-{millis}.html", file_stem(word)));
+    fs::write(&path, html)?;
+    Ok(path)
+}
+
+/// Save `html` to `<word>.html` in `dir` for `--record`, replacing an earlier
+/// recording, so that the directory can be checked with
+/// `ydt::test_util::check_recorded_pages`.
+fn record_html(dir: &Path, word: &str, html: &str) {
+    let path = dir.join(format!("{}.html", file_stem(word)));
+    if let Err(err) = fs::create_dir_all(dir).and_then(|()| fs::write(&path, html)) {
+        eprintln!("Failed to record {}: {err}", path.display());
+    }
+}
+
+/// Whether WORD should be machine-translated rather than looked up.
+fn is_sentence(args: &Args, word: &str) -> bool {
+    if args.sentence {
+        return true;
+    }
+    let threshold = args
+        .config
+        .sentence_threshold
+        .unwrap_or(ydt::DEFAULT_SENTENCE_THRESHOLD);
+    !args.offline
+        && args.format() == OutputFormat::Plain
+        && ydt::looks_like_sentence(word, threshold)
+}
+
+/// The word to look up from the clipboard `contents`: its only non-empty
+/// line, or all of them joined and machine-translated as one sentence.
+fn clip_word(args: &mut Args, contents: &str) -> Result<String, YdtError> {
+    let lines: Vec<&str> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let text = if lines.len() > 1 && !args.offline {
+        args.sentence = true;
+        lines.join(" ")
+    } else {
+        // The local dictionary cannot translate sentences.
+        lines.first().copied().unwrap_or_default().to_string()
+    };
+    ydt::normalize_word(&text, ydt::DEFAULT_MAX_SENTENCE_LEN)
+}
+
+/// Copy `text` to the clipboard for `--copy`. Failures are reported without
+/// changing the exit status, as the result has been printed already.
+fn copy(args: &Args, text: &str) {
+    if args.copy {
+        if let Err(msg) = clipboard::write(text) {
+            print_failure(args, "write_clipboard", None, &msg);
+        }
+    }
+}
+
+fn run_sentence(client: &YdtClient, args: &Args, text: &str) -> i32 {
+    match client.translate_sentence(text) {
+        Ok(Some(translated)) => {
+            println!("{translated}");
+            copy(args, &translated);
+            0
+        }
+        Ok(None) => {
+            let message = format!("No translation found for: {text}");
+            print_failure(args, "no_results", Some(text), &message);
+            EXIT_NO_RESULTS
+        }
+        Err(err) => {
+            print_error(args, None, &err);
+            exit_code(&err)
+        }
+    }
+}
+
+fn run_single(client: &YdtClient, args: &Args, word: &str) -> i32 {
+    let (translation, html, info) = match client.translate_with_page(word) {
+        Ok(result) => result,
+        Err(err) => return print_single(args, word, Err(err)),
+    };
+    if let Some(dir) = args
+        .dump_on_empty
+        .as_ref()
+        .filter(|_| translation.is_empty())
+    {
+        match dump_html(dir, word, &html) {
+            Ok(path) => eprintln!("Saved result page to {}", path.display()),
+            Err(err) => {
+                let message = format!("Failed to save result page: {err}");
+                print_failure(args, "save_page", Some(word), &message);
+            }
+        }
+    }
+    if args.verbosity > 0 {
+        if let Some(direction) = translation.direction {
+            eprintln!("direction: {direction}");
+        }
+    }
+    let status = print_single(args, word, Ok(select_sections(translation, args)));
+    if args.verbosity > 0 {
+        eprintln!("{}", render_fetch_info(&info));
+    }
+    status
+}
+
+/// Print a single lookup: the result to stdout, and errors or the lack of
+/// results to stderr, so that stdout only ever holds a result.
+fn print_single(args: &Args, word: &str, result: Result<Translation, YdtError>) -> i32 {
+    let translation = match result {
+        Ok(translation) => translation,
+        Err(err) => {
+            print_error(args, None, &err);
+            return exit_code(&err);
+        }
+    };
+    let unfiltered = sense_count(&translation);
+    let translation = filter_senses(translation, args);
+    if unfiltered > 0 && sense_count(&translation) == 0 {
+        let message = format!("(no senses match filter; {unfiltered} without it)");
+        print_failure(args, "no_matching_senses", Some(word), &message);
+        return EXIT_NO_RESULTS;
+    }
+    if translation.is_empty() {
+        let suggestions = &translation.suggestions;
+        let message = if !suggestions.is_empty() {
+            format!("Did you mean: {}?", suggestions.join(", "))
+        } else if is_number(word) {
+            "No results: numbers are not translated.".to_string()
+        } else {
+            "No results.".to_string()
+        };
+        if args.json_errors() {
+            let mut error = json!({ "kind": "no_results", "word": word, "message": message });
+            if !suggestions.is_empty() {
+                error["suggestions"] = json!(suggestions);
+            }
+            eprintln!("{}", json!({ "error": error }));
+        } else if !suggestions.is_empty() || is_number(word) || args.sections == Sections::All {
+            eprintln!("{message}");
+        }
+        return EXIT_NO_RESULTS;
+    }
+
+    match args.format() {
+        OutputFormat::Plain => {
+            let output = render(args, &translation);
+            println!("{output}");
+            if args.one_line {
+                copy(args, &output);
+            } else {
+                // Without the wrapping and escape sequences meant for the terminal.
+                let options = RenderOptions {
+                    sections: args.sections,
+                    show_source: args.show_source,
+                    color: false,
+                    labels: args.labels(),
+                };
+                copy(args, &ydt::render_translation(&translation, &options));
+            }
+        }
+        OutputFormat::Tsv => {
+            if args.header {
+                println!("{}", ydt::TSV_HEADER);
+            }
+            let row = ydt::render_tsv_row(word, &Ok(translation));
+            println!("{row}");
+            copy(args, &row);
+        }
+        OutputFormat::Ndjson => {
+            let line = ydt::render_ndjson_line(word, &Ok(translation));
+            println!("{line}");
+            copy(args, &line);
+        }
+    }
+    0
+}
+
+/// Look up text whenever it is copied until Ctrl-C, printing one line per
+/// lookup.
+#[cfg(feature = "clipboard")]
+fn run_watch(client: &YdtClient, args: &Args) -> i32 {
+    let interrupted = interrupt_flag();
+    let max_len = args.max_len.unwrap_or(watch::DEFAULT_MAX_LEN);
+    let watcher = clipboard::System::open()
+        .and_then(|source| watch::Watcher::start(source, max_len, watch::DEBOUNCE));
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(msg) => {
+            print_failure(args, "read_clipboard", None, &msg);
+            return EXIT_FAILURE;
+        }
+    };
+    if args.terminals.stderr {
+        eprintln!("Watching the clipboard, press Ctrl-C to stop.");
+    }
+    // Report a failure to read the clipboard once rather than on every poll.
+    let mut last_error = None;
+    while !interrupted.load(Ordering::SeqCst) {
+        thread::sleep(watch::POLL_INTERVAL);
+        match watcher.poll(Instant::now()) {
+            Ok(text) => {
+                last_error = None;
+                if let Some(text) = text {
+                    print_watched(client, args, &text);
+                }
+            }
+            Err(msg) => {
+                if last_error.as_ref() != Some(&msg) {
+                    print_failure(args, "read_clipboard", None, &msg);
+                }
+                last_error = Some(msg);
+            }
+        }
+    }
+    0
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn run_watch(_client: &YdtClient, args: &Args) -> i32 {
+    print_failure(args, "unsupported", None, clipboard::UNSUPPORTED);
+    EXIT_FAILURE
+}
+
+/// Print the lookup of copied `text` on one line, or in `--format`.
+#[cfg(feature = "clipboard")]
+fn print_watched(client: &YdtClient, args: &Args, text: &str) {
+    let mut stdout = io::stdout().lock();
+    if is_sentence(args, text) {
+        match client.translate_sentence(text) {
+            Ok(Some(translated)) => {
+                let _ = writeln!(stdout, "{text} {translated}");
+            }
+            Ok(None) => print_failure(
+                args,
+                "no_results",
+                Some(text),
+                &format!("{text}: No results."),
+            ),
+            Err(err) => print_error(args, Some(text), &err),
+        }
+        return;
+    }
+    let result = lookup(client, args, text).map(|translation| filter_senses(translation, args));
+    let _ = match (args.format(), result) {
+        (OutputFormat::Tsv, result) => writeln!(stdout, "{}", ydt::render_tsv_row(text, &result)),
+        (OutputFormat::Ndjson, result) => {
+            writeln!(stdout, "{}", ydt::render_ndjson_line(text, &result))
+        }
+        (OutputFormat::Plain, Ok(translation)) if translation.is_empty() => {
+            print_failure(
+                args,
+                "no_results",
+                Some(text),
+                &format!("{text}: No results."),
+            );
+            Ok(())
+        }
+        (OutputFormat::Plain, Ok(translation)) => {
+            let width = args.width.unwrap_or_else(terminal_width);
+            let line = ydt::render_one_line_labeled(&translation, width, args.labels());
+            writeln!(stdout, "{line}")
+        }
+        (OutputFormat::Plain, Err(err)) => {
+            print_error(args, Some(text), &err);
+            Ok(())
+        }
+    };
+    let _ = stdout.flush();
+}
+
+/// A flag set when Ctrl-C is pressed, which then no longer exits the process.
+fn interrupt_flag() -> Arc<AtomicBool> {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    if let Err(err) = ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst)) {
+        eprintln!("Failed to handle Ctrl-C: {err}");
+    }
+    interrupted
+}
+
+/// Single-line batch progress indicator drawn on stderr.
+struct ProgressBar {
+    enabled: bool,
+    drawn: bool,
+}
+
+impl ProgressBar {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            drawn: false,
+        }
+    }
+
+    fn clear(&mut self) {
+        if self.drawn {
+            eprint!("\r\x1b[K");
+            self.drawn = false;
+        }
+    }
+
+    fn draw(&mut self, completed: usize, total: usize, failed: usize, word: &str) {
+        if !self.enabled {
+            return;
+        }
+        let failed = if failed > 0 {
+            format!(" ({failed} failed)")
+        } else {
+            String::new()
+        };
+        eprint!("\r\x1b[K[{completed}/{total}] {word}{failed}");
+        self.drawn = true;
+    }
+}
+
+/// Prints the results of a batch as they come, and counts them.
+struct BatchPrinter<'a> {
+    args: &'a Args,
+    stdout: io::StdoutLock<'static>,
+    progress: ProgressBar,
+    /// Whether no plain result was printed yet.
+    first: bool,
+    completed: usize,
+    failed: usize,
+    skipped: usize,
+    aborted_after: usize,
+}
+
+impl<'a> BatchPrinter<'a> {
+    fn new(args: &'a Args) -> Self {
+        let mut stdout = io::stdout().lock();
+        if args.format() == OutputFormat::Tsv && args.header {
+            let _ = writeln!(stdout, "{}", ydt::TSV_HEADER);
+        }
+        Self {
+            args,
+            stdout,
+            progress: ProgressBar::new(!args.quiet && args.terminals.stderr),
+            first: true,
+            completed: 0,
+            failed: 0,
+            skipped: 0,
+            aborted_after: 0,
+        }
+    }
+
+    /// Count a completed lookup.
+    fn record(&mut self, result: &Result<Translation, YdtError>) {
+        self.completed += 1;
+        match result {
+            Err(YdtError::CircuitOpen { failures, .. }) => {
+                self.failed += 1;
+                self.skipped += 1;
+                self.aborted_after = *failures;
+            }
+            Err(_) => self.failed += 1,
+            Ok(_) => {}
+        }
+    }
+
+    /// Print the result of looking up `word`, under `header` in plain output.
+    fn print(&mut self, word: &str, header: &str, result: &Result<Translation, YdtError>) {
+        let args = self.args;
+        self.progress.clear();
+        let stdout = &mut self.stdout;
+        let _ = match (args.format(), result) {
+            (OutputFormat::Tsv, result) => {
+                writeln!(stdout, "{}", ydt::render_tsv_row(word, result))
+            }
+            (OutputFormat::Ndjson, result) => {
+                writeln!(stdout, "{}", ydt::render_ndjson_line(word, result))
+            }
+            (OutputFormat::Plain, Ok(translation)) if args.one_line => {
+                writeln!(stdout, "{}", render(args, translation))
+            }
+            (OutputFormat::Plain, Ok(translation)) => {
+                let separator = if self.first { "" } else { "\n" };
+                self.first = false;
+                writeln!(stdout, "{separator}{header}\n{}", render(args, translation))
+            }
+            // Skipped words are summed up below rather than listed.
+            (OutputFormat::Plain, Err(YdtError::CircuitOpen { .. })) => Ok(()),
+            (OutputFormat::Plain, Err(err)) => {
+                print_error(args, Some(word), err);
+                Ok(())
+            }
+        };
+        // Flush per word so that consumers see results as soon as they complete.
+        let _ = stdout.flush();
+    }
+
+    fn draw_progress(&mut self, total: usize, word: &str) {
+        self.progress.draw(self.completed, total, self.failed, word);
+    }
+
+    /// Sum up the batch and return its exit status; `queued` is the number of
+    /// words read, when interrupted.
+    fn finish(mut self, interrupted: Option<usize>) -> i32 {
+        self.progress.clear();
+        if self.skipped > 0 {
+            eprintln!(
+                "Skipped {} lookups after {} consecutive failures",
+                self.skipped, self.aborted_after
+            );
+        }
+        if let Some(queued) = interrupted {
+            eprintln!(
+                "Interrupted: looked up {} of {queued} words read",
+                self.completed
+            );
+            return EXIT_INTERRUPTED;
+        }
+
+        if self.failed > 0 {
+            EXIT_FAILURE
+        } else {
+            0
+        }
+    }
+}
+
+fn run_batch(client: &YdtClient, args: &Args) -> i32 {
+    // The total is only known once stdin is exhausted, so progress is reported
+    // against the number of words read so far.
+    let queued = Arc::new(AtomicUsize::new(0));
+    let (sender, words) = mpsc::channel();
+    let reader_queued = Arc::clone(&queued);
+    let read_errors = Arc::new(Mutex::new(Vec::new()));
+    let reader_errors = Arc::clone(&read_errors);
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    // A line that is not UTF-8 has been read past; other
+                    // errors end the input.
+                    let invalid = err.kind() == io::ErrorKind::InvalidData;
+                    if let Ok(mut errors) = reader_errors.lock() {
+                        errors.push(err);
+                    }
+                    if invalid {
+                        continue;
+                    }
+                    break;
+                }
+            };
+            let word = line.trim();
+            if word.is_empty() {
+                continue;
+            }
+            reader_queued.fetch_add(1, Ordering::SeqCst);
+            if sender.send(word.to_string()).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Stop taking words once Ctrl-C is pressed, even while waiting for stdin;
+    // the lookups in progress are finished and printed.
+    let interrupted = interrupt_flag();
+    let flag = Arc::clone(&interrupted);
+    let words = std::iter::from_fn(move || {
+        while !flag.load(Ordering::SeqCst) {
+            match words.recv_timeout(INTERRUPT_POLL_INTERVAL) {
+                Ok(word) => return Some(word),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+        None
+    });
+
+    let mut printer = BatchPrinter::new(args);
+    client.translate_each_concurrent(words, args.concurrency, args.order, |word, result| {
+        let result =
+            result.map(|translation| filter_senses(select_sections(translation, args), args));
+        printer.record(&result);
+        printer.print(word, word, &result);
+        printer.draw_progress(queued.load(Ordering::SeqCst), word);
+    });
+    let interrupted = interrupted
+        .load(Ordering::SeqCst)
+        .then(|| queued.load(Ordering::SeqCst));
+    let status = printer.finish(interrupted);
+    let read_errors = match read_errors.lock() {
+        Ok(errors) => errors,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    for err in read_errors.iter() {
+        print_failure(
+            args,
+            "read_stdin",
+            None,
+            &format!("Failed to read stdin: {err}"),
+        );
+    }
+    if read_errors.is_empty() || status == EXIT_INTERRUPTED {
+        status
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+/// Look up the words listed in the file at `path`, as `run_batch` does those
+/// read from stdin.
+///
+/// Words listed more than once are looked up once, and their result printed
+/// for every line listing them.
+fn run_word_file(client: &YdtClient, args: &Args, path: &Path) -> i32 {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) => {
+            let message = format!("Failed to read {}: {err}", path.display());
+            print_failure(args, "read_word_file", None, &message);
+            return EXIT_FAILURE;
+        }
+    };
+    let list = word_file::parse(&text);
+    for warning in &list.warnings {
+        eprintln!(
+            "{}:{}: {}; line skipped",
+            path.display(),
+            warning.line,
+            warning.message
+        );
+    }
+
+    let indices: HashMap<_, _> = list
+        .queries
+        .iter()
+        .enumerate()
+        .map(|(index, query)| (query, index))
+        .collect();
+    let interrupted = interrupt_flag();
+    let flag = Arc::clone(&interrupted);
+    let queries = list
+        .queries
+        .iter()
+        .take_while(move |_| !flag.load(Ordering::SeqCst))
+        .map(|query| {
+            let options = TranslationOptions {
+                lang: query.lang.clone(),
+                ..TranslationOptions::default()
+            };
+            (query.word.as_str(), options)
+        });
+
+    let mut printer = BatchPrinter::new(args);
+    let mut results = Vec::new();
+    results.resize_with(list.queries.len(), || None);
+    // Index into `list.lines` of the next line to print, in input order.
+    let mut next_line = 0;
+    client.translate_each_concurrent_with(
+        queries,
+        args.concurrency,
+        args.order,
+        |word, options, result| {
+            let query = word_file::Query {
+                word: word.to_string(),
+                lang: options.lang.clone(),
+            };
+            let index = indices[&query];
+            let result =
+                result.map(|translation| filter_senses(select_sections(translation, args), args));
+            printer.record(&result);
+            if args.order == ResultOrder::Completion {
+                for _ in list.lines.iter().filter(|&&line| line == index) {
+                    printer.print(word, &query.to_string(), &result);
+                }
+            } else {
+                results[index] = Some(result);
+                while let Some(Some(result)) = list.lines.get(next_line).map(|&i| &results[i]) {
+                    let query = &list.queries[list.lines[next_line]];
+                    printer.print(&query.word, &query.to_string(), result);
+                    next_line += 1;
+                }
+            }
+            printer.draw_progress(list.queries.len(), word);
+        },
+    );
+    let interrupted = interrupted
+        .load(Ordering::SeqCst)
+        .then_some(list.queries.len());
+    printer.finish(interrupted)
+}
+
+/// A request read by `run_stdio`.
+enum StdioRequest {
+    Translate(String),
+    Suggest { prefix: String, limit: usize },
+    Quit,
+}
+
+/// Parse one line of `--stdio` input into its `id` (null when missing) and
+/// request.
+fn parse_stdio_request(line: &str) -> (Value, Result<StdioRequest, String>) {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request @ Value::Object(_)) => request,
+        Ok(_) => return (Value::Null, Err("Request is not a JSON object".to_string())),
+        Err(err) => return (Value::Null, Err(format!("Invalid JSON: {err}"))),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let field = |name: &str| {
+        request[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| format!("Missing string field `{name}`"))
+    };
+    let parsed = match request["op"].as_str() {
+        Some("translate") => field("word").map(StdioRequest::Translate),
+        Some("suggest") => {
+            let limit = match &request["limit"] {
+                Value::Null => Ok(DEFAULT_SUGGEST_LIMIT),
+                limit => limit
+                    .as_u64()
+                    .filter(|limit| *limit > 0)
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .ok_or_else(|| format!("Invalid limit: {limit}")),
+            };
+            limit.and_then(|limit| {
+                let prefix = field("prefix")?;
+                Ok(StdioRequest::Suggest { prefix, limit })
+            })
+        }
+        Some("quit") => Ok(StdioRequest::Quit),
+        Some(op) => Err(format!("Unknown op: {op:?}")),
+        None => Err("Missing string field `op`".to_string()),
+    };
+    (id, parsed)
+}
+
+fn stdio_error(kind: &str, message: &str) -> Value {
+    json!({ "kind": kind, "message": message })
+}
+
+fn stdio_lookup(client: &YdtClient, args: &Args, request: StdioRequest) -> Result<Value, Value> {
+    let report = |err: YdtError| {
+        let report = err.to_owned_report();
+        stdio_error(&report.kind, &report.message)
+    };
+    match request {
+        StdioRequest::Translate(word) => {
+            let translation = lookup(client, args, &word).map_err(report)?;
+            let translation = filter_senses(translation, args);
+            if translation.is_empty() {
+                let mut error = stdio_error(
+                    "no_results",
+                    &format!("No results found for: {}", translation.word),
+                );
+                if !translation.suggestions.is_empty() {
+                    error["suggestions"] = translation.suggestions.into();
+                }
+                return Err(error);
+            }
+            Ok(ydt::translation_to_json(&translation))
+        }
+        StdioRequest::Suggest { prefix, limit } => {
+            let suggestions = client.suggest(&prefix, limit).map_err(report)?;
+            Ok(suggestions
+                .iter()
+                .map(|suggestion| json!({ "word": suggestion.word, "gloss": suggestion.gloss }))
+                .collect())
+        }
+        StdioRequest::Quit => Ok(Value::Null),
+    }
+}
+
+fn write_stdio_response(id: Value, result: Result<Value, Value>) {
+    let response = match result {
+        Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+        Err(error) => json!({ "id": id, "ok": false, "error": error }),
+    };
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{response}");
+    let _ = stdout.flush();
+}
+
+/// Answer newline-delimited JSON requests from stdin until EOF or a `quit`
+/// request, for editor plugins keeping one process around.
+///
+/// Up to `--concurrency` requests are looked up at a time, so responses can
+/// come out of order; each carries the `id` of its request. Lines that are not
+/// valid requests, including lines that are not UTF-8, are answered with an
+/// `invalid_request` error. Failing to read stdin ends the loop with
+/// `EXIT_FAILURE`.
+fn run_stdio(client: &YdtClient, args: &Args) -> i32 {
+    let mut status = 0;
+    let (sender, requests) = mpsc::channel::<(Value, StdioRequest)>();
+    let requests = Mutex::new(requests);
+    thread::scope(|scope| {
+        for _ in 0..args.concurrency {
+            scope.spawn(|| loop {
+                let next = requests.lock().map(|requests| requests.recv());
+                let Ok(Ok((id, request))) = next else { break };
+                write_stdio_response(id, stdio_lookup(client, args, request));
+            });
+        }
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                // The line has been read past; answer it and go on.
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                    let message = format!("Invalid request: {err}");
+                    write_stdio_response(
+                        Value::Null,
+                        Err(stdio_error("invalid_request", &message)),
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    let message = format!("Failed to read stdin: {err}");
+                    print_failure(args, "read_stdin", None, &message);
+                    status = EXIT_FAILURE;
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_stdio_request(&line) {
+                (_, Ok(StdioRequest::Quit)) => break,
+                (id, Ok(request)) => {
+                    let _ = sender.send((id, request));
+                }
+                (id, Err(message)) => {
+                    write_stdio_response(id, Err(stdio_error("invalid_request", &message)));
+                }
+            }
+        }
+        // Workers finish the requests already read, then stop.
+        drop(sender);
+    });
+    status
+}
+
+fn config_path() -> Result<PathBuf, String> {
+    Config::default_path().ok_or_else(|| "Could not determine the config directory".to_string())
+}
+
+fn init_config() -> Result<PathBuf, String> {
+    let path = config_path()?;
+    if path.exists() {
+        return Err(format!("Config file already exists: {}", path.display()));
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create {}: {err}", dir.display()))?;
+    }
+    fs::write(&path, config::TEMPLATE)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    Ok(path)
+}
+
+/// Where state is kept between runs, such as cookies: `~/.cache/ydt`, or
+/// `%LOCALAPPDATA%\ydt` on Windows.
+fn cache_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+    base.map(|base| base.join("ydt"))
+}
+
+/// Print the version, and with `--verbose` the features of the build and the
+/// base URL and user agent lookups would use.
+fn print_version(args: Args) {
+    print!("{}", Cli::command().render_version());
+    if args.verbosity == 0 {
+        return;
+    }
+    let info = ydt::build_info();
+    // A broken config file is reported by lookups; the defaults are shown here.
+    let config = load_config().unwrap_or_default().merge(args.config);
+    let base_url = config.base_url.as_deref();
+    let user_agent = config.user_agent.as_deref();
+    println!("base URL: {}", base_url.unwrap_or(info.default_base_url));
+    println!("features: {}", info.features.join(", "));
+    println!(
+        "user agent: {}",
+        user_agent.unwrap_or(info.default_user_agent)
+    );
+}
+
+fn load_config() -> Result<Config, YdtError> {
+    let file = match Config::default_path() {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    Ok(file.merge(Config::from_env()?))
+}
+
+/// Layer `args` over the config file and the environment, or exit when they
+/// cannot be read.
+fn with_config(mut args: Args) -> Args {
+    match load_config() {
+        Ok(loaded) => args.config = loaded.merge(args.config),
+        Err(err) => {
+            print_error(&args, None, &err);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+    args
+}
+
+/// Build the client for lookups and subcommands from `args`, keeping cookies
+/// and the rate-limit cooldown in the cache directory unless offline.
+fn build_client(args: &Args) -> YdtClient {
+    let mut builder = YdtClient::builder()
+        .config(&args.config)
+        .strict(args.strict)
+        .auto_correct(args.auto_correct)
+        .convert_traditional(args.convert_traditional)
+        .offline(args.offline)
+        .translation_options(TranslationOptions {
+            accent: args.config.accent,
+            max_field_len: args.config.max_field_len,
+            ..args.translation_options.clone()
+        });
+    if let Some(dir) = args.record.clone() {
+        builder = builder.on_page(move |word, html| record_html(&dir, word, html));
+    }
+    if let Some(dir) = cache_dir().filter(|_| !args.offline) {
+        if args.config.cookie_file != Some(false) {
+            builder = builder.cookie_store_path(dir.join("cookies.json"));
+        }
+        builder = builder.cooldown_path(dir.join("cooldown"));
+    }
+    builder.build()
+}
+
+fn main() {
+    console::setup();
+    let cli = Cli::try_parse_args(std::env::args_os()).unwrap_or_else(|err| exit_with(err));
+    let mut args = Args::from(cli.translate);
+    if cli.version {
+        print_version(args);
+        process::exit(0);
+    }
+    match cli.command {
+        None => {}
+        Some(Command::Suggest { prefix, limit }) => {
+            let args = with_config(args);
+            process::exit(run_suggest(&build_client(&args), &args, &prefix, limit));
+        }
+        Some(Command::Diff {
+            width,
+            first,
+            second,
+        }) => {
+            let args = with_config(args);
+            let width = width.unwrap_or_else(terminal_width);
+            process::exit(run_diff(
+                &build_client(&args),
+                &args,
+                [&first, &second],
+                width,
+            ));
+        }
+        Some(Command::Open { word }) => {
+            let args = with_config(args);
+            process::exit(run_open(&build_client(&args), &args, &word));
+        }
+        Some(Command::Serve { host, port }) => {
+            let args = with_config(args);
+            process::exit(run_serve(build_client(&args), &host, port));
+        }
+        Some(Command::Doctor { json }) => {
+            let passed = doctor::run(Config::default_path(), load_config(), cache_dir(), json);
+            process::exit(if passed { 0 } else { EXIT_FAILURE });
+        }
+        Some(Command::Config {
+            action: ConfigAction::Path,
+        }) => match config_path() {
+            Ok(path) => {
+                println!("{}", path.display());
+                process::exit(0);
+            }
+            Err(msg) => {
+                print_failure(&args, "config_path", None, &msg);
+                process::exit(EXIT_FAILURE);
+            }
+        },
+        Some(Command::Config {
+            action: ConfigAction::Init,
+        }) => match init_config() {
+            Ok(path) => {
+                println!("Wrote {}", path.display());
+                process::exit(0);
+            }
+            Err(msg) => {
+                print_failure(&args, "write_config", None, &msg);
+                process::exit(EXIT_FAILURE);
+            }
+        },
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Cli::command(), "ydt", &mut io::stdout());
+            process::exit(0);
+        }
+    }
+    if args.clip {
+        let contents = clipboard::read().unwrap_or_else(|msg| {
+            print_failure(&args, "read_clipboard", None, &msg);
+            process::exit(EXIT_FAILURE);
+        });
+        match clip_word(&mut args, &contents) {
+            Ok(word) => args.word = Some(word),
+            Err(err) => {
+                print_error(&args, None, &err);
+                process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+    if args.word.is_none()
+        && args.from.is_none()
+        && !args.stdio
+        && !args.watch_clipboard
+        && io::stdin().is_terminal()
+    {
+        usage_error(
+            ErrorKind::MissingRequiredArgument,
+            "Please provide a word to translate",
+        );
+    }
+
+    let mut args = with_config(args);
+
+    if args.offline && args.config.cedict.is_none() {
+        usage_error(
+            ErrorKind::MissingRequiredArgument,
+            "--offline requires a dictionary from --cedict or the config file",
+        );
+    }
+
+    if args.verbosity > 0 {
+        let level = match args.verbosity {
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        env_logger::Builder::new()
+            .filter_level(LevelFilter::Warn)
+            .filter_module("ydt", level)
+            .init();
+    }
+
+    if let Some(word) = args.word.take() {
+        // Checked when parsed, so normalizing only folds the text.
+        let word = match ydt::normalize_word(&word, ydt::DEFAULT_MAX_SENTENCE_LEN) {
+            Ok(normalized) => {
+                if args.verbosity > 0 && normalized != word {
+                    eprintln!("normalized: {word:?} → {normalized:?}");
+                }
+                normalized
+            }
+            Err(_) => word,
+        };
+        // Only sentences may be longer than a word.
+        let len = word.chars().count();
+        if len > ydt::DEFAULT_MAX_WORD_LEN && !is_sentence(&args, &word) {
+            let err = YdtError::WordTooLong {
+                len,
+                max: ydt::DEFAULT_MAX_WORD_LEN,
+            };
+            usage_error(ErrorKind::ValueValidation, &err.to_string());
+        }
+        args.word = Some(word);
+    }
+
+    let client = build_client(&args);
+    let status = match &args.word {
+        Some(word) if args.dry_run => run_dry_run(&client, &args, word),
+        Some(word) if args.raw_html => run_raw_html(&client, &args, word),
+        Some(word) if is_sentence(&args, word) => run_sentence(&client, &args, word),
+        Some(word) => run_single(&client, &args, word),
+        None if args.watch_clipboard => run_watch(&client, &args),
+        None if args.stdio => run_stdio(&client, &args),
+        None => match &args.from {
+            Some(path) => run_word_file(&client, &args, path),
+            None => run_batch(&client, &args),
+        },
+    };
+    process::exit(status);
 }
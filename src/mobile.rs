@@ -0,0 +1,151 @@
+//! Parsing result pages from Youdao's mobile site.
+
+use std::sync::OnceLock;
+
+use scraper::{Html, Selector};
+
+use crate::{
+    capped, contains_cjk_ideograph, parse_document, trimmed_text, Entry, EntrySource, Layout,
+    Phonetic, Translation, YdtError, MAX_PARSED_ITEMS,
+};
+
+/// Selectors for the mobile layout, which changes far less often than the
+/// desktop one and so is not configurable.
+struct MobileSelectors {
+    /// Pronunciation with its accent label, in the English-Chinese block.
+    pronounce: Selector,
+    /// Transcription inside a pronunciation.
+    phonetic: Selector,
+    /// Definition line in the English-Chinese block.
+    definition: Selector,
+    /// English candidate in the Chinese-English block.
+    candidate: Selector,
+}
+
+static MOBILE_SELECTORS: OnceLock<MobileSelectors> = OnceLock::new();
+
+fn mobile_selectors() -> &'static MobileSelectors {
+    MOBILE_SELECTORS.get_or_init(|| {
+        let parse = |css| Selector::parse(css).expect("built-in selectors are valid CSS");
+        MobileSelectors {
+            pronounce: parse("#ec span.pronounce"),
+            phonetic: parse("span.phonetic"),
+            definition: parse("#ec ul > li"),
+            candidate: parse("#ce ul > li a"),
+        }
+    })
+}
+
+/// Parse a result page from Youdao's mobile site (`m.youdao.com/dict`) into a
+/// [`Translation`].
+///
+/// The mobile page lists definitions as plain lines such as `int. 喂；哈罗`; a
+/// leading part of speech is split off into [`Entry::pos`]. This function does
+/// not perform network I/O.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div id="ec">
+///   <h2><span class="pronounce">英<span class="phonetic">[həˈləʊ]</span></span></h2>
+///   <ul><li>int. 喂；哈罗</li></ul>
+/// </div>
+/// "#;
+/// let translation = ydt::parse_mobile_html("hello", html).unwrap();
+/// assert_eq!(translation.phonetics[0].label, "英");
+/// assert_eq!(translation.entries[0].pos, "int.");
+/// assert_eq!(translation.entries[0].meaning, "喂；哈罗");
+/// ```
+pub fn parse_mobile_html(word: &str, html: &str) -> Result<Translation, YdtError> {
+    let document = parse_document(html);
+    let selectors = mobile_selectors();
+    let layouts = if contains_cjk_ideograph(word) {
+        [Layout::ChineseEnglish, Layout::EnglishChinese]
+    } else {
+        [Layout::EnglishChinese, Layout::ChineseEnglish]
+    };
+    for layout in layouts {
+        let mut translation = Translation {
+            word: word.to_string(),
+            layout: Some(layout),
+            direction: Some(layout.into()),
+            ..Translation::default()
+        };
+        match layout {
+            Layout::EnglishChinese => parse_english_chinese(&document, selectors, &mut translation),
+            Layout::ChineseEnglish => {
+                translation.entries = document
+                    .select(&selectors.candidate)
+                    .map(|candidate| Entry {
+                        pos: String::new(),
+                        meaning: trimmed_text(candidate),
+                        source: EntrySource::Mobile,
+                    })
+                    .filter(|entry| !entry.meaning.is_empty())
+                    .take(MAX_PARSED_ITEMS)
+                    .collect();
+            }
+            // Not tried: the mobile site has no pinyin candidates.
+            Layout::Pinyin => {}
+        }
+        if !translation.is_empty() {
+            return Ok(translation);
+        }
+    }
+    log::warn!("no phonetics or definitions found on the mobile page for {word:?}");
+    Ok(Translation {
+        word: word.to_string(),
+        ..Translation::default()
+    })
+}
+
+fn parse_english_chinese(
+    document: &Html,
+    selectors: &MobileSelectors,
+    translation: &mut Translation,
+) {
+    for pronounce in document.select(&selectors.pronounce).take(MAX_PARSED_ITEMS) {
+        // The label is the text before the nested transcription.
+        let label = crate::text::normalize(pronounce.text().next().unwrap_or_default());
+        let Some(phonetic) = pronounce.select(&selectors.phonetic).next() else {
+            continue;
+        };
+        if !label.is_empty() {
+            translation.phonetics.push(Phonetic {
+                label: capped(label),
+                text: trimmed_text(phonetic),
+            });
+        }
+    }
+    for definition in document
+        .select(&selectors.definition)
+        .take(MAX_PARSED_ITEMS)
+    {
+        let entry = split_part_of_speech(&trimmed_text(definition), EntrySource::Mobile);
+        if !entry.meaning.is_empty() {
+            translation.entries.push(entry);
+        }
+    }
+}
+
+/// Turn a definition line such as `int. 喂；哈罗` into an [`Entry`], splitting off
+/// a leading part of speech if there is one, tagged with `source`.
+pub(crate) fn split_part_of_speech(line: &str, source: EntrySource) -> Entry {
+    let (pos, meaning) = match line.trim().split_once(' ') {
+        Some((pos, meaning)) if is_part_of_speech(pos) => (pos, meaning.trim()),
+        _ => ("", line.trim()),
+    };
+    Entry {
+        pos: pos.to_string(),
+        meaning: meaning.to_string(),
+        source,
+    }
+}
+
+/// Abbreviations such as `n.`, `vt.` or `adj.`.
+fn is_part_of_speech(token: &str) -> bool {
+    token
+        .strip_suffix('.')
+        .is_some_and(|abbr| !abbr.is_empty() && abbr.chars().all(|ch| ch.is_ascii_lowercase()))
+}
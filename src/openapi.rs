@@ -0,0 +1,287 @@
+//! The official Youdao text translation API (有道智云).
+//!
+//! Requests are signed with an app key and secret; see
+//! [`sign`]. Responses are parsed into the same [`Translation`] as scraped pages.
+//!
+//! ```
+//! use ydt::openapi::{self, Credentials};
+//!
+//! let credentials = Credentials::new("my-app-key", "my-secret");
+//! let url = openapi::request_url(openapi::DEFAULT_API_URL, &credentials, "hello", "salt", 1700000000)
+//!     .unwrap();
+//! assert!(url.as_str().contains("signType=v3"));
+//! ```
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::mobile::split_part_of_speech;
+use crate::{
+    contains_cjk_ideograph, Direction, Entry, EntrySource, Layout, Phonetic, Translation, YdtError,
+};
+
+/// Youdao text translation API endpoint used unless configured otherwise.
+pub const DEFAULT_API_URL: &str = "https://openapi.youdao.com/api";
+
+/// App key and secret of a Youdao API application.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// Application ID (`appKey`).
+    pub app_key: String,
+    /// Application secret; never sent, only used for signing.
+    pub app_secret: String,
+}
+
+impl Credentials {
+    /// Credentials for the application `app_key`.
+    pub fn new(app_key: impl Into<String>, app_secret: impl Into<String>) -> Self {
+        Self {
+            app_key: app_key.into(),
+            app_secret: app_secret.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("app_key", &self.app_key)
+            .field("app_secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Shorten `q` the way the v3 signature expects: inputs longer than 20
+/// characters become their first 10 characters, the length and their last 10
+/// characters.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::openapi::truncate_input("hello"), "hello");
+/// assert_eq!(
+///     ydt::openapi::truncate_input("how are you doing today"),
+///     "how are yo23oing today"
+/// );
+/// ```
+pub fn truncate_input(q: &str) -> String {
+    let chars: Vec<char> = q.chars().collect();
+    if chars.len() <= 20 {
+        return q.to_string();
+    }
+    let head: String = chars[..10].iter().collect();
+    let tail: String = chars[chars.len() - 10..].iter().collect();
+    format!("{head}{}{tail}", chars.len())
+}
+
+/// The v3 signature: the lowercase hex SHA-256 of app key, truncated input,
+/// salt, current time and app secret.
+pub fn sign(credentials: &Credentials, q: &str, salt: &str, curtime: u64) -> String {
+    let digest = Sha256::digest(format!(
+        "{}{}{salt}{curtime}{}",
+        credentials.app_key,
+        truncate_input(q),
+        credentials.app_secret
+    ));
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Build the signed request URL for translating `q`, with the language pair
+/// detected by Youdao.
+///
+/// # Errors
+///
+/// Returns [`YdtError::BuildRequestUrl`] if `api_url` is not a valid URL.
+pub fn request_url(
+    api_url: &str,
+    credentials: &Credentials,
+    q: &str,
+    salt: &str,
+    curtime: u64,
+) -> Result<Url, YdtError> {
+    let curtime_param = curtime.to_string();
+    let sign = sign(credentials, q, salt, curtime);
+    Url::parse_with_params(
+        api_url,
+        &[
+            ("q", q),
+            ("from", "auto"),
+            ("to", "auto"),
+            ("appKey", &credentials.app_key),
+            ("salt", salt),
+            ("sign", &sign),
+            ("signType", "v3"),
+            ("curtime", &curtime_param),
+        ],
+    )
+    .map_err(YdtError::BuildRequestUrl)
+}
+
+#[cfg(feature = "http")]
+/// Signed request URL with a fresh salt and the current time.
+pub(crate) fn signed_request_url(
+    api_url: &str,
+    credentials: &Credentials,
+    q: &str,
+) -> Result<Url, YdtError> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let salt = format!(
+        "{:x}{:x}",
+        now.as_nanos(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    request_url(api_url, credentials, q, &salt, now.as_secs())
+}
+
+/// Description of an API error code, as documented by Youdao.
+pub fn error_message(code: u32) -> &'static str {
+    match code {
+        101 => "missing required parameter",
+        102 => "unsupported language",
+        103 => "text too long",
+        108 => "invalid app key",
+        110 => "no valid service instance for this application",
+        111 => "invalid developer account",
+        113 => "query is empty",
+        202 => "signature check failed",
+        206 => "invalid timestamp",
+        401 => "account overdue",
+        411 => "access frequency limited",
+        412 => "too many long requests",
+        _ => "unknown error",
+    }
+}
+
+/// Parse a translation API response for `word` into a [`Translation`].
+///
+/// Dictionary results (`basic`) become phonetics and definitions; web
+/// definitions of the word itself are added with the part of speech `网络`.
+/// Without a dictionary result, the machine translation is used.
+///
+/// # Errors
+///
+/// Returns [`YdtError::ApiError`] when the response carries a non-zero
+/// `errorCode`, and [`YdtError::InvalidApiResponse`] when it is not a valid
+/// response.
+///
+/// # Examples
+///
+/// ```
+/// let json = r#"{"errorCode": "0", "basic": {"uk-phonetic": "həˈləʊ", "explains": ["int. 喂；哈罗"]}}"#;
+/// let translation = ydt::openapi::parse_response("hello", json).unwrap();
+/// assert_eq!(translation.phonetics[0].text, "/həˈləʊ/");
+/// assert_eq!(translation.entries[0].meaning, "喂；哈罗");
+/// ```
+pub fn parse_response(word: &str, json: &str) -> Result<Translation, YdtError> {
+    let invalid = |message: String| YdtError::InvalidApiResponse {
+        word: word.to_string(),
+        message,
+    };
+    let response: Value = serde_json::from_str(json).map_err(|err| invalid(err.to_string()))?;
+    let code = match response.get("errorCode") {
+        Some(Value::String(code)) => code.parse::<u32>().ok(),
+        Some(Value::Number(code)) => code.as_u64().and_then(|code| u32::try_from(code).ok()),
+        _ => None,
+    }
+    .ok_or_else(|| invalid("missing `errorCode`".to_string()))?;
+    if code != 0 {
+        return Err(YdtError::ApiError {
+            word: word.to_string(),
+            code,
+            msg: error_message(code).to_string(),
+        });
+    }
+
+    let cjk = contains_cjk_ideograph(word);
+    let mut translation = Translation {
+        word: word.to_string(),
+        ..Translation::default()
+    };
+    if let Some(basic) = response.get("basic") {
+        let phonetic = |key| {
+            basic
+                .get(key)
+                .and_then(Value::as_str)
+                .filter(|text| !text.is_empty())
+        };
+        for (key, label) in [("uk-phonetic", "英"), ("us-phonetic", "美")] {
+            if let Some(text) = phonetic(key) {
+                translation.phonetics.push(Phonetic {
+                    label: label.to_string(),
+                    text: format!("/{text}/"),
+                });
+            }
+        }
+        if translation.phonetics.is_empty() {
+            if let Some(text) = phonetic("phonetic") {
+                translation.phonetics.push(Phonetic {
+                    label: if cjk { "拼音" } else { "" }.to_string(),
+                    text: format!("/{text}/"),
+                });
+            }
+        }
+        translation.entries = strings(basic.get("explains"))
+            .map(|line| split_part_of_speech(line, EntrySource::OpenApi))
+            .filter(|entry| !entry.meaning.is_empty())
+            .collect();
+    }
+    // Without a dictionary entry, only the machine translation is left.
+    let sentence = translation.entries.is_empty();
+    if sentence {
+        translation.entries = strings(response.get("translation"))
+            .map(|text| Entry {
+                pos: String::new(),
+                meaning: text.trim().to_string(),
+                source: EntrySource::OpenApi,
+            })
+            .filter(|entry| !entry.meaning.is_empty())
+            .collect();
+    }
+    for web in response
+        .get("web")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let key = web.get("key").and_then(Value::as_str).unwrap_or_default();
+        if key.trim().eq_ignore_ascii_case(word) {
+            let values: Vec<&str> = strings(web.get("value")).collect();
+            if !values.is_empty() {
+                translation.entries.push(Entry {
+                    pos: "网络".to_string(),
+                    meaning: values.join("；"),
+                    source: EntrySource::WebTrans,
+                });
+            }
+        }
+    }
+    if !translation.is_empty() {
+        let layout = if cjk {
+            Layout::ChineseEnglish
+        } else {
+            Layout::EnglishChinese
+        };
+        translation.layout = Some(layout);
+        translation.direction = Some(if sentence && translation.phonetics.is_empty() {
+            Direction::Sentence
+        } else {
+            layout.into()
+        });
+    }
+    Ok(translation)
+}
+
+fn strings(value: Option<&Value>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+}
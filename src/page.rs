@@ -0,0 +1,168 @@
+//! Extracting single sections of a result page, for callers that only need
+//! part of a translation.
+
+use std::cell::OnceCell;
+
+use scraper::Html;
+
+use crate::collins::parse_collins;
+use crate::selectors::{default_selectors, Selectors};
+use crate::{ce_entries, ec_entries, ec_phonetics, parse_document, parse_suggestions};
+use crate::{Entry, Example, ParseWarning, Phonetic, YdtError, MAX_PARSED_ITEMS};
+
+/// A result page parsed once, from which sections are extracted on demand.
+///
+/// Parsing the HTML is the most expensive step, so a page kept around can be
+/// asked for several sections without parsing it again. Each section is
+/// extracted the first time it is asked for and kept for later calls. Parse
+/// warnings are logged at the `debug` level instead of being returned.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div class="trans-container">
+///   <div class="per-phone">
+///     <span>英</span><span class="phonetic">/həˈləʊ/</span>
+///   </div>
+/// </div>
+/// <div class="trans-container">
+///   <li class="word-exp"><span class="pos">int.</span><span class="trans">你好</span></li>
+/// </div>
+/// "#;
+/// let page = ydt::ParsedPage::parse(html);
+/// assert_eq!(page.phonetics()[0].text, "/həˈləʊ/");
+/// assert_eq!(page.definitions()[0].meaning, "你好");
+/// ```
+#[derive(Debug)]
+pub struct ParsedPage<'a> {
+    document: Html,
+    selectors: &'a Selectors,
+    phonetics: OnceCell<Vec<Phonetic>>,
+    definitions: OnceCell<Vec<Entry>>,
+    ce_candidates: OnceCell<Vec<Entry>>,
+    examples: OnceCell<Vec<Example>>,
+    suggestions: OnceCell<Vec<String>>,
+}
+
+impl ParsedPage<'static> {
+    /// Parse `html` with the built-in selectors, within the same limits as
+    /// [`parse_translation`](crate::parse_translation).
+    pub fn parse(html: &str) -> Self {
+        Self::with_selectors(html, default_selectors())
+    }
+}
+
+impl<'a> ParsedPage<'a> {
+    /// Parse `html`, locating sections with custom [`Selectors`].
+    pub fn with_selectors(html: &str, selectors: &'a Selectors) -> Self {
+        Self {
+            document: parse_document(html),
+            selectors,
+            phonetics: OnceCell::new(),
+            definitions: OnceCell::new(),
+            ce_candidates: OnceCell::new(),
+            examples: OnceCell::new(),
+            suggestions: OnceCell::new(),
+        }
+    }
+
+    /// The phonetics of an English word.
+    pub fn phonetics(&self) -> &[Phonetic] {
+        self.phonetics.get_or_init(|| {
+            self.extract(|document, selectors, warnings| {
+                ec_phonetics(document, selectors, warnings)
+            })
+        })
+    }
+
+    /// The definitions of an English word, including what an all-caps word
+    /// stands for.
+    pub fn definitions(&self) -> &[Entry] {
+        self.definitions.get_or_init(|| {
+            self.extract(|document, selectors, warnings| {
+                ec_entries(document, selectors, MAX_PARSED_ITEMS, warnings)
+            })
+        })
+    }
+
+    /// The English words given for a Chinese word, as definitions without a
+    /// part of speech.
+    pub fn ce_candidates(&self) -> &[Entry] {
+        self.ce_candidates.get_or_init(|| {
+            self.extract(|document, selectors, warnings| {
+                ce_entries(document, selectors, MAX_PARSED_ITEMS, warnings)
+            })
+        })
+    }
+
+    /// The example sentences of the Collins dictionary senses, in page
+    /// order.
+    pub fn examples(&self) -> &[Example] {
+        self.examples.get_or_init(|| {
+            self.extract(|document, selectors, warnings| {
+                parse_collins(document, selectors, MAX_PARSED_ITEMS, warnings)
+                    .into_iter()
+                    .flat_map(|sense| sense.examples)
+                    .take(MAX_PARSED_ITEMS)
+                    .collect()
+            })
+        })
+    }
+
+    /// The words suggested for a misspelled query.
+    pub fn suggestions(&self) -> &[String] {
+        self.suggestions
+            .get_or_init(|| parse_suggestions(&self.document, self.selectors))
+    }
+
+    /// The parsed document, for the sections without a method of their own.
+    pub(crate) fn document(&self) -> &Html {
+        &self.document
+    }
+
+    fn extract<T>(
+        &self,
+        extract: impl FnOnce(&Html, &Selectors, &mut Vec<ParseWarning>) -> T,
+    ) -> T {
+        let mut warnings = Vec::new();
+        let section = extract(&self.document, self.selectors, &mut warnings);
+        for warning in &warnings {
+            log::debug!("{warning}");
+        }
+        section
+    }
+}
+
+/// Parse only the phonetics of an English word from a result page.
+///
+/// This function does not perform network I/O.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"
+/// <div class="trans-container">
+///   <div class="per-phone"><span>美</span><span class="phonetic">/həˈloʊ/</span></div>
+/// </div>
+/// "#;
+/// let phonetics = ydt::parse_phonetics(html).unwrap();
+/// assert_eq!(phonetics[0].label, "美");
+/// ```
+pub fn parse_phonetics(html: &str) -> Result<Vec<Phonetic>, YdtError> {
+    Ok(ParsedPage::parse(html).phonetics().to_vec())
+}
+
+/// Parse only the definitions of an English word from a result page.
+///
+/// This function does not perform network I/O.
+pub fn parse_definitions(html: &str) -> Result<Vec<Entry>, YdtError> {
+    Ok(ParsedPage::parse(html).definitions().to_vec())
+}
+
+/// Parse only the English words given for a Chinese word from a result page.
+///
+/// This function does not perform network I/O.
+pub fn parse_ce_candidates(html: &str) -> Result<Vec<Entry>, YdtError> {
+    Ok(ParsedPage::parse(html).ce_candidates().to_vec())
+}
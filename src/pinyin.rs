@@ -0,0 +1,85 @@
+//! Recognizing queries typed as toneless pinyin, such as `xuexi`, whose result
+//! pages list candidate Chinese words.
+
+/// Pinyin syllables without tones, with `ü` written as `v`.
+const SYLLABLES: &[&str] = &[
+    "a", "ai", "an", "ang", "ao", "ba", "bai", "ban", "bang", "bao", "bei", "ben", "beng", "bi",
+    "bian", "biao", "bie", "bin", "bing", "bo", "bu", "ca", "cai", "can", "cang", "cao", "ce",
+    "cen", "ceng", "cha", "chai", "chan", "chang", "chao", "che", "chen", "cheng", "chi", "chong",
+    "chou", "chu", "chua", "chuai", "chuan", "chuang", "chui", "chun", "chuo", "ci", "cong", "cou",
+    "cu", "cuan", "cui", "cun", "cuo", "da", "dai", "dan", "dang", "dao", "de", "dei", "den",
+    "deng", "di", "dia", "dian", "diao", "die", "ding", "diu", "dong", "dou", "du", "duan", "dui",
+    "dun", "duo", "e", "ei", "en", "eng", "er", "fa", "fan", "fang", "fei", "fen", "feng", "fo",
+    "fou", "fu", "ga", "gai", "gan", "gang", "gao", "ge", "gei", "gen", "geng", "gong", "gou",
+    "gu", "gua", "guai", "guan", "guang", "gui", "gun", "guo", "ha", "hai", "han", "hang", "hao",
+    "he", "hei", "hen", "heng", "hong", "hou", "hu", "hua", "huai", "huan", "huang", "hui", "hun",
+    "huo", "ji", "jia", "jian", "jiang", "jiao", "jie", "jin", "jing", "jiong", "jiu", "ju",
+    "juan", "jue", "jun", "ka", "kai", "kan", "kang", "kao", "ke", "kei", "ken", "keng", "kong",
+    "kou", "ku", "kua", "kuai", "kuan", "kuang", "kui", "kun", "kuo", "la", "lai", "lan", "lang",
+    "lao", "le", "lei", "leng", "li", "lia", "lian", "liang", "liao", "lie", "lin", "ling", "liu",
+    "long", "lou", "lu", "luan", "lun", "luo", "lv", "lve", "ma", "mai", "man", "mang", "mao",
+    "me", "mei", "men", "meng", "mi", "mian", "miao", "mie", "min", "ming", "miu", "mo", "mou",
+    "mu", "na", "nai", "nan", "nang", "nao", "ne", "nei", "nen", "neng", "ni", "nian", "niang",
+    "niao", "nie", "nin", "ning", "niu", "nong", "nou", "nu", "nuan", "nuo", "nv", "nve", "o",
+    "ou", "pa", "pai", "pan", "pang", "pao", "pei", "pen", "peng", "pi", "pian", "piao", "pie",
+    "pin", "ping", "po", "pou", "pu", "qi", "qia", "qian", "qiang", "qiao", "qie", "qin", "qing",
+    "qiong", "qiu", "qu", "quan", "que", "qun", "ran", "rang", "rao", "re", "ren", "reng", "ri",
+    "rong", "rou", "ru", "rua", "ruan", "rui", "run", "ruo", "sa", "sai", "san", "sang", "sao",
+    "se", "sen", "seng", "sha", "shai", "shan", "shang", "shao", "she", "shei", "shen", "sheng",
+    "shi", "shou", "shu", "shua", "shuai", "shuan", "shuang", "shui", "shun", "shuo", "si", "song",
+    "sou", "su", "suan", "sui", "sun", "suo", "ta", "tai", "tan", "tang", "tao", "te", "teng",
+    "ti", "tian", "tiao", "tie", "ting", "tong", "tou", "tu", "tuan", "tui", "tun", "tuo", "wa",
+    "wai", "wan", "wang", "wei", "wen", "weng", "wo", "wu", "xi", "xia", "xian", "xiang", "xiao",
+    "xie", "xin", "xing", "xiong", "xiu", "xu", "xuan", "xue", "xun", "ya", "yan", "yang", "yao",
+    "ye", "yi", "yin", "ying", "yo", "yong", "you", "yu", "yuan", "yue", "yun", "za", "zai", "zan",
+    "zang", "zao", "ze", "zei", "zen", "zeng", "zha", "zhai", "zhan", "zhang", "zhao", "zhe",
+    "zhei", "zhen", "zheng", "zhi", "zhong", "zhou", "zhu", "zhua", "zhuai", "zhuan", "zhuang",
+    "zhui", "zhun", "zhuo", "zi", "zong", "zou", "zu", "zuan", "zui", "zun", "zuo",
+];
+
+/// Longest syllable in [`SYLLABLES`], in bytes.
+const MAX_SYLLABLE_LEN: usize = 6;
+
+/// Returns `true` when `text` can be read as toneless pinyin of at least two
+/// syllables, e.g. `xuexi` or `xue xi`.
+///
+/// Many English words, such as `change`, read as pinyin too, so this only
+/// decides whether a page that yields no English-Chinese results is worth
+/// reading as pinyin candidates.
+///
+/// # Examples
+///
+/// ```
+/// assert!(ydt::looks_like_pinyin("xuexi"));
+/// assert!(ydt::looks_like_pinyin("Bei Jing"));
+/// assert!(!ydt::looks_like_pinyin("hello"));
+/// assert!(!ydt::looks_like_pinyin("ma"));
+/// ```
+pub fn looks_like_pinyin(text: &str) -> bool {
+    let mut syllables = 0;
+    for part in text.split([' ', '\'']).filter(|part| !part.is_empty()) {
+        if !part.bytes().all(|byte| byte.is_ascii_alphabetic()) {
+            return false;
+        }
+        match count_syllables(&part.to_ascii_lowercase()) {
+            Some(count) => syllables += count,
+            None => return false,
+        }
+    }
+    syllables >= 2
+}
+
+/// The fewest syllables `text` splits into, or `None` when it does not split
+/// into syllables.
+fn count_syllables(text: &str) -> Option<usize> {
+    // fewest[i]: fewest syllables making up text[..i].
+    let mut fewest = vec![None; text.len() + 1];
+    fewest[0] = Some(0);
+    for end in 1..=text.len() {
+        fewest[end] = (end.saturating_sub(MAX_SYLLABLE_LEN)..end)
+            .filter(|&start| SYLLABLES.contains(&&text[start..end]))
+            .filter_map(|start| fewest[start].map(|count: usize| count + 1))
+            .min();
+    }
+    fewest[text.len()]
+}
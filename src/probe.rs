@@ -0,0 +1,86 @@
+//! Connectivity checks behind [`YdtClient::probe`](crate::YdtClient::probe).
+
+use log::debug;
+use reqwest::blocking::Client;
+use reqwest::{StatusCode, Url};
+use std::io;
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+/// Whether the result page endpoint can be reached, checked one step at a time.
+///
+/// Each step only runs when the one before it succeeded, so a failure points at
+/// the first thing that is broken: name resolution, the network path, or TLS
+/// and HTTP. When requests go through a proxy, the proxy is resolved and
+/// connected to instead of the endpoint.
+#[derive(Debug)]
+pub struct Probe {
+    /// Host and port that were resolved and connected to, e.g.
+    /// `www.youdao.com:443`.
+    pub host: String,
+    /// Addresses the host resolved to.
+    pub resolved: io::Result<Vec<SocketAddr>>,
+    /// Time taken to open a TCP connection to the first address accepting one.
+    pub connected: Option<io::Result<Duration>>,
+    /// Status the endpoint answered a `HEAD` request with, over TLS for `https`
+    /// URLs. Any status counts as an answer.
+    pub responded: Option<Result<StatusCode, reqwest::Error>>,
+}
+
+impl Probe {
+    /// Whether every step succeeded.
+    pub fn is_ok(&self) -> bool {
+        matches!(self.responded, Some(Ok(_)))
+    }
+}
+
+/// Check `endpoint`, or `proxy` for the first two steps when given, with
+/// requests sent through `client`.
+pub(crate) fn run(
+    endpoint: &Url,
+    proxy: Option<&Url>,
+    timeout: Duration,
+    client: &Client,
+) -> Probe {
+    let target = proxy.unwrap_or(endpoint);
+    let host = format!(
+        "{}:{}",
+        target.host_str().unwrap_or_default(),
+        target.port_or_known_default().unwrap_or_default()
+    );
+    let resolved = target.socket_addrs(|| None);
+    let connected = resolved.as_ref().ok().map(|addrs| connect(addrs, timeout));
+    let responded = match &connected {
+        Some(Ok(_)) => {
+            debug!("HEAD {endpoint}");
+            Some(
+                client
+                    .head(endpoint.clone())
+                    .send()
+                    .map(|resp| resp.status()),
+            )
+        }
+        _ => None,
+    };
+    Probe {
+        host,
+        resolved,
+        connected,
+        responded,
+    }
+}
+
+fn connect(addrs: &[SocketAddr], timeout: Duration) -> io::Result<Duration> {
+    let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no addresses to connect to");
+    for addr in addrs {
+        let start = Instant::now();
+        match TcpStream::connect_timeout(addr, timeout) {
+            Ok(_) => return Ok(start.elapsed()),
+            Err(err) => {
+                debug!("connecting to {addr} failed: {err}");
+                last_err = err;
+            }
+        }
+    }
+    Err(last_err)
+}
@@ -0,0 +1,84 @@
+//! Phonetics and recordings of a word, gathered by
+//! [`YdtClient::pronunciation_pack`].
+//!
+//! [`YdtClient::pronunciation_pack`]: crate::YdtClient::pronunciation_pack
+
+use std::fmt;
+
+use url::Url;
+
+use crate::{Accent, YdtError};
+
+/// Youdao audio endpoint used unless configured otherwise.
+pub const DEFAULT_AUDIO_URL: &str = "https://dict.youdao.com/dictvoice";
+
+pub(crate) fn audio_url(base_url: &str, word: &str, accent: Accent) -> Result<Url, YdtError> {
+    let kind = match accent {
+        Accent::Uk => "1",
+        Accent::Us => "2",
+    };
+    Url::parse_with_params(base_url, &[("audio", word), ("type", kind)])
+        .map_err(YdtError::BuildRequestUrl)
+}
+
+/// How a word is pronounced in one accent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pronunciation {
+    /// Transcription from the result page, e.g. `/həˈləʊ/`; `None` when the
+    /// page has none for this accent or could not be looked up.
+    pub phonetic: Option<String>,
+    /// Recording as served by the audio endpoint, usually MP3; `None` when
+    /// it is empty or could not be downloaded.
+    pub audio: Option<Vec<u8>>,
+}
+
+/// A part of a [`PronunciationPack`] that is requested separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PronunciationItem {
+    /// The phonetics, from the result page.
+    Phonetics,
+    /// The recording in an accent.
+    Audio(Accent),
+}
+
+impl fmt::Display for PronunciationItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Phonetics => f.write_str("phonetics"),
+            Self::Audio(accent) => write!(f, "{accent} audio"),
+        }
+    }
+}
+
+/// Phonetics and recordings of a word in each accent, returned by
+/// [`YdtClient::pronunciation_pack`].
+///
+/// [`YdtClient::pronunciation_pack`]: crate::YdtClient::pronunciation_pack
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PronunciationPack {
+    /// The word as looked up, after normalization.
+    pub word: String,
+    /// British English.
+    pub uk: Pronunciation,
+    /// American English.
+    pub us: Pronunciation,
+    /// The parts that failed, and why; the rest of the pack is still filled
+    /// in.
+    pub errors: Vec<(PronunciationItem, YdtError)>,
+}
+
+impl PronunciationPack {
+    /// The pronunciation in `accent`.
+    pub fn get(&self, accent: Accent) -> &Pronunciation {
+        match accent {
+            Accent::Uk => &self.uk,
+            Accent::Us => &self.us,
+        }
+    }
+
+    /// Whether every part was requested successfully.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
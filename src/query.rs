@@ -0,0 +1,168 @@
+//! Preparing lookup queries.
+
+use url::Url;
+
+use crate::text::is_invisible;
+use crate::YdtError;
+
+/// Youdao result page endpoint used unless configured otherwise.
+pub const DEFAULT_BASE_URL: &str = "https://www.youdao.com/result";
+/// Youdao mobile result page endpoint, tried when the desktop page yields nothing.
+pub const DEFAULT_MOBILE_URL: &str = "https://m.youdao.com/dict";
+/// Value of the `lang` query parameter used unless configured otherwise.
+pub const DEFAULT_LANG: &str = "en";
+
+/// Longest word, in characters, accepted unless configured otherwise.
+pub const DEFAULT_MAX_WORD_LEN: usize = 100;
+
+/// Punctuation of Chinese text dropped from around a word, so that text
+/// copied with it, such as `「hello」` or `hello，`, is looked up as the word.
+const CJK_PUNCTUATION: &[char] = &[
+    '，', '。', '、', '；', '：', '？', '！', '「', '」', '『', '』', '【', '】', '《', '》', '〈',
+    '〉', '（', '）', '〔', '〕', '…', '·',
+];
+
+/// Quotation marks replaced by the straight quote they stand for.
+const CURLY_QUOTES: &[(char, char)] = &[
+    ('\u{2018}', '\''),
+    ('\u{2019}', '\''),
+    ('\u{201a}', '\''),
+    ('\u{201b}', '\''),
+    ('\u{201c}', '"'),
+    ('\u{201d}', '"'),
+    ('\u{201e}', '"'),
+    ('\u{201f}', '"'),
+];
+
+/// `ch` as typed on an ASCII keyboard: full-width forms such as `ｈ` or `＇`
+/// become their ASCII counterparts and curly quotes straight ones.
+fn fold_char(ch: char) -> char {
+    if let Some(&(_, straight)) = CURLY_QUOTES.iter().find(|&&(curly, _)| curly == ch) {
+        return straight;
+    }
+    match ch {
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(ch as u32 - 0xfee0).unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+/// Trim `word` and collapse internal runs of whitespace to single spaces.
+///
+/// Text copied from Chinese pages is folded to what would have been typed:
+/// Chinese punctuation around the word, such as `，` or `「」`, is dropped,
+/// full-width letters, digits and punctuation become ASCII, and curly quotes
+/// become straight ones. Invisible characters copied along, such as
+/// zero-width spaces, soft hyphens and bidi controls, are dropped.
+///
+/// # Errors
+///
+/// Returns [`YdtError::EmptyWord`] when nothing but whitespace is left,
+/// [`YdtError::WordTooLong`] when the result is longer than `max_len` characters,
+/// and [`YdtError::UnsupportedCharacter`] when it contains a control character.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::normalize_word("  ice \t cream\n", 100).unwrap(), "ice cream");
+/// assert_eq!(ydt::normalize_word("「ｈｅｌｌｏ」", 100).unwrap(), "hello");
+/// assert_eq!(ydt::normalize_word("don\u{2019}t", 100).unwrap(), "don't");
+/// assert!(ydt::normalize_word(" \n", 100).is_err());
+/// ```
+pub fn normalize_word(word: &str, max_len: usize) -> Result<String, YdtError> {
+    let word = word.trim_matches(|ch: char| {
+        ch.is_whitespace() || is_invisible(ch) || CJK_PUNCTUATION.contains(&ch)
+    });
+    let mut normalized = String::with_capacity(word.len());
+    let mut space = false;
+    for ch in word.chars().filter(|&ch| !is_invisible(ch)) {
+        if ch.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            normalized.push(' ');
+        }
+        space = false;
+        normalized.push(fold_char(ch));
+    }
+    if normalized.is_empty() {
+        return Err(YdtError::EmptyWord);
+    }
+    if let Some(ch) = normalized.chars().find(|ch| ch.is_control()) {
+        return Err(YdtError::UnsupportedCharacter {
+            word: normalized,
+            ch,
+        });
+    }
+    let len = normalized.chars().count();
+    if len > max_len {
+        return Err(YdtError::WordTooLong { len, max: max_len });
+    }
+    Ok(normalized)
+}
+
+/// Returns `true` when `word` is a number, such as `42`, `-1`, `3.14` or
+/// `1,000`, which the dictionary has no translation for.
+///
+/// Numbers with units, such as `5km` or `km/h`, are not numbers here: the
+/// dictionary may know the unit.
+///
+/// # Examples
+///
+/// ```
+/// assert!(ydt::is_number("42"));
+/// assert!(ydt::is_number("3.14"));
+/// assert!(!ydt::is_number("km/h"));
+/// assert!(!ydt::is_number("1.2.3"));
+/// ```
+pub fn is_number(word: &str) -> bool {
+    let unsigned = word.strip_prefix(['+', '-']).unwrap_or(word);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    // Thousands separators split the integer part into groups of three.
+    let mut groups = integer.split(',');
+    let integer_ok = match groups.next() {
+        Some(first) if integer.contains(',') => {
+            digits(first)
+                && first.len() <= 3
+                && groups.all(|group| digits(group) && group.len() == 3)
+        }
+        _ => digits(integer) || (integer.is_empty() && fraction.is_some()),
+    };
+    integer_ok && fraction.is_none_or(digits)
+}
+
+/// Build the result page URL for `word` on [`DEFAULT_BASE_URL`].
+///
+/// The word is sent as a form-encoded query parameter, so characters such as
+/// `'`, `&` and `#` are percent-encoded.
+///
+/// # Errors
+///
+/// Returns [`YdtError::BuildRequestUrl`] if the URL cannot be built.
+///
+/// # Examples
+///
+/// ```
+/// let url = ydt::build_result_url("AT&T", "en").unwrap();
+/// assert_eq!(url.as_str(), "https://www.youdao.com/result?word=AT%26T&lang=en");
+/// ```
+pub fn build_result_url(word: &str, lang: &str) -> Result<Url, YdtError> {
+    result_url(DEFAULT_BASE_URL, word, lang)
+}
+
+pub(crate) fn result_url(base_url: &str, word: &str, lang: &str) -> Result<Url, YdtError> {
+    Url::parse_with_params(base_url, &[("word", word), ("lang", lang)])
+        .map_err(YdtError::BuildRequestUrl)
+}
+
+#[cfg(feature = "http")]
+/// Mobile result page URL; the mobile site expects `eng` rather than `en`.
+pub(crate) fn mobile_result_url(base_url: &str, word: &str, lang: &str) -> Result<Url, YdtError> {
+    let lang = if lang == "en" { "eng" } else { lang };
+    Url::parse_with_params(base_url, &[("q", word), ("le", lang)])
+        .map_err(YdtError::BuildRequestUrl)
+}
@@ -0,0 +1,98 @@
+//! Minimum-interval rate limiting shared by all requests of a client, and
+//! backing off when the server rate-limits us.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Enforces a minimum interval between the start of consecutive requests.
+///
+/// Each caller reserves the next free slot while holding the lock and sleeps until
+/// that slot after releasing it, so the limit holds across threads without
+/// serializing the requests themselves.
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Block until the caller may send a request, returning how long it waited.
+    pub(crate) fn acquire(&self) -> Duration {
+        let now = Instant::now();
+        let slot = {
+            let mut next_slot = match self.next_slot.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let slot = next_slot.map_or(now, |next| next.max(now));
+            *next_slot = Some(slot + self.interval);
+            slot
+        };
+        let wait = slot.saturating_duration_since(now);
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+        wait
+    }
+}
+
+/// Parse a `Retry-After` value, given either in seconds or as an HTTP date.
+///
+/// A date in the past means the request may be retried right away.
+pub(crate) fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+/// A file recording until when no requests should be sent, so that a
+/// rate limit outlives the process that hit it.
+///
+/// The file holds the end of the cooldown in seconds since the Unix epoch.
+#[derive(Debug, Clone)]
+pub(crate) struct Cooldown {
+    path: PathBuf,
+}
+
+impl Cooldown {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Time left until the cooldown ends, or `None` when there is none.
+    ///
+    /// An unreadable file is treated as no cooldown.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        let text = fs::read_to_string(&self.path).ok()?;
+        let until = UNIX_EPOCH + Duration::from_secs(text.trim().parse().ok()?);
+        until
+            .duration_since(SystemTime::now())
+            .ok()
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Start a cooldown of `duration` from now.
+    pub(crate) fn start(&self, duration: Duration) -> io::Result<()> {
+        let until = SystemTime::now() + duration;
+        // Rounded up, so that the cooldown is never shorter than requested.
+        let until = until.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = until.as_secs() + u64::from(until.subsec_nanos() > 0);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, format!("{seconds}\n"))
+    }
+}
@@ -0,0 +1,51 @@
+//! The related words section (同根词) of result pages: words derived from
+//! the same root, grouped by part of speech.
+
+use scraper::{ElementRef, Html, Node};
+
+use crate::selectors::Selectors;
+use crate::text::normalize;
+use crate::translation::{RelatedGroup, RelatedWord};
+use crate::{capped, trimmed_text, MAX_PARSED_ITEMS};
+
+/// Parse the related word groups; groups without a part of speech, such as
+/// the line naming the root (词根), are skipped.
+pub(crate) fn parse_related(document: &Html, selectors: &Selectors) -> Vec<RelatedGroup> {
+    document
+        .select(&selectors.related_group)
+        .filter_map(|group| {
+            let pos = group
+                .select(&selectors.related_pos)
+                .next()
+                .map(trimmed_text)
+                .filter(|pos| !pos.is_empty())?;
+            let words: Vec<_> = group
+                .select(&selectors.related_word)
+                .take(MAX_PARSED_ITEMS)
+                .map(|word| RelatedWord {
+                    word: trimmed_text(word),
+                    gloss: gloss(word, selectors),
+                })
+                .filter(|word| !word.word.is_empty())
+                .collect();
+            (!words.is_empty()).then_some(RelatedGroup { pos, words })
+        })
+        .take(MAX_PARSED_ITEMS)
+        .collect()
+}
+
+/// The text after `word` up to the next related word, trimmed.
+fn gloss(word: ElementRef<'_>, selectors: &Selectors) -> String {
+    let mut gloss = String::new();
+    for sibling in word.next_siblings() {
+        if let Some(element) = ElementRef::wrap(sibling) {
+            if selectors.related_word.matches(&element) {
+                break;
+            }
+            gloss.extend(element.text());
+        } else if let Node::Text(text) = sibling.value() {
+            gloss.push_str(text);
+        }
+    }
+    capped(normalize(&gloss))
+}
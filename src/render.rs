@@ -0,0 +1,1204 @@
+//! Rendering of structured translations into display text.
+
+use serde_json::{json, Value};
+use std::str::FromStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::translation::{
+    Accent, Candidate, CollinsSense, Direction, Entry, EntrySource, Idiom, Layout, Phonetic,
+    RelatedGroup, ResultKind, Translation,
+};
+use crate::{whole_secs, BuildInfo, YdtError};
+
+const ELLIPSIS: char = '…';
+
+/// Escape sequences around dimmed text.
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Header row for [`render_tsv_row`] output.
+pub const TSV_HEADER: &str = "word\tphonetics\tdefinitions";
+
+/// Output format for lookups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Plain,
+    /// Tab-separated rows, see [`render_tsv_row`].
+    Tsv,
+    /// One JSON object per line, see [`render_ndjson_line`].
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "tsv" => Ok(Self::Tsv),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!(
+                "unknown format `{s}`, expected `plain`, `tsv` or `ndjson`"
+            )),
+        }
+    }
+}
+
+/// When to color output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color output for terminals only.
+    #[default]
+    Auto,
+    /// Always color output.
+    Always,
+    /// Never color output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output written to a terminal when `terminal` is true.
+    pub fn enabled(self, terminal: bool) -> bool {
+        match self {
+            Self::Auto => terminal,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "unknown color choice `{s}`, expected `auto`, `always` or `never`"
+            )),
+        }
+    }
+}
+
+/// Which sections of a translation to render.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sections {
+    /// Phonetics followed by definitions.
+    #[default]
+    All,
+    /// Phonetics only.
+    PhoneticsOnly,
+    /// Definitions only.
+    DefinitionsOnly,
+}
+
+/// Options controlling [`render_translation`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Sections to include in the output.
+    pub sections: Sections,
+    /// Follow each definition with a tag naming where it comes from, e.g.
+    /// `[mobile]`; see [`EntrySource`].
+    pub show_source: bool,
+    /// Dim the source tags with escape sequences, for terminals.
+    pub color: bool,
+    /// Language of the labels around the results.
+    pub labels: Labels,
+}
+
+/// Language of the labels rendered around results, such as accents and
+/// directions. The results themselves are rendered as found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Labels {
+    /// As on Youdao's pages, e.g. `英` and `[拼音]`.
+    #[default]
+    Chinese,
+    /// In English, e.g. `UK` and `[pinyin]`.
+    English,
+}
+
+impl Labels {
+    /// The labels for a locale such as `en_US.UTF-8` or `zh_CN`: Chinese for
+    /// Chinese locales and English for others, or `None` for `C`, `POSIX`
+    /// and empty locales, which name no language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::Labels;
+    ///
+    /// assert_eq!(Labels::from_locale("de_DE.UTF-8"), Some(Labels::English));
+    /// assert_eq!(Labels::from_locale("zh_TW.UTF-8"), Some(Labels::Chinese));
+    /// assert_eq!(Labels::from_locale("C.UTF-8"), None);
+    /// ```
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default();
+        if language.is_empty() || language == "C" || language == "POSIX" {
+            None
+        } else if language.eq_ignore_ascii_case("zh") {
+            Some(Self::Chinese)
+        } else {
+            Some(Self::English)
+        }
+    }
+}
+
+/// A label the parser has classified, such as the accent of a phonetic.
+#[derive(Debug, Clone, Copy)]
+enum Label {
+    Accent(Accent),
+    Layout(Layout),
+    IdiomExplanation,
+    IdiomSource,
+    IdiomExample,
+}
+
+impl Label {
+    /// The label in `labels`.
+    fn text(self, labels: Labels) -> &'static str {
+        let (chinese, english) = match self {
+            Self::Accent(Accent::Uk) => ("英", "UK"),
+            Self::Accent(Accent::Us) => ("美", "US"),
+            Self::Layout(Layout::EnglishChinese) => ("[英汉]", "[en→zh]"),
+            Self::Layout(Layout::ChineseEnglish) => ("[汉英]", "[zh→en]"),
+            Self::Layout(Layout::Pinyin) => ("[拼音]", "[pinyin]"),
+            Self::IdiomExplanation => ("释义", "meaning"),
+            Self::IdiomSource => ("出处", "origin"),
+            Self::IdiomExample => ("例句", "example"),
+        };
+        match labels {
+            Labels::Chinese => chinese,
+            Labels::English => english,
+        }
+    }
+}
+
+/// The label of `phonetic`: as on the page, unless it names an accent and
+/// `labels` are English.
+fn phonetic_label(phonetic: &Phonetic, labels: Labels) -> &str {
+    match phonetic.accent() {
+        Some(accent) if labels == Labels::English => Label::Accent(accent).text(labels),
+        _ => &phonetic.label,
+    }
+}
+
+/// How definitions are tagged with their [`EntrySource`].
+#[derive(Debug, Clone, Copy)]
+struct SourceTags {
+    show: bool,
+    color: bool,
+}
+
+impl SourceTags {
+    const NONE: Self = Self {
+        show: false,
+        color: false,
+    };
+
+    fn of(options: &RenderOptions) -> Self {
+        Self {
+            show: options.show_source,
+            color: options.color,
+        }
+    }
+
+    /// `line` followed by the tag of `source`, if tags are shown.
+    fn tag(self, line: String, source: EntrySource) -> String {
+        match (self.show, self.color) {
+            (false, _) => line,
+            (true, false) => format!("{line} [{source}]"),
+            (true, true) => format!("{line} {DIM}[{source}]{RESET}"),
+        }
+    }
+
+    /// Follow the last of `lines` with the tag of `source`, or put the tag on
+    /// a line of its own after `indent` columns when it would not fit in
+    /// `width`.
+    fn tag_last(
+        self,
+        lines: &mut [String],
+        indent: usize,
+        width: Option<usize>,
+        source: EntrySource,
+    ) {
+        let Some(last) = lines.last_mut().filter(|_| self.show) else {
+            return;
+        };
+        let tag_width = display_width(&format!(" [{source}]"));
+        if width.is_some_and(|width| display_width(last) + tag_width > width) {
+            let tag = self.tag(String::new(), source);
+            last.push('\n');
+            last.push_str(&" ".repeat(indent));
+            last.push_str(tag.trim_start());
+        } else {
+            *last = self.tag(std::mem::take(last), source);
+        }
+    }
+}
+
+fn render_entry(entry: &Entry) -> String {
+    if entry.pos.is_empty() {
+        entry.meaning.clone()
+    } else {
+        format!("{}: {}", entry.pos, entry.meaning)
+    }
+}
+
+/// A pinyin candidate as `学习 xué xí study; learn`, leaving out missing parts.
+fn render_candidate(candidate: &Candidate) -> String {
+    [&candidate.hanzi, &candidate.pinyin, &candidate.gloss]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One line per definition, then one per pinyin candidate, then the web
+/// translations when there is nothing else.
+fn definition_lines(
+    translation: &Translation,
+    tags: SourceTags,
+) -> impl Iterator<Item = String> + '_ {
+    let entries = translation
+        .entries
+        .iter()
+        .map(move |entry| tags.tag(render_entry(entry), entry.source));
+    entries
+        .chain(translation.candidates.iter().map(render_candidate))
+        .chain(translation.conversions.iter().cloned())
+        .chain(web_line(translation).map(move |line| tags.tag(line, EntrySource::WebTrans)))
+}
+
+/// Marker in front of web translations, which are less reliable than the
+/// dictionary.
+const WEB_MARKER: &str = "(web) ";
+
+/// The web translations joined into one line after [`WEB_MARKER`], such as
+/// `(web) 你好；哈喽`, for a [`ResultKind::WebOnly`] translation.
+fn web_line(translation: &Translation) -> Option<String> {
+    (translation.result_kind() == ResultKind::WebOnly)
+        .then(|| format!("{WEB_MARKER}{}", translation.web_translations.join("；")))
+}
+
+/// What an empty translation renders as: the suggestions for a misspelled
+/// word, as `Did you mean: hello, hellos?`, or `No results.`.
+fn no_results(translation: &Translation) -> String {
+    if translation.suggestions.is_empty() {
+        "No results.".to_string()
+    } else {
+        format!("Did you mean: {}?", translation.suggestions.join(", "))
+    }
+}
+
+/// Line pointing from the queried word to a different headword, such as
+/// `→ run (现在分词)` when looking up `running`.
+///
+/// Returns `None` when the page shows results for the queried word itself.
+///
+/// # Examples
+///
+/// ```
+/// let translation = ydt::Translation {
+///     word: "running".to_string(),
+///     headword: Some("run".to_string()),
+///     lemma_note: Some("现在分词".to_string()),
+///     ..ydt::Translation::default()
+/// };
+/// assert_eq!(ydt::render_lemma_notice(&translation).unwrap(), "→ run (现在分词)");
+/// ```
+pub fn render_lemma_notice(translation: &Translation) -> Option<String> {
+    let headword = translation.headword.as_deref()?;
+    if headword.eq_ignore_ascii_case(&translation.word) {
+        return None;
+    }
+    Some(match &translation.lemma_note {
+        Some(note) => format!("→ {headword} ({note})"),
+        None => format!("→ {headword}"),
+    })
+}
+
+/// Line showing the word as given and the Simplified form it was looked up
+/// as, such as `學習 → 学习`; see [`Translation::converted_from`].
+///
+/// # Examples
+///
+/// ```
+/// let translation = ydt::Translation {
+///     word: "学习".to_string(),
+///     converted_from: Some("學習".to_string()),
+///     ..ydt::Translation::default()
+/// };
+/// assert_eq!(ydt::render_conversion_notice(&translation).unwrap(), "學習 → 学习");
+/// ```
+pub fn render_conversion_notice(translation: &Translation) -> Option<String> {
+    let from = translation.converted_from.as_deref()?;
+    Some(format!("{from} → {}", translation.word))
+}
+
+/// Render a translation as plain text.
+///
+/// With [`Sections::All`] an empty translation renders as `Did you mean: …?`
+/// when Youdao suggests other spellings and as `No results.` otherwise; when
+/// only one section is selected and it is empty, the result is an empty
+/// string. [Web translations](Translation::web_translations) are only shown
+/// when there are no dictionary results, on one line marked `(web)`.
+/// An [idiom explanation](Translation::idiom) precedes the definitions, and
+/// [related words](Translation::related) and [Collins senses](Translation::collins)
+/// follow them, each after a blank line. Results [in other
+/// directions](Translation::other_directions) come last, each under a label
+/// such as `[拼音]`. Values longer than the
+/// [display limit](Translation::display_limit) are truncated with `…`, here
+/// and in the other text renderings.
+pub fn render_translation(translation: &Translation, options: &RenderOptions) -> String {
+    let truncated = translation.truncated_for_display();
+    let translation = truncated.as_ref().unwrap_or(translation);
+    let phonetics_str = phonetics_line(translation, options.labels);
+    let tags = SourceTags::of(options);
+    let translations_str = definition_lines(translation, tags)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let translations_str = with_details(translations_str, translation, None, tags);
+    let translations_str = with_idiom(translations_str, translation, None, options.labels);
+
+    let body = match options.sections {
+        Sections::PhoneticsOnly => phonetics_str,
+        Sections::DefinitionsOnly => translations_str,
+        Sections::All => join_sections(translation, phonetics_str, translations_str),
+    };
+    with_other_directions(body, translation, options.labels, |other| {
+        render_translation(other, options)
+    })
+}
+
+fn phonetics_line(translation: &Translation, labels: Labels) -> String {
+    translation
+        .phonetics
+        .iter()
+        .map(|phonetic| format!("{} {}", phonetic_label(phonetic, labels), phonetic.text))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Label of the results read in `layout`, shown when a page was read in
+/// several directions.
+fn layout_label(layout: Option<Layout>, labels: Labels) -> &'static str {
+    layout.map_or("", |layout| Label::Layout(layout).text(labels))
+}
+
+/// `body` and the [results in other directions](Translation::other_directions)
+/// rendered with `render`, each under its label and after a blank line.
+fn with_other_directions(
+    body: String,
+    translation: &Translation,
+    labels: Labels,
+    render: impl Fn(&Translation) -> String,
+) -> String {
+    if translation.other_directions.is_empty() {
+        return body;
+    }
+    let label = layout_label(translation.layout, labels);
+    let mut blocks = vec![format!("{label}\n{body}")];
+    for other in &translation.other_directions {
+        let label = layout_label(other.layout, labels);
+        blocks.push(format!("{label}\n{}", render(other)));
+    }
+    blocks.join("\n\n")
+}
+
+/// `definitions` preceded by the idiom explanation, if any, and a blank line.
+fn with_idiom(
+    definitions: String,
+    translation: &Translation,
+    width: Option<usize>,
+    labels: Labels,
+) -> String {
+    let Some(idiom) = &translation.idiom else {
+        return definitions;
+    };
+    let idiom = idiom_lines(idiom, width, labels).join("\n");
+    if definitions.is_empty() {
+        idiom
+    } else {
+        format!("{idiom}\n\n{definitions}")
+    }
+}
+
+/// `definitions` followed by the related words and the Collins senses that
+/// were parsed, each after a blank line, wrapped at `width` columns when given.
+fn with_details(
+    definitions: String,
+    translation: &Translation,
+    width: Option<usize>,
+    tags: SourceTags,
+) -> String {
+    let mut blocks = vec![definitions];
+    if !translation.related.is_empty() {
+        blocks.push(related_lines(&translation.related).join("\n"));
+    }
+    if !translation.collins.is_empty() {
+        blocks.push(collins_lines(&translation.collins, width, tags).join("\n"));
+    }
+    blocks.retain(|block| !block.is_empty());
+    blocks.join("\n\n")
+}
+
+/// Push `text` onto `lines` after `first`, wrapped at `width` columns when
+/// given, with continuation lines indented by `indent` columns.
+fn push_wrapped(
+    lines: &mut Vec<String>,
+    first: &str,
+    indent: usize,
+    text: &str,
+    width: Option<usize>,
+) {
+    let wrapped = match width {
+        Some(width) => wrap_to_width(text, width.saturating_sub(display_width(first))),
+        None => vec![text.to_string()],
+    };
+    for (i, line) in wrapped.into_iter().enumerate() {
+        if i == 0 {
+            lines.push(format!("{first}{line}"));
+        } else {
+            lines.push(format!("{}{line}", " ".repeat(indent)));
+        }
+    }
+}
+
+/// The pinyin of an idiom, then its explanation, source and example, each
+/// after its label, e.g. `释义 画蛇时给蛇添上脚。`.
+fn idiom_lines(idiom: &Idiom, width: Option<usize>, labels: Labels) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !idiom.pinyin.is_empty() {
+        lines.push(idiom.pinyin.clone());
+    }
+    for (label, text) in [
+        (Label::IdiomExplanation, &idiom.explanation),
+        (Label::IdiomSource, &idiom.source),
+        (Label::IdiomExample, &idiom.example),
+    ] {
+        if !text.is_empty() {
+            let label = format!("{} ", label.text(labels));
+            push_wrapped(&mut lines, &label, display_width(&label), text, width);
+        }
+    }
+    lines
+}
+
+/// One line per related word, with the part of speech before the first word
+/// of each group, e.g. `adj. creative 创造性的`.
+fn related_lines(groups: &[RelatedGroup]) -> Vec<String> {
+    let pos_width = groups
+        .iter()
+        .map(|group| display_width(&group.pos))
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for group in groups {
+        for (i, word) in group.words.iter().enumerate() {
+            let pos = if i == 0 { group.pos.as_str() } else { "" };
+            let padding = pos_width - display_width(pos) + 1;
+            let line = format!("{pos}{}{} {}", " ".repeat(padding), word.word, word.gloss);
+            lines.push(line.trim_end().to_string());
+        }
+    }
+    lines
+}
+
+/// Each Collins sense starts with its number, part of speech and English
+/// definition; the Chinese definition and the examples follow, indented.
+fn collins_lines(senses: &[CollinsSense], width: Option<usize>, tags: SourceTags) -> Vec<String> {
+    let mut lines = Vec::new();
+    for sense in senses {
+        let number = format!("{}. ", sense.number);
+        let indent = number.len();
+        let head = if sense.pos.is_empty() {
+            number
+        } else {
+            format!("{number}{} ", sense.pos)
+        };
+        push_wrapped(&mut lines, &head, indent, &sense.en_def, width);
+        tags.tag_last(&mut lines, indent, width, EntrySource::Collins);
+        let margin = " ".repeat(indent);
+        if !sense.zh_def.is_empty() {
+            push_wrapped(&mut lines, &margin, indent, &sense.zh_def, width);
+        }
+        for example in &sense.examples {
+            let bullet = format!("{margin}· ");
+            push_wrapped(&mut lines, &bullet, indent + 2, &example.en, width);
+            if !example.zh.is_empty() {
+                let margin = " ".repeat(indent + 2);
+                push_wrapped(&mut lines, &margin, indent + 2, &example.zh, width);
+            }
+        }
+    }
+    lines
+}
+
+/// Render a translation with fixed line positions, for scripts reading the
+/// output line by line.
+///
+/// With [`Sections::All`] the first line holds the phonetics, and is empty when
+/// there are none, followed by one line per definition; `No results.` is never
+/// printed, and an idiom explanation follows the definitions rather than
+/// preceding them. Other sections render as with [`render_translation`].
+///
+/// # Examples
+///
+/// ```
+/// use ydt::{Entry, EntrySource, RenderOptions, Translation};
+///
+/// let translation = Translation {
+///     word: "学习".to_string(),
+///     entries: vec![Entry {
+///         pos: String::new(),
+///         meaning: "study".to_string(),
+///         source: EntrySource::Concise,
+///     }],
+///     ..Translation::default()
+/// };
+/// assert_eq!(
+///     ydt::render_stable(&translation, &RenderOptions::default()),
+///     "\nstudy"
+/// );
+/// ```
+pub fn render_stable(translation: &Translation, options: &RenderOptions) -> String {
+    let truncated = translation.truncated_for_display();
+    let translation = truncated.as_ref().unwrap_or(translation);
+    if options.sections != Sections::All {
+        return render_translation(translation, options);
+    }
+    let mut lines = vec![phonetics_line(translation, options.labels)];
+    let tags = SourceTags::of(options);
+    lines.extend(definition_lines(translation, tags));
+    if let Some(idiom) = &translation.idiom {
+        lines.push(String::new());
+        lines.extend(idiom_lines(idiom, None, options.labels));
+    }
+    let mut body = with_details(lines.join("\n"), translation, None, tags);
+    // Labeled after the rest, keeping the line positions of the first result.
+    for other in &translation.other_directions {
+        body.push_str(&format!(
+            "\n\n{}\n{}",
+            layout_label(other.layout, options.labels),
+            render_stable(other, options)
+        ));
+    }
+    body
+}
+
+fn join_sections(
+    translation: &Translation,
+    phonetics_str: String,
+    translations_str: String,
+) -> String {
+    if phonetics_str.is_empty() && translations_str.is_empty() {
+        no_results(translation)
+    } else if phonetics_str.is_empty() {
+        translations_str
+    } else if translations_str.is_empty() {
+        phonetics_str
+    } else {
+        format!("{}\n{}", phonetics_str, translations_str)
+    }
+}
+
+/// Terminal display width of `text`, counting wide (e.g. CJK) characters as two columns.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Truncate `text` so that it fits in `width` columns, marking the cut with `…`.
+///
+/// Text that already fits is returned unchanged.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(ELLIPSIS.width().unwrap_or(1));
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > budget {
+            break;
+        }
+        used += ch_width;
+        out.push(ch);
+    }
+    if width > 0 {
+        out.push(ELLIPSIS);
+    }
+    out
+}
+
+/// Render a translation on a single line that fits in `width` display columns.
+///
+/// The line starts with the word and its phonetics, followed by the definitions
+/// separated by ` | `.
+///
+/// # Examples
+///
+/// ```
+/// use ydt::{Entry, EntrySource, Phonetic, Translation};
+///
+/// let translation = Translation {
+///     word: "hello".to_string(),
+///     phonetics: vec![Phonetic { label: "英".to_string(), text: "/həˈləʊ/".to_string() }],
+///     entries: vec![
+///         Entry {
+///             pos: "int.".to_string(),
+///             meaning: "你好".to_string(),
+///             source: EntrySource::Concise,
+///         },
+///         Entry {
+///             pos: "n.".to_string(),
+///             meaning: "表示问候".to_string(),
+///             source: EntrySource::Concise,
+///         },
+///     ],
+///     ..Translation::default()
+/// };
+/// assert_eq!(
+///     ydt::render_one_line(&translation, 120),
+///     "hello 英/həˈləʊ/ int.你好 | n.表示问候"
+/// );
+/// assert_eq!(ydt::render_one_line(&translation, 20), "hello 英/həˈləʊ/ in…");
+/// ```
+pub fn render_one_line(translation: &Translation, width: usize) -> String {
+    render_one_line_labeled(translation, width, Labels::Chinese)
+}
+
+/// Like [`render_one_line`], with the accents of phonetics labeled in
+/// `labels`, e.g. `hello UK/həˈləʊ/ int.你好` with [`Labels::English`].
+pub fn render_one_line_labeled(translation: &Translation, width: usize, labels: Labels) -> String {
+    let truncated = translation.truncated_for_display();
+    let translation = truncated.as_ref().unwrap_or(translation);
+    if translation.is_empty() {
+        return truncate_to_width(&no_results(translation), width);
+    }
+    let mut parts = vec![translation.word.clone()];
+    parts.extend(
+        translation
+            .phonetics
+            .iter()
+            .map(|phonetic| format!("{}{}", phonetic_label(phonetic, labels), phonetic.text)),
+    );
+    let definitions = translation
+        .entries
+        .iter()
+        .map(|entry| format!("{}{}", entry.pos, entry.meaning))
+        .chain(translation.candidates.iter().map(render_candidate))
+        .chain(translation.conversions.iter().cloned())
+        .chain(web_line(translation))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    if !definitions.is_empty() {
+        parts.push(definitions);
+    }
+    truncate_to_width(&parts.join(" "), width)
+}
+
+fn is_wide(ch: char) -> bool {
+    ch.width().unwrap_or(0) > 1
+}
+
+fn is_opening_punctuation(ch: char) -> bool {
+    matches!(ch, '（' | '「' | '『' | '【' | '《')
+}
+
+fn is_closing_punctuation(ch: char) -> bool {
+    matches!(
+        ch,
+        '，' | '。' | '；' | '：' | '、' | '！' | '？' | '）' | '」' | '』' | '】' | '》'
+    )
+}
+
+/// Split `text` into words that may not be broken, each paired with whether it is
+/// separated from the previous word by whitespace. Wide characters are their own
+/// words, since CJK text may break between any two characters.
+fn wrap_units(text: &str) -> Vec<(bool, String)> {
+    let mut units: Vec<(bool, String)> = Vec::new();
+    let mut current = String::new();
+    let mut spaced = false;
+    for ch in text.chars() {
+        if ch.is_whitespace() || is_wide(ch) {
+            if !current.is_empty() && !current.chars().all(is_opening_punctuation) {
+                units.push((spaced, std::mem::take(&mut current)));
+                spaced = false;
+            }
+            if ch.is_whitespace() {
+                spaced = true;
+            } else if is_opening_punctuation(ch) {
+                // Keep opening punctuation on the same line as the text it precedes.
+                current.push(ch);
+            } else if current.is_empty() && !spaced && is_closing_punctuation(ch) {
+                // Keep closing punctuation on the same line as the text it follows.
+                match units.last_mut() {
+                    Some((_, last)) => last.push(ch),
+                    None => units.push((false, ch.to_string())),
+                }
+            } else {
+                current.push(ch);
+                units.push((spaced, std::mem::take(&mut current)));
+                spaced = false;
+            }
+        } else {
+            current.push(ch);
+        }
+    }
+    if !current.is_empty() {
+        units.push((spaced, current));
+    }
+    units
+}
+
+/// Wrap `text` into lines no wider than `width` display columns.
+///
+/// Lines break at whitespace or between wide characters; words longer than
+/// `width` are broken at character boundaries.
+pub fn wrap_to_width(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for (spaced, unit) in wrap_units(text) {
+        let unit_width = display_width(&unit);
+        let gap = usize::from(spaced && !line.is_empty());
+        if !line.is_empty() && line_width + gap + unit_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        } else if gap == 1 {
+            line.push(' ');
+            line_width += 1;
+        }
+        if unit_width <= width {
+            line.push_str(&unit);
+            line_width += unit_width;
+            continue;
+        }
+        for ch in unit.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if line_width + ch_width > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(ch);
+            line_width += ch_width;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// A frequency rating as filled and empty stars, e.g. `★★★☆☆` for 3.
+fn render_stars(frequency: u8) -> String {
+    let filled = usize::from(frequency.min(5));
+    format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
+}
+
+/// Render a translation for a terminal that is `width` columns wide.
+///
+/// Parts of speech are padded to a common column and definitions are wrapped at
+/// `width`, with continuation lines indented under the definition text. With
+/// [`Sections::All`], the [frequency](Translation::frequency) follows the
+/// phonetics as stars, e.g. `★★★☆☆`.
+pub fn render_pretty(translation: &Translation, options: &RenderOptions, width: usize) -> String {
+    let truncated = translation.truncated_for_display();
+    let translation = truncated.as_ref().unwrap_or(translation);
+    let pos_width = translation
+        .entries
+        .iter()
+        .map(|entry| display_width(&entry.pos))
+        .max()
+        .unwrap_or(0);
+    let indent = if pos_width == 0 { 0 } else { pos_width + 1 };
+    let text_width = width.saturating_sub(indent);
+
+    let tags = SourceTags::of(options);
+    let mut definition_lines = Vec::new();
+    for entry in &translation.entries {
+        let mut lines = wrap_to_width(&entry.meaning, text_width);
+        tags.tag_last(&mut lines, 0, Some(text_width), entry.source);
+        for (i, line) in lines.iter().flat_map(|line| line.split('\n')).enumerate() {
+            let prefix = if i == 0 { entry.pos.as_str() } else { "" };
+            let padding = indent.saturating_sub(display_width(prefix));
+            definition_lines.push(format!("{prefix}{}{line}", " ".repeat(padding)));
+        }
+    }
+    for candidate in &translation.candidates {
+        if candidate.gloss.is_empty() {
+            definition_lines.push(render_candidate(candidate));
+            continue;
+        }
+        // Glosses wrap under themselves, after the word and its pinyin.
+        let head = render_candidate(&Candidate {
+            gloss: String::new(),
+            ..candidate.clone()
+        });
+        let head = format!("{head} ");
+        let indent = display_width(&head);
+        push_wrapped(
+            &mut definition_lines,
+            &head,
+            indent,
+            &candidate.gloss,
+            Some(width),
+        );
+    }
+    for conversion in &translation.conversions {
+        push_wrapped(&mut definition_lines, "", 0, conversion, Some(width));
+    }
+    if translation.result_kind() == ResultKind::WebOnly {
+        push_wrapped(
+            &mut definition_lines,
+            WEB_MARKER,
+            display_width(WEB_MARKER),
+            &translation.web_translations.join("；"),
+            Some(width),
+        );
+        tags.tag_last(
+            &mut definition_lines,
+            display_width(WEB_MARKER),
+            Some(width),
+            EntrySource::WebTrans,
+        );
+    }
+
+    let phonetics_str = phonetics_line(translation, options.labels);
+    let translations_str =
+        with_details(definition_lines.join("\n"), translation, Some(width), tags);
+    let translations_str = with_idiom(translations_str, translation, Some(width), options.labels);
+
+    let body = match options.sections {
+        Sections::PhoneticsOnly => phonetics_str,
+        Sections::DefinitionsOnly => translations_str,
+        Sections::All => {
+            let header = match translation.frequency.filter(|_| !translation.is_empty()) {
+                Some(frequency) if phonetics_str.is_empty() => render_stars(frequency),
+                Some(frequency) => format!("{phonetics_str} {}", render_stars(frequency)),
+                None => phonetics_str,
+            };
+            join_sections(translation, header, translations_str)
+        }
+    };
+    with_other_directions(body, translation, options.labels, |other| {
+        render_pretty(other, options, width)
+    })
+}
+
+/// Replace tabs and line breaks so `field` fits in a single TSV cell.
+fn sanitize_tsv_field(field: &str) -> String {
+    field
+        .split(['\t', '\r', '\n'])
+        .filter(|part| !part.trim().is_empty())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Render one lookup as a `word<TAB>phonetics<TAB>definitions` row.
+///
+/// Definitions are joined with `; `. A failed lookup renders as
+/// `word<TAB><TAB>ERROR: message` so that every input word yields exactly one row.
+///
+/// # Examples
+///
+/// ```
+/// use ydt::{Entry, EntrySource, Translation};
+///
+/// let translation = Translation {
+///     word: "hello".to_string(),
+///     phonetics: Vec::new(),
+///     entries: vec![Entry {
+///         pos: "int.".to_string(),
+///         meaning: "你好".to_string(),
+///         source: EntrySource::Concise,
+///     }],
+///     ..Translation::default()
+/// };
+/// assert_eq!(ydt::render_tsv_row("hello", &Ok(translation)), "hello\t\tint.: 你好");
+/// ```
+pub fn render_tsv_row(word: &str, result: &Result<Translation, YdtError>) -> String {
+    let word = sanitize_tsv_field(word);
+    match result {
+        Ok(translation) => {
+            let truncated = translation.truncated_for_display();
+            let translation = truncated.as_ref().unwrap_or(translation);
+            let phonetics = translation
+                .phonetics
+                .iter()
+                .map(|phonetic| format!("{} {}", phonetic.label, phonetic.text))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let definitions = definition_lines(translation, SourceTags::NONE)
+                .map(|line| sanitize_tsv_field(&line))
+                .collect::<Vec<_>>()
+                .join("; ");
+            format!("{word}\t{}\t{definitions}", sanitize_tsv_field(&phonetics))
+        }
+        Err(err) => format!("{word}\t\tERROR: {}", sanitize_tsv_field(&err.to_string())),
+    }
+}
+
+/// Convert a translation into a JSON value; `kind` holds the
+/// [`ResultKind`] in snake case, and `source_url`, `candidates`, `conversions`,
+/// `web_translations`, `suggestions`, `idiom`, `related` and `collins` are
+/// only present when the page has them and they were parsed. Values are kept
+/// whole; `truncated_for_display` is `true` when text renderings truncate
+/// some of them, see [`Translation::display_limit`].
+pub fn translation_to_json(translation: &Translation) -> Value {
+    let mut value = json!({
+        "word": translation.word,
+        "kind": result_kind_name(translation.result_kind()),
+        "phonetics": translation
+            .phonetics
+            .iter()
+            .map(|phonetic| json!({ "label": phonetic.label, "text": phonetic.text }))
+            .collect::<Vec<_>>(),
+        "entries": translation
+            .entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "pos": entry.pos,
+                    "meaning": entry.meaning,
+                    "source": entry.source.to_string(),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+    if let Some(direction) = translation.direction {
+        value["direction"] = json!(direction_name(direction));
+    }
+    if let Some(url) = &translation.source_url {
+        value["source_url"] = json!(url.as_str());
+    }
+    if translation.display_limit.is_some() {
+        value["truncated_for_display"] = json!(true);
+    }
+    if !translation.candidates.is_empty() {
+        value["candidates"] = translation
+            .candidates
+            .iter()
+            .map(|candidate| {
+                json!({
+                    "hanzi": candidate.hanzi,
+                    "pinyin": candidate.pinyin,
+                    "gloss": candidate.gloss,
+                })
+            })
+            .collect();
+    }
+    if !translation.conversions.is_empty() {
+        value["conversions"] = json!(translation.conversions);
+    }
+    if !translation.web_translations.is_empty() {
+        value["web_translations"] = json!(translation.web_translations);
+    }
+    if !translation.suggestions.is_empty() {
+        value["suggestions"] = json!(translation.suggestions);
+    }
+    if let Some(idiom) = &translation.idiom {
+        value["idiom"] = json!({
+            "pinyin": idiom.pinyin,
+            "explanation": idiom.explanation,
+            "source": idiom.source,
+            "example": idiom.example,
+        });
+    }
+    if !translation.related.is_empty() {
+        value["related"] = translation
+            .related
+            .iter()
+            .map(|group| {
+                json!({
+                    "pos": group.pos,
+                    "words": group
+                        .words
+                        .iter()
+                        .map(|word| json!({ "word": word.word, "gloss": word.gloss }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+    }
+    if !translation.collins.is_empty() {
+        value["collins"] = translation
+            .collins
+            .iter()
+            .map(|sense| {
+                json!({
+                    "number": sense.number,
+                    "pos": sense.pos,
+                    "en_def": sense.en_def,
+                    "zh_def": sense.zh_def,
+                    "examples": sense
+                        .examples
+                        .iter()
+                        .map(|example| json!({ "en": example.en, "zh": example.zh }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+    }
+    if !translation.other_directions.is_empty() {
+        value["other_directions"] = translation
+            .other_directions
+            .iter()
+            .map(|other| {
+                let mut other_value = translation_to_json(other);
+                other_value["layout"] = json!(other.layout.map(layout_name));
+                other_value
+            })
+            .collect();
+    }
+    value
+}
+
+/// `err` as a JSON object with its [`kind`](YdtError::kind), message, and
+/// the word, HTTP status and `Retry-After` seconds when it has them.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let err = ydt::YdtError::RateLimited {
+///     word: "hello".to_string(),
+///     retry_after: Some(std::time::Duration::from_secs(30)),
+/// };
+/// assert_eq!(
+///     ydt::error_to_json(&err),
+///     json!({
+///         "kind": "rate_limited",
+///         "word": "hello",
+///         "status": 429,
+///         "retry_after_secs": 30,
+///         "message": "Rate limited by the server for \"hello\"; try again in 30s",
+///     })
+/// );
+/// ```
+pub fn error_to_json(err: &YdtError) -> Value {
+    let mut value = json!({ "kind": err.kind() });
+    if let Some(word) = err.word() {
+        value["word"] = json!(word);
+    }
+    if let Some(status) = err.status_code() {
+        value["status"] = json!(status.as_u16());
+    }
+    if let Some(wait) = err.retry_after() {
+        value["retry_after_secs"] = json!(whole_secs(wait));
+    }
+    value["message"] = json!(err.to_string());
+    value
+}
+
+/// `info` as a JSON object, with the same fields as [`BuildInfo`].
+///
+/// # Examples
+///
+/// ```
+/// let json = ydt::build_info_to_json(&ydt::build_info());
+/// assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+/// assert_eq!(json["features"].as_array().unwrap().len(), ydt::build_info().features.len());
+/// ```
+pub fn build_info_to_json(info: &BuildInfo) -> Value {
+    json!({
+        "version": info.version,
+        "default_base_url": info.default_base_url,
+        "features": info.features,
+        "default_user_agent": info.default_user_agent,
+    })
+}
+
+/// Name of `layout` in JSON output.
+fn layout_name(layout: Layout) -> &'static str {
+    match layout {
+        Layout::EnglishChinese => "english_chinese",
+        Layout::ChineseEnglish => "chinese_english",
+        Layout::Pinyin => "pinyin",
+    }
+}
+
+/// Name of `direction` in JSON output.
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::EnToZh => "en_to_zh",
+        Direction::ZhToEn => "zh_to_en",
+        Direction::Pinyin => "pinyin",
+        Direction::Sentence => "sentence",
+    }
+}
+
+/// Name of `kind` in JSON output.
+fn result_kind_name(kind: ResultKind) -> &'static str {
+    match kind {
+        ResultKind::Full => "full",
+        ResultKind::WebOnly => "web_only",
+        ResultKind::SuggestionsOnly => "suggestions_only",
+        ResultKind::Empty => "empty",
+    }
+}
+
+/// `err` as a JSON object with the fields of [`ErrorReport`](crate::ErrorReport),
+/// leaving out those that are `None`.
+#[cfg(any(feature = "ffi", feature = "server"))]
+pub(crate) fn error_json(err: &YdtError) -> Value {
+    let report = err.to_owned_report();
+    let mut error = json!({ "kind": report.kind, "message": report.message });
+    if let Some(word) = report.word {
+        error["word"] = word.into();
+    }
+    if let Some(status) = report.status {
+        error["status"] = status.into();
+    }
+    error
+}
+
+/// Render one lookup as a single-line JSON object.
+///
+/// Successful lookups render as `{"word":...,"ok":true,"result":{...}}` and failed
+/// ones as `{"word":...,"ok":false,"error":"..."}`.
+///
+/// # Examples
+///
+/// ```
+/// use ydt::{StatusCode, YdtError};
+///
+/// let err = YdtError::HttpStatus {
+///     word: "hello".to_string(),
+///     url: "https://www.youdao.com/result?word=hello&lang=en".to_string(),
+///     status: StatusCode::FORBIDDEN,
+/// };
+/// let line = ydt::render_ndjson_line("hello", &Err(err));
+/// assert_eq!(
+///     line,
+///     r#"{"word":"hello","ok":false,"error":"Request for \"hello\" failed with status: 403 Forbidden"}"#
+/// );
+/// ```
+pub fn render_ndjson_line(word: &str, result: &Result<Translation, YdtError>) -> String {
+    let value = match result {
+        Ok(translation) => json!({
+            "word": word,
+            "ok": true,
+            "result": translation_to_json(translation),
+        }),
+        Err(err) => json!({
+            "word": word,
+            "ok": false,
+            "error": err.to_string(),
+        }),
+    };
+    value.to_string()
+}
@@ -0,0 +1,64 @@
+//! Translations kept in memory for [`YdtClient::translate_cached`].
+//!
+//! [`YdtClient::translate_cached`]: crate::YdtClient::translate_cached
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::Translation;
+
+/// Up to `capacity` translations by normalized word, dropping the one cached
+/// first when full.
+pub(crate) struct ResultCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    translations: HashMap<String, Arc<Translation>>,
+    /// Cached words, first cached first.
+    order: VecDeque<String>,
+}
+
+impl ResultCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::default(),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    pub(crate) fn get(&self, word: &str) -> Option<Arc<Translation>> {
+        self.entries().translations.get(word).cloned()
+    }
+
+    pub(crate) fn insert(&self, word: String, translation: Arc<Translation>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries();
+        // Another thread may have looked the word up at the same time.
+        if entries.translations.contains_key(&word) {
+            return;
+        }
+        if entries.order.len() == self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.translations.remove(&oldest);
+            }
+        }
+        entries.order.push_back(word.clone());
+        entries.translations.insert(word, translation);
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.entries() = Entries::default();
+    }
+}
@@ -0,0 +1,284 @@
+//! CSS selectors used to find results on Youdao pages.
+
+use std::sync::OnceLock;
+
+use scraper::Selector;
+
+use crate::YdtError;
+
+/// CSS selectors used by the parser, so that markup changes on Youdao's side can
+/// be worked around without a new release.
+///
+/// [`Default`] holds the selectors matching the current result page.
+///
+/// # Examples
+///
+/// ```
+/// let selectors = ydt::SelectorConfig {
+///     word_exp: "li.word-exp-new".to_string(),
+///     ..ydt::SelectorConfig::default()
+/// };
+/// # #[cfg(feature = "http")]
+/// let client = ydt::YdtClient::builder().selectors(selectors).build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorConfig {
+    /// Containers of the phonetics (first match) and definitions (second match).
+    pub trans_container: String,
+    /// Phonetic block inside the first container.
+    pub phone: String,
+    /// Accent label inside a phonetic block.
+    pub phone_label: String,
+    /// Transcription inside a phonetic block.
+    pub phonetic: String,
+    /// Definition inside the second container.
+    pub word_exp: String,
+    /// Part of speech inside a definition.
+    pub pos: String,
+    /// Definition text inside a definition.
+    pub trans: String,
+    /// English candidate for a Chinese query.
+    pub word_exp_ce: String,
+    /// Candidate word inside an English candidate.
+    pub point: String,
+    /// Headword the page shows results for.
+    pub headword: String,
+    /// Note relating an inflected query to its headword.
+    pub lemma_note: String,
+    /// Frequency rating of the headword, with the number of stars in a
+    /// `star<N>` class.
+    pub frequency: String,
+    /// Suggested correction for a misspelled query.
+    pub suggestion: String,
+    /// Machine translation of a sentence.
+    pub sentence: String,
+    /// Numbered sense of the Collins dictionary section.
+    pub collins_sense: String,
+    /// Sense number inside a Collins sense.
+    pub collins_number: String,
+    /// Grammatical label inside a Collins sense.
+    pub collins_pos: String,
+    /// English definition inside a Collins sense.
+    pub collins_en_def: String,
+    /// Chinese definition inside a Collins sense.
+    pub collins_zh_def: String,
+    /// Example inside a Collins sense.
+    pub example: String,
+    /// English sentence inside an example.
+    pub example_en: String,
+    /// Chinese translation inside an example.
+    pub example_zh: String,
+    /// Group of related words (同根词) sharing a part of speech.
+    pub related_group: String,
+    /// Part of speech inside a related word group.
+    pub related_pos: String,
+    /// Related word inside a group; its gloss is the text following it.
+    pub related_word: String,
+    /// Candidate Chinese word for a query typed as pinyin; its characters are
+    /// in `point`.
+    pub pinyin_candidate: String,
+    /// Pinyin inside a pinyin candidate.
+    pub candidate_pinyin: String,
+    /// English gloss inside a pinyin candidate.
+    pub candidate_gloss: String,
+    /// Explanation block of an idiom (成语).
+    pub idiom: String,
+    /// Pinyin inside the idiom block.
+    pub idiom_pinyin: String,
+    /// Explanation (释义) inside the idiom block.
+    pub idiom_explanation: String,
+    /// Source (出处) inside the idiom block.
+    pub idiom_source: String,
+    /// Example sentence (例句) inside the idiom block.
+    pub idiom_example: String,
+    /// Expansion of an abbreviation, shown for queries such as `NASA`.
+    pub abbreviation: String,
+    /// Spelled-out words inside an abbreviation expansion.
+    pub abbreviation_full: String,
+    /// Chinese gloss inside an abbreviation expansion.
+    pub abbreviation_gloss: String,
+    /// Reading of a number or conversion of a unit.
+    pub conversion: String,
+    /// Translation collected from the web, shown when the dictionary has no
+    /// entry for the word.
+    pub web_translation: String,
+}
+
+impl Default for SelectorConfig {
+    fn default() -> Self {
+        Self {
+            trans_container: "div.trans-container".to_string(),
+            phone: "div.per-phone".to_string(),
+            phone_label: "span".to_string(),
+            phonetic: "span.phonetic".to_string(),
+            word_exp: "li.word-exp".to_string(),
+            pos: "span.pos".to_string(),
+            trans: "span.trans".to_string(),
+            word_exp_ce: "li.word-exp-ce.mcols-layout".to_string(),
+            point: "a.point".to_string(),
+            headword: "div.word-head div.title".to_string(),
+            lemma_note: "div.word-head .tips".to_string(),
+            frequency: "div.word-head .star".to_string(),
+            suggestion: "div.typo-rel a.title".to_string(),
+            sentence: "div.fanyi .trans-content".to_string(),
+            collins_sense: "div.collins li.mcols-layout".to_string(),
+            collins_number: "span.index".to_string(),
+            collins_pos: ".collins-def span.pos".to_string(),
+            collins_en_def: "span.def-en".to_string(),
+            collins_zh_def: "span.def-ch".to_string(),
+            example: "div.exam".to_string(),
+            example_en: "div.sen-eng".to_string(),
+            example_zh: "div.sen-ch".to_string(),
+            related_group: "div.rel_word .wordGroup".to_string(),
+            related_pos: ".contentTitle".to_string(),
+            related_word: "a.search-js".to_string(),
+            pinyin_candidate: "li.pinyin-candidate".to_string(),
+            candidate_pinyin: "span.pinyin".to_string(),
+            candidate_gloss: "p.word-exp_tran".to_string(),
+            idiom: "div.idiom".to_string(),
+            idiom_pinyin: ".idiom-pinyin".to_string(),
+            idiom_explanation: ".idiom-explain".to_string(),
+            idiom_source: ".idiom-source".to_string(),
+            idiom_example: ".idiom-example".to_string(),
+            abbreviation: "div.abbr li".to_string(),
+            abbreviation_full: ".abbr-full".to_string(),
+            abbreviation_gloss: ".abbr-trans".to_string(),
+            conversion: "div.num-conversion li".to_string(),
+            web_translation: "div#webTrans .wt-container .title".to_string(),
+        }
+    }
+}
+
+impl SelectorConfig {
+    /// Compile the selectors for use with
+    /// [`parse_translation_with_selectors`](crate::parse_translation_with_selectors).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`YdtError::ParseCssSelector`] with the first selector that is not
+    /// valid CSS.
+    pub fn compile(&self) -> Result<Selectors, YdtError> {
+        self.try_compile().map_err(YdtError::from)
+    }
+
+    pub(crate) fn try_compile(&self) -> Result<Selectors, InvalidSelector> {
+        fn parse(css: &str) -> Result<Selector, InvalidSelector> {
+            Selector::parse(css).map_err(|err| InvalidSelector {
+                css: css.to_string(),
+                message: err.to_string(),
+            })
+        }
+
+        Ok(Selectors {
+            trans_container: parse(&self.trans_container)?,
+            phone: parse(&self.phone)?,
+            phone_label: parse(&self.phone_label)?,
+            phonetic: parse(&self.phonetic)?,
+            word_exp: parse(&self.word_exp)?,
+            pos: parse(&self.pos)?,
+            trans: parse(&self.trans)?,
+            word_exp_ce: parse(&self.word_exp_ce)?,
+            point: parse(&self.point)?,
+            headword: parse(&self.headword)?,
+            lemma_note: parse(&self.lemma_note)?,
+            frequency: parse(&self.frequency)?,
+            suggestion: parse(&self.suggestion)?,
+            sentence: parse(&self.sentence)?,
+            collins_sense: parse(&self.collins_sense)?,
+            collins_number: parse(&self.collins_number)?,
+            collins_pos: parse(&self.collins_pos)?,
+            collins_en_def: parse(&self.collins_en_def)?,
+            collins_zh_def: parse(&self.collins_zh_def)?,
+            example: parse(&self.example)?,
+            example_en: parse(&self.example_en)?,
+            example_zh: parse(&self.example_zh)?,
+            related_group: parse(&self.related_group)?,
+            related_pos: parse(&self.related_pos)?,
+            related_word: parse(&self.related_word)?,
+            pinyin_candidate: parse(&self.pinyin_candidate)?,
+            candidate_pinyin: parse(&self.candidate_pinyin)?,
+            candidate_gloss: parse(&self.candidate_gloss)?,
+            idiom: parse(&self.idiom)?,
+            idiom_pinyin: parse(&self.idiom_pinyin)?,
+            idiom_explanation: parse(&self.idiom_explanation)?,
+            idiom_source: parse(&self.idiom_source)?,
+            idiom_example: parse(&self.idiom_example)?,
+            abbreviation: parse(&self.abbreviation)?,
+            abbreviation_full: parse(&self.abbreviation_full)?,
+            abbreviation_gloss: parse(&self.abbreviation_gloss)?,
+            conversion: parse(&self.conversion)?,
+            web_translation: parse(&self.web_translation)?,
+        })
+    }
+}
+
+/// A selector that failed to compile, kept so the error can be reported on every
+/// lookup.
+#[derive(Debug, Clone)]
+pub(crate) struct InvalidSelector {
+    css: String,
+    message: String,
+}
+
+impl From<InvalidSelector> for YdtError {
+    fn from(err: InvalidSelector) -> Self {
+        YdtError::ParseCssSelector {
+            css: err.css,
+            message: err.message,
+        }
+    }
+}
+
+/// Compiled form of a [`SelectorConfig`].
+#[derive(Debug, Clone)]
+pub struct Selectors {
+    pub(crate) trans_container: Selector,
+    pub(crate) phone: Selector,
+    pub(crate) phone_label: Selector,
+    pub(crate) phonetic: Selector,
+    pub(crate) word_exp: Selector,
+    pub(crate) pos: Selector,
+    pub(crate) trans: Selector,
+    pub(crate) word_exp_ce: Selector,
+    pub(crate) point: Selector,
+    pub(crate) headword: Selector,
+    pub(crate) lemma_note: Selector,
+    pub(crate) frequency: Selector,
+    pub(crate) suggestion: Selector,
+    pub(crate) sentence: Selector,
+    pub(crate) collins_sense: Selector,
+    pub(crate) collins_number: Selector,
+    pub(crate) collins_pos: Selector,
+    pub(crate) collins_en_def: Selector,
+    pub(crate) collins_zh_def: Selector,
+    pub(crate) example: Selector,
+    pub(crate) example_en: Selector,
+    pub(crate) example_zh: Selector,
+    pub(crate) related_group: Selector,
+    pub(crate) related_pos: Selector,
+    pub(crate) related_word: Selector,
+    pub(crate) pinyin_candidate: Selector,
+    pub(crate) candidate_pinyin: Selector,
+    pub(crate) candidate_gloss: Selector,
+    pub(crate) idiom: Selector,
+    pub(crate) idiom_pinyin: Selector,
+    pub(crate) idiom_explanation: Selector,
+    pub(crate) idiom_source: Selector,
+    pub(crate) idiom_example: Selector,
+    pub(crate) abbreviation: Selector,
+    pub(crate) abbreviation_full: Selector,
+    pub(crate) abbreviation_gloss: Selector,
+    pub(crate) conversion: Selector,
+    pub(crate) web_translation: Selector,
+}
+
+static DEFAULT_SELECTORS: OnceLock<Selectors> = OnceLock::new();
+
+/// The compiled [`SelectorConfig::default`] selectors, shared by all callers.
+pub(crate) fn default_selectors() -> &'static Selectors {
+    DEFAULT_SELECTORS.get_or_init(|| {
+        SelectorConfig::default()
+            .compile()
+            .expect("built-in selectors are valid CSS")
+    })
+}
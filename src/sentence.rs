@@ -0,0 +1,79 @@
+//! Machine translation of whole sentences.
+
+use scraper::Html;
+
+use crate::contains_cjk_ideograph;
+use crate::selectors::{default_selectors, Selectors};
+use crate::text::normalize;
+
+/// Number of words from which the command line treats its input as a sentence.
+pub const DEFAULT_SENTENCE_THRESHOLD: usize = 4;
+
+/// Longest sentence, in characters, machine-translated unless configured
+/// otherwise.
+pub const DEFAULT_MAX_SENTENCE_LEN: usize = 5000;
+
+/// Guess whether `text` is a sentence rather than a word or phrase to look up
+/// in the dictionary.
+///
+/// Several words or Chinese text ending in sentence punctuation are always a
+/// sentence, while abbreviations such as `etc.` are not. Otherwise English
+/// text needs at least `threshold` words, and Chinese text, which has no spaces,
+/// at least twice as many characters. A `threshold` of 0 only recognizes the
+/// punctuation.
+///
+/// # Examples
+///
+/// ```
+/// assert!(ydt::looks_like_sentence("how are you doing today", 4));
+/// assert!(!ydt::looks_like_sentence("take off", 4));
+/// assert!(ydt::looks_like_sentence("我今天很高兴。", 4));
+/// assert!(!ydt::looks_like_sentence("etc.", 4));
+/// ```
+pub fn looks_like_sentence(text: &str, threshold: usize) -> bool {
+    let text = text.trim();
+    let cjk = contains_cjk_ideograph(text);
+    if text.ends_with(['.', '?', '!', '。', '？', '！'])
+        && (cjk || text.contains(char::is_whitespace))
+    {
+        return true;
+    }
+    if threshold == 0 {
+        return false;
+    }
+    if cjk {
+        text.chars().filter(|ch| !ch.is_whitespace()).count() >= threshold * 2
+    } else {
+        text.split_whitespace().count() >= threshold
+    }
+}
+
+/// Extract the machine translation Youdao shows for a sentence, in either
+/// direction.
+///
+/// Returns `None` when the page has no machine translation, e.g. because it is a
+/// dictionary entry.
+///
+/// # Examples
+///
+/// ```
+/// let html = r#"<div class="fanyi"><p class="trans-content">你好吗？</p></div>"#;
+/// assert_eq!(ydt::parse_sentence_translation(html).as_deref(), Some("你好吗？"));
+/// ```
+pub fn parse_sentence_translation(html: &str) -> Option<String> {
+    parse_sentence_with_selectors(html, default_selectors())
+}
+
+pub(crate) fn parse_sentence_with_selectors(html: &str, selectors: &Selectors) -> Option<String> {
+    let document = Html::parse_document(html);
+    let translated = document
+        .select(&selectors.sentence)
+        .map(|content| normalize(&content.text().collect::<String>()))
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>();
+    if translated.is_empty() {
+        log::warn!("no machine translation found on the page");
+        return None;
+    }
+    Some(translated.join("\n"))
+}
@@ -0,0 +1,298 @@
+//! Local HTTP server answering lookups with JSON, as run by `ydt serve`.
+//!
+//! | Request | Response |
+//! | --- | --- |
+//! | `GET /translate?word=hello` | the translation, as from [`translation_to_json`] |
+//! | `GET /suggest?q=hel&limit=5` | `[{"word":"hello","gloss":"int. 喂；哈罗"}, ...]` |
+//! | `GET /healthz` | `{"status":"ok","build":{...}}`, `build` as from [`build_info_to_json`] |
+//!
+//! `/translate` also takes `lang`, the `lang` query parameter of the lookup;
+//! the client's [`lang`](YdtClient::lang) when left out. All requests share one
+//! [`YdtClient`], so its request interval and rate-limit cooldown apply across
+//! them.
+//!
+//! Failures answer with the fields of [`ErrorReport`](crate::ErrorReport) as
+//! JSON: `400 Bad Request` for invalid parameters, `404 Not Found` when Youdao
+//! has no page or no results for the word, `429 Too Many Requests` (with
+//! `Retry-After` when Youdao sent one) when rate-limited and `502 Bad Gateway`
+//! for other failures.
+//!
+//! Each request is identified by its `X-Request-Id` header, or by a random
+//! UUID when it has none or an invalid one. The ID is sent back in the
+//! `X-Request-Id` header of the response, in the `request_id` field of error
+//! bodies and in the log, and is passed to the lookup as its
+//! [`request_id`](crate::TranslationOptions::request_id).
+//!
+//! ```no_run
+//! let server = ydt::server::Server::bind("127.0.0.1:8080", ydt::YdtClient::new())?;
+//! server.run();
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+use url::Url;
+
+use crate::render::error_json;
+use crate::{
+    build_info, build_info_to_json, translation_to_json, whole_secs, StatusCode,
+    TranslationOptions, YdtClient, YdtError,
+};
+
+/// Number of requests handled at the same time.
+pub const DEFAULT_WORKERS: usize = 8;
+
+/// Completions returned by `/suggest` without a `limit`.
+const DEFAULT_SUGGEST_LIMIT: usize = 10;
+
+/// Header carrying the ID of a request, both ways.
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Longest `X-Request-Id` taken from a request.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// HTTP server sharing one [`YdtClient`] between all requests.
+pub struct Server {
+    http: Arc<tiny_http::Server>,
+    client: Arc<YdtClient>,
+    workers: usize,
+}
+
+impl Server {
+    /// Listen on `addr`; port 0 picks a free port, see [`local_addr`](Self::local_addr).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the address cannot be bound, e.g. because the
+    /// port is in use.
+    pub fn bind(addr: impl ToSocketAddrs, client: YdtClient) -> io::Result<Self> {
+        let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(Self {
+            http: Arc::new(http),
+            client: Arc::new(client),
+            workers: DEFAULT_WORKERS,
+        })
+    }
+
+    /// Handle up to `workers` requests at the same time. Defaults to
+    /// [`DEFAULT_WORKERS`]; 0 is treated as 1.
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// The address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.http
+            .server_addr()
+            .to_ip()
+            .expect("server listens on a TCP address")
+    }
+
+    /// Answer requests until the process exits.
+    pub fn run(self) {
+        let workers: Vec<_> = (0..self.workers)
+            .map(|_| {
+                let http = Arc::clone(&self.http);
+                let client = Arc::clone(&self.client);
+                thread::spawn(move || {
+                    for request in http.incoming_requests() {
+                        handle(&client, request);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A JSON response: the status, the body and the `Retry-After` seconds.
+type Reply = (StatusCode, Value, Option<u64>);
+
+fn handle(client: &YdtClient, request: Request) {
+    let id = request_id(&request);
+    let (status, mut body, retry_after) = route(client, request.method(), request.url(), &id);
+    log::info!(
+        "[{id}] {} {} -> {}",
+        request.method(),
+        request.url(),
+        status.as_u16()
+    );
+    if !status.is_success() {
+        if let Some(error) = body.as_object_mut() {
+            error.insert("request_id".to_string(), id.clone().into());
+        }
+    }
+    let mut response = Response::from_string(body.to_string())
+        .with_status_code(status.as_u16())
+        .with_header(
+            Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+                .expect("valid header"),
+        )
+        .with_header(Header::from_bytes(REQUEST_ID_HEADER, id).expect("valid header"));
+    if let Some(secs) = retry_after {
+        response
+            .add_header(Header::from_bytes("Retry-After", secs.to_string()).expect("valid header"));
+    }
+    if let Err(err) = request.respond(response) {
+        log::warn!("Failed to send response: {err}");
+    }
+}
+
+/// The `X-Request-Id` of `request` when it is printable ASCII of at most
+/// [`MAX_REQUEST_ID_LEN`] bytes, or else a new random UUID.
+fn request_id(request: &Request) -> String {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(REQUEST_ID_HEADER))
+        .map(|header| header.value.as_str().trim())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id.bytes().all(|byte| byte.is_ascii_graphic())
+        })
+        .map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string)
+}
+
+fn route(client: &YdtClient, method: &Method, target: &str, id: &str) -> Reply {
+    // Only the path and query of `target` are used.
+    let Ok(url) = Url::parse("http://localhost").and_then(|base| base.join(target)) else {
+        return bad_request("Invalid request target");
+    };
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let route = url.path();
+    if !matches!(route, "/translate" | "/suggest" | "/healthz") {
+        return error(StatusCode::NOT_FOUND, "not_found", "No such endpoint");
+    }
+    if *method != Method::Get {
+        return error(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "method_not_allowed",
+            "Only GET is supported",
+        );
+    }
+    match route {
+        "/translate" => {
+            let Some(word) = param("word") else {
+                return bad_request("Missing `word` parameter");
+            };
+            translate(client, &word, param("lang"), id)
+        }
+        "/suggest" => {
+            let Some(prefix) = param("q") else {
+                return bad_request("Missing `q` parameter");
+            };
+            let limit = match param("limit") {
+                None => DEFAULT_SUGGEST_LIMIT,
+                Some(raw) => match raw.parse() {
+                    Ok(limit) if limit > 0 => limit,
+                    _ => return bad_request(&format!("Invalid limit: {raw}")),
+                },
+            };
+            match client.suggest(&prefix, limit) {
+                Ok(suggestions) => {
+                    let suggestions = suggestions
+                        .iter()
+                        .map(|suggestion| {
+                            json!({ "word": suggestion.word, "gloss": suggestion.gloss })
+                        })
+                        .collect();
+                    (StatusCode::OK, Value::Array(suggestions), None)
+                }
+                Err(err) => failure(&err),
+            }
+        }
+        _ => {
+            let build = build_info_to_json(&build_info());
+            (
+                StatusCode::OK,
+                json!({ "status": "ok", "build": build }),
+                None,
+            )
+        }
+    }
+}
+
+/// Look `word` up with the `lang` of the request, or the client's when it
+/// has none.
+fn translate(client: &YdtClient, word: &str, lang: Option<String>, id: &str) -> Reply {
+    let options = TranslationOptions {
+        lang: lang.filter(|lang| !lang.is_empty()),
+        request_id: Some(id.to_string()),
+        ..TranslationOptions::default()
+    };
+    match client.translate_with(word, &options) {
+        Ok(translation) if translation.is_empty() => {
+            let mut body = json!({
+                "kind": "no_results",
+                "message": format!("No results for {:?}", translation.word),
+                "word": translation.word,
+            });
+            if !translation.suggestions.is_empty() {
+                body["suggestions"] = translation.suggestions.clone().into();
+            }
+            (StatusCode::NOT_FOUND, body, None)
+        }
+        Ok(translation) => (StatusCode::OK, translation_to_json(&translation), None),
+        Err(err) => failure(&err),
+    }
+}
+
+fn failure(err: &YdtError) -> Reply {
+    let status = if matches!(
+        err,
+        YdtError::EmptyWord | YdtError::WordTooLong { .. } | YdtError::UnsupportedCharacter { .. }
+    ) {
+        StatusCode::BAD_REQUEST
+    } else if err.is_not_found() {
+        StatusCode::NOT_FOUND
+    } else if is_rate_limited(err) {
+        StatusCode::TOO_MANY_REQUESTS
+    } else {
+        StatusCode::BAD_GATEWAY
+    };
+    let retry_after = retry_after(err).map(whole_secs);
+    (status, error_json(err), retry_after)
+}
+
+/// Whether Youdao rate-limited the lookup, in any of its sources.
+fn is_rate_limited(err: &YdtError) -> bool {
+    match err {
+        YdtError::AllSourcesFailed { errors, .. } => {
+            errors.iter().any(|(_, err)| is_rate_limited(err))
+        }
+        _ => err.status_code() == Some(StatusCode::TOO_MANY_REQUESTS),
+    }
+}
+
+/// The wait Youdao asked for when it rate-limited the lookup.
+fn retry_after(err: &YdtError) -> Option<Duration> {
+    match err {
+        YdtError::RateLimited { retry_after, .. } => *retry_after,
+        YdtError::AllSourcesFailed { errors, .. } => {
+            errors.iter().find_map(|(_, err)| retry_after(err))
+        }
+        _ => None,
+    }
+}
+
+fn bad_request(message: &str) -> Reply {
+    error(StatusCode::BAD_REQUEST, "invalid_argument", message)
+}
+
+fn error(status: StatusCode, kind: &str, message: &str) -> Reply {
+    (status, json!({ "kind": kind, "message": message }), None)
+}
@@ -0,0 +1,50 @@
+//! Pluggable places to look words up.
+
+use crate::{Translation, YdtError};
+
+/// A place [`YdtClient`](crate::YdtClient) can look words up, such as a local
+/// dictionary.
+///
+/// The client tries its sources in order until one finds results; see
+/// [`YdtClientBuilder::source`](crate::YdtClientBuilder::source).
+///
+/// # Examples
+///
+/// ```
+/// use ydt::{Entry, EntrySource, Translation, TranslationSource, YdtError};
+///
+/// struct Glossary;
+///
+/// impl TranslationSource for Glossary {
+///     fn name(&self) -> &str {
+///         "glossary"
+///     }
+///
+///     fn lookup(&self, word: &str, _lang: &str) -> Result<Translation, YdtError> {
+///         let mut translation = Translation { word: word.to_string(), ..Translation::default() };
+///         if word == "ydt" {
+///             translation.entries.push(Entry {
+///                 pos: "n.".to_string(),
+///                 meaning: "有道词典".to_string(),
+///                 source: EntrySource::Concise,
+///             });
+///         }
+///         Ok(translation)
+///     }
+/// }
+///
+/// # #[cfg(feature = "http")]
+/// let client = ydt::YdtClient::builder().source(Glossary).build();
+/// ```
+pub trait TranslationSource: Send + Sync {
+    /// Short name identifying the source in logs and in
+    /// [`YdtError::AllSourcesFailed`].
+    fn name(&self) -> &str;
+
+    /// Look up the normalized `word`, with `lang` being the client's `lang`
+    /// setting.
+    ///
+    /// Return an empty [`Translation`] when the word is not found, so that the
+    /// next source is tried.
+    fn lookup(&self, word: &str, lang: &str) -> Result<Translation, YdtError>;
+}
@@ -0,0 +1,71 @@
+//! Completions from Youdao's suggestion endpoint.
+
+use serde_json::Value;
+use url::Url;
+
+use crate::YdtError;
+
+/// Youdao suggestion endpoint used unless configured otherwise.
+pub const DEFAULT_SUGGEST_URL: &str = "https://dict.youdao.com/suggest";
+
+/// A completion for a prefix, with a short gloss.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The completed word.
+    pub word: String,
+    /// Brief definition shown next to the word; empty when Youdao gives none.
+    pub gloss: String,
+}
+
+pub(crate) fn suggest_url(
+    base_url: &str,
+    prefix: &str,
+    limit: usize,
+    lang: &str,
+) -> Result<Url, YdtError> {
+    Url::parse_with_params(
+        base_url,
+        &[
+            ("q", prefix),
+            ("num", &limit.to_string()),
+            ("doctype", "json"),
+            ("le", lang),
+        ],
+    )
+    .map_err(YdtError::BuildRequestUrl)
+}
+
+/// Parse the JSON returned by the suggestion endpoint.
+///
+/// A payload without `data.entries` has no suggestions; anything else that does
+/// not have the expected shape is reported as
+/// [`YdtError::InvalidSuggestPayload`].
+pub(crate) fn parse_suggestions(body: &str) -> Result<Vec<Suggestion>, YdtError> {
+    fn invalid(message: impl Into<String>) -> YdtError {
+        YdtError::InvalidSuggestPayload(message.into())
+    }
+
+    let payload: Value = serde_json::from_str(body).map_err(|err| invalid(err.to_string()))?;
+    if !payload.is_object() {
+        return Err(invalid("expected a JSON object"));
+    }
+    let entries = match payload.get("data").and_then(|data| data.get("entries")) {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(entries)) => entries,
+        Some(_) => return Err(invalid("`data.entries` is not an array")),
+    };
+    entries
+        .iter()
+        .map(|entry| {
+            let word = entry
+                .get("entry")
+                .and_then(Value::as_str)
+                .ok_or_else(|| invalid("suggestion without an `entry` string"))?;
+            let gloss = entry.get("explain").and_then(Value::as_str).unwrap_or("");
+            Ok(Suggestion {
+                word: word.trim().to_string(),
+                gloss: gloss.trim().to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,78 @@
+//! Golden tests over recorded result pages, for parser regressions.
+//!
+//! Pages saved with `ydt --record <DIR>` are named after the word looked up;
+//! next to each `<word>.html`, a `<word>.txt` holds the expected output of
+//! [`parse_translation_from_html`](crate::parse_translation_from_html).
+//! [`check_recorded_pages`] compares the two, and with the [`BLESS_VAR`]
+//! environment variable set writes the current output as the expected one
+//! instead:
+//!
+//! ```sh
+//! YDT_BLESS=1 cargo test --test recorded
+//! ```
+//!
+//! Needs the `test-util` feature.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::parse_translation_from_html;
+
+/// Environment variable that makes [`check_recorded_pages`] write the expected
+/// output instead of checking it.
+pub const BLESS_VAR: &str = "YDT_BLESS";
+
+/// Check every `<word>.html` page in `dir` against its `<word>.txt`, and
+/// return the number of pages checked.
+///
+/// Pages that fail to parse are expected to produce `error: <message>`.
+///
+/// # Panics
+///
+/// Panics listing every page whose output differs or has no expected output,
+/// and when `dir` cannot be read.
+pub fn check_recorded_pages(dir: impl AsRef<Path>) -> usize {
+    let dir = dir.as_ref();
+    let bless = env::var_os(BLESS_VAR).is_some_and(|value| !value.is_empty());
+    let mut pages: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .collect();
+    pages.sort();
+
+    let mut failures = Vec::new();
+    for page in &pages {
+        let word = page.file_stem().unwrap().to_string_lossy();
+        let html = fs::read_to_string(page)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", page.display()));
+        let actual = match parse_translation_from_html(&word, &html) {
+            Ok(rendered) => rendered,
+            Err(err) => format!("error: {err}"),
+        };
+        let golden = page.with_extension("txt");
+        if bless {
+            fs::write(&golden, format!("{actual}\n"))
+                .unwrap_or_else(|err| panic!("failed to write {}: {err}", golden.display()));
+            continue;
+        }
+        match fs::read_to_string(&golden) {
+            Ok(expected) if expected.trim_end() == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{}:\n--- expected\n{}\n--- actual\n{actual}",
+                page.display(),
+                expected.trim_end()
+            )),
+            Err(_) => failures.push(format!("{}: missing {}", page.display(), golden.display())),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} recorded pages changed; rerun with {BLESS_VAR}=1 to accept the new output\n\n{}",
+        failures.len(),
+        pages.len(),
+        failures.join("\n\n")
+    );
+    pages.len()
+}
@@ -0,0 +1,118 @@
+//! Normalization of text scraped from result pages, so that the same
+//! definition always comes out the same way.
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+/// Characters that are not seen and are dropped: zero-width spaces and
+/// joiners left between words by the page's markup, soft hyphens and bidi
+/// controls, as copied from PDFs.
+const INVISIBLE: &[char] = &[
+    '\u{ad}', '\u{61c}', '\u{200b}', '\u{200c}', '\u{200d}', '\u{200e}', '\u{200f}', '\u{202a}',
+    '\u{202b}', '\u{202c}', '\u{202d}', '\u{202e}', '\u{2060}', '\u{2066}', '\u{2067}', '\u{2068}',
+    '\u{2069}', '\u{feff}',
+];
+
+/// Whether `ch` is a character [`normalize`] drops, such as a zero-width
+/// space, soft hyphen or bidi control.
+pub(crate) fn is_invisible(ch: char) -> bool {
+    INVISIBLE.contains(&ch)
+}
+
+/// Named entities decoded when they survive in text, usually because the
+/// page escaped them twice.
+const ENTITIES: &[(&str, char)] = &[
+    ("nbsp", '\u{a0}'),
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+];
+
+/// Longest entity decoded, `&#x10FFFF;` without the `&`.
+const MAX_ENTITY_LEN: usize = 9;
+
+/// Normalize text scraped from a page.
+///
+/// Leftover HTML entities such as `&nbsp;` and `&#160;` are decoded, the text
+/// is put in Unicode normalization form C, invisible characters such as
+/// zero-width spaces, soft hyphens and bidi controls are dropped and runs of
+/// whitespace, including non-breaking and full-width
+/// spaces, become a single space. Surrounding whitespace is trimmed.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::text::normalize(" 你好&nbsp;\n  喂\u{200b} "), "你好 喂");
+/// assert_eq!(ydt::text::normalize("cafe\u{301}"), "café");
+/// ```
+pub fn normalize(text: &str) -> String {
+    let decoded = decode_entities(text);
+    let composed = match is_nfc_quick(decoded.chars()) {
+        IsNormalized::Yes => decoded,
+        _ => decoded.nfc().collect(),
+    };
+    let mut normalized = String::with_capacity(composed.len());
+    let mut space = false;
+    let mut dropped = 0;
+    for ch in composed.chars() {
+        if is_invisible(ch) {
+            dropped += 1;
+            continue;
+        }
+        if ch.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space && !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        space = false;
+        normalized.push(ch);
+    }
+    if dropped > 0 {
+        log::info!("dropped {dropped} invisible characters from {normalized:?}");
+    }
+    normalized
+}
+
+/// Decode the entities in `text`, leaving those not recognized as they are.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let entity = rest
+            .char_indices()
+            .take(MAX_ENTITY_LEN + 1)
+            .find(|&(_, ch)| ch == ';')
+            .and_then(|(end, _)| Some((end, entity_char(&rest[..end])?)));
+        match entity {
+            Some((end, ch)) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => decoded.push('&'),
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The character named by `name`, an entity without its `&` and `;`.
+fn entity_char(name: &str) -> Option<char> {
+    let code = match name.strip_prefix('#') {
+        Some(number) => match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        },
+        None => {
+            return ENTITIES
+                .iter()
+                .find(|(entity, _)| *entity == name)
+                .map(|&(_, ch)| ch)
+        }
+    };
+    char::from_u32(code)
+}
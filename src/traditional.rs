@@ -0,0 +1,590 @@
+//! Conversion between Traditional and Simplified Chinese characters, so that
+//! words copied from Traditional texts, such as 學習, are looked up as 学习.
+//!
+//! Only common characters are covered, one at a time: characters missing from
+//! the table, including those written the same in both scripts, are kept.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Traditional characters and their Simplified forms.
+///
+/// Where several Traditional characters share a Simplified form, such as 發
+/// and 髮 for 发, the first one listed is used to convert back.
+const TABLE: &[(char, char)] = &[
+    ('學', '学'),
+    ('習', '习'),
+    ('語', '语'),
+    ('國', '国'),
+    ('們', '们'),
+    ('個', '个'),
+    ('來', '来'),
+    ('時', '时'),
+    ('說', '说'),
+    ('會', '会'),
+    ('對', '对'),
+    ('這', '这'),
+    ('過', '过'),
+    ('後', '后'),
+    ('開', '开'),
+    ('關', '关'),
+    ('長', '长'),
+    ('發', '发'),
+    ('問', '问'),
+    ('間', '间'),
+    ('門', '门'),
+    ('見', '见'),
+    ('現', '现'),
+    ('為', '为'),
+    ('與', '与'),
+    ('當', '当'),
+    ('從', '从'),
+    ('動', '动'),
+    ('經', '经'),
+    ('書', '书'),
+    ('買', '买'),
+    ('賣', '卖'),
+    ('東', '东'),
+    ('車', '车'),
+    ('馬', '马'),
+    ('鳥', '鸟'),
+    ('魚', '鱼'),
+    ('龍', '龙'),
+    ('風', '风'),
+    ('飛', '飞'),
+    ('電', '电'),
+    ('話', '话'),
+    ('讀', '读'),
+    ('寫', '写'),
+    ('聽', '听'),
+    ('氣', '气'),
+    ('愛', '爱'),
+    ('歡', '欢'),
+    ('樂', '乐'),
+    ('難', '难'),
+    ('興', '兴'),
+    ('實', '实'),
+    ('體', '体'),
+    ('頭', '头'),
+    ('臉', '脸'),
+    ('覺', '觉'),
+    ('認', '认'),
+    ('識', '识'),
+    ('記', '记'),
+    ('許', '许'),
+    ('謝', '谢'),
+    ('請', '请'),
+    ('讓', '让'),
+    ('還', '还'),
+    ('進', '进'),
+    ('運', '运'),
+    ('達', '达'),
+    ('遠', '远'),
+    ('邊', '边'),
+    ('義', '义'),
+    ('業', '业'),
+    ('機', '机'),
+    ('樹', '树'),
+    ('權', '权'),
+    ('歷', '历'),
+    ('區', '区'),
+    ('醫', '医'),
+    ('華', '华'),
+    ('萬', '万'),
+    ('億', '亿'),
+    ('價', '价'),
+    ('錢', '钱'),
+    ('銀', '银'),
+    ('鐵', '铁'),
+    ('鐘', '钟'),
+    ('錯', '错'),
+    ('陽', '阳'),
+    ('陰', '阴'),
+    ('際', '际'),
+    ('隊', '队'),
+    ('雙', '双'),
+    ('雖', '虽'),
+    ('雞', '鸡'),
+    ('離', '离'),
+    ('雲', '云'),
+    ('靜', '静'),
+    ('題', '题'),
+    ('類', '类'),
+    ('顏', '颜'),
+    ('願', '愿'),
+    ('館', '馆'),
+    ('飯', '饭'),
+    ('餓', '饿'),
+    ('點', '点'),
+    ('黨', '党'),
+    ('齊', '齐'),
+    ('歲', '岁'),
+    ('歸', '归'),
+    ('殺', '杀'),
+    ('漢', '汉'),
+    ('湯', '汤'),
+    ('滿', '满'),
+    ('濕', '湿'),
+    ('灣', '湾'),
+    ('無', '无'),
+    ('燈', '灯'),
+    ('爺', '爷'),
+    ('獨', '独'),
+    ('環', '环'),
+    ('產', '产'),
+    ('畫', '画'),
+    ('療', '疗'),
+    ('盡', '尽'),
+    ('監', '监'),
+    ('眾', '众'),
+    ('礎', '础'),
+    ('確', '确'),
+    ('禮', '礼'),
+    ('禍', '祸'),
+    ('種', '种'),
+    ('穩', '稳'),
+    ('窮', '穷'),
+    ('筆', '笔'),
+    ('節', '节'),
+    ('範', '范'),
+    ('簡', '简'),
+    ('糧', '粮'),
+    ('紀', '纪'),
+    ('約', '约'),
+    ('紅', '红'),
+    ('級', '级'),
+    ('紙', '纸'),
+    ('細', '细'),
+    ('組', '组'),
+    ('結', '结'),
+    ('絕', '绝'),
+    ('給', '给'),
+    ('統', '统'),
+    ('絲', '丝'),
+    ('綠', '绿'),
+    ('網', '网'),
+    ('線', '线'),
+    ('練', '练'),
+    ('總', '总'),
+    ('績', '绩'),
+    ('續', '续'),
+    ('罰', '罚'),
+    ('聯', '联'),
+    ('職', '职'),
+    ('腦', '脑'),
+    ('腳', '脚'),
+    ('舊', '旧'),
+    ('藝', '艺'),
+    ('藥', '药'),
+    ('蘭', '兰'),
+    ('處', '处'),
+    ('號', '号'),
+    ('蟲', '虫'),
+    ('補', '补'),
+    ('裝', '装'),
+    ('製', '制'),
+    ('複', '复'),
+    ('規', '规'),
+    ('視', '视'),
+    ('親', '亲'),
+    ('觀', '观'),
+    ('計', '计'),
+    ('訂', '订'),
+    ('討', '讨'),
+    ('訓', '训'),
+    ('設', '设'),
+    ('訪', '访'),
+    ('證', '证'),
+    ('評', '评'),
+    ('試', '试'),
+    ('詩', '诗'),
+    ('該', '该'),
+    ('詳', '详'),
+    ('誤', '误'),
+    ('課', '课'),
+    ('調', '调'),
+    ('談', '谈'),
+    ('論', '论'),
+    ('諸', '诸'),
+    ('講', '讲'),
+    ('議', '议'),
+    ('護', '护'),
+    ('變', '变'),
+    ('讚', '赞'),
+    ('豐', '丰'),
+    ('貝', '贝'),
+    ('負', '负'),
+    ('財', '财'),
+    ('貨', '货'),
+    ('質', '质'),
+    ('貴', '贵'),
+    ('費', '费'),
+    ('貿', '贸'),
+    ('資', '资'),
+    ('賓', '宾'),
+    ('趕', '赶'),
+    ('趨', '趋'),
+    ('踐', '践'),
+    ('軍', '军'),
+    ('輕', '轻'),
+    ('較', '较'),
+    ('輛', '辆'),
+    ('輸', '输'),
+    ('轉', '转'),
+    ('辦', '办'),
+    ('農', '农'),
+    ('連', '连'),
+    ('週', '周'),
+    ('遊', '游'),
+    ('違', '违'),
+    ('遲', '迟'),
+    ('選', '选'),
+    ('遺', '遗'),
+    ('郵', '邮'),
+    ('鄉', '乡'),
+    ('鄰', '邻'),
+    ('釋', '释'),
+    ('針', '针'),
+    ('鋼', '钢'),
+    ('錄', '录'),
+    ('鍵', '键'),
+    ('閉', '闭'),
+    ('閱', '阅'),
+    ('隨', '随'),
+    ('險', '险'),
+    ('隱', '隐'),
+    ('雜', '杂'),
+    ('響', '响'),
+    ('頁', '页'),
+    ('頂', '顶'),
+    ('項', '项'),
+    ('順', '顺'),
+    ('須', '须'),
+    ('預', '预'),
+    ('領', '领'),
+    ('頻', '频'),
+    ('顧', '顾'),
+    ('顯', '显'),
+    ('飲', '饮'),
+    ('養', '养'),
+    ('驗', '验'),
+    ('驚', '惊'),
+    ('鬆', '松'),
+    ('麵', '面'),
+    ('麼', '么'),
+    ('黃', '黄'),
+    ('齒', '齿'),
+    ('龜', '龟'),
+    ('劃', '划'),
+    ('劇', '剧'),
+    ('務', '务'),
+    ('勞', '劳'),
+    ('勢', '势'),
+    ('勵', '励'),
+    ('協', '协'),
+    ('卻', '却'),
+    ('厲', '厉'),
+    ('參', '参'),
+    ('叢', '丛'),
+    ('嗎', '吗'),
+    ('圖', '图'),
+    ('團', '团'),
+    ('圍', '围'),
+    ('園', '园'),
+    ('圓', '圆'),
+    ('壓', '压'),
+    ('壞', '坏'),
+    ('報', '报'),
+    ('場', '场'),
+    ('塊', '块'),
+    ('壯', '壮'),
+    ('壽', '寿'),
+    ('夢', '梦'),
+    ('夠', '够'),
+    ('奪', '夺'),
+    ('奮', '奋'),
+    ('婦', '妇'),
+    ('嬰', '婴'),
+    ('寧', '宁'),
+    ('寶', '宝'),
+    ('將', '将'),
+    ('導', '导'),
+    ('尋', '寻'),
+    ('層', '层'),
+    ('屬', '属'),
+    ('島', '岛'),
+    ('嶺', '岭'),
+    ('幣', '币'),
+    ('師', '师'),
+    ('帶', '带'),
+    ('幫', '帮'),
+    ('幹', '干'),
+    ('廣', '广'),
+    ('廳', '厅'),
+    ('張', '张'),
+    ('強', '强'),
+    ('彈', '弹'),
+    ('徑', '径'),
+    ('復', '复'),
+    ('徵', '征'),
+    ('態', '态'),
+    ('憂', '忧'),
+    ('憶', '忆'),
+    ('應', '应'),
+    ('懷', '怀'),
+    ('戰', '战'),
+    ('戲', '戏'),
+    ('擁', '拥'),
+    ('據', '据'),
+    ('擇', '择'),
+    ('擊', '击'),
+    ('擔', '担'),
+    ('擴', '扩'),
+    ('攝', '摄'),
+    ('數', '数'),
+    ('斷', '断'),
+    ('於', '于'),
+    ('暫', '暂'),
+    ('曆', '历'),
+    ('條', '条'),
+    ('楊', '杨'),
+    ('極', '极'),
+    ('構', '构'),
+    ('標', '标'),
+    ('槍', '枪'),
+    ('樣', '样'),
+    ('橋', '桥'),
+    ('檢', '检'),
+    ('櫃', '柜'),
+    ('歐', '欧'),
+    ('歎', '叹'),
+    ('沒', '没'),
+    ('決', '决'),
+    ('況', '况'),
+    ('淚', '泪'),
+    ('淨', '净'),
+    ('測', '测'),
+    ('溫', '温'),
+    ('準', '准'),
+    ('漁', '渔'),
+    ('潔', '洁'),
+    ('澤', '泽'),
+    ('濟', '济'),
+    ('灑', '洒'),
+    ('災', '灾'),
+    ('烏', '乌'),
+    ('煙', '烟'),
+    ('熱', '热'),
+    ('爭', '争'),
+    ('狀', '状'),
+    ('獎', '奖'),
+    ('獲', '获'),
+    ('瑪', '玛'),
+    ('員', '员'),
+    ('貓', '猫'),
+    ('麗', '丽'),
+    ('傳', '传'),
+    ('傷', '伤'),
+    ('僅', '仅'),
+    ('優', '优'),
+    ('備', '备'),
+    ('債', '债'),
+    ('傘', '伞'),
+    ('儲', '储'),
+    ('兒', '儿'),
+    ('內', '内'),
+    ('兩', '两'),
+    ('冊', '册'),
+    ('凍', '冻'),
+    ('則', '则'),
+    ('剛', '刚'),
+    ('創', '创'),
+    ('劍', '剑'),
+    ('勝', '胜'),
+    ('勳', '勋'),
+    ('匯', '汇'),
+    ('單', '单'),
+    ('賽', '赛'),
+    ('廠', '厂'),
+    ('廟', '庙'),
+    ('廢', '废'),
+    ('彎', '弯'),
+    ('戶', '户'),
+    ('掃', '扫'),
+    ('掛', '挂'),
+    ('採', '采'),
+    ('換', '换'),
+    ('揮', '挥'),
+    ('損', '损'),
+    ('搖', '摇'),
+    ('擺', '摆'),
+    ('擠', '挤'),
+    ('攜', '携'),
+    ('敵', '敌'),
+    ('斂', '敛'),
+    ('晝', '昼'),
+    ('曉', '晓'),
+    ('術', '术'),
+    ('殘', '残'),
+    ('毀', '毁'),
+    ('氫', '氢'),
+    ('湧', '涌'),
+    ('滅', '灭'),
+    ('滬', '沪'),
+    ('漲', '涨'),
+    ('潛', '潜'),
+    ('濃', '浓'),
+    ('燒', '烧'),
+    ('燭', '烛'),
+    ('爐', '炉'),
+    ('牆', '墙'),
+    ('猶', '犹'),
+    ('獅', '狮'),
+    ('瓊', '琼'),
+    ('畢', '毕'),
+    ('異', '异'),
+    ('癒', '愈'),
+    ('盤', '盘'),
+    ('碼', '码'),
+    ('礦', '矿'),
+    ('祕', '秘'),
+    ('禦', '御'),
+    ('穀', '谷'),
+    ('競', '竞'),
+    ('築', '筑'),
+    ('簽', '签'),
+    ('籃', '篮'),
+    ('紋', '纹'),
+    ('純', '纯'),
+    ('紛', '纷'),
+    ('紮', '扎'),
+    ('終', '终'),
+    ('綜', '综'),
+    ('緊', '紧'),
+    ('緒', '绪'),
+    ('緣', '缘'),
+    ('編', '编'),
+    ('縣', '县'),
+    ('織', '织'),
+    ('繩', '绳'),
+    ('繪', '绘'),
+    ('繼', '继'),
+    ('罷', '罢'),
+    ('羅', '罗'),
+    ('聖', '圣'),
+    ('聞', '闻'),
+    ('聲', '声'),
+    ('肅', '肃'),
+    ('腸', '肠'),
+    ('膽', '胆'),
+    ('臨', '临'),
+    ('艦', '舰'),
+    ('蘇', '苏'),
+    ('蘋', '苹'),
+    ('葉', '叶'),
+    ('蓋', '盖'),
+    ('蔥', '葱'),
+    ('虛', '虚'),
+    ('衛', '卫'),
+    ('衝', '冲'),
+    ('襪', '袜'),
+    ('訊', '讯'),
+    ('託', '托'),
+    ('訴', '诉'),
+    ('詞', '词'),
+    ('誇', '夸'),
+    ('誠', '诚'),
+    ('誰', '谁'),
+    ('諾', '诺'),
+    ('謎', '谜'),
+    ('譯', '译'),
+    ('貧', '贫'),
+    ('購', '购'),
+    ('賴', '赖'),
+    ('贏', '赢'),
+    ('趙', '赵'),
+    ('躍', '跃'),
+    ('軟', '软'),
+    ('輩', '辈'),
+    ('輪', '轮'),
+    ('辭', '辞'),
+    ('鄭', '郑'),
+    ('醜', '丑'),
+    ('醬', '酱'),
+    ('鈔', '钞'),
+    ('鈴', '铃'),
+    ('銷', '销'),
+    ('鋪', '铺'),
+    ('鍋', '锅'),
+    ('鎖', '锁'),
+    ('鏡', '镜'),
+    ('閃', '闪'),
+    ('閒', '闲'),
+    ('闖', '闯'),
+    ('陳', '陈'),
+    ('陸', '陆'),
+    ('隻', '只'),
+    ('霧', '雾'),
+    ('靈', '灵'),
+    ('韓', '韩'),
+    ('頓', '顿'),
+    ('顆', '颗'),
+    ('颱', '台'),
+    ('餅', '饼'),
+    ('餘', '余'),
+    ('餵', '喂'),
+    ('驅', '驱'),
+    ('騎', '骑'),
+    ('騙', '骗'),
+    ('髮', '发'),
+    ('鬥', '斗'),
+    ('鬧', '闹'),
+    ('魯', '鲁'),
+    ('鮮', '鲜'),
+    ('鴨', '鸭'),
+    ('鵝', '鹅'),
+    ('鹽', '盐'),
+    ('麥', '麦'),
+    ('齡', '龄'),
+];
+
+static TO_SIMPLIFIED: OnceLock<HashMap<char, char>> = OnceLock::new();
+static TO_TRADITIONAL: OnceLock<HashMap<char, char>> = OnceLock::new();
+
+/// `text` with its Traditional characters replaced by Simplified ones.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::to_simplified("學習"), "学习");
+/// // Characters are converted one at a time, so mixed input works too.
+/// assert_eq!(ydt::to_simplified("学習 C語言"), "学习 C语言");
+/// ```
+pub fn to_simplified(text: &str) -> String {
+    let table = TO_SIMPLIFIED.get_or_init(|| TABLE.iter().copied().collect());
+    convert(text, table)
+}
+
+/// `text` with its Simplified characters replaced by Traditional ones.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ydt::to_traditional("学习"), "學習");
+/// ```
+pub fn to_traditional(text: &str) -> String {
+    let table = TO_TRADITIONAL.get_or_init(|| {
+        let mut table = HashMap::new();
+        for &(traditional, simplified) in TABLE {
+            table.entry(simplified).or_insert(traditional);
+        }
+        table
+    });
+    convert(text, table)
+}
+
+fn convert(text: &str, table: &HashMap<char, char>) -> String {
+    text.chars()
+        .map(|ch| table.get(&ch).copied().unwrap_or(ch))
+        .collect()
+}
@@ -0,0 +1,1002 @@
+//! Structured translation results.
+
+use std::fmt;
+use std::str::FromStr;
+
+use url::Url;
+
+/// Pronunciation variant of a phonetic transcription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Accent {
+    /// British English (`英` or `UK`).
+    Uk,
+    /// American English (`美` or `US`).
+    Us,
+}
+
+impl Accent {
+    /// Map a page label such as `英` or `美` to an accent.
+    ///
+    /// Pages served in English label accents `UK` and `US` instead; these are
+    /// recognized too, ignoring ASCII case.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim() {
+            "英" => Some(Self::Uk),
+            "美" => Some(Self::Us),
+            label if label.eq_ignore_ascii_case("uk") => Some(Self::Uk),
+            label if label.eq_ignore_ascii_case("us") => Some(Self::Us),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Accent {
+    type Err = String;
+
+    /// Parse `uk` or `us`, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "uk" => Ok(Self::Uk),
+            "us" => Ok(Self::Us),
+            _ => Err(format!("unknown accent `{s}`, expected `uk` or `us`")),
+        }
+    }
+}
+
+impl fmt::Display for Accent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uk => f.write_str("uk"),
+            Self::Us => f.write_str("us"),
+        }
+    }
+}
+
+/// A phonetic transcription with its accent label, e.g. `英 /həˈləʊ/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Phonetic {
+    /// Accent label as shown on the page (`英`, `美`, ...).
+    pub label: String,
+    /// Transcription text, including the surrounding slashes.
+    pub text: String,
+}
+
+impl Phonetic {
+    /// The accent this transcription belongs to, if its label is recognized.
+    pub fn accent(&self) -> Option<Accent> {
+        Accent::from_label(&self.label)
+    }
+
+    /// This transcription in ASCII, for terminals that cannot show IPA or
+    /// Chinese: the text is transliterated with [`ipa_to_ascii`] and the
+    /// `英`/`美` labels become `UK`/`US`.
+    ///
+    /// [`ipa_to_ascii`]: crate::ipa_to_ascii
+    pub fn to_ascii(&self) -> Self {
+        let label = match self.accent() {
+            Some(accent) => accent.to_string().to_ascii_uppercase(),
+            None => self.label.chars().filter(char::is_ascii).collect(),
+        };
+        Self {
+            label,
+            text: crate::ipa_to_ascii(&self.text),
+        }
+    }
+}
+
+/// A single definition line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// Part of speech (`n.`, `int.`, ...). Empty for Chinese-English candidates.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub pos: String,
+    /// Definition text.
+    pub meaning: String,
+    /// Where the definition comes from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub source: EntrySource,
+}
+
+/// Where a definition comes from, when several places can contribute to a
+/// lookup; see [`Entry::source`].
+///
+/// Definitions of a result page are [`Concise`](Self::Concise); the others
+/// come from the fallbacks a client was configured with. Sources added with
+/// [`YdtClientBuilder::source`](crate::YdtClientBuilder::source) tag their
+/// definitions as they see fit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EntrySource {
+    /// The concise definitions of the desktop result page.
+    #[default]
+    Concise,
+    /// Translations Youdao collected from the web (网络释义).
+    WebTrans,
+    /// The Collins dictionary section.
+    Collins,
+    /// The mobile site, see
+    /// [`YdtClientBuilder::mobile_fallback`](crate::YdtClientBuilder::mobile_fallback).
+    Mobile,
+    /// A local CC-CEDICT file.
+    Cedict,
+    /// The official Youdao API.
+    OpenApi,
+}
+
+impl fmt::Display for EntrySource {
+    /// The snake case name, as in JSON output, e.g. `web_trans`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Concise => "concise",
+            Self::WebTrans => "web_trans",
+            Self::Collins => "collins",
+            Self::Mobile => "mobile",
+            Self::Cedict => "cedict",
+            Self::OpenApi => "open_api",
+        })
+    }
+}
+
+/// A numbered sense of the Collins dictionary section, parsed when
+/// [`TranslationOptions::include_collins`](crate::TranslationOptions::include_collins)
+/// is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollinsSense {
+    /// Sense number as shown on the page, counting from 1.
+    pub number: usize,
+    /// Grammatical label, e.g. `VERB` or `N-COUNT`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub pos: String,
+    /// English definition.
+    pub en_def: String,
+    /// Chinese definition.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub zh_def: String,
+    /// Example sentences, in page order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub examples: Vec<Example>,
+}
+
+/// An example sentence with its translation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Example {
+    /// The English sentence.
+    pub en: String,
+    /// Its Chinese translation.
+    pub zh: String,
+}
+
+/// A Chinese word offered for a query typed as pinyin; see [`Layout::Pinyin`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate {
+    /// The word in Chinese characters, e.g. `学习`.
+    pub hanzi: String,
+    /// Its pinyin with tone marks, e.g. `xué xí`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub pinyin: String,
+    /// Its English gloss.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub gloss: String,
+}
+
+/// Explanation of a Chinese idiom (成语), such as 画蛇添足.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Idiom {
+    /// Pinyin of the idiom, e.g. `huà shé tiān zú`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub pinyin: String,
+    /// What the idiom means (释义).
+    pub explanation: String,
+    /// Where it comes from (出处).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub source: String,
+    /// An example sentence (例句).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub example: String,
+}
+
+/// Related words (同根词) sharing a part of speech, parsed when
+/// [`TranslationOptions::include_related`](crate::TranslationOptions::include_related)
+/// is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelatedGroup {
+    /// Part of speech, e.g. `n.`.
+    pub pos: String,
+    /// The words, in page order.
+    pub words: Vec<RelatedWord>,
+}
+
+/// A word derived from the same root as the word looked up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelatedWord {
+    /// The word, e.g. `creation`.
+    pub word: String,
+    /// Its short Chinese gloss; empty when the page gives none.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub gloss: String,
+}
+
+/// Result page layout a translation was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Layout {
+    /// English-Chinese dictionary entry with phonetics and definitions.
+    EnglishChinese,
+    /// Chinese-English list of candidate English words.
+    ChineseEnglish,
+    /// Candidate Chinese words for a query typed as pinyin, such as `xuexi`.
+    Pinyin,
+}
+
+/// Direction a translation reads in, set by whichever way of reading the
+/// result actually matched; see [`Translation::direction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Direction {
+    /// English word explained in Chinese.
+    EnToZh,
+    /// Chinese word translated to English.
+    ZhToEn,
+    /// Query typed as pinyin, answered with candidate Chinese words.
+    Pinyin,
+    /// Machine translation of a whole sentence or phrase.
+    Sentence,
+}
+
+impl From<Layout> for Direction {
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::EnglishChinese => Self::EnToZh,
+            Layout::ChineseEnglish => Self::ZhToEn,
+            Layout::Pinyin => Self::Pinyin,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    /// Short form shown in verbose output, e.g. `en→zh`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::EnToZh => "en→zh",
+            Self::ZhToEn => "zh→en",
+            Self::Pinyin => "pinyin",
+            Self::Sentence => "sentence",
+        })
+    }
+}
+
+/// How much of a result page a translation holds; see
+/// [`Translation::result_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ResultKind {
+    /// Dictionary results: phonetics, definitions, pinyin candidates,
+    /// conversions or an idiom explanation.
+    Full,
+    /// No dictionary results, only [web
+    /// translations](Translation::web_translations).
+    WebOnly,
+    /// Nothing but [suggestions](Translation::suggestions) for a misspelled
+    /// word.
+    SuggestionsOnly,
+    /// Nothing at all.
+    Empty,
+}
+
+/// Parsed result of a single lookup.
+///
+/// With the `serde` feature, this and the types it contains implement
+/// `Serialize` and `Deserialize`. Fields keep their names, enum values are
+/// written in snake case (`english_chinese`), and empty lists, unset options and
+/// empty parts of speech are left out:
+///
+/// ```json
+/// {
+///   "word": "hello",
+///   "phonetics": [{ "label": "英", "text": "/həˈləʊ/" }],
+///   "entries": [{ "pos": "int.", "meaning": "你好；喂", "source": "concise" }],
+///   "layout": "english_chinese"
+/// }
+/// ```
+///
+/// # Examples
+///
+/// Translations compare equal field by field, so parsed pages can be checked
+/// whole:
+///
+/// ```
+/// use ydt::{Direction, Entry, EntrySource, Layout, Phonetic, Translation};
+///
+/// let html = r#"
+/// <div class="trans-container">
+///   <div class="per-phone"><span>英</span><span class="phonetic">/həˈləʊ/</span></div>
+/// </div>
+/// <div class="trans-container">
+///   <li class="word-exp"><span class="pos">int.</span><span class="trans">你好</span></li>
+/// </div>
+/// "#;
+/// assert_eq!(
+///     ydt::parse_translation("hello", html).unwrap(),
+///     Translation {
+///         word: "hello".to_string(),
+///         phonetics: vec![Phonetic {
+///             label: "英".to_string(),
+///             text: "/həˈləʊ/".to_string(),
+///         }],
+///         entries: vec![Entry {
+///             pos: "int.".to_string(),
+///             meaning: "你好".to_string(),
+///             source: EntrySource::Concise,
+///         }],
+///         layout: Some(Layout::EnglishChinese),
+///         direction: Some(Direction::EnToZh),
+///         ..Translation::default()
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Translation {
+    /// The queried word.
+    pub word: String,
+    /// Phonetic transcriptions, in page order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub phonetics: Vec<Phonetic>,
+    /// Definitions, in page order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub entries: Vec<Entry>,
+    /// Layout the results were found in; `None` when nothing was found.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub layout: Option<Layout>,
+    /// Direction the results read in; `None` when nothing was found.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub direction: Option<Direction>,
+    /// Headword the page shows results for, e.g. `run` when looking up `running`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub headword: Option<String>,
+    /// How the queried word relates to a different headword, e.g. `现在分词`
+    /// (present participle). Only set when the headword differs from the word.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub lemma_note: Option<String>,
+    /// How common the word is, from 1 to 5 stars as rated on the page; `None`
+    /// when the page shows no rating.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub frequency: Option<u8>,
+    /// Results of reading the page in other directions, each with its
+    /// [`layout`](Translation::layout), with [`LookupStrategy::Both`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub other_directions: Vec<Translation>,
+    /// Chinese words offered for a query typed as pinyin, in page order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub candidates: Vec<Candidate>,
+    /// Readings of a number or conversions of a unit shown for queries such
+    /// as `42` or `km/h`, e.g. `四十二` and `forty-two`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub conversions: Vec<String>,
+    /// Translations collected from the web, in page order; shown only when
+    /// the dictionary has no entry for the word.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub web_translations: Vec<String>,
+    /// Explanation of the idiom looked up, when the page has one.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub idiom: Option<Idiom>,
+    /// Senses of the Collins dictionary section; only parsed when
+    /// [`TranslationOptions::include_collins`](crate::TranslationOptions::include_collins)
+    /// is set, as the section is large.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub collins: Vec<CollinsSense>,
+    /// Words derived from the same root, grouped by part of speech; only
+    /// parsed when
+    /// [`TranslationOptions::include_related`](crate::TranslationOptions::include_related)
+    /// is set.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub related: Vec<RelatedGroup>,
+    /// Corrections Youdao suggests for a misspelled word, in page order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub suggestions: Vec<String>,
+    /// The word as given, when the client looked up its Simplified form
+    /// instead; see [`YdtClientBuilder::convert_traditional`].
+    ///
+    /// [`YdtClientBuilder::convert_traditional`]: crate::YdtClientBuilder::convert_traditional
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub converted_from: Option<String>,
+    /// The word originally looked up, when the client replaced it with the
+    /// first suggestion; see [`YdtClientBuilder::auto_correct`].
+    ///
+    /// [`YdtClientBuilder::auto_correct`]: crate::YdtClientBuilder::auto_correct
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub corrected_from: Option<String>,
+    /// Result page the translation can be read in full on, with the `lang`
+    /// of the lookup and after any redirects; `None` when it was not looked
+    /// up on Youdao's pages, e.g. parsed from given HTML or found by a custom
+    /// source.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub source_url: Option<Url>,
+    /// The [`max_field_len`](TranslationOptions::max_field_len) of the
+    /// lookup, when a value such as a definition is longer: text renderings
+    /// truncate values to it, while the translation keeps them whole.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub display_limit: Option<usize>,
+}
+
+/// Longest value, in characters, rendered whole by default; see
+/// [`TranslationOptions::max_field_len`].
+pub const DEFAULT_MAX_FIELD_LEN: usize = 500;
+
+/// What to look up and keep of a result page beyond phonetics and
+/// definitions.
+///
+/// Every field is optional: options passed to
+/// [`YdtClient::translate_with`](crate::YdtClient::translate_with) fall back
+/// to those the client was built with, then to the default noted on each field;
+/// see [`merge`](Self::merge).
+///
+/// # Examples
+///
+/// ```
+/// let options = ydt::TranslationOptions {
+///     include_collins: Some(true),
+///     max_senses: Some(3),
+///     ..Default::default()
+/// };
+/// assert_eq!(ydt::TranslationOptions::default().include_collins, None);
+/// # let _ = options;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranslationOptions {
+    /// The `lang` query parameter of the request; the client's
+    /// [`lang`](crate::YdtClientBuilder::lang) when `None`. Not passed to
+    /// custom [fetchers](crate::Fetcher), which are only given the word.
+    pub lang: Option<String>,
+    /// Keep only the phonetics of this accent; all of them when `None`.
+    pub accent: Option<Accent>,
+    /// Parse the Collins dictionary section into [`Translation::collins`];
+    /// off by default.
+    pub include_collins: Option<bool>,
+    /// Most Collins senses kept; all of them when `None`.
+    pub max_senses: Option<usize>,
+    /// Keep the example sentences of Collins senses; on by default.
+    pub include_examples: Option<bool>,
+    /// Parse the related words section (同根词) into [`Translation::related`];
+    /// off by default.
+    pub include_related: Option<bool>,
+    /// Most definitions and pinyin candidates kept; all of them when `None`.
+    pub max_definitions: Option<usize>,
+    /// Merge definitions sharing a part of speech into the first of them,
+    /// joining their meanings with `；`; off by default.
+    pub group_by_pos: Option<bool>,
+    /// Which directions to read the page in; [`LookupStrategy::Auto`] by
+    /// default.
+    pub strategy: Option<LookupStrategy>,
+    /// Identifier of the request the lookup serves, logged with the lookup
+    /// and echoed back in [`FetchInfo::request_id`](crate::FetchInfo::request_id)
+    /// to correlate the two.
+    pub request_id: Option<String>,
+    /// Longest value, in characters, of a definition, gloss, example or web
+    /// translation rendered whole; longer values are truncated with `…` when
+    /// rendered as text, and reported with [`ParseWarning::FieldTooLong`].
+    /// [`DEFAULT_MAX_FIELD_LEN`] when `None`.
+    pub max_field_len: Option<usize>,
+}
+
+impl TranslationOptions {
+    /// Combine `self` with `overrides`, preferring the fields set in
+    /// `overrides`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::{Accent, TranslationOptions};
+    ///
+    /// let client = TranslationOptions {
+    ///     accent: Some(Accent::Us),
+    ///     include_collins: Some(true),
+    ///     ..TranslationOptions::default()
+    /// };
+    /// let call = TranslationOptions {
+    ///     include_collins: Some(false),
+    ///     ..TranslationOptions::default()
+    /// };
+    /// let merged = client.merge(call);
+    /// assert_eq!(merged.accent, Some(Accent::Us));
+    /// assert_eq!(merged.include_collins, Some(false));
+    /// ```
+    pub fn merge(self, overrides: TranslationOptions) -> TranslationOptions {
+        TranslationOptions {
+            lang: overrides.lang.or(self.lang),
+            accent: overrides.accent.or(self.accent),
+            include_collins: overrides.include_collins.or(self.include_collins),
+            max_senses: overrides.max_senses.or(self.max_senses),
+            include_examples: overrides.include_examples.or(self.include_examples),
+            include_related: overrides.include_related.or(self.include_related),
+            max_definitions: overrides.max_definitions.or(self.max_definitions),
+            group_by_pos: overrides.group_by_pos.or(self.group_by_pos),
+            strategy: overrides.strategy.or(self.strategy),
+            request_id: overrides.request_id.or(self.request_id),
+            max_field_len: overrides.max_field_len.or(self.max_field_len),
+        }
+    }
+
+    /// Drop or merge the parts of `translation` these options leave out.
+    pub(crate) fn apply(&self, translation: &mut Translation) {
+        if let Some(accent) = self.accent {
+            translation
+                .phonetics
+                .retain(|phonetic| phonetic.accent() == Some(accent));
+        }
+        if self.group_by_pos == Some(true) {
+            let mut grouped: Vec<Entry> = Vec::with_capacity(translation.entries.len());
+            for entry in translation.entries.drain(..) {
+                match grouped.iter_mut().find(|group| {
+                    !entry.pos.is_empty() && group.pos == entry.pos && group.source == entry.source
+                }) {
+                    Some(group) => {
+                        group.meaning.push('；');
+                        group.meaning.push_str(&entry.meaning);
+                    }
+                    None => grouped.push(entry),
+                }
+            }
+            translation.entries = grouped;
+        }
+        if let Some(max) = self.max_definitions {
+            translation.entries.truncate(max);
+            translation.candidates.truncate(max);
+        }
+        if self.include_examples == Some(false) {
+            for sense in &mut translation.collins {
+                sense.examples.clear();
+            }
+        }
+        let max_field_len = self.max_field_len();
+        if !translation.long_fields(max_field_len).is_empty() {
+            translation.display_limit = Some(max_field_len);
+        }
+    }
+
+    /// [`max_field_len`](Self::max_field_len) or its default.
+    pub(crate) fn max_field_len(&self) -> usize {
+        self.max_field_len.unwrap_or(DEFAULT_MAX_FIELD_LEN)
+    }
+}
+
+/// Which directions a result page is read in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LookupStrategy {
+    /// Read the page in the direction the script of the query suggests,
+    /// trying the others only when it yields nothing.
+    #[default]
+    Auto,
+    /// For queries of Latin letters, which may be English words or pinyin
+    /// such as `ha`, also read the page in the other directions and keep
+    /// those with results in [`Translation::other_directions`].
+    Both,
+}
+
+impl Translation {
+    /// Returns `true` when neither phonetics, definitions, pinyin candidates,
+    /// conversions, an idiom explanation nor web translations were found.
+    pub fn is_empty(&self) -> bool {
+        !self.has_dictionary_results() && self.web_translations.is_empty()
+    }
+
+    /// How much was found, in the order results are rendered: dictionary
+    /// results, then web translations, then suggestions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::{ResultKind, Translation};
+    ///
+    /// let mut translation = Translation {
+    ///     word: "helllo".to_string(),
+    ///     suggestions: vec!["hello".to_string()],
+    ///     ..Translation::default()
+    /// };
+    /// assert_eq!(translation.result_kind(), ResultKind::SuggestionsOnly);
+    /// translation.web_translations.push("你好".to_string());
+    /// assert_eq!(translation.result_kind(), ResultKind::WebOnly);
+    /// ```
+    pub fn result_kind(&self) -> ResultKind {
+        if self.has_dictionary_results() {
+            ResultKind::Full
+        } else if !self.web_translations.is_empty() {
+            ResultKind::WebOnly
+        } else if !self.suggestions.is_empty() {
+            ResultKind::SuggestionsOnly
+        } else {
+            ResultKind::Empty
+        }
+    }
+
+    /// The free-text values of the translation and of the results in other
+    /// directions, each with what it is, e.g. `web translation`.
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)> {
+        let mut fields: Vec<(&'static str, &mut String)> = Vec::new();
+        for entry in &mut self.entries {
+            fields.push(("definition", &mut entry.meaning));
+        }
+        for candidate in &mut self.candidates {
+            fields.push(("candidate gloss", &mut candidate.gloss));
+        }
+        for conversion in &mut self.conversions {
+            fields.push(("conversion", conversion));
+        }
+        for web in &mut self.web_translations {
+            fields.push(("web translation", web));
+        }
+        if let Some(idiom) = &mut self.idiom {
+            fields.push(("idiom explanation", &mut idiom.explanation));
+            fields.push(("idiom source", &mut idiom.source));
+            fields.push(("idiom example", &mut idiom.example));
+        }
+        for sense in &mut self.collins {
+            fields.push(("Collins definition", &mut sense.en_def));
+            fields.push(("Collins definition", &mut sense.zh_def));
+            for example in &mut sense.examples {
+                fields.push(("example", &mut example.en));
+                fields.push(("example", &mut example.zh));
+            }
+        }
+        for group in &mut self.related {
+            for word in &mut group.words {
+                fields.push(("related word gloss", &mut word.gloss));
+            }
+        }
+        for other in &mut self.other_directions {
+            fields.extend(other.text_fields_mut());
+        }
+        fields
+    }
+
+    /// What each value longer than `max` characters is, and its length.
+    pub(crate) fn long_fields(&mut self, max: usize) -> Vec<(&'static str, usize)> {
+        self.text_fields_mut()
+            .into_iter()
+            .map(|(field, text)| (field, text.chars().count()))
+            .filter(|&(_, len)| len > max)
+            .collect()
+    }
+
+    /// A copy with the values longer than the
+    /// [`display_limit`](Self::display_limit) truncated to it, the last
+    /// character kept replaced with `…`.
+    pub(crate) fn truncated_for_display(&self) -> Option<Self> {
+        let max = self.display_limit?;
+        let mut translation = self.clone();
+        for (_, text) in translation.text_fields_mut() {
+            if text.chars().count() > max {
+                let mut truncated: String = text.chars().take(max.saturating_sub(1)).collect();
+                truncated.push('…');
+                *text = truncated;
+            }
+        }
+        Some(translation)
+    }
+
+    fn has_dictionary_results(&self) -> bool {
+        !(self.phonetics.is_empty()
+            && self.entries.is_empty()
+            && self.candidates.is_empty()
+            && self.conversions.is_empty()
+            && self.idiom.is_none())
+    }
+
+    /// A copy keeping only the senses whose text contains any of `filters`.
+    ///
+    /// Definitions, pinyin candidates, conversions, web translations, related
+    /// words and the idiom explanation are kept when they match. A Collins
+    /// sense whose definition matches is kept whole; otherwise only its
+    /// matching examples are, if any. Matching ignores case for ASCII
+    /// letters only. Phonetics and the other fields are left as they are, and
+    /// without filters nothing is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::{Entry, EntrySource, Translation};
+    ///
+    /// let translation = Translation {
+    ///     word: "run".to_string(),
+    ///     entries: vec![
+    ///         Entry {
+    ///             pos: "v.".to_string(),
+    ///             meaning: "跑；经营".to_string(),
+    ///             source: EntrySource::Concise,
+    ///         },
+    ///         Entry {
+    ///             pos: "n.".to_string(),
+    ///             meaning: "赛跑；Run (计算机)".to_string(),
+    ///             source: EntrySource::Concise,
+    ///         },
+    ///     ],
+    ///     ..Translation::default()
+    /// };
+    /// assert_eq!(translation.filtered(&["经营"]).entries[0].pos, "v.");
+    /// assert_eq!(translation.filtered(&["RUN"]).entries[0].pos, "n.");
+    /// assert!(translation.filtered(&["走"]).entries.is_empty());
+    /// ```
+    pub fn filtered(&self, filters: &[&str]) -> Translation {
+        if filters.is_empty() {
+            return self.clone();
+        }
+        let filters: Vec<String> = filters.iter().map(|f| f.to_ascii_lowercase()).collect();
+        let matches = |text: &str| {
+            let text = text.to_ascii_lowercase();
+            filters.iter().any(|filter| text.contains(filter.as_str()))
+        };
+        let mut translation = self.clone();
+        translation.entries.retain(|entry| matches(&entry.meaning));
+        translation.candidates.retain(|candidate| {
+            matches(&candidate.hanzi) || matches(&candidate.pinyin) || matches(&candidate.gloss)
+        });
+        translation
+            .conversions
+            .retain(|conversion| matches(conversion));
+        translation.web_translations.retain(|web| matches(web));
+        translation.collins.retain_mut(|sense| {
+            if matches(&sense.en_def) || matches(&sense.zh_def) {
+                return true;
+            }
+            sense
+                .examples
+                .retain(|example| matches(&example.en) || matches(&example.zh));
+            !sense.examples.is_empty()
+        });
+        for group in &mut translation.related {
+            group
+                .words
+                .retain(|word| matches(&word.word) || matches(&word.gloss));
+        }
+        translation.related.retain(|group| !group.words.is_empty());
+        translation.idiom = translation
+            .idiom
+            .take()
+            .filter(|idiom| matches(&idiom.explanation) || matches(&idiom.example));
+        let filters: Vec<&str> = filters.iter().map(String::as_str).collect();
+        translation.other_directions = self
+            .other_directions
+            .iter()
+            .map(|other| other.filtered(&filters))
+            .collect();
+        translation
+    }
+
+    /// The definitions, in page order, without copying them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let html = r#"
+    /// <div class="trans-container"></div>
+    /// <div class="trans-container">
+    ///   <li class="word-exp"><span class="pos">int.</span><span class="trans">你好</span></li>
+    ///   <li class="word-exp"><span class="pos">n.</span><span class="trans">问候</span></li>
+    /// </div>
+    /// "#;
+    /// let translation = ydt::parse_translation("hello", html).unwrap();
+    /// let parts = translation.definitions().map(|entry| entry.pos.as_str());
+    /// assert_eq!(parts.collect::<Vec<_>>(), ["int.", "n."]);
+    /// ```
+    pub fn definitions(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// The first definition, if any.
+    pub fn first_definition(&self) -> Option<&Entry> {
+        self.entries.first()
+    }
+
+    /// Transcription of the first phonetic of `accent`, e.g. `/həˈloʊ/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ydt::Accent;
+    ///
+    /// let html = r#"
+    /// <div class="trans-container">
+    ///   <div class="per-phone"><span>英</span><span class="phonetic">/həˈləʊ/</span></div>
+    ///   <div class="per-phone"><span>美</span><span class="phonetic">/həˈloʊ/</span></div>
+    /// </div>
+    /// "#;
+    /// let translation = ydt::parse_translation("hello", html).unwrap();
+    /// assert_eq!(translation.phonetic(Accent::Us), Some("/həˈloʊ/"));
+    /// ```
+    pub fn phonetic(&self, accent: Accent) -> Option<&str> {
+        self.phonetics
+            .iter()
+            .find(|phonetic| phonetic.accent() == Some(accent))
+            .map(|phonetic| phonetic.text.as_str())
+    }
+}
+
+/// Sign that a result page was only partially recognized, usually because its
+/// markup changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParseWarning {
+    /// A phonetic block (`div.per-phone`) has no accent label.
+    PhoneticLabelMissing,
+    /// A phonetic block has an accent label but no transcription (`span.phonetic`).
+    PhoneticTextMissing {
+        /// The accent label that was found.
+        label: String,
+    },
+    /// The definitions container (second `div.trans-container`) has no
+    /// definitions (`li.word-exp`).
+    DefinitionsMissing,
+    /// A definition (`li.word-exp`) has a part of speech (`span.pos`) but
+    /// no text (`span.trans`); the rest of its text is used instead.
+    DefinitionIncomplete,
+    /// A definition (`li.word-exp`) has no text besides its part of
+    /// speech; it is skipped.
+    DefinitionEmpty,
+    /// A Chinese-English or pinyin candidate (`li.word-exp-ce`,
+    /// `li.pinyin-candidate`) has no word (`a.point`).
+    CandidateTextMissing,
+    /// The frequency rating (`.star`) has a number of stars outside 1 to 5; it
+    /// is clamped into that range.
+    FrequencyOutOfRange {
+        /// The number of stars on the page.
+        stars: u32,
+    },
+    /// A Collins sense (`li.mcols-layout`) has no English definition
+    /// (`span.def-en`); it is skipped.
+    CollinsDefinitionMissing,
+    /// The idiom block (`div.idiom`) has no explanation (`.idiom-explain`).
+    IdiomExplanationMissing,
+    /// A value is longer than the
+    /// [`max_field_len`](TranslationOptions::max_field_len); it is kept
+    /// whole but truncated when rendered as text.
+    FieldTooLong {
+        /// What the value is, e.g. `web translation`.
+        field: String,
+        /// Its length, in characters.
+        len: usize,
+        /// The limit it exceeds.
+        max: usize,
+    },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PhoneticLabelMissing => {
+                f.write_str("found div.per-phone but no accent label span inside")
+            }
+            Self::PhoneticTextMissing { label } => {
+                write!(
+                    f,
+                    "phonetic label {label} present but span.phonetic missing"
+                )
+            }
+            Self::DefinitionsMissing => {
+                f.write_str("found div.trans-container but no li.word-exp inside")
+            }
+            Self::DefinitionIncomplete => {
+                f.write_str("found li.word-exp with span.pos but no span.trans")
+            }
+            Self::DefinitionEmpty => f.write_str("found li.word-exp with no definition text"),
+            Self::CandidateTextMissing => f.write_str("found li.word-exp-ce but no a.point inside"),
+            Self::FrequencyOutOfRange { stars } => {
+                write!(
+                    f,
+                    "frequency rating of {stars} stars is not between 1 and 5"
+                )
+            }
+            Self::CollinsDefinitionMissing => {
+                f.write_str("found a Collins sense but no span.def-en inside")
+            }
+            Self::IdiomExplanationMissing => {
+                f.write_str("found div.idiom but no .idiom-explain inside")
+            }
+            Self::FieldTooLong { field, len, max } => write!(
+                f,
+                "{field} of {len} characters is longer than {max}; truncated for display"
+            ),
+        }
+    }
+}
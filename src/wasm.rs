@@ -0,0 +1,59 @@
+//! Asynchronous fetching for WebAssembly, where the blocking client of the
+//! `http` feature is not available.
+//!
+//! In the browser requests go through `fetch()`, so TLS, cookies and proxies
+//! are the browser's. Youdao does not allow cross-origin requests: pages need a
+//! browser extension with host permissions for `www.youdao.com`.
+
+use crate::query::{result_url, DEFAULT_BASE_URL, DEFAULT_LANG, DEFAULT_MAX_WORD_LEN};
+use crate::{normalize_word, parse_translation, Translation, YdtError};
+
+/// Fetch the raw Youdao result page for a word without parsing it.
+///
+/// # Errors
+///
+/// Returns [`YdtError`] when `word` is rejected by [`normalize_word`], or when
+/// the request, HTTP status validation or response reading fails.
+pub async fn fetch_html_async(word: &str) -> Result<String, YdtError> {
+    let word = normalize_word(word, DEFAULT_MAX_WORD_LEN)?;
+    fetch(&word).await
+}
+
+/// Fetch and parse the translation for a word from Youdao.
+///
+/// # Errors
+///
+/// Returns [`YdtError`] under the same conditions as [`fetch_html_async`].
+pub async fn translate_async(word: &str) -> Result<Translation, YdtError> {
+    let word = normalize_word(word, DEFAULT_MAX_WORD_LEN)?;
+    let html = fetch(&word).await?;
+    parse_translation(&word, &html)
+}
+
+/// Result page for an already normalized `word`.
+async fn fetch(word: &str) -> Result<String, YdtError> {
+    let url = result_url(DEFAULT_BASE_URL, word, DEFAULT_LANG)?;
+    let response = reqwest::get(url.clone())
+        .await
+        .map_err(|source| YdtError::Fetch {
+            word: word.to_string(),
+            url: url.to_string(),
+            source,
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(YdtError::HttpStatus {
+            word: word.to_string(),
+            url: url.to_string(),
+            status,
+        });
+    }
+    response
+        .text()
+        .await
+        .map_err(|source| YdtError::ReadResponse {
+            word: word.to_string(),
+            url: url.to_string(),
+            source,
+        })
+}
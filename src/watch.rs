@@ -0,0 +1,83 @@
+//! Deciding what `ydt --watch-clipboard` looks up as the clipboard changes.
+//!
+//! Kept apart from `main.rs` and independent of the system clipboard so that
+//! `tests/watch.rs` can drive it with scripted contents.
+
+use std::time::{Duration, Instant};
+
+/// How often the clipboard is read.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// How long new contents must stay unchanged before they are looked up, so
+/// that text copied several times in a row, e.g. while adjusting a selection,
+/// is only looked up once.
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+/// Longest contents looked up by default, in characters.
+pub const DEFAULT_MAX_LEN: usize = 50;
+
+/// Where watched text comes from.
+pub trait ClipboardSource {
+    /// The text currently on the clipboard; empty when it holds none, e.g.
+    /// when an image was copied.
+    fn text(&mut self) -> Result<String, String>;
+}
+
+/// Reads a [`ClipboardSource`] and reports text worth looking up: new
+/// contents once they settle, skipping repeats and contents that are empty or
+/// longer than the maximum length.
+pub struct Watcher<S> {
+    source: S,
+    max_len: usize,
+    debounce: Duration,
+    /// Changed contents waiting to settle, and when they were first read.
+    pending: Option<(String, Instant)>,
+    /// The contents handled last, looked up or skipped.
+    last: String,
+}
+
+impl<S: ClipboardSource> Watcher<S> {
+    /// Start watching `source`. The contents already on the clipboard are not
+    /// looked up; reading them checks that the clipboard is available.
+    pub fn start(mut source: S, max_len: usize, debounce: Duration) -> Result<Self, String> {
+        let last = normalize(&source.text()?);
+        Ok(Self {
+            source,
+            max_len,
+            debounce,
+            pending: None,
+            last,
+        })
+    }
+
+    /// Read the clipboard at `now` and return its text if it should be looked
+    /// up.
+    ///
+    /// Whitespace is collapsed, so the same text copied from differently
+    /// wrapped lines counts as a repeat.
+    pub fn poll(&mut self, now: Instant) -> Result<Option<String>, String> {
+        let text = normalize(&self.source.text()?);
+        if text == self.last {
+            self.pending = None;
+            return Ok(None);
+        }
+        let since = match &self.pending {
+            Some((pending, since)) if *pending == text => *since,
+            _ => {
+                self.pending = Some((text.clone(), now));
+                now
+            }
+        };
+        if now.duration_since(since) < self.debounce {
+            return Ok(None);
+        }
+        self.pending = None;
+        self.last = text.clone();
+        if text.is_empty() || text.chars().count() > self.max_len {
+            return Ok(None);
+        }
+        Ok(Some(text))
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
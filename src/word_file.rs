@@ -0,0 +1,113 @@
+//! Word lists read with `ydt --from FILE`.
+//!
+//! Kept apart from `main.rs` so that `tests/word_file.rs` can check parsing
+//! without running lookups.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A word of the list, with the `lang` of its lookup when overridden.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Query {
+    pub word: String,
+    /// From a suffix such as `@fr`; the client's `lang` when `None`.
+    pub lang: Option<String>,
+}
+
+impl fmt::Display for Query {
+    /// The word as written in the list, e.g. `amour @fr`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.word)?;
+        if let Some(lang) = &self.lang {
+            write!(f, " @{lang}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A line of the list that was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineWarning {
+    /// 1-based.
+    pub line: usize,
+    pub message: String,
+}
+
+/// A parsed word list.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WordList {
+    /// Words to look up, each once, in order of first appearance.
+    pub queries: Vec<Query>,
+    /// For each word of the list, duplicates included, the index of its query.
+    pub lines: Vec<usize>,
+    pub warnings: Vec<LineWarning>,
+}
+
+/// Parse a word list: one word per line, optionally followed by a language
+/// override such as `@fr`.
+///
+/// Blank lines and lines starting with `#` are skipped, as are comments
+/// after a word, which start with a `#` following whitespace so that words
+/// such as `C#` are kept whole. Lines with a malformed override are skipped
+/// with a warning.
+pub fn parse(text: &str) -> WordList {
+    let mut list = WordList::default();
+    let mut indices = HashMap::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let query = match parse_line(line) {
+            Ok(query) => query,
+            Err(message) => {
+                list.warnings.push(LineWarning {
+                    line: number + 1,
+                    message,
+                });
+                continue;
+            }
+        };
+        let index = *indices.entry(query.clone()).or_insert_with(|| {
+            list.queries.push(query);
+            list.queries.len() - 1
+        });
+        list.lines.push(index);
+    }
+    list
+}
+
+fn strip_comment(line: &str) -> &str {
+    if line.trim_start().starts_with('#') {
+        return "";
+    }
+    let comment = line
+        .char_indices()
+        .find(|&(i, ch)| ch == '#' && line[..i].ends_with(char::is_whitespace));
+    match comment {
+        Some((i, _)) => &line[..i],
+        None => line,
+    }
+}
+
+fn parse_line(line: &str) -> Result<Query, String> {
+    let Some((word, lang)) = line
+        .rsplit_once(char::is_whitespace)
+        .filter(|(_, suffix)| suffix.starts_with('@'))
+    else {
+        return Ok(Query {
+            word: line.to_string(),
+            lang: None,
+        });
+    };
+    let lang = &lang[1..];
+    if lang.len() < 2 || lang.len() > 3 || !lang.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Err(format!(
+            "malformed language override `@{lang}`, expected two or three letters such as `@fr`"
+        ));
+    }
+    Ok(Query {
+        word: word.trim_end().to_string(),
+        lang: Some(lang.to_ascii_lowercase()),
+    })
+}
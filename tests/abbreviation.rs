@@ -0,0 +1,102 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockFetcher, MockServer};
+#[cfg(feature = "http")]
+use ydt::YdtClient;
+use ydt::{
+    parse_translation_with_options, parse_translation_with_warnings, render_translation, Entry,
+    EntrySource, Layout, RenderOptions, SelectorConfig, TranslationOptions,
+};
+
+const NASA_ABBR_HTML: &str = include_str!("fixtures/nasa_abbr.html");
+
+fn abbr(meaning: &str) -> Entry {
+    Entry {
+        pos: "abbr.".to_string(),
+        meaning: meaning.to_string(),
+        source: EntrySource::Concise,
+    }
+}
+
+#[test]
+fn expansions_are_parsed_as_entries() {
+    let (translation, warnings) = parse_translation_with_warnings("NASA", NASA_ABBR_HTML).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(translation.phonetics.len(), 2);
+    assert_eq!(
+        translation.entries,
+        [
+            abbr("National Aeronautics and Space Administration （美国）国家航空航天局"),
+            abbr("National Association of Securities Analysts 全国证券分析师协会"),
+            abbr("North American Soccer Association"),
+        ]
+    );
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default())
+            .lines()
+            .nth(1),
+        Some("abbr.: National Aeronautics and Space Administration （美国）国家航空航天局")
+    );
+}
+
+#[test]
+fn expansions_count_towards_max_definitions() {
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let options = TranslationOptions {
+        max_definitions: Some(2),
+        ..TranslationOptions::default()
+    };
+    let (translation, _) =
+        parse_translation_with_options("NASA", NASA_ABBR_HTML, &selectors, &options).unwrap();
+    assert_eq!(translation.entries.len(), 2);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn query_case_is_preserved() {
+    // The mock fetcher only answers the word exactly as given.
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("NASA", NASA_ABBR_HTML));
+    let translation = client.translate("NASA").unwrap();
+    assert_eq!(translation.word, "NASA");
+    assert_eq!(translation.entries.len(), 3);
+
+    let server = MockServer::start(NASA_ABBR_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build();
+    client.translate("HTTP").unwrap();
+    assert!(
+        server
+            .next_request()
+            .target
+            .starts_with("/result?word=HTTP&"),
+        "the query was not sent as typed"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_expansions() {
+    let server = MockServer::start(NASA_ABBR_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--format", "plain", "NASA"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("abbreviation"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("abbr.: National Association of Securities Analysts 全国证券分析师协会\n"),
+        "{stdout}"
+    );
+    assert!(server.next_request().target.contains("word=NASA&"));
+}
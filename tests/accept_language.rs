@@ -0,0 +1,74 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{parse_translation, Accent, YdtClient, DEFAULT_ACCEPT_LANGUAGE};
+
+const HELLO_EN_LABELS_HTML: &str = include_str!("fixtures/hello_en_labels.html");
+
+fn accept_language(server: &MockServer) -> Option<String> {
+    server
+        .next_request()
+        .headers
+        .get("accept-language")
+        .cloned()
+}
+
+#[test]
+fn chinese_is_requested_by_default() {
+    let server = MockServer::start(HELLO_HTML);
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .build()
+        .translate("hello")
+        .unwrap();
+
+    assert_eq!(
+        accept_language(&server).as_deref(),
+        Some(DEFAULT_ACCEPT_LANGUAGE)
+    );
+}
+
+#[test]
+fn header_can_be_overridden_or_dropped() {
+    for (value, expected) in [("en-US,en", Some("en-US,en")), ("", None)] {
+        let server = MockServer::start(HELLO_HTML);
+        YdtClient::builder()
+            .base_url(format!("{}/result", server.url))
+            .accept_language(value)
+            .build()
+            .fetch_html("hello")
+            .unwrap();
+
+        assert_eq!(accept_language(&server).as_deref(), expected, "{value:?}");
+    }
+}
+
+#[test]
+fn english_accent_labels_are_recognized() {
+    let english = parse_translation("hello", HELLO_EN_LABELS_HTML).unwrap();
+    let chinese = parse_translation("hello", HELLO_HTML).unwrap();
+
+    let accents = |translation: &ydt::Translation| -> Vec<_> {
+        translation.phonetics.iter().map(|p| p.accent()).collect()
+    };
+    assert_eq!(english.phonetics[0].label, "UK");
+    assert_eq!(accents(&english), [Some(Accent::Uk), Some(Accent::Us)]);
+    assert_eq!(accents(&english), accents(&chinese));
+}
+
+#[test]
+fn accent_labels_ignore_case() {
+    assert_eq!(Accent::from_label(" uk "), Some(Accent::Uk));
+    assert_eq!(Accent::from_label("Us"), Some(Accent::Us));
+    assert_eq!(Accent::from_label("AU"), None);
+}
+
+#[test]
+fn config_sets_the_header() {
+    let config = Config::from_toml("accept_language = \"en\"").unwrap();
+    assert_eq!(config.accept_language.as_deref(), Some("en"));
+    assert!(Config::from_toml("accept_language = \"en\\n\"").is_err());
+}
@@ -0,0 +1,94 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::{ipa_to_ascii, Phonetic};
+
+#[test]
+fn transliterates_english_transcriptions() {
+    for (ipa, ascii) in [
+        ("/həˈləʊ/", "/h@\"l@U/"),
+        ("/həˈloʊ/", "/h@\"loU/"),
+        ("/rʌn/", "/rVn/"),
+        ("/θæŋk/", "/T{Nk/"),
+        ("/ðɪs/", "/DIs/"),
+        ("/ˈmʌðə(r)/", "/\"mVD@(r)/"),
+        ("/ˈmʌðɚ/", "/\"mVD@`/"),
+        ("/ˈvɪʒn/", "/\"vIZn/"),
+        ("/ʃɔːt/", "/SO:t/"),
+        ("/bɜːd/", "/b3:d/"),
+        ("/bɝd/", "/b3`d/"),
+        ("/kɑːm/", "/kA:m/"),
+        ("/hɒt/", "/hQt/"),
+        ("/bed/", "/bed/"),
+        ("/ɡet/", "/get/"),
+        ("/ˌʌndəˈstænd/", "/%Vnd@\"st{nd/"),
+        ("/ʧɜːʧ/", "/tS3:tS/"),
+        ("/ʤʌʤ/", "/dZVdZ/"),
+        ("/ˈbʌtər/", "/\"bVt@r/"),
+        ("/ˈbʌɾɚ/", "/\"bV4@`/"),
+    ] {
+        assert_eq!(ipa_to_ascii(ipa), ascii, "{ipa}");
+    }
+}
+
+#[test]
+fn symbols_without_equivalent_are_dropped() {
+    // The tie bar, syllabic mark, `ʉ` and `ʝ` are not in the table.
+    assert_eq!(ipa_to_ascii("/t͡ʃeɪn̩ ʉ ʝ/"), "/tSeIn  /");
+}
+
+#[test]
+fn ascii_text_is_unchanged() {
+    assert_eq!(ipa_to_ascii("/ab-c (d)/"), "/ab-c (d)/");
+    assert_eq!(ipa_to_ascii(""), "");
+}
+
+#[test]
+fn labels_become_accent_names() {
+    let phonetic = Phonetic {
+        label: "英".to_string(),
+        text: "/həˈləʊ/".to_string(),
+    };
+    assert_eq!(
+        phonetic.to_ascii(),
+        Phonetic {
+            label: "UK".to_string(),
+            text: "/h@\"l@U/".to_string(),
+        }
+    );
+    let phonetic = Phonetic {
+        label: "美".to_string(),
+        text: "/rʌn/".to_string(),
+    };
+    assert_eq!(phonetic.to_ascii().label, "US");
+    let phonetic = Phonetic {
+        label: "拼音".to_string(),
+        text: "xué xí".to_string(),
+    };
+    assert_eq!(phonetic.to_ascii().label, "");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_ascii_phonetics() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--ascii-phonetics", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("ascii_phonetics"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().next(),
+        Some("UK /h@\"l@U/ US /h@\"loU/"),
+        "{stdout}"
+    );
+}
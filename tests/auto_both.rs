@@ -0,0 +1,100 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    parse_translation_with_options, render_stable, render_translation, translation_to_json, Layout,
+    LookupStrategy, RenderOptions, SelectorConfig, Selectors, TranslationOptions,
+};
+
+const HA_BOTH_HTML: &str = include_str!("fixtures/ha_both.html");
+
+fn selectors() -> Selectors {
+    SelectorConfig::default().compile().unwrap()
+}
+
+fn both() -> TranslationOptions {
+    TranslationOptions {
+        strategy: Some(LookupStrategy::Both),
+        ..TranslationOptions::default()
+    }
+}
+
+#[test]
+fn every_direction_is_read() {
+    let (translation, warnings) =
+        parse_translation_with_options("ha", HA_BOTH_HTML, &selectors(), &both()).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(translation.entries.len(), 2);
+    assert!(translation.candidates.is_empty());
+
+    assert_eq!(translation.other_directions.len(), 1);
+    let pinyin = &translation.other_directions[0];
+    assert_eq!(pinyin.layout, Some(Layout::Pinyin));
+    assert_eq!(pinyin.candidates[0].hanzi, "哈");
+    assert_eq!(pinyin.candidates[1].hanzi, "蛤");
+}
+
+#[test]
+fn auto_keeps_the_first_direction() {
+    let options = TranslationOptions::default();
+    let (translation, _) =
+        parse_translation_with_options("ha", HA_BOTH_HTML, &selectors(), &options).unwrap();
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert!(translation.other_directions.is_empty());
+    assert!(translation_to_json(&translation)
+        .get("other_directions")
+        .is_none());
+}
+
+#[test]
+fn directions_render_under_labels() {
+    let (translation, _) =
+        parse_translation_with_options("ha", HA_BOTH_HTML, &selectors(), &both()).unwrap();
+    let options = RenderOptions::default();
+    assert_eq!(
+        render_translation(&translation, &options),
+        "[英汉]\n英 /hɑː/\nint.: 哈（表示惊奇、喜悦等）\nabbr.: 公顷（hectare）\n\n[拼音]\n哈 hā laugh; breathe out\n蛤 há frog; toad"
+    );
+    // The first result keeps its lines in stable output.
+    assert!(render_stable(&translation, &options).ends_with(
+        "abbr.: 公顷（hectare）\n\n[拼音]\n\n哈 hā laugh; breathe out\n蛤 há frog; toad"
+    ));
+
+    let json = translation_to_json(&translation);
+    assert_eq!(json["other_directions"][0]["layout"], "pinyin");
+    assert_eq!(json["other_directions"][0]["candidates"][1]["hanzi"], "蛤");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_every_direction() {
+    let server = MockServer::start(HA_BOTH_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args([
+            "--no-mobile-fallback",
+            "--auto-both",
+            "--format",
+            "plain",
+            "ha",
+        ])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("auto-both"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[英汉]\n"), "{stdout}");
+    assert!(
+        stdout.contains("\n[拼音]\n哈 hā laugh; breathe out\n"),
+        "{stdout}"
+    );
+    // Both directions come from the one result page.
+    assert_eq!(server.pending_requests().len(), 1);
+}
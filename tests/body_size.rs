@@ -0,0 +1,127 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{YdtClient, YdtError};
+
+const CHUNK: usize = 64 * 1024;
+
+/// Answer a single request with a `total`-byte body written in chunks, and report
+/// how many bytes were written before the client hung up.
+fn serve_streaming(total: usize) -> (String, Receiver<usize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, sent) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+            line.clear();
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {total}\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let chunk = vec![b'x'; CHUNK];
+        let mut written = 0;
+        while written < total && stream.write_all(&chunk).is_ok() {
+            written += CHUNK;
+        }
+        tx.send(written).unwrap();
+    });
+    (url, sent)
+}
+
+#[test]
+fn oversized_body_is_aborted_early() {
+    let total = 256 * 1024 * 1024;
+    let (url, sent) = serve_streaming(total);
+    let client = YdtClient::builder()
+        .base_url(format!("{url}/result"))
+        .mobile_fallback(false)
+        .max_body_size(1024 * 1024)
+        .build();
+
+    let err = client.fetch_html("hello").unwrap_err();
+
+    match err {
+        YdtError::ResponseTooLarge {
+            limit, received, ..
+        } => {
+            assert_eq!(limit, 1024 * 1024);
+            assert!(received > limit && received < 2 * limit, "{received}");
+        }
+        err => panic!("{err:?}"),
+    }
+    drop(client);
+    let sent = sent.recv().unwrap();
+    assert!(sent < total, "server wrote the whole body");
+}
+
+#[test]
+fn body_at_the_limit_is_read() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .max_body_size(HELLO_HTML.len())
+        .build();
+
+    assert_eq!(client.fetch_html("hello").unwrap(), HELLO_HTML);
+}
+
+#[test]
+fn limit_is_reported() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .max_body_size(100)
+        .build();
+
+    let err = client.translate("hello").unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("Response body for \"hello\" is larger than 100 bytes"),
+        "{err}"
+    );
+}
+
+#[test]
+fn config_sets_the_limit() {
+    let config = Config::from_toml("max_body_size = 1024").unwrap();
+    assert_eq!(config.max_body_size, Some(1024));
+    for toml in ["max_body_size = 0", "max_body_size = \"1M\""] {
+        assert!(Config::from_toml(toml).is_err(), "{toml}");
+    }
+}
+
+#[test]
+fn cli_flag_sets_the_limit() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--max-body-size", "100", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("body_size_cli"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("larger than 100 bytes"), "{stderr}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--max-body-size", "0", "hello"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+}
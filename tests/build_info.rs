@@ -0,0 +1,107 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::{build_info, build_info_to_json, DEFAULT_BASE_URL};
+
+/// Features of this build, told one by one.
+fn enabled_features() -> Vec<&'static str> {
+    [
+        ("http", cfg!(feature = "http")),
+        ("rustls-tls", cfg!(feature = "rustls-tls")),
+        ("native-tls", cfg!(feature = "native-tls")),
+        ("clipboard", cfg!(feature = "clipboard")),
+        ("open", cfg!(feature = "open")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("server", cfg!(feature = "server")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("serde", cfg!(feature = "serde")),
+        ("test-util", cfg!(feature = "test-util")),
+    ]
+    .into_iter()
+    .filter(|&(_, enabled)| enabled)
+    .map(|(name, _)| name)
+    .collect()
+}
+
+#[test]
+fn features_are_those_built_with() {
+    let info = build_info();
+
+    assert_eq!(info.features, enabled_features());
+    // Enabled for every test run by the dev-dependency on the crate itself.
+    assert!(info.features.contains(&"test-util"));
+    assert_eq!(
+        info.features.contains(&"http"),
+        info.features.contains(&"rustls-tls") || info.features.contains(&"native-tls")
+    );
+}
+
+#[test]
+fn json_has_every_field() {
+    let info = build_info();
+
+    assert_eq!(
+        build_info_to_json(&info),
+        serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "default_base_url": DEFAULT_BASE_URL,
+            "features": enabled_features(),
+            "default_user_agent": format!(
+                "ydt/{} (+https://github.com/yushengyangchem/ydt)",
+                env!("CARGO_PKG_VERSION")
+            ),
+        })
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn default_user_agent_is_the_one_sent() {
+    let server = MockServer::start(HELLO_HTML);
+    ydt::YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build()
+        .translate("hello")
+        .unwrap();
+
+    assert_eq!(
+        server.next_request().headers["user-agent"],
+        build_info().default_user_agent
+    );
+}
+
+#[cfg(feature = "http")]
+fn ydt_version(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(args)
+        .env("YDT_BASE_URL", "http://127.0.0.1:1/result")
+        .env_remove("YDT_USER_AGENT")
+        .env("XDG_CONFIG_HOME", temp_dir("build_info"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_build_info_with_verbose() {
+    let version = format!("ydt {}\n", env!("CARGO_PKG_VERSION"));
+    assert_eq!(ydt_version(&["-V"]), version);
+
+    let info = build_info();
+    assert_eq!(
+        ydt_version(&["--version", "--verbose"]),
+        format!(
+            "{version}base URL: http://127.0.0.1:1/result\nfeatures: {}\nuser agent: {}\n",
+            info.features.join(", "),
+            info.default_user_agent
+        )
+    );
+}
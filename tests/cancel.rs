@@ -0,0 +1,103 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use common::{MockFetcher, HELLO_HTML};
+use ydt::{Fetcher, YdtClient, YdtError};
+
+/// Fetcher that sets a cancellation flag when asked for `stop`.
+struct CancellingFetcher {
+    cancel: Arc<AtomicBool>,
+}
+
+impl Fetcher for CancellingFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        if word == "stop" {
+            self.cancel.store(true, Ordering::SeqCst);
+        }
+        Ok(HELLO_HTML.to_string())
+    }
+}
+
+#[test]
+fn lookups_stop_once_cancelled() {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let client = YdtClient::with_fetcher(CancellingFetcher {
+        cancel: Arc::clone(&cancel),
+    });
+
+    let results = client.translate_many_with_cancel(&["hello", "stop", "hi", "hey"], 1, &cancel);
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[test]
+fn nothing_is_looked_up_when_already_cancelled() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    let cancel = AtomicBool::new(true);
+
+    assert!(client
+        .translate_many_with_cancel(&["hello"], 2, &cancel)
+        .is_empty());
+}
+
+#[test]
+fn uncancelled_batch_looks_up_every_word() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    let cancel = AtomicBool::new(false);
+
+    let results = client.translate_many_with_cancel(&["hello", "missing", "hello"], 2, &cancel);
+
+    assert_eq!(results.len(), 3);
+    assert!(results[1].is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn ctrl_c_stops_a_batch_with_a_summary() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::process::{Command, Stdio};
+
+    use common::{temp_dir, MockServer};
+
+    let server = MockServer::start(HELLO_HTML);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--format", "tsv"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("cancel_cli"))
+        .env("XDG_CACHE_HOME", temp_dir("cancel_cli_cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Keep stdin open so that the batch is waiting for more words.
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"hello\n").unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    assert!(line.starts_with("hello\t"), "{line}");
+
+    let status = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    let status = child.wait().unwrap();
+    drop(stdin);
+
+    assert_eq!(status.code(), Some(130), "{stderr}");
+    assert_eq!(stderr, "Interrupted: looked up 1 of 1 words read\n");
+}
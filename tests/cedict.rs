@@ -0,0 +1,189 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockFetcher, MockServer};
+use ydt::cedict::{parse_line, Cedict, CedictEntry};
+use ydt::{Entry, EntrySource, Layout, Phonetic, TranslationSource};
+#[cfg(feature = "http")]
+use ydt::{PageSource, YdtClient, YdtError};
+
+const SAMPLE: &str = include_str!("fixtures/cedict_sample.u8");
+#[cfg(feature = "http")]
+const SAMPLE_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/cedict_sample.u8"
+);
+
+#[test]
+fn parses_dictionary_lines() {
+    assert_eq!(
+        parse_line("傳統 传统 [chuan2 tong3] /tradition/traditional/convention/"),
+        Some(CedictEntry {
+            traditional: "傳統".to_string(),
+            simplified: "传统".to_string(),
+            pinyin: "chuan2 tong3".to_string(),
+            glosses: vec![
+                "tradition".to_string(),
+                "traditional".to_string(),
+                "convention".to_string(),
+            ],
+        })
+    );
+    let entry = parse_line("  你好 你好 [ni3 hao3] / hello /hi/\r").unwrap();
+    assert_eq!(entry.glosses, ["hello", "hi"]);
+}
+
+#[test]
+fn rejects_comments_and_malformed_lines() {
+    for line in [
+        "",
+        "# CC-CEDICT",
+        "#! version=1",
+        "學習",
+        "學習 学习",
+        "學習 学习 xue2 xi2 /to learn/",
+        "學習 学习 [xue2 xi2 /to learn/",
+        "學習 学习 [xue2 xi2] to learn",
+        "學習 学习 [xue2 xi2] /to learn",
+        "學習 学习 [xue2 xi2] //",
+        "學習 学习 [] /to learn/",
+    ] {
+        assert_eq!(parse_line(line), None, "{line:?}");
+    }
+}
+
+#[test]
+fn counts_skipped_lines() {
+    let dictionary = Cedict::parse(SAMPLE);
+    assert_eq!(dictionary.len(), 5);
+    assert_eq!(dictionary.skipped(), 2);
+}
+
+#[test]
+fn looks_up_simplified_and_traditional_forms() {
+    let dictionary = Cedict::parse(SAMPLE);
+    for word in ["学习", "學習"] {
+        let translation = dictionary.lookup(word, "en").unwrap();
+        assert_eq!(translation.word, word);
+        assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+        assert_eq!(
+            translation.phonetics,
+            [Phonetic {
+                label: "拼音".to_string(),
+                text: "[xue2 xi2]".to_string(),
+            }]
+        );
+        assert_eq!(
+            translation.entries,
+            [
+                Entry {
+                    pos: String::new(),
+                    meaning: "to learn".to_string(),
+                    source: EntrySource::Cedict,
+                },
+                Entry {
+                    pos: String::new(),
+                    meaning: "to study".to_string(),
+                    source: EntrySource::Cedict,
+                },
+            ]
+        );
+    }
+}
+
+#[test]
+fn readings_are_merged() {
+    let translation = Cedict::parse(SAMPLE).lookup("行", "en").unwrap();
+    let readings: Vec<_> = translation
+        .phonetics
+        .iter()
+        .map(|phonetic| phonetic.text.as_str())
+        .collect();
+    assert_eq!(readings, ["[xing2]", "[hang2]"]);
+    assert_eq!(translation.entries.len(), 6);
+}
+
+#[test]
+fn only_chinese_words_are_answered() {
+    let dictionary = Cedict::parse("hello hello [he4 lou2] /hello/\n");
+    assert!(dictionary.lookup("hello", "en").unwrap().is_empty());
+    assert!(dictionary.lookup("再见", "en").unwrap().is_empty());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn client_falls_back_to_the_dictionary() {
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .cedict_path(SAMPLE_PATH)
+        .build();
+
+    let (translation, info) = client.translate_detailed("学习").unwrap();
+    assert_eq!(translation.entries[0].meaning, "to learn");
+    assert_eq!(info.source, PageSource::Custom);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn offline_client_makes_no_requests() {
+    let server = MockServer::start(common::HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .cedict_path(SAMPLE_PATH)
+        .offline(true)
+        .build();
+
+    assert_eq!(client.translate("學習").unwrap().entries.len(), 2);
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert!(server.pending_requests().is_empty());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn unreadable_dictionary_fails_lookups() {
+    let path = temp_dir("cedict_missing").join("missing.u8");
+    let client = YdtClient::builder()
+        .cedict_path(&path)
+        .offline(true)
+        .build();
+
+    let err = client.translate("学习").unwrap_err();
+    assert!(matches!(err, YdtError::ReadDictionary(_)), "{err:?}");
+    assert!(err.to_string().contains("missing.u8"), "{err}");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_looks_words_up_offline() {
+    let config_dir = temp_dir("cedict_cli");
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--offline", "--cedict", SAMPLE_PATH, "学习"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .env("YDT_BASE_URL", "http://127.0.0.1:9/result")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[xue2 xi2]"), "{stdout}");
+    assert!(stdout.contains("to study"), "{stdout}");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_offline_requires_a_dictionary() {
+    let config_dir = temp_dir("cedict_cli_missing");
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--offline", "学习"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--offline requires"), "{stderr}");
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::MockServer;
+use ydt::{CharsetSource, YdtClient, YdtError};
+
+const GBK_HTML: &[u8] = include_bytes!("fixtures/gbk_hello.html");
+const GBK_META_HTML: &[u8] = include_bytes!("fixtures/gbk_hello_meta.html");
+
+fn client_for(server: &MockServer) -> YdtClient {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build()
+}
+
+fn assert_decoded(server: &MockServer, source: CharsetSource) {
+    let (translation, info) = client_for(server).translate_detailed("hello").unwrap();
+
+    assert_eq!(translation.entries[0].meaning, "你好；喂");
+    assert_eq!(info.charset, "GBK");
+    assert_eq!(info.charset_source, source);
+}
+
+#[test]
+fn header_charset_is_honored() {
+    let server = MockServer::start_bytes_as("text/html; charset=GBK", GBK_HTML);
+    assert_decoded(&server, CharsetSource::Header);
+}
+
+#[test]
+fn meta_charset_is_sniffed_without_header() {
+    let server = MockServer::start_bytes(GBK_META_HTML);
+    assert_decoded(&server, CharsetSource::Meta);
+}
+
+#[test]
+fn wrong_header_charset_is_overridden_by_meta() {
+    let server = MockServer::start_bytes_as("text/html; charset=utf-8", GBK_META_HTML);
+    assert_decoded(&server, CharsetSource::Meta);
+}
+
+#[test]
+fn quoted_header_charset_is_honored() {
+    let server = MockServer::start_bytes_as("text/html;charset=\"gb2312\"", GBK_HTML);
+    assert_decoded(&server, CharsetSource::Header);
+}
+
+#[test]
+fn undeclared_utf8_is_detected() {
+    let server = MockServer::start(common::HELLO_HTML);
+    let (_, info) = client_for(&server).translate_detailed("hello").unwrap();
+
+    assert_eq!(info.charset, "UTF-8");
+    assert_eq!(info.charset_source, CharsetSource::Utf8);
+}
+
+#[test]
+fn undeclared_gbk_is_decoded_lossily() {
+    let server = MockServer::start_bytes(GBK_HTML);
+    let client = client_for(&server);
+    let (_, info) = client.translate_detailed("hello").unwrap();
+    let html = client.fetch_html("hello").unwrap();
+
+    assert_eq!(info.charset_source, CharsetSource::Lossy);
+    assert!(html.contains('\u{fffd}'));
+    assert!(html.contains("<span class=\"pos\">int.</span>"));
+}
+
+#[test]
+fn binary_body_is_undecodable() {
+    let server = MockServer::start_bytes(&[0xff; 64]);
+    let err = client_for(&server).fetch_html("hello").unwrap_err();
+    assert!(matches!(err, YdtError::Decode { .. }), "{err:?}");
+}
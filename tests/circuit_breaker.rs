@@ -0,0 +1,199 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::config::Config;
+use ydt::{Fetcher, YdtClient, YdtError, DEFAULT_ABORT_AFTER};
+
+/// Fetcher counting its requests and failing those for words starting with
+/// `fail` with `status`.
+struct CountingFetcher {
+    status: StatusCode,
+    requests: AtomicUsize,
+}
+
+impl CountingFetcher {
+    fn failing_with(status: StatusCode) -> Arc<Self> {
+        Arc::new(Self {
+            status,
+            requests: AtomicUsize::new(0),
+        })
+    }
+
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+impl Fetcher for CountingFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if word.starts_with("fail") {
+            Err(YdtError::HttpStatus {
+                word: word.to_string(),
+                url: format!("mock://{word}"),
+                status: self.status,
+            })
+        } else {
+            Ok(HELLO_HTML.to_string())
+        }
+    }
+}
+
+fn failing_words(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("fail{i}")).collect()
+}
+
+fn client(fetcher: &Arc<CountingFetcher>, abort_after: usize) -> YdtClient {
+    YdtClient::builder()
+        .fetcher(Arc::clone(fetcher))
+        .abort_after(abort_after)
+        .build()
+}
+
+#[test]
+fn no_requests_are_made_after_the_breaker_trips() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::SERVICE_UNAVAILABLE);
+    let client = client(&fetcher, 3);
+
+    let results = client.translate_many(&failing_words(10), 1);
+
+    assert_eq!(fetcher.requests(), 3);
+    for result in &results[..3] {
+        assert!(
+            matches!(result, Err(YdtError::HttpStatus { .. })),
+            "{result:?}"
+        );
+    }
+    for result in &results[3..] {
+        assert!(
+            matches!(result, Err(YdtError::CircuitOpen { failures: 3, .. })),
+            "{result:?}"
+        );
+    }
+    assert_eq!(
+        results[3].as_ref().unwrap_err().to_string(),
+        "Skipped \"fail3\" after 3 consecutive failures"
+    );
+}
+
+#[test]
+fn forbidden_trips_the_breaker_by_default() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::FORBIDDEN);
+    let client = YdtClient::builder().fetcher(Arc::clone(&fetcher)).build();
+
+    let mut results = Vec::new();
+    client.translate_each(failing_words(8), |_, result| results.push(result));
+
+    assert_eq!(fetcher.requests(), DEFAULT_ABORT_AFTER);
+    assert!(matches!(
+        results.last(),
+        Some(Err(YdtError::CircuitOpen { .. }))
+    ));
+}
+
+#[test]
+fn success_starts_the_count_over() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::BAD_GATEWAY);
+    let client = client(&fetcher, 3);
+    let words = ["fail1", "fail2", "hello", "fail3", "fail4", "hello"];
+
+    let results = client.translate_many(&words, 1);
+
+    assert_eq!(fetcher.requests(), words.len());
+    assert!(results[5].is_ok());
+}
+
+#[test]
+fn other_failures_do_not_trip_the_breaker() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::NOT_FOUND);
+    let client = client(&fetcher, 2);
+
+    client.translate_many(&failing_words(5), 1);
+
+    assert_eq!(fetcher.requests(), 5);
+}
+
+#[test]
+fn breaker_can_be_reset() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::SERVICE_UNAVAILABLE);
+    let client = client(&fetcher, 2);
+    client.translate_many(&failing_words(4), 1);
+    assert_eq!(fetcher.requests(), 2);
+
+    client.reset_circuit_breaker();
+    let results = client.translate_many(&["hello"], 1);
+
+    assert!(results[0].is_ok());
+    assert_eq!(fetcher.requests(), 3);
+}
+
+#[test]
+fn zero_never_trips_the_breaker() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::SERVICE_UNAVAILABLE);
+    let client = client(&fetcher, 0);
+
+    client.translate_many(&failing_words(20), 2);
+
+    assert_eq!(fetcher.requests(), 20);
+}
+
+#[test]
+fn single_lookups_ignore_the_breaker() {
+    let fetcher = CountingFetcher::failing_with(StatusCode::SERVICE_UNAVAILABLE);
+    let client = client(&fetcher, 1);
+    client.translate_many(&failing_words(3), 1);
+
+    assert!(client.translate("hello").is_ok());
+}
+
+#[test]
+fn config_sets_the_threshold() {
+    let config = Config::from_toml("abort_after = 2").unwrap();
+    assert_eq!(config.abort_after, Some(2));
+    assert!(Config::from_toml("abort_after = -1").is_err());
+}
+
+#[test]
+fn cli_reports_skipped_lookups() {
+    let server = MockServer::start_with(|_| (503, ""));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args([
+            "--no-mobile-fallback",
+            "--concurrency",
+            "1",
+            "--abort-after",
+            "2",
+        ])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("circuit_breaker_cli"))
+        .env("XDG_CACHE_HOME", temp_dir("circuit_breaker_cli_cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"one\ntwo\nthree\nfour\nfive\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Skipped 3 lookups after 2 consecutive failures"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("\"three\""), "{stderr}");
+    assert_eq!(server.pending_requests().len(), 2);
+}
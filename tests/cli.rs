@@ -0,0 +1,301 @@
+#![cfg(feature = "http")]
+
+// The argument definitions of the binary, checked without running it.
+#[allow(dead_code)]
+#[path = "../src/cli.rs"]
+mod cli;
+
+use std::process::Command as Process;
+use std::time::Duration;
+
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use cli::{Cli, Command, ConfigAction, Detail};
+use ydt::{Accent, ColorChoice, OutputFormat};
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_args(std::iter::once("ydt").chain(args.iter().copied()))
+}
+
+fn error_kind(args: &[&str]) -> ErrorKind {
+    parse(args).unwrap_err().kind()
+}
+
+#[test]
+fn definitions_are_consistent() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn word_and_flags_are_parsed() {
+    let cli = parse(&[
+        "--no-phonetic",
+        "--format",
+        "tsv",
+        "--accent",
+        "US",
+        "--color",
+        "never",
+        "--timeout",
+        "2.5",
+        "-vv",
+        "  hello  ",
+    ])
+    .unwrap();
+
+    assert!(cli.command.is_none());
+    let args = cli.translate;
+    assert_eq!(args.word.as_deref(), Some("hello"));
+    assert!(args.no_phonetic);
+    assert_eq!(args.format, Some(OutputFormat::Tsv));
+    assert_eq!(args.accent, Some(Accent::Us));
+    assert_eq!(args.color, Some(ColorChoice::Never));
+    assert_eq!(args.timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(args.verbose, 2);
+    assert_eq!(args.concurrency, ydt::DEFAULT_CONCURRENCY);
+}
+
+#[test]
+fn word_is_optional() {
+    let args = parse(&["--unordered"]).unwrap().translate;
+    assert_eq!(args.word, None);
+    assert!(args.unordered);
+}
+
+#[test]
+fn subcommands_are_parsed() {
+    let cli = parse(&["suggest", "--limit", "3", "hel"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Suggest { ref prefix, limit: 3 }) if prefix == "hel"
+    ));
+
+    let cli = parse(&["diff", "--width", "100", "affect", "effect"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Diff { width: Some(100), ref first, ref second })
+            if first == "affect" && second == "effect"
+    ));
+
+    let cli = parse(&["serve", "--port", "0"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Serve { ref host, port: 0 }) if host == "127.0.0.1"
+    ));
+
+    let cli = parse(&["doctor", "--json"]).unwrap();
+    assert!(matches!(cli.command, Some(Command::Doctor { json: true })));
+
+    let cli = parse(&["config", "init"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Config {
+            action: ConfigAction::Init
+        })
+    ));
+}
+
+#[test]
+fn network_flags_apply_to_subcommands() {
+    let cli = parse(&[
+        "suggest",
+        "--proxy",
+        "http://localhost:3128",
+        "--timeout",
+        "3",
+        "--no-cookie-file",
+        "hel",
+    ])
+    .unwrap();
+    assert!(matches!(cli.command, Some(Command::Suggest { .. })));
+    let args = cli.translate;
+    assert_eq!(args.proxy.as_deref(), Some("http://localhost:3128"));
+    assert_eq!(args.timeout, Some(Duration::from_secs(3)));
+    assert!(args.no_cookie_file);
+
+    for subcommand in [&["diff", "a", "b"][..], &["serve"], &["open", "hello"]] {
+        let args = parse(&[subcommand, &["--retries", "2", "--user-agent", "ua"]].concat())
+            .unwrap()
+            .translate;
+        assert_eq!(args.retries, Some(2), "{subcommand:?}");
+        assert_eq!(args.user_agent.as_deref(), Some("ua"), "{subcommand:?}");
+    }
+}
+
+#[test]
+fn network_flags_apply_before_subcommands() {
+    let cli = parse(&["--json-errors", "--timeout", "5", "suggest", "hel"]).unwrap();
+    let Some(Command::Suggest { prefix, .. }) = cli.command else {
+        panic!("{:?}", cli.command);
+    };
+    assert_eq!(prefix, "hel");
+    assert!(cli.translate.json_errors);
+    assert_eq!(cli.translate.timeout, Some(Duration::from_secs(5)));
+
+    let cli = parse(&["--retries=2", "diff", "--user-agent", "ua", "a", "b"]).unwrap();
+    assert!(matches!(cli.command, Some(Command::Diff { .. })));
+    assert_eq!(cli.translate.retries, Some(2));
+    assert_eq!(cli.translate.user_agent.as_deref(), Some("ua"));
+
+    let cli = parse(&["--no-cookie-file", "config", "path"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Command::Config {
+            action: ConfigAction::Path
+        })
+    ));
+    assert!(cli.translate.no_cookie_file);
+
+    // Flags other than network ones still conflict, before or among them.
+    assert_eq!(
+        error_kind(&["--timeout", "5", "--one-line", "suggest", "hel"]),
+        ErrorKind::ArgumentConflict
+    );
+    // A word is not taken for a subcommand.
+    let cli = parse(&["--timeout", "5", "hello"]).unwrap();
+    assert!(cli.command.is_none());
+    assert_eq!(cli.translate.word.as_deref(), Some("hello"));
+}
+
+#[test]
+fn unknown_flags_are_errors() {
+    assert_eq!(
+        error_kind(&["--bogus", "hello"]),
+        ErrorKind::UnknownArgument
+    );
+    assert_eq!(
+        error_kind(&["suggest", "--bogus", "hel"]),
+        ErrorKind::UnknownArgument
+    );
+}
+
+#[test]
+fn conflicting_flags_are_errors() {
+    for args in [
+        &["--phonetic-only", "--no-phonetic", "hello"][..],
+        &["--offline", "--raw-html", "hello"],
+        &["--stdio", "hello"],
+        &["--clip", "hello"],
+        &["--stdio", "--clip"],
+        &["--copy", "--raw-html", "hello"],
+        &["--watch-clipboard", "hello"],
+        &["--watch-clipboard", "--stdio"],
+        &["--from", "words.txt", "hello"],
+        &["--from", "words.txt", "--stdio"],
+        &["--dry-run", "--raw-html", "hello"],
+        // Translation flags do not apply to subcommands.
+        &["--one-line", "suggest", "hel"],
+    ] {
+        assert_eq!(error_kind(args), ErrorKind::ArgumentConflict, "{args:?}");
+    }
+}
+
+#[test]
+fn flags_needing_a_word_require_one() {
+    for flag in ["--raw-html", "--sentence", "--copy", "--dry-run"] {
+        assert_eq!(
+            error_kind(&[flag]),
+            ErrorKind::MissingRequiredArgument,
+            "{flag}"
+        );
+    }
+}
+
+#[test]
+fn clipboard_can_replace_the_word() {
+    let args = parse(&["--clip", "--sentence", "--copy"])
+        .unwrap()
+        .translate;
+    assert_eq!(args.word, None);
+    assert!(args.clip && args.sentence && args.copy);
+
+    let args = parse(&["--watch-clipboard", "--max-len", "20"])
+        .unwrap()
+        .translate;
+    assert!(args.watch_clipboard);
+    assert_eq!(args.max_len, Some(20));
+    assert_eq!(
+        error_kind(&["--watch-clipboard", "--max-len", "0"]),
+        ErrorKind::ValueValidation
+    );
+}
+
+#[test]
+fn details_are_parsed() {
+    let args = parse(&["--detail", "collins", "--max-senses", "3", "take"])
+        .unwrap()
+        .translate;
+    assert_eq!(args.detail, [Detail::Collins]);
+    assert_eq!(args.max_senses, Some(3));
+    assert!(parse(&["take"]).unwrap().translate.detail.is_empty());
+    assert!(parse(&["--related", "create"]).unwrap().translate.related);
+    assert!(
+        parse(&["--no-convert", "學習"])
+            .unwrap()
+            .translate
+            .no_convert
+    );
+    assert!(parse(&["--auto-both", "ha"]).unwrap().translate.auto_both);
+}
+
+#[test]
+fn invalid_values_are_errors() {
+    for args in [
+        &["--concurrency", "0"][..],
+        &["--timeout", "0"],
+        &["--timeout", "1e20", "hello"],
+        &["--deadline", "inf", "hello"],
+        &["--format", "xml"],
+        &["--max-body-size", "0"],
+        &["--detail", "etymology", "take"],
+        &["--detail", "collins", "--max-senses", "0", "take"],
+        &["suggest", "--limit", "0", "hel"],
+        &["serve", "--port", "http"],
+        &["  "],
+    ] {
+        assert_eq!(error_kind(args), ErrorKind::ValueValidation, "{args:?}");
+    }
+}
+
+#[test]
+fn help_and_version_are_available() {
+    assert_eq!(error_kind(&["--help"]), ErrorKind::DisplayHelp);
+    assert!(parse(&["--version"]).unwrap().version);
+    assert!(parse(&["-V", "-v"]).unwrap().version);
+}
+
+#[test]
+fn completions_are_hidden_from_help() {
+    let help = Cli::command().render_long_help().to_string();
+    assert!(help.contains("suggest"), "{help}");
+    assert!(!help.contains("  completions "), "{help}");
+}
+
+#[test]
+fn cli_prints_completion_scripts() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = Process::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{shell}");
+        let script = String::from_utf8(output.stdout).unwrap();
+        assert!(script.contains("phonetic-only"), "{shell}: {script}");
+        assert!(script.contains("suggest"), "{shell}: {script}");
+    }
+}
+
+#[test]
+fn cli_prints_its_version() {
+    let output = Process::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("--version")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("ydt {}\n", env!("CARGO_PKG_VERSION"))
+    );
+}
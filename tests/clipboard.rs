@@ -0,0 +1,60 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::{Command, Output};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+
+/// Run the binary with `args` without a display, so that no clipboard is
+/// available even with the `clipboard` feature.
+fn ydt(args: &[&str], server: &MockServer) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("--no-mobile-fallback")
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("clipboard"))
+        .env_remove("DISPLAY")
+        .env_remove("WAYLAND_DISPLAY")
+        .output()
+        .unwrap()
+}
+
+#[cfg(not(feature = "clipboard"))]
+const ERROR: &str = "built without clipboard support";
+#[cfg(feature = "clipboard")]
+const ERROR: &str = "clipboard";
+
+#[test]
+fn clip_fails_without_a_clipboard() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["--clip"], &server);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(ERROR), "{stderr}");
+    assert!(server.pending_requests().is_empty());
+}
+
+#[test]
+fn copy_failures_keep_the_result() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["--copy", "hello"], &server);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("你好；喂"), "{stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(ERROR), "{stderr}");
+}
+
+#[test]
+fn watching_fails_without_a_clipboard() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["--watch-clipboard"], &server);
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(ERROR), "{stderr}");
+}
@@ -0,0 +1,181 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    parse_translation, parse_translation_with_options, render_pretty, render_translation,
+    translation_to_json, CollinsSense, Example, ParseWarning, RenderOptions, Sections,
+    SelectorConfig, Selectors, TranslationOptions,
+};
+
+const TAKE_COLLINS_HTML: &str = include_str!("fixtures/take_collins.html");
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+
+fn selectors() -> Selectors {
+    SelectorConfig::default().compile().unwrap()
+}
+
+fn collins(max_senses: Option<usize>) -> TranslationOptions {
+    TranslationOptions {
+        include_collins: Some(true),
+        max_senses,
+        ..TranslationOptions::default()
+    }
+}
+
+#[test]
+fn senses_are_parsed_with_their_examples() {
+    let (translation, warnings) =
+        parse_translation_with_options("take", TAKE_COLLINS_HTML, &selectors(), &collins(None))
+            .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.collins.len(), 3);
+    assert_eq!(
+        translation.collins[0],
+        CollinsSense {
+            number: 1,
+            pos: "VERB".to_string(),
+            en_def: "If you take something, you reach out for it and hold it.".to_string(),
+            zh_def: "拿；取".to_string(),
+            examples: vec![
+                Example {
+                    en: "Let me take your coat.".to_string(),
+                    zh: "我来帮你拿外套吧。".to_string(),
+                },
+                Example {
+                    en: "She took my hand.".to_string(),
+                    zh: "她握住了我的手。".to_string(),
+                },
+            ],
+        }
+    );
+    assert_eq!(translation.collins[2].number, 3);
+    assert_eq!(translation.collins[2].pos, "N-COUNT");
+    assert!(translation.collins[2].examples.is_empty());
+    // The concise definitions are parsed as before.
+    assert_eq!(translation.entries.len(), 2);
+}
+
+#[test]
+fn pages_without_a_collins_section_have_no_senses() {
+    let (translation, warnings) =
+        parse_translation_with_options("hello", HELLO_HTML, &selectors(), &collins(None)).unwrap();
+    assert!(translation.collins.is_empty());
+    assert!(warnings.is_empty(), "{warnings:?}");
+
+    // Numbered web translations are not mistaken for Collins senses.
+    let take = include_str!("fixtures/take_full.html");
+    let (translation, _) =
+        parse_translation_with_options("take", take, &selectors(), &collins(None)).unwrap();
+    assert!(translation.collins.is_empty());
+}
+
+#[test]
+fn collins_is_only_parsed_when_requested() {
+    let translation = parse_translation("take", TAKE_COLLINS_HTML).unwrap();
+    assert!(translation.collins.is_empty());
+}
+
+#[test]
+fn max_senses_caps_the_senses() {
+    let (translation, _) =
+        parse_translation_with_options("take", TAKE_COLLINS_HTML, &selectors(), &collins(Some(2)))
+            .unwrap();
+    let numbers: Vec<_> = translation
+        .collins
+        .iter()
+        .map(|sense| sense.number)
+        .collect();
+    assert_eq!(numbers, [1, 2]);
+}
+
+#[test]
+fn senses_without_a_definition_are_skipped_with_a_warning() {
+    let html = TAKE_COLLINS_HTML.replace(
+        r#"<span class="def-en">If you take something or someone with you, you carry or bring them.</span>"#,
+        "",
+    );
+    let (translation, warnings) =
+        parse_translation_with_options("take", &html, &selectors(), &collins(None)).unwrap();
+    let numbers: Vec<_> = translation
+        .collins
+        .iter()
+        .map(|sense| sense.number)
+        .collect();
+    assert_eq!(numbers, [1, 3]);
+    assert_eq!(warnings, [ParseWarning::CollinsDefinitionMissing]);
+}
+
+#[test]
+fn senses_follow_the_definitions() {
+    let (translation, _) =
+        parse_translation_with_options("take", TAKE_COLLINS_HTML, &selectors(), &collins(Some(2)))
+            .unwrap();
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "英 /teɪk/ 美 /teɪk/
+v.: 拿，取；带去；服用
+n.: 镜头；收入
+
+1. VERB If you take something, you reach out for it and hold it.
+   拿；取
+   · Let me take your coat.
+     我来帮你拿外套吧。
+   · She took my hand.
+     她握住了我的手。
+2. VERB If you take something or someone with you, you carry or bring them.
+   带去；拿去"
+    );
+
+    let rendered = render_pretty(&translation, &RenderOptions::default(), 40);
+    assert!(
+        rendered.contains(
+            "1. VERB If you take something, you reach
+   out for it and hold it."
+        ),
+        "{rendered}"
+    );
+    let phonetics = RenderOptions {
+        sections: Sections::PhoneticsOnly,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        render_translation(&translation, &phonetics),
+        "英 /teɪk/ 美 /teɪk/"
+    );
+
+    let json = translation_to_json(&translation);
+    assert_eq!(json["collins"][0]["examples"][1]["en"], "She took my hand.");
+    assert_eq!(json["collins"][1]["zh_def"], "带去；拿去");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_shows_collins_with_detail() {
+    let server = MockServer::start(TAKE_COLLINS_HTML);
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--no-wrap"])
+            .args(args)
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir("collins"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let stdout = run(&["--detail", "collins", "--max-senses", "1", "take"]);
+    assert!(
+        stdout.contains("1. VERB If you take something, you reach out for it and hold it."),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("2. VERB"), "{stdout}");
+
+    let stdout = run(&["take"]);
+    assert!(!stdout.contains("VERB"), "{stdout}");
+}
@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+#[cfg(feature = "http")]
+use ydt::{Fetcher, StatusCode, YdtError};
+
+pub const HELLO_HTML: &str = include_str!("../fixtures/hello.html");
+pub const RUN_HTML: &str = include_str!("../fixtures/run.html");
+
+/// Fresh, empty directory under the system temp dir, unique to this test process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ydt-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Fetcher serving canned pages; unknown words fail with `404 Not Found`.
+#[cfg(feature = "http")]
+#[derive(Default)]
+pub struct MockFetcher {
+    pages: HashMap<String, Result<String, StatusCode>>,
+}
+
+#[cfg(feature = "http")]
+impl MockFetcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn page(mut self, word: &str, html: &str) -> Self {
+        self.pages.insert(word.to_string(), Ok(html.to_string()));
+        self
+    }
+
+    pub fn status(mut self, word: &str, status: StatusCode) -> Self {
+        self.pages.insert(word.to_string(), Err(status));
+        self
+    }
+}
+
+#[cfg(feature = "http")]
+impl Fetcher for MockFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        let status = match self.pages.get(word) {
+            Some(Ok(html)) => return Ok(html.clone()),
+            Some(Err(status)) => *status,
+            None => StatusCode::NOT_FOUND,
+        };
+        Err(YdtError::HttpStatus {
+            word: word.to_string(),
+            url: format!("mock://{word}"),
+            status,
+        })
+    }
+}
+
+/// Fetcher counting the pages it is asked for, served by a [`MockFetcher`].
+#[cfg(feature = "http")]
+pub struct CountingFetcher {
+    inner: MockFetcher,
+    fetches: AtomicUsize,
+}
+
+#[cfg(feature = "http")]
+impl CountingFetcher {
+    pub fn new(inner: MockFetcher) -> Self {
+        Self {
+            inner,
+            fetches: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of pages asked for so far.
+    pub fn fetches(&self) -> usize {
+        self.fetches.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(feature = "http")]
+impl Fetcher for CountingFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        self.inner.fetch(word)
+    }
+}
+
+/// Request line and headers received by a [`MockServer`].
+#[derive(Debug)]
+pub struct RecordedRequest {
+    /// Request target, e.g. `/result?word=hello&lang=en`.
+    pub target: String,
+    /// Headers with lowercase names.
+    pub headers: HashMap<String, String>,
+}
+
+/// Local HTTP server recording the requests it receives.
+pub struct MockServer {
+    pub url: String,
+    requests: Receiver<RecordedRequest>,
+}
+
+impl MockServer {
+    /// Answer every request with `200 OK` and `body`.
+    pub fn start(body: &'static str) -> Self {
+        Self::start_with(move |_| (200, body))
+    }
+
+    /// Answer every request with `200 OK` and a body that need not be UTF-8.
+    pub fn start_bytes(body: &'static [u8]) -> Self {
+        Self::serve(Vec::new(), move |_| (200, body))
+    }
+
+    /// Like [`start_bytes`](Self::start_bytes), also sending `content_type` as
+    /// the `Content-Type` header.
+    pub fn start_bytes_as(content_type: &'static str, body: &'static [u8]) -> Self {
+        Self::start_bytes_with_headers(&[("Content-Type", content_type)], body)
+    }
+
+    /// Like [`start_bytes`](Self::start_bytes), also sending `headers` with
+    /// every response.
+    pub fn start_bytes_with_headers(
+        headers: &[(&'static str, &'static str)],
+        body: &'static [u8],
+    ) -> Self {
+        Self::serve(headers.to_vec(), move |_| (200, body))
+    }
+
+    /// Answer each request with the status and body returned by `respond`.
+    ///
+    /// For redirect statuses (3xx) the body is sent as the `Location` header.
+    pub fn start_with(
+        respond: impl Fn(&RecordedRequest) -> (u16, &'static str) + Send + 'static,
+    ) -> Self {
+        Self::start_with_headers(&[], respond)
+    }
+
+    /// Like [`start_with`](Self::start_with), also sending `headers` with every
+    /// response.
+    pub fn start_with_headers(
+        headers: &[(&'static str, &'static str)],
+        respond: impl Fn(&RecordedRequest) -> (u16, &'static str) + Send + 'static,
+    ) -> Self {
+        Self::serve(headers.to_vec(), move |request| {
+            let (status, body) = respond(request);
+            (status, body.as_bytes())
+        })
+    }
+
+    fn serve(
+        response_headers: Vec<(&'static str, &'static str)>,
+        respond: impl Fn(&RecordedRequest) -> (u16, &'static [u8]) + Send + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let target = line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut headers = HashMap::new();
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).unwrap() == 0 || line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+                    }
+                }
+                let request = RecordedRequest { target, headers };
+                let (status, body) = respond(&request);
+                // Record before answering so the request is visible once the client returns.
+                if tx.send(request).is_err() {
+                    break;
+                }
+                let (mut extra, body): (_, &[u8]) = match status {
+                    300..=399 => (
+                        format!("Location: {}\r\n", String::from_utf8_lossy(body)),
+                        b"",
+                    ),
+                    _ => (String::new(), body),
+                };
+                for (name, value) in &response_headers {
+                    extra.push_str(&format!("{name}: {value}\r\n"));
+                }
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Mock\r\n{extra}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(body);
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// The next request the server received.
+    pub fn next_request(&self) -> RecordedRequest {
+        self.requests.recv().unwrap()
+    }
+
+    /// Requests received so far that have not been taken yet.
+    pub fn pending_requests(&self) -> Vec<RecordedRequest> {
+        self.requests.try_iter().collect()
+    }
+}
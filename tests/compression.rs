@@ -0,0 +1,99 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{YdtClient, YdtError};
+
+const HELLO_GZIP: &[u8] = include_bytes!("fixtures/hello.html.gz");
+const HELLO_BROTLI: &[u8] = include_bytes!("fixtures/hello.html.br");
+
+fn client(server: &MockServer) -> YdtClient {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build()
+}
+
+#[test]
+fn gzip_and_brotli_are_requested_and_decoded() {
+    let mut expected = client(&MockServer::start(HELLO_HTML))
+        .translate("hello")
+        .unwrap();
+    // Each server listens on a port of its own.
+    expected.source_url = None;
+    for (encoding, body) in [("gzip", HELLO_GZIP), ("br", HELLO_BROTLI)] {
+        let server = MockServer::start_bytes_with_headers(&[("Content-Encoding", encoding)], body);
+        let (mut translation, info) = client(&server).translate_detailed("hello").unwrap();
+
+        translation.source_url = None;
+        assert_eq!(translation, expected, "{encoding}");
+        assert_eq!(info.content_encoding, Some(encoding));
+        let request = server.next_request();
+        assert_eq!(
+            request.headers.get("accept-encoding").map(String::as_str),
+            Some("gzip, br")
+        );
+    }
+}
+
+#[test]
+fn uncompressed_pages_have_no_content_encoding() {
+    let server = MockServer::start(HELLO_HTML);
+    let (_, info) = client(&server).translate_detailed("hello").unwrap();
+    assert_eq!(info.content_encoding, None);
+}
+
+#[test]
+fn gzip_body_without_header_is_detected() {
+    let server = MockServer::start_bytes(HELLO_GZIP);
+    let err = client(&server).translate("hello").unwrap_err();
+
+    match &err {
+        YdtError::CompressedBody { word, encoding, .. } => {
+            assert_eq!(word, "hello");
+            assert_eq!(encoding, "gzip");
+        }
+        other => panic!("expected CompressedBody, got {other:?}"),
+    }
+    assert!(err.to_string().contains("Content-Encoding"), "{err}");
+    assert_eq!(err.to_owned_report().kind, "compressed_body");
+}
+
+#[test]
+fn unsupported_encodings_are_reported() {
+    let server = MockServer::start_bytes_with_headers(
+        &[("Content-Encoding", "zstd")],
+        &[0x28, 0xb5, 0x2f, 0xfd, 0x00],
+    );
+    let err = client(&server).translate("hello").unwrap_err();
+    assert!(
+        matches!(&err, YdtError::CompressedBody { encoding, .. } if encoding == "zstd"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn corrupt_gzip_body_fails_to_decode() {
+    let server =
+        MockServer::start_bytes_with_headers(&[("Content-Encoding", "gzip")], &HELLO_GZIP[..40]);
+    let err = client(&server).translate("hello").unwrap_err();
+    assert!(matches!(err, YdtError::Decode { .. }), "{err:?}");
+}
+
+#[test]
+fn decompressed_body_is_held_to_the_size_limit() {
+    assert!(HELLO_GZIP.len() < 300 && HELLO_HTML.len() > 300);
+    let server = MockServer::start_bytes_with_headers(&[("Content-Encoding", "gzip")], HELLO_GZIP);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .max_body_size(300)
+        .build();
+    let err = client.translate("hello").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("decompressed body is larger than 300 bytes"),
+        "{err}"
+    );
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "http")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use ydt::{Fetcher, ResultOrder, YdtClient, YdtError};
+
+/// Fetcher that tracks how many requests are in flight at once.
+#[derive(Default)]
+struct CountingFetcher {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl Fetcher for CountingFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(10));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(format!(
+            r#"<li class="word-exp-ce mcols-layout"><a class="point">{word}</a></li>"#
+        ))
+    }
+}
+
+fn words() -> Vec<String> {
+    (0..12).map(|i| format!("词{i}")).collect()
+}
+
+#[test]
+fn never_exceeds_concurrency_limit() {
+    let fetcher = Arc::new(CountingFetcher::default());
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    let results = client.translate_many(&words(), 3);
+
+    assert_eq!(results.len(), 12);
+    assert!(fetcher.max_in_flight.load(Ordering::SeqCst) <= 3);
+    assert!(fetcher.max_in_flight.load(Ordering::SeqCst) > 1);
+}
+
+#[test]
+fn results_keep_input_order() {
+    let fetcher = Arc::new(CountingFetcher::default());
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    let results = client.translate_many(&words(), 4);
+
+    let meanings: Vec<String> = results
+        .into_iter()
+        .map(|result| result.unwrap().entries.remove(0).meaning)
+        .collect();
+    assert_eq!(meanings, words());
+}
+
+#[test]
+fn completion_order_reports_every_word() {
+    let fetcher = Arc::new(CountingFetcher::default());
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+    let seen = Mutex::new(Vec::new());
+
+    client.translate_each_concurrent(words(), 4, ResultOrder::Completion, |word, result| {
+        assert!(result.is_ok());
+        seen.lock().unwrap().push(word.to_string());
+    });
+
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort();
+    let mut expected = words();
+    expected.sort();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn single_worker_is_sequential() {
+    let fetcher = Arc::new(CountingFetcher::default());
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    client.translate_many(&words()[..4], 1);
+
+    assert_eq!(fetcher.max_in_flight.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+use ydt::config::{Config, TEMPLATE};
+use ydt::{Accent, ColorChoice, OutputFormat, YdtError};
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    common::temp_dir(&format!("config-{name}"))
+}
+
+#[test]
+fn flags_override_file_which_overrides_defaults() {
+    let file = Config::from_toml(
+        r#"
+        timeout = 5
+        retries = 2
+        accent = "uk"
+        color = "always"
+        cache_capacity = 0
+        "#,
+    )
+    .unwrap();
+    let flags = Config {
+        timeout: Some(Duration::from_secs(3)),
+        format: Some(OutputFormat::Tsv),
+        color: Some(ColorChoice::Never),
+        ..Config::default()
+    };
+
+    let merged = Config::default().merge(file).merge(flags);
+
+    assert_eq!(merged.timeout, Some(Duration::from_secs(3)));
+    assert_eq!(merged.retries, Some(2));
+    assert_eq!(merged.accent, Some(Accent::Uk));
+    assert_eq!(merged.format, Some(OutputFormat::Tsv));
+    assert_eq!(merged.color, Some(ColorChoice::Never));
+    assert_eq!(merged.cache_capacity, Some(0));
+    assert_eq!(merged.proxy, None);
+}
+
+#[test]
+fn template_is_a_valid_empty_config() {
+    assert_eq!(Config::from_toml(TEMPLATE).unwrap(), Config::default());
+}
+
+#[test]
+fn invalid_values_name_the_offending_key() {
+    for (text, key) in [
+        ("timeout = \"fast\"", "timeout"),
+        ("timeout = 0", "timeout"),
+        ("timeout = 1e20", "timeout"),
+        ("deadline = 1e20", "deadline"),
+        ("deadline = inf", "deadline"),
+        ("retries = 1.5", "retries"),
+        ("proxy = \"not a url\"", "proxy"),
+        ("accent = \"au\"", "accent"),
+        ("format = \"xml\"", "format"),
+        ("color = \"sometimes\"", "color"),
+        ("cache_capacity = -1", "cache_capacity"),
+        ("colour = \"never\"", "colour"),
+    ] {
+        match Config::from_toml(text) {
+            Err(YdtError::ConfigFile {
+                key: Some(found), ..
+            }) => assert_eq!(found, key),
+            other => panic!("expected error for {key}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn syntax_errors_are_reported() {
+    let err = Config::from_toml("timeout = ").unwrap_err();
+    assert!(matches!(err, YdtError::ConfigFile { key: None, .. }));
+}
+
+#[test]
+fn missing_file_is_empty_config() {
+    let dir = temp_dir("missing");
+    assert_eq!(
+        Config::load(&dir.join("config.toml")).unwrap(),
+        Config::default()
+    );
+}
+
+#[test]
+fn cli_prints_path_and_writes_template() {
+    let dir = temp_dir("cli");
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(args)
+            .env("XDG_CONFIG_HOME", &dir)
+            .output()
+            .unwrap()
+    };
+    let expected = dir.join("ydt").join("config.toml");
+
+    let output = run(&["config", "path"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        expected.display().to_string()
+    );
+
+    assert!(run(&["config", "init"]).status.success());
+    assert_eq!(fs::read_to_string(&expected).unwrap(), TEMPLATE);
+    assert!(!run(&["config", "init"]).status.success());
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use common::HELLO_HTML;
+use ydt::{YdtClient, YdtClientBuilder};
+
+/// Local HTTP/1.1 server that keeps connections open, counting the
+/// connections it accepts.
+struct KeepAliveServer {
+    url: String,
+    connections: Arc<AtomicUsize>,
+}
+
+impl KeepAliveServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = Arc::clone(&connections);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                accepted.fetch_add(1, Ordering::SeqCst);
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    loop {
+                        // Request line and headers, up to the blank line.
+                        let mut ended = true;
+                        loop {
+                            line.clear();
+                            match reader.read_line(&mut line) {
+                                Ok(0) | Err(_) => return,
+                                Ok(_) if line.trim().is_empty() => break,
+                                Ok(_) => ended = false,
+                            }
+                        }
+                        if ended {
+                            return;
+                        }
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{HELLO_HTML}",
+                            HELLO_HTML.len()
+                        );
+                        if stream.write_all(response.as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        Self { url, connections }
+    }
+
+    fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+fn builder(server: &KeepAliveServer) -> YdtClientBuilder {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        // Settings other than the defaults give the client a pool of its own.
+        .tcp_keepalive(Duration::from_secs(30))
+}
+
+#[test]
+fn idle_connections_are_reused() {
+    let server = KeepAliveServer::start();
+    let client = builder(&server).build();
+    for _ in 0..3 {
+        client.translate("hello").unwrap();
+    }
+    assert_eq!(server.connections(), 1);
+}
+
+#[test]
+fn no_idle_connections_open_one_per_request() {
+    let server = KeepAliveServer::start();
+    let client = builder(&server).pool_max_idle_per_host(0).build();
+    for _ in 0..3 {
+        client.translate("hello").unwrap();
+    }
+    assert_eq!(server.connections(), 3);
+}
+
+#[test]
+fn connections_idle_past_the_timeout_are_closed() {
+    let server = KeepAliveServer::start();
+    let client = builder(&server)
+        .pool_idle_timeout(Duration::from_millis(50))
+        .build();
+    client.translate("hello").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    client.translate("hello").unwrap();
+    assert_eq!(server.connections(), 2);
+}
+
+#[test]
+fn time_to_headers_is_reported() {
+    let server = KeepAliveServer::start();
+    let client = builder(&server).build();
+    let (_, info) = client.translate_detailed("hello").unwrap();
+    let time_to_headers = info.time_to_headers.unwrap();
+    assert!(time_to_headers <= info.elapsed, "{info:?}");
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::config::Config;
+use ydt::YdtClient;
+
+const SET_COOKIE: (&str, &str) = ("Set-Cookie", "visitor=abc123; Max-Age=3600; Path=/");
+
+fn server_setting_cookie() -> MockServer {
+    MockServer::start_with_headers(&[SET_COOKIE], |_| (200, HELLO_HTML))
+}
+
+fn lookup(server: &MockServer, cookie_file: &Path) {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .cookie_store_path(cookie_file)
+        .build()
+        .translate("hello")
+        .unwrap();
+}
+
+#[test]
+fn cookie_is_saved_and_sent_by_the_next_client() {
+    let dir = temp_dir("cookie_round_trip");
+    let path = dir.join("cache").join("cookies.json");
+    let server = server_setting_cookie();
+
+    lookup(&server, &path);
+    assert_eq!(server.next_request().headers.get("cookie"), None);
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("abc123"), "{saved}");
+
+    lookup(&server, &path);
+    assert_eq!(
+        server
+            .next_request()
+            .headers
+            .get("cookie")
+            .map(String::as_str),
+        Some("visitor=abc123")
+    );
+}
+
+#[test]
+fn corrupt_cookie_file_is_replaced() {
+    let dir = temp_dir("cookie_corrupt");
+    let path = dir.join("cookies.json");
+    fs::write(&path, "{ not json").unwrap();
+    let server = server_setting_cookie();
+
+    lookup(&server, &path);
+
+    assert_eq!(server.next_request().headers.get("cookie"), None);
+    assert!(fs::read_to_string(&path).unwrap().contains("abc123"));
+}
+
+#[test]
+fn nothing_is_written_without_new_cookies() {
+    let dir = temp_dir("cookie_unchanged");
+    let path = dir.join("cookies.json");
+    let server = MockServer::start(HELLO_HTML);
+
+    lookup(&server, &path);
+
+    assert!(!path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn cookie_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_dir("cookie_private");
+    let path = dir.join("cookies.json");
+    fs::write(&path, "[]").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    lookup(&server_setting_cookie(), &path);
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn config_can_disable_the_cookie_file() {
+    let config = Config::from_toml("cookie_file = false").unwrap();
+    assert_eq!(config.cookie_file, Some(false));
+    assert!(Config::from_toml("cookie_file = \"no\"").is_err());
+}
+
+fn run_cli(server: &MockServer, dir: &Path, extra: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(extra)
+        .arg("hello")
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn cli_keeps_cookies_between_runs() {
+    let dir = temp_dir("cookie_cli");
+    let server = server_setting_cookie();
+
+    run_cli(&server, &dir, &[]);
+    run_cli(&server, &dir, &[]);
+
+    assert!(dir.join("cache/ydt/cookies.json").exists());
+    let requests = server.pending_requests();
+    assert_eq!(requests[0].headers.get("cookie"), None);
+    assert_eq!(
+        requests[1].headers.get("cookie").map(String::as_str),
+        Some("visitor=abc123")
+    );
+}
+
+#[test]
+fn cli_flag_disables_the_cookie_file() {
+    let dir = temp_dir("cookie_cli_disabled");
+    let server = server_setting_cookie();
+
+    run_cli(&server, &dir, &["--no-cookie-file"]);
+
+    assert!(!dir.join("cache").exists());
+    assert_eq!(server.pending_requests().len(), 1);
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{Fetcher, YdtClient, YdtError};
+
+/// Leeway for the time a lookup takes beyond its deadline.
+const TOLERANCE: Duration = Duration::from_millis(400);
+
+/// Server answering every request with `status` after `delay`.
+fn slow_server(delay: Duration, status: u16) -> MockServer {
+    MockServer::start_with(move |_| {
+        thread::sleep(delay);
+        (status, HELLO_HTML)
+    })
+}
+
+fn deadline_tier(err: YdtError) -> String {
+    match err {
+        YdtError::DeadlineExceeded { tier, .. } => tier,
+        other => panic!("expected DeadlineExceeded, got {other:?}"),
+    }
+}
+
+#[test]
+fn slow_request_is_cut_short_by_the_deadline() {
+    let server = slow_server(Duration::from_secs(2), 200);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_url(format!("{}/dict", server.url))
+        .retries(2)
+        .deadline(Duration::from_millis(500))
+        .build();
+
+    let start = Instant::now();
+    let err = client.translate("hello").unwrap_err();
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed < Duration::from_millis(500) + TOLERANCE,
+        "{elapsed:?}"
+    );
+    assert_eq!(err.to_owned_report().kind, "deadline_exceeded");
+    assert_eq!(err.word(), Some("hello"));
+    assert!(err.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "Lookup of \"hello\" took longer than 500ms; stopped during the desktop source \
+         (request with the project user agent)"
+    );
+}
+
+#[test]
+fn requests_other_than_lookups_are_cut_short_by_the_deadline() {
+    let server = slow_server(Duration::from_millis(300), 500);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .suggest_url(format!("{}/suggest", server.url))
+        .retries(5)
+        .deadline(Duration::from_millis(500))
+        .build();
+
+    let requests: [&dyn Fn() -> Result<(), YdtError>; 2] =
+        [&|| client.fetch_html("hello").map(drop), &|| {
+            client.suggest("hel", 5).map(drop)
+        }];
+    for request in requests {
+        let start = Instant::now();
+        let err = request().unwrap_err();
+        let elapsed = start.elapsed();
+
+        assert_eq!(err.to_owned_report().kind, "deadline_exceeded", "{err:?}");
+        assert!(
+            elapsed < Duration::from_millis(500) + TOLERANCE,
+            "{elapsed:?}"
+        );
+    }
+}
+
+#[test]
+fn retries_do_not_wait_past_the_deadline() {
+    let server = slow_server(Duration::from_millis(100), 500);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .retries(5)
+        .deadline(Duration::from_secs(1))
+        .build();
+
+    let start = Instant::now();
+    let err = client.translate("hello").unwrap_err();
+    let elapsed = start.elapsed();
+
+    // The first retry waits 500ms and the second would wait a second.
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+    assert_eq!(deadline_tier(err), "desktop source (wait before retry 2)");
+    assert_eq!(server.pending_requests().len(), 2);
+}
+
+#[test]
+fn deadline_spans_the_mobile_fallback() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/result") {
+            thread::sleep(Duration::from_millis(300));
+            (503, "")
+        } else {
+            thread::sleep(Duration::from_secs(2));
+            (200, HELLO_HTML)
+        }
+    });
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_url(format!("{}/dict", server.url))
+        .ua_strategy(ydt::UaStrategy::BrowserOnly)
+        .deadline(Duration::from_millis(800))
+        .build();
+
+    let start = Instant::now();
+    let err = client.translate("hello").unwrap_err();
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed < Duration::from_millis(800) + TOLERANCE,
+        "{elapsed:?}"
+    );
+    assert_eq!(
+        deadline_tier(err),
+        "mobile source (request with the browser user agent)"
+    );
+}
+
+/// Fetcher that answers with its page after a while.
+struct SlowFetcher(&'static str);
+
+impl Fetcher for SlowFetcher {
+    fn fetch(&self, _word: &str) -> Result<String, YdtError> {
+        thread::sleep(Duration::from_millis(300));
+        Ok(self.0.to_string())
+    }
+}
+
+#[test]
+fn lookups_within_the_deadline_succeed() {
+    let client = YdtClient::builder()
+        .fetcher(SlowFetcher(HELLO_HTML))
+        .deadline(Duration::from_secs(5))
+        .build();
+    assert!(client.translate("hello").is_ok());
+
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .deadline(Duration::ZERO)
+        .build();
+    assert!(client.translate("hello").is_ok());
+}
+
+#[test]
+fn no_source_is_tried_after_the_deadline() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .fetcher(SlowFetcher("<html><body></body></html>"))
+        .mobile_url(format!("{}/dict", server.url))
+        .mobile_fallback(true)
+        .deadline(Duration::from_millis(100))
+        .build();
+
+    // The custom fetcher overran the deadline and found nothing, which is
+    // returned instead of trying the mobile site.
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert!(server.pending_requests().is_empty());
+
+    let client = YdtClient::builder()
+        .fetcher(SlowFetcher("<html><body></body></html>"))
+        .mobile_url(format!("{}/dict", server.url))
+        .mobile_fallback(true)
+        .build();
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[test]
+fn deadline_is_read_from_config() {
+    let config = Config::from_toml("deadline = 2.5").unwrap();
+    assert_eq!(config.deadline, Some(Duration::from_millis(2500)));
+    assert!(Config::from_toml("deadline = 0").is_err());
+}
@@ -0,0 +1,70 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use common::{MockFetcher, HELLO_HTML};
+#[cfg(feature = "http")]
+use ydt::YdtClient;
+use ydt::{
+    parse_translation, parse_translation_with_options, Accent, SelectorConfig, TranslationOptions,
+};
+
+const TAKE_FULL_HTML: &str = include_str!("fixtures/take_full.html");
+
+#[test]
+fn definitions_are_borrowed_in_page_order() {
+    let translation = parse_translation("take", TAKE_FULL_HTML).unwrap();
+    assert_eq!(translation.definitions().count(), translation.entries.len());
+    assert_eq!(translation.first_definition(), translation.entries.first());
+    assert!(std::ptr::eq(
+        translation.definitions().next().unwrap(),
+        &translation.entries[0]
+    ));
+}
+
+#[test]
+fn phonetics_are_found_by_accent() {
+    let html = include_str!("fixtures/hello.html");
+    let translation = parse_translation("hello", html).unwrap();
+    assert_eq!(translation.phonetic(Accent::Uk), Some("/həˈləʊ/"));
+    assert_eq!(translation.phonetic(Accent::Us), Some("/həˈloʊ/"));
+
+    let translation = parse_translation("学习", include_str!("fixtures/xuexi_full.html")).unwrap();
+    assert_eq!(translation.phonetic(Accent::Us), None);
+}
+
+#[test]
+fn parsing_stops_after_the_definitions_kept() {
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let options = TranslationOptions {
+        max_definitions: Some(1),
+        ..TranslationOptions::default()
+    };
+    let (first, warnings) =
+        parse_translation_with_options("take", TAKE_FULL_HTML, &selectors, &options).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    let full = parse_translation("take", TAKE_FULL_HTML).unwrap();
+    assert_eq!(first.entries, full.entries[..1]);
+    assert_eq!(first.phonetics, full.phonetics);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn translate_first_returns_the_first_meaning() {
+    let client = YdtClient::builder()
+        .fetcher(
+            MockFetcher::new()
+                .page("hello", HELLO_HTML)
+                .page("qwzx", r#"<div class="trans-container"></div>"#),
+        )
+        .translation_options(TranslationOptions {
+            include_collins: Some(true),
+            ..TranslationOptions::default()
+        })
+        .build();
+    assert_eq!(
+        client.translate_first("hello").unwrap().as_deref(),
+        Some("你好；喂")
+    );
+    assert_eq!(client.translate_first("qwzx").unwrap(), None);
+}
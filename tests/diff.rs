@@ -0,0 +1,145 @@
+// The side-by-side layout of `ydt diff`, checked without running lookups.
+#[cfg(feature = "http")]
+mod common;
+
+#[allow(dead_code)]
+#[path = "../src/diff.rs"]
+mod diff;
+
+use diff::{render, Side};
+use ydt::{display_width, Entry, EntrySource, Phonetic, Translation, YdtError};
+
+fn translation(word: &str, phonetic: &str, entries: &[(&str, &str)]) -> Translation {
+    Translation {
+        word: word.to_string(),
+        phonetics: vec![Phonetic {
+            label: "英".to_string(),
+            text: phonetic.to_string(),
+        }],
+        entries: entries
+            .iter()
+            .map(|(pos, meaning)| Entry {
+                pos: pos.to_string(),
+                meaning: meaning.to_string(),
+                source: EntrySource::Concise,
+            })
+            .collect(),
+        ..Translation::default()
+    }
+}
+
+fn affect() -> Result<Translation, YdtError> {
+    Ok(translation(
+        "affect",
+        "/əˈfekt/",
+        &[("v.", "影响；感动"), ("n.", "情感")],
+    ))
+}
+
+fn effect() -> Result<Translation, YdtError> {
+    Ok(translation("effect", "/ɪˈfekt/", &[("n.", "效果；影响")]))
+}
+
+fn side<'a>(word: &'a str, result: &'a Result<Translation, YdtError>) -> Side<'a> {
+    Side { word, result }
+}
+
+#[test]
+fn words_are_shown_in_aligned_columns() {
+    let (affect, effect) = (affect(), effect());
+    assert_eq!(
+        render(&side("affect", &affect), &side("effect", &effect), 47),
+        "affect                 │ effect\n\
+         英 /əˈfekt/            │ 英 /ɪˈfekt/\n\
+         v. 影响；感动          │ n. 效果；影响\n\
+         n. 情感                │"
+    );
+}
+
+#[test]
+fn wide_characters_wrap_within_their_column() {
+    let long = Ok(translation(
+        "effect",
+        "/ɪˈfekt/",
+        &[("n.", "效果；影响；作用；结果；印象")],
+    ));
+    let affect = affect();
+    let output = render(&side("affect", &affect), &side("effect", &long), 47);
+
+    assert_eq!(
+        output,
+        "affect                 │ effect\n\
+         英 /əˈfekt/            │ 英 /ɪˈfekt/\n\
+         v. 影响；感动          │ n. 效果；影响；作用；\n\
+         \x20                      │ 结果；印象\n\
+         n. 情感                │"
+    );
+    assert!(output.lines().all(|line| display_width(line) <= 47));
+}
+
+#[test]
+fn narrow_terminals_stack_the_words() {
+    let (affect, effect) = (affect(), effect());
+    assert_eq!(
+        render(&side("affect", &affect), &side("effect", &effect), 40),
+        "affect\n英 /əˈfekt/\nv. 影响；感动\nn. 情感\n\n\
+         effect\n英 /ɪˈfekt/\nn. 效果；影响"
+    );
+}
+
+#[test]
+fn a_failed_lookup_is_shown_next_to_the_other() {
+    let affect = affect();
+    let message = YdtError::EmptyWord.to_string();
+    let failed = Err(YdtError::EmptyWord);
+    let empty = Ok(Translation {
+        word: "xyzzy".to_string(),
+        ..Translation::default()
+    });
+
+    let output = render(&side("affect", &affect), &side("", &failed), 80);
+    assert!(output.lines().nth(2).unwrap().ends_with(&message));
+
+    assert_eq!(
+        render(&side("xyzzy", &empty), &side("affect", &affect), 47),
+        "xyzzy                  │ affect\n\
+         \x20                      │ 英 /əˈfekt/\n\
+         No results.            │ v. 影响；感动\n\
+         \x20                      │ n. 情感"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_shows_both_words_when_one_fails() {
+    use std::process::Command;
+
+    use common::{temp_dir, MockServer, HELLO_HTML};
+
+    let server = MockServer::start_with(|request| {
+        if request.target.contains("word=hello&") {
+            (200, HELLO_HTML)
+        } else {
+            (404, "")
+        }
+    });
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["diff", "--width", "60", "hello", "helo"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("YDT_MOBILE_URL", format!("{}/dict", server.url))
+        .env("YDT_RETRIES", "0")
+        .env("XDG_CONFIG_HOME", temp_dir("diff_cli"))
+        .env("XDG_CACHE_HOME", temp_dir("diff_cli_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert!(
+        lines[0].starts_with("hello ") && lines[0].ends_with("│ helo"),
+        "{stdout}"
+    );
+    assert!(lines[2].starts_with("int. 你好；喂"), "{stdout}");
+    assert!(lines[2].contains("│ "), "{stdout}");
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    openapi, parse_translation, parse_translation_with_options, translation_to_json, Direction,
+    LookupStrategy, SelectorConfig, TranslationOptions,
+};
+
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+const C_LANGUAGE_HTML: &str = include_str!("fixtures/c_language.html");
+const USB_CE_HTML: &str = include_str!("fixtures/usb_ce_layout.html");
+const XUEXI_PINYIN_HTML: &str = include_str!("fixtures/xuexi_pinyin.html");
+const HA_BOTH_HTML: &str = include_str!("fixtures/ha_both.html");
+
+#[test]
+fn english_words_read_english_to_chinese() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert_eq!(translation.direction, Some(Direction::EnToZh));
+    assert_eq!(translation_to_json(&translation)["direction"], "en_to_zh");
+}
+
+#[test]
+fn direction_follows_the_layout_that_matched() {
+    // Chinese query answered with the English-Chinese layout.
+    let translation = parse_translation("C语言", C_LANGUAGE_HTML).unwrap();
+    assert_eq!(translation.direction, Some(Direction::EnToZh));
+
+    // ASCII query answered with the Chinese-English layout.
+    let translation = parse_translation("usb", USB_CE_HTML).unwrap();
+    assert_eq!(translation.direction, Some(Direction::ZhToEn));
+    assert_eq!(translation_to_json(&translation)["direction"], "zh_to_en");
+}
+
+#[test]
+fn pinyin_queries_read_as_pinyin() {
+    let translation = parse_translation("xuexi", XUEXI_PINYIN_HTML).unwrap();
+    assert_eq!(translation.direction, Some(Direction::Pinyin));
+}
+
+#[test]
+fn every_direction_read_is_recorded() {
+    let options = TranslationOptions {
+        strategy: Some(LookupStrategy::Both),
+        ..TranslationOptions::default()
+    };
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let (translation, _) =
+        parse_translation_with_options("ha", HA_BOTH_HTML, &selectors, &options).unwrap();
+    assert_eq!(translation.direction, Some(Direction::EnToZh));
+    assert_eq!(
+        translation.other_directions[0].direction,
+        Some(Direction::Pinyin)
+    );
+}
+
+#[test]
+fn empty_results_have_no_direction() {
+    let translation = parse_translation("xyzzy", "<html><body></body></html>").unwrap();
+    assert_eq!(translation.direction, None);
+    assert!(translation_to_json(&translation).get("direction").is_none());
+}
+
+#[test]
+fn machine_translations_read_as_sentences() {
+    let json = r#"{"errorCode": "0", "translation": ["你好吗？"]}"#;
+    let translation = openapi::parse_response("how are you?", json).unwrap();
+    assert_eq!(translation.direction, Some(Direction::Sentence));
+    assert_eq!(translation.direction.unwrap().to_string(), "sentence");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_the_direction_when_verbose() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "-v", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("direction"))
+        .env("XDG_CACHE_HOME", temp_dir("direction_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("direction: en→zh\n"), "{stderr}");
+}
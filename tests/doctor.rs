@@ -0,0 +1,200 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use serde_json::Value;
+use ydt::{build_info, build_info_to_json, StatusCode, YdtClient};
+
+/// An address nothing listens on.
+fn closed_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}/result", listener.local_addr().unwrap())
+}
+
+/// Run `ydt doctor --json` against `base_url`, with config and cache
+/// directories under `dir`, and return its exit code and checks by name.
+fn doctor(dir: &Path, base_url: &str) -> (Option<i32>, Value) {
+    let output: Output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["doctor", "--json"])
+        .env("YDT_BASE_URL", base_url)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .output()
+        .unwrap();
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["build"], build_info_to_json(&build_info()));
+    let checks = report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|check| (check["name"].as_str().unwrap().to_string(), check.clone()))
+        .collect::<serde_json::Map<_, _>>();
+    assert_eq!(
+        report["ok"],
+        Value::Bool(output.status.success()),
+        "{report}"
+    );
+    (output.status.code(), Value::Object(checks))
+}
+
+/// Config directory under `dir` turning off the mobile fallback, so that only
+/// the mock server is asked.
+fn config_dir(name: &str) -> std::path::PathBuf {
+    let dir = temp_dir(name);
+    fs::create_dir_all(dir.join("config/ydt")).unwrap();
+    fs::write(
+        dir.join("config/ydt/config.toml"),
+        "mobile_fallback = false\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn status(checks: &Value, name: &str) -> String {
+    checks[name]["status"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn probe_reaches_a_server() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .build();
+
+    let probe = client.probe().unwrap();
+    assert!(probe.is_ok(), "{probe:?}");
+    assert!(!probe.resolved.unwrap().is_empty());
+    assert_eq!(probe.responded.unwrap().unwrap(), StatusCode::OK);
+    let requests = server.pending_requests();
+    assert!(
+        requests.iter().any(|request| request.target == "/result"),
+        "{requests:?}"
+    );
+}
+
+#[test]
+fn probe_stops_at_the_failed_step() {
+    let client = YdtClient::builder().base_url(closed_url()).build();
+
+    let probe = client.probe().unwrap();
+    assert!(!probe.is_ok());
+    assert!(probe.resolved.is_ok());
+    assert!(probe.connected.unwrap().is_err());
+    assert!(probe.responded.is_none());
+}
+
+#[test]
+fn probe_rejects_an_invalid_url() {
+    let client = YdtClient::builder().base_url("not a url").build();
+    assert!(client.probe().is_err());
+}
+
+#[test]
+fn doctor_passes_with_a_working_setup() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = config_dir("doctor_pass");
+    let (code, checks) = doctor(&dir, &format!("{}/result", server.url));
+
+    assert_eq!(code, Some(0), "{checks}");
+    for name in [
+        "config", "cache", "dns", "connect", "http", "lookup", "parser",
+    ] {
+        assert_eq!(status(&checks, name), "pass", "{checks}");
+    }
+    assert_eq!(
+        checks["parser"]["detail"], "2 phonetics, 2 definitions",
+        "{checks}"
+    );
+}
+
+#[test]
+fn doctor_reports_an_unreachable_endpoint() {
+    let dir = config_dir("doctor_connect");
+    let (code, checks) = doctor(&dir, &closed_url());
+
+    assert_eq!(code, Some(2));
+    assert_eq!(status(&checks, "dns"), "pass");
+    assert_eq!(status(&checks, "connect"), "fail");
+    for name in ["http", "lookup", "parser"] {
+        assert_eq!(status(&checks, name), "skip", "{checks}");
+    }
+}
+
+#[test]
+fn doctor_reports_a_failed_lookup() {
+    let server = MockServer::start_with(|_| (403, ""));
+    let dir = config_dir("doctor_lookup");
+    let (code, checks) = doctor(&dir, &format!("{}/result", server.url));
+
+    assert_eq!(code, Some(2));
+    assert_eq!(status(&checks, "http"), "pass");
+    assert_eq!(checks["http"]["detail"], "403 Forbidden");
+    assert_eq!(status(&checks, "lookup"), "fail");
+    assert_eq!(status(&checks, "parser"), "skip");
+}
+
+#[test]
+fn doctor_reports_a_parser_breakage() {
+    // Definitions under a renamed class, as after a change of Youdao's markup.
+    let server = MockServer::start(include_str!("fixtures/degraded_no_word_exp.html"));
+    let dir = config_dir("doctor_parser");
+    let (code, checks) = doctor(&dir, &format!("{}/result", server.url));
+
+    assert_eq!(code, Some(2));
+    assert_eq!(status(&checks, "lookup"), "pass");
+    assert_eq!(status(&checks, "parser"), "fail");
+    let detail = checks["parser"]["detail"].as_str().unwrap();
+    assert!(detail.ends_with("1 phonetics, 0 definitions"), "{detail}");
+}
+
+#[test]
+fn doctor_reports_an_unwritable_cache_dir() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = config_dir("doctor_cache");
+    // A file where the cache directory should be created.
+    fs::write(dir.join("cache"), "").unwrap();
+    let (code, checks) = doctor(&dir, &format!("{}/result", server.url));
+
+    assert_eq!(code, Some(2));
+    assert_eq!(status(&checks, "cache"), "fail");
+    assert_eq!(status(&checks, "parser"), "pass");
+}
+
+#[test]
+fn doctor_reports_an_invalid_config_file() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = config_dir("doctor_config");
+    fs::write(dir.join("config/ydt/config.toml"), "retries = \"many\"\n").unwrap();
+    let (code, checks) = doctor(&dir, &format!("{}/result", server.url));
+
+    assert_eq!(code, Some(2));
+    assert_eq!(status(&checks, "config"), "fail");
+    let detail = checks["config"]["detail"].as_str().unwrap();
+    assert!(detail.contains("retries"), "{detail}");
+}
+
+#[test]
+fn doctor_prints_one_line_per_check() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = config_dir("doctor_plain");
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("doctor")
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 7, "{stdout}");
+    assert!(
+        stdout.lines().all(|line| line.starts_with("pass  ")),
+        "{stdout}"
+    );
+}
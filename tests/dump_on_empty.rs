@@ -0,0 +1,90 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+use ydt::{StatusCode, YdtClient};
+
+const EMPTY_HTML: &str = "<html><body><p>nothing here</p></body></html>";
+
+fn run_ydt(server: &MockServer, dump_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("--dump-on-empty")
+        .arg(dump_dir)
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("YDT_MOBILE_URL", format!("{}/dict", server.url))
+        .env("XDG_CONFIG_HOME", dump_dir.join("no-config"))
+        .output()
+        .unwrap()
+}
+
+fn html_files(dir: &Path) -> Vec<std::path::PathBuf> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .collect()
+}
+
+#[test]
+fn translate_with_html_returns_the_parsed_page() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    let (translation, html) = client.translate_with_html("hello").unwrap();
+
+    assert_eq!(html, HELLO_HTML);
+    assert_eq!(translation, client.translate("hello").unwrap());
+}
+
+#[test]
+fn translate_with_page_returns_the_page_and_fetch_info() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    let (translation, html, info) = client.translate_with_page("hello").unwrap();
+
+    assert_eq!(html, HELLO_HTML);
+    assert_eq!(translation, client.translate("hello").unwrap());
+    assert_eq!(info.status, StatusCode::OK);
+}
+
+#[test]
+fn empty_result_page_is_dumped() {
+    let server = MockServer::start(EMPTY_HTML);
+    let dir = temp_dir("dump-empty");
+    let output = run_ydt(&server, &dir, &["zzyzx"]);
+
+    let files = html_files(&dir);
+    assert_eq!(files.len(), 1, "{files:?}");
+    assert_eq!(fs::read_to_string(&files[0]).unwrap(), EMPTY_HTML);
+    let name = files[0].file_name().unwrap().to_str().unwrap();
+    assert!(name.starts_with("zzyzx-"), "{name}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(&files[0].display().to_string()), "{stderr}");
+}
+
+#[test]
+fn non_empty_result_page_is_not_dumped() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = temp_dir("dump-hello");
+    let output = run_ydt(&server, &dir, &["hello"]);
+
+    assert!(output.status.success());
+    assert!(html_files(&dir).is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn verbose_dump_also_prints_fetch_info() {
+    let server = MockServer::start(EMPTY_HTML);
+    let dir = temp_dir("dump-verbose");
+    let output = run_ydt(&server, &dir, &["-v", "zzyzx"]);
+
+    let files = html_files(&dir);
+    assert_eq!(files.len(), 1, "{files:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(&files[0].display().to_string()), "{stderr}");
+    assert!(stderr.contains("status: 200"), "{stderr}");
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use ydt::{
+    render_pretty, render_translation, translation_to_json, CollinsSense, Entry, EntrySource,
+    RenderOptions, Translation,
+};
+
+fn entry(pos: &str, meaning: &str, source: EntrySource) -> Entry {
+    Entry {
+        pos: pos.to_string(),
+        meaning: meaning.to_string(),
+        source,
+    }
+}
+
+/// A result combining the definitions of several sources.
+fn merged() -> Translation {
+    Translation {
+        word: "hello".to_string(),
+        entries: vec![
+            entry("int.", "喂；哈罗", EntrySource::Concise),
+            entry("n.", "表示问候", EntrySource::Mobile),
+            entry("", "hello", EntrySource::Cedict),
+            entry("", "你好", EntrySource::OpenApi),
+            entry("网络", "您好；哈啰", EntrySource::WebTrans),
+        ],
+        collins: vec![CollinsSense {
+            number: 1,
+            en_def: "You say hello to greet someone.".to_string(),
+            ..CollinsSense::default()
+        }],
+        ..Translation::default()
+    }
+}
+
+fn showing_sources() -> RenderOptions {
+    RenderOptions {
+        show_source: true,
+        ..RenderOptions::default()
+    }
+}
+
+#[test]
+fn each_definition_is_tagged_with_its_source() {
+    assert_eq!(
+        render_translation(&merged(), &showing_sources()),
+        "int.: 喂；哈罗 [concise]\n\
+         n.: 表示问候 [mobile]\n\
+         hello [cedict]\n\
+         你好 [open_api]\n\
+         网络: 您好；哈啰 [web_trans]\n\
+         \n\
+         1. You say hello to greet someone. [collins]"
+    );
+}
+
+#[test]
+fn tags_are_hidden_by_default_and_dimmed_in_color() {
+    let plain = render_translation(&merged(), &RenderOptions::default());
+    assert!(!plain.contains('['), "{plain}");
+
+    let options = RenderOptions {
+        color: true,
+        ..showing_sources()
+    };
+    let colored = render_translation(&merged(), &options);
+    assert!(
+        colored.starts_with("int.: 喂；哈罗 \x1b[2m[concise]\x1b[0m\n"),
+        "{colored:?}"
+    );
+}
+
+#[test]
+fn tags_that_do_not_fit_go_on_their_own_line() {
+    let translation = Translation {
+        word: "hello".to_string(),
+        entries: vec![
+            entry("int.", "喂；哈罗", EntrySource::Concise),
+            entry("n.", "表示问候；招呼声", EntrySource::Mobile),
+        ],
+        ..Translation::default()
+    };
+    assert_eq!(
+        render_pretty(&translation, &showing_sources(), 24),
+        "int. 喂；哈罗 [concise]\n\
+         n.   表示问候；招呼声\n\
+         \x20    [mobile]"
+    );
+}
+
+#[test]
+fn json_always_names_the_source() {
+    let json = translation_to_json(&merged());
+    let sources: Vec<_> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["source"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        sources,
+        ["concise", "mobile", "cedict", "open_api", "web_trans"]
+    );
+}
+
+#[cfg(feature = "http")]
+mod lookups {
+    use std::process::Command;
+
+    use super::common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+    use ydt::{EntrySource, YdtClient};
+
+    const MOBILE_HELLO_HTML: &str = include_str!("fixtures/mobile_hello.html");
+    const CEDICT_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/cedict_sample.u8"
+    );
+
+    fn sources(translation: &ydt::Translation) -> Vec<EntrySource> {
+        translation
+            .entries
+            .iter()
+            .map(|entry| entry.source)
+            .collect()
+    }
+
+    #[test]
+    fn each_fallback_tags_its_definitions() {
+        let server = MockServer::start_with(|request| {
+            if request.target.starts_with("/dict") {
+                (200, MOBILE_HELLO_HTML)
+            } else if request.target.contains("word=hello&") {
+                (200, HELLO_HTML)
+            } else {
+                (200, "<html><body></body></html>")
+            }
+        });
+        let client = YdtClient::builder()
+            .base_url(format!("{}/result", server.url))
+            .mobile_url(format!("{}/dict", server.url))
+            .build();
+        let desktop = client.translate("hello").unwrap();
+        assert_eq!(sources(&desktop), [EntrySource::Concise; 2]);
+
+        let mobile = client.translate("hi").unwrap();
+        assert!(!mobile.entries.is_empty());
+        assert!(sources(&mobile)
+            .iter()
+            .all(|&source| source == EntrySource::Mobile));
+
+        let client = YdtClient::builder()
+            .fetcher(MockFetcher::new())
+            .cedict_path(CEDICT_PATH)
+            .build();
+        let cedict = client.translate("学习").unwrap();
+        assert!(sources(&cedict)
+            .iter()
+            .all(|&source| source == EntrySource::Cedict));
+    }
+
+    #[test]
+    fn cli_shows_sources_when_asked() {
+        let server = MockServer::start(HELLO_HTML);
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args([
+                "--no-mobile-fallback",
+                "--format",
+                "plain",
+                "--show-source",
+                "hello",
+            ])
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir("entry_source"))
+            .env("XDG_CACHE_HOME", temp_dir("entry_source_cache"))
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(0), "{output:?}");
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "英 /həˈləʊ/ 美 /həˈloʊ/\nint.: 你好；喂 [concise]\nn.: 表示问候 [concise]\n"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::env;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{YdtClient, YdtClientBuilder, YdtError};
+
+const VARS: [&str; 10] = [
+    "YDT_TIMEOUT",
+    "YDT_RETRIES",
+    "YDT_PROXY",
+    "YDT_BASE_URL",
+    "YDT_USER_AGENT",
+    "YDT_LANG",
+    "YDT_MOBILE_URL",
+    "YDT_SUGGEST_URL",
+    "YDT_APP_KEY",
+    "YDT_APP_SECRET",
+];
+
+// Tests in this file mutate the process environment, so they must not overlap.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn with_env<T>(vars: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    for var in VARS {
+        env::remove_var(var);
+    }
+    for (var, value) in vars {
+        env::set_var(var, value);
+    }
+    let result = f();
+    for var in VARS {
+        env::remove_var(var);
+    }
+    result
+}
+
+#[test]
+fn reads_all_variables() {
+    let config = with_env(
+        &[
+            ("YDT_TIMEOUT", "2.5"),
+            ("YDT_RETRIES", "4"),
+            ("YDT_PROXY", "http://127.0.0.1:8080"),
+            ("YDT_BASE_URL", "http://localhost/result"),
+            ("YDT_USER_AGENT", "my-tool/1.0"),
+            ("YDT_LANG", "fr"),
+            ("YDT_MOBILE_URL", "http://localhost/dict"),
+            ("YDT_SUGGEST_URL", "http://localhost/suggest"),
+            ("YDT_APP_KEY", "key"),
+            ("YDT_APP_SECRET", "secret"),
+        ],
+        Config::from_env,
+    )
+    .unwrap();
+
+    assert_eq!(config.timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(config.retries, Some(4));
+    assert_eq!(config.proxy.as_deref(), Some("http://127.0.0.1:8080"));
+    assert_eq!(config.base_url.as_deref(), Some("http://localhost/result"));
+    assert_eq!(config.user_agent.as_deref(), Some("my-tool/1.0"));
+    assert_eq!(config.lang.as_deref(), Some("fr"));
+    assert_eq!(config.mobile_url.as_deref(), Some("http://localhost/dict"));
+    assert_eq!(
+        config.suggest_url.as_deref(),
+        Some("http://localhost/suggest")
+    );
+    assert_eq!(config.app_key.as_deref(), Some("key"));
+    assert_eq!(config.app_secret.as_deref(), Some("secret"));
+}
+
+#[test]
+fn empty_variables_are_ignored() {
+    let config = with_env(&[("YDT_TIMEOUT", ""), ("YDT_LANG", "")], Config::from_env).unwrap();
+    assert_eq!(config, Config::default());
+}
+
+#[test]
+fn malformed_values_name_the_variable() {
+    for (var, value) in [
+        ("YDT_TIMEOUT", "soon"),
+        ("YDT_TIMEOUT", "-1"),
+        ("YDT_TIMEOUT", "1e20"),
+        ("YDT_RETRIES", "many"),
+        ("YDT_PROXY", "not a url"),
+        ("YDT_BASE_URL", "/relative"),
+        ("YDT_LANG", "en us"),
+    ] {
+        let err = with_env(&[(var, value)], YdtClient::from_env)
+            .err()
+            .unwrap();
+        match &err {
+            YdtError::InvalidConfig {
+                var: got,
+                value: got_value,
+            } => {
+                assert_eq!((got.as_str(), got_value.as_str()), (var, value));
+            }
+            other => panic!("expected InvalidConfig for {var}, got {other:?}"),
+        }
+        assert!(err.to_string().contains(var), "{err}");
+    }
+}
+
+#[test]
+fn requests_use_base_url_lang_and_user_agent() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = with_env(
+        &[
+            ("YDT_BASE_URL", &format!("{}/result", server.url)),
+            ("YDT_LANG", "fr"),
+            ("YDT_USER_AGENT", "my-tool/1.0"),
+        ],
+        YdtClient::from_env,
+    )
+    .unwrap();
+
+    let translation = client.translate("hello").unwrap();
+    assert_eq!(translation.word, "hello");
+
+    let request = server.next_request();
+    assert_eq!(request.target, "/result?word=hello&lang=fr");
+    assert_eq!(request.headers["user-agent"], "my-tool/1.0");
+}
+
+#[test]
+fn builder_settings_override_environment() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = with_env(
+        &[
+            ("YDT_BASE_URL", "http://127.0.0.1:9/unreachable"),
+            ("YDT_LANG", "fr"),
+        ],
+        || {
+            YdtClientBuilder::from_env().map(|builder| {
+                builder
+                    .base_url(format!("{}/result", server.url))
+                    .lang("de")
+                    .build()
+            })
+        },
+    )
+    .unwrap();
+
+    client.translate("hello").unwrap();
+    assert_eq!(server.next_request().target, "/result?word=hello&lang=de");
+}
@@ -0,0 +1,394 @@
+use std::io;
+use std::time::Duration;
+
+use ydt::{ParseWarning, StatusCode, YdtError};
+
+fn word() -> String {
+    "hello".to_string()
+}
+
+fn url() -> String {
+    "https://www.youdao.com/result?word=hello&lang=en".to_string()
+}
+
+#[cfg(feature = "http")]
+fn reqwest_error() -> reqwest::Error {
+    reqwest::blocking::Client::new()
+        .get("not a url")
+        .send()
+        .unwrap_err()
+}
+
+fn status(status: StatusCode) -> YdtError {
+    YdtError::HttpStatus {
+        word: word(),
+        url: url(),
+        status,
+    }
+}
+
+fn all_failed(errors: Vec<YdtError>) -> YdtError {
+    YdtError::AllSourcesFailed {
+        word: word(),
+        errors: errors
+            .into_iter()
+            .enumerate()
+            .map(|(i, err)| (format!("source{i}"), err))
+            .collect(),
+    }
+}
+
+#[cfg(feature = "http")]
+fn fetch() -> YdtError {
+    YdtError::Fetch {
+        word: word(),
+        url: url(),
+        source: reqwest_error(),
+    }
+}
+
+type Case = (YdtError, bool, bool, bool, Option<StatusCode>);
+
+/// Every variant with its expected `is_retryable`, `is_network`,
+/// `is_not_found` and `status_code`.
+fn cases() -> Vec<Case> {
+    let cases = vec![
+        (
+            YdtError::BuildRequestUrl(url::Url::parse("").unwrap_err()),
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            status(StatusCode::NOT_FOUND),
+            false,
+            false,
+            true,
+            Some(StatusCode::NOT_FOUND),
+        ),
+        (
+            status(StatusCode::GONE),
+            false,
+            false,
+            true,
+            Some(StatusCode::GONE),
+        ),
+        (
+            status(StatusCode::FORBIDDEN),
+            false,
+            false,
+            false,
+            Some(StatusCode::FORBIDDEN),
+        ),
+        (
+            status(StatusCode::REQUEST_TIMEOUT),
+            true,
+            false,
+            false,
+            Some(StatusCode::REQUEST_TIMEOUT),
+        ),
+        (
+            status(StatusCode::SERVICE_UNAVAILABLE),
+            true,
+            false,
+            false,
+            Some(StatusCode::SERVICE_UNAVAILABLE),
+        ),
+        (
+            YdtError::ParseCssSelector {
+                css: "span..trans".to_string(),
+                message: "unexpected token".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::ReadConfig(io::Error::other("denied")),
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::ConfigFile {
+                key: Some("retries".to_string()),
+                message: "expected an integer".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::InvalidConfig {
+                var: "YDT_RETRIES".to_string(),
+                value: "many".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::UnrecognizedLayout {
+                word: word(),
+                warnings: vec![ParseWarning::DefinitionsMissing],
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (YdtError::EmptyWord, false, false, false, None),
+        (
+            YdtError::WordTooLong { len: 101, max: 100 },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::UnsupportedCharacter {
+                word: "a\u{0}b".to_string(),
+                ch: '\u{0}',
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::InvalidSuggestPayload("not JSON".to_string()),
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::ApiError {
+                word: word(),
+                code: 401,
+                msg: "account overdue".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::InvalidApiResponse {
+                word: word(),
+                message: "missing `errorCode`".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (YdtError::MissingApiCredentials, false, false, false, None),
+        (
+            all_failed(vec![
+                status(StatusCode::NOT_FOUND),
+                status(StatusCode::GONE),
+            ]),
+            false,
+            false,
+            true,
+            None,
+        ),
+        // No source ran, so nothing says what kind of failure it was.
+        (all_failed(vec![]), false, false, false, None),
+        (
+            YdtError::ReadDictionary(io::Error::other("missing")),
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::ResponseTooLarge {
+                word: word(),
+                url: url(),
+                limit: 100,
+                received: 200,
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::Decode {
+                word: word(),
+                url: url(),
+                message: "not UTF-8".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::UnexpectedRedirect {
+                word: word(),
+                url: url(),
+                to: "https://example.com/".to_string(),
+            },
+            false,
+            false,
+            false,
+            None,
+        ),
+        (
+            YdtError::RateLimited {
+                word: word(),
+                retry_after: Some(Duration::from_secs(30)),
+            },
+            true,
+            false,
+            false,
+            Some(StatusCode::TOO_MANY_REQUESTS),
+        ),
+        (
+            YdtError::CircuitOpen {
+                word: word(),
+                failures: 5,
+            },
+            true,
+            false,
+            false,
+            None,
+        ),
+    ];
+    #[cfg(feature = "http")]
+    let cases = cases.into_iter().chain(http_cases()).collect();
+    cases
+}
+
+/// Variants wrapping errors from the HTTP client.
+#[cfg(feature = "http")]
+fn http_cases() -> Vec<Case> {
+    vec![
+        (
+            YdtError::CreateHttpClient(reqwest_error()),
+            false,
+            false,
+            false,
+            None,
+        ),
+        (fetch(), true, true, false, None),
+        (
+            YdtError::ReadResponse {
+                word: word(),
+                url: url(),
+                source: reqwest_error(),
+            },
+            true,
+            true,
+            false,
+            None,
+        ),
+        (
+            all_failed(vec![fetch(), status(StatusCode::NOT_FOUND)]),
+            true,
+            false,
+            false,
+            None,
+        ),
+        (all_failed(vec![fetch(), fetch()]), true, true, false, None),
+    ]
+}
+
+#[test]
+fn retryable_errors() {
+    for (err, retryable, ..) in cases() {
+        assert_eq!(err.is_retryable(), retryable, "{err:?}");
+    }
+}
+
+#[test]
+fn network_errors() {
+    for (err, _, network, ..) in cases() {
+        assert_eq!(err.is_network(), network, "{err:?}");
+    }
+}
+
+#[test]
+fn not_found_errors() {
+    for (err, _, _, not_found, _) in cases() {
+        assert_eq!(err.is_not_found(), not_found, "{err:?}");
+    }
+}
+
+#[test]
+fn status_codes() {
+    for (err, .., status) in cases() {
+        assert_eq!(err.status_code(), status, "{err:?}");
+    }
+}
+
+#[test]
+fn blocked_errors() {
+    let cases = [
+        (status(StatusCode::FORBIDDEN), true),
+        (status(StatusCode::TOO_MANY_REQUESTS), true),
+        (
+            YdtError::RateLimited {
+                word: word(),
+                retry_after: None,
+            },
+            true,
+        ),
+        (
+            YdtError::CircuitOpen {
+                word: word(),
+                failures: 5,
+            },
+            true,
+        ),
+        (
+            all_failed(vec![
+                status(StatusCode::FORBIDDEN),
+                status(StatusCode::NOT_FOUND),
+            ]),
+            true,
+        ),
+        (status(StatusCode::NOT_FOUND), false),
+        (status(StatusCode::SERVICE_UNAVAILABLE), false),
+        (
+            all_failed(vec![
+                status(StatusCode::NOT_FOUND),
+                status(StatusCode::GONE),
+            ]),
+            false,
+        ),
+        (YdtError::EmptyWord, false),
+    ];
+    for (err, blocked) in cases {
+        assert_eq!(err.is_blocked(), blocked, "{err:?}");
+    }
+}
+
+#[test]
+fn reports_capture_kind_word_and_status() {
+    for (err, ..) in cases() {
+        let report = err.to_owned_report();
+        assert_eq!(report.message, err.to_string());
+        assert_eq!(report.word.as_deref(), err.word());
+        assert_eq!(report.status, err.status_code().map(|s| s.as_u16()));
+        assert!(
+            !report.kind.is_empty()
+                && report
+                    .kind
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch == '_'),
+            "{report:?}"
+        );
+    }
+    let report = status(StatusCode::NOT_FOUND).to_owned_report();
+    assert_eq!(report.kind, "http_status");
+    assert_eq!(report.clone(), report);
+}
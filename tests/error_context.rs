@@ -0,0 +1,143 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::io::Write;
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::{YdtClient, YdtError};
+
+/// Answer lookups of `missing` with `404 Not Found` and others with the result
+/// page.
+fn server() -> MockServer {
+    MockServer::start_with(|request| {
+        if request.target.contains("word=missing") {
+            (404, "")
+        } else {
+            (200, HELLO_HTML)
+        }
+    })
+}
+
+#[test]
+fn http_status_error_names_the_word_and_url() {
+    let server = server();
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build();
+
+    let err = client.translate("missing").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Request for \"missing\" failed with status: 404 Not Found"
+    );
+    assert_eq!(err.word(), Some("missing"));
+    let YdtError::HttpStatus { url, .. } = &err else {
+        panic!("{err:?}");
+    };
+    assert!(url.starts_with(&server.url), "{url}");
+    assert!(url.contains("word=missing"), "{url}");
+}
+
+#[test]
+fn connection_error_names_the_word_and_url() {
+    // Bound and dropped, so that nothing listens on the port.
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/result", listener.local_addr().unwrap())
+    };
+    let client = YdtClient::builder()
+        .base_url(&url)
+        .mobile_fallback(false)
+        .build();
+
+    let err = client.translate("hello").unwrap_err();
+
+    assert!(
+        err.to_string()
+            .starts_with("Failed to fetch translation for \"hello\": "),
+        "{err}"
+    );
+    let YdtError::Fetch {
+        word, url: failed, ..
+    } = &err
+    else {
+        panic!("{err:?}");
+    };
+    assert_eq!(word, "hello");
+    assert!(failed.starts_with(&url), "{failed}");
+}
+
+#[test]
+fn errors_without_a_word_say_so() {
+    assert_eq!(YdtError::EmptyWord.word(), None);
+    let err = YdtClient::builder()
+        .max_word_len(3)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+    assert_eq!(err.word(), None);
+}
+
+#[test]
+fn cli_batch_errors_name_the_failed_word() {
+    let server = server();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--concurrency", "1"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("error_context_cli"))
+        .env("XDG_CACHE_HOME", temp_dir("error_context_cli_cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"hello\nmissing\nhello\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr.lines().collect::<Vec<_>>(),
+        ["Request for \"missing\" failed with status: 404 Not Found"],
+    );
+}
+
+#[test]
+fn cli_batch_reports_lines_that_are_not_utf8() {
+    let server = server();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--concurrency", "1"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("error_context_utf8"))
+        .env("XDG_CACHE_HOME", temp_dir("error_context_utf8_cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"hello\n\xff\xfe\nhello\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    // The words after the bad line are still looked up.
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("你好").count(), 2, "{stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Failed to read stdin: "), "{stderr}");
+    assert_eq!(server.pending_requests().len(), 2);
+}
@@ -0,0 +1,367 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::collections::BTreeSet;
+use std::io;
+use std::process::Command;
+use std::time::Duration;
+
+use common::{temp_dir, MockServer};
+use serde_json::{json, Value};
+use ydt::{error_to_json, ParseWarning, StatusCode, YdtError};
+
+const README: &str = include_str!("../README.md");
+
+fn word() -> String {
+    "hello".to_string()
+}
+
+fn url() -> String {
+    "https://www.youdao.com/result?word=hello&lang=en".to_string()
+}
+
+fn reqwest_error() -> reqwest::Error {
+    reqwest::blocking::Client::new()
+        .get("not a url")
+        .send()
+        .unwrap_err()
+}
+
+/// Every variant, with the kind it must keep.
+fn variants() -> Vec<(YdtError, &'static str)> {
+    vec![
+        (
+            YdtError::CreateHttpClient(reqwest_error()),
+            "create_http_client",
+        ),
+        (
+            YdtError::BuildRequestUrl(url::Url::parse("").unwrap_err()),
+            "build_request_url",
+        ),
+        (
+            YdtError::Fetch {
+                word: word(),
+                url: url(),
+                source: reqwest_error(),
+            },
+            "fetch",
+        ),
+        (
+            YdtError::HttpStatus {
+                word: word(),
+                url: url(),
+                status: StatusCode::NOT_FOUND,
+            },
+            "http_status",
+        ),
+        (
+            YdtError::ReadResponse {
+                word: word(),
+                url: url(),
+                source: reqwest_error(),
+            },
+            "read_response",
+        ),
+        (
+            YdtError::ParseCssSelector {
+                css: "span..trans".to_string(),
+                message: "unexpected token".to_string(),
+            },
+            "parse_css_selector",
+        ),
+        (
+            YdtError::ReadConfig(io::Error::other("denied")),
+            "read_config",
+        ),
+        (
+            YdtError::ConfigFile {
+                key: None,
+                message: "expected a table".to_string(),
+            },
+            "config_file",
+        ),
+        (
+            YdtError::InvalidConfig {
+                var: "YDT_RETRIES".to_string(),
+                value: "many".to_string(),
+            },
+            "invalid_config",
+        ),
+        (
+            YdtError::UnrecognizedLayout {
+                word: word(),
+                warnings: vec![ParseWarning::DefinitionsMissing],
+            },
+            "unrecognized_layout",
+        ),
+        (YdtError::EmptyWord, "empty_word"),
+        (
+            YdtError::WordTooLong { len: 101, max: 100 },
+            "word_too_long",
+        ),
+        (
+            YdtError::UnsupportedCharacter {
+                word: "a\u{0}b".to_string(),
+                ch: '\u{0}',
+            },
+            "unsupported_character",
+        ),
+        (
+            YdtError::InvalidSuggestPayload("not JSON".to_string()),
+            "invalid_suggest_payload",
+        ),
+        (
+            YdtError::ApiError {
+                word: word(),
+                code: 401,
+                msg: "account overdue".to_string(),
+            },
+            "api_error",
+        ),
+        (
+            YdtError::InvalidApiResponse {
+                word: word(),
+                message: "missing `errorCode`".to_string(),
+            },
+            "invalid_api_response",
+        ),
+        (YdtError::MissingApiCredentials, "missing_api_credentials"),
+        (
+            YdtError::AllSourcesFailed {
+                word: word(),
+                errors: vec![("desktop".to_string(), YdtError::EmptyWord)],
+            },
+            "all_sources_failed",
+        ),
+        (
+            YdtError::ReadDictionary(io::Error::other("missing")),
+            "read_dictionary",
+        ),
+        (
+            YdtError::ResponseTooLarge {
+                word: word(),
+                url: url(),
+                limit: 100,
+                received: 200,
+            },
+            "response_too_large",
+        ),
+        (
+            YdtError::Decode {
+                word: word(),
+                url: url(),
+                message: "not UTF-8".to_string(),
+            },
+            "decode",
+        ),
+        (
+            YdtError::CompressedBody {
+                word: word(),
+                url: url(),
+                encoding: "zstd".to_string(),
+            },
+            "compressed_body",
+        ),
+        (
+            YdtError::UnexpectedRedirect {
+                word: word(),
+                url: url(),
+                to: "https://example.com/".to_string(),
+            },
+            "unexpected_redirect",
+        ),
+        (
+            YdtError::RateLimited {
+                word: word(),
+                retry_after: Some(Duration::from_secs(30)),
+            },
+            "rate_limited",
+        ),
+        (
+            YdtError::CircuitOpen {
+                word: word(),
+                failures: 5,
+            },
+            "circuit_open",
+        ),
+        (YdtError::NumberQuery { word: word() }, "number_query"),
+        (
+            YdtError::DeadlineExceeded {
+                word: word(),
+                deadline: Duration::from_secs(15),
+                tier: "desktop source".to_string(),
+            },
+            "deadline_exceeded",
+        ),
+    ]
+}
+
+/// Kinds listed in the README table of JSON errors.
+fn documented_kinds() -> BTreeSet<&'static str> {
+    let table = README
+        .split_once("| Kind ")
+        .expect("README lists error kinds")
+        .1;
+    table
+        .lines()
+        .skip(2)
+        .take_while(|line| line.starts_with('|'))
+        .map(|line| line.split('`').nth(1).unwrap())
+        .collect()
+}
+
+#[test]
+fn every_variant_keeps_its_kind() {
+    for (err, kind) in variants() {
+        assert_eq!(err.kind(), kind, "{err:?}");
+        assert_eq!(err.to_owned_report().kind, kind, "{err:?}");
+    }
+}
+
+#[test]
+fn kinds_are_unique_and_documented() {
+    let kinds: Vec<_> = variants().into_iter().map(|(_, kind)| kind).collect();
+    let unique: BTreeSet<_> = kinds.iter().copied().collect();
+    assert_eq!(unique.len(), kinds.len(), "{kinds:?}");
+
+    // The command-line tool adds kinds for failures other than errors.
+    let mut expected = unique;
+    expected.extend([
+        "read_word_file",
+        "read_stdin",
+        "read_clipboard",
+        "write_clipboard",
+        "unsupported",
+        "save_page",
+        "no_page",
+        "open_browser",
+        "config_path",
+        "write_config",
+    ]);
+    assert_eq!(documented_kinds(), expected);
+}
+
+#[test]
+fn errors_convert_to_json() {
+    assert_eq!(
+        error_to_json(&YdtError::EmptyWord),
+        json!({ "kind": "empty_word", "message": "Word to translate is empty" })
+    );
+    let err = YdtError::HttpStatus {
+        word: word(),
+        url: url(),
+        status: StatusCode::FORBIDDEN,
+    };
+    assert_eq!(
+        error_to_json(&err),
+        json!({
+            "kind": "http_status",
+            "word": "hello",
+            "status": 403,
+            "message": "Request for \"hello\" failed with status: 403 Forbidden",
+        })
+    );
+    let report = YdtError::RateLimited {
+        word: word(),
+        retry_after: Some(Duration::from_secs(30)),
+    }
+    .to_owned_report();
+    assert_eq!(report.retry_after_secs, Some(30));
+
+    // Part of a second is waited as a whole one, as the message says.
+    let err = YdtError::RateLimited {
+        word: word(),
+        retry_after: Some(Duration::from_millis(500)),
+    };
+    assert_eq!(err.to_owned_report().retry_after_secs, Some(1));
+    assert_eq!(error_to_json(&err)["retry_after_secs"], 1);
+    assert!(err.to_string().ends_with("try again in 1s"), "{err}");
+}
+
+/// Runs `ydt` against `server`, without the mobile fallback (set in the
+/// config file, so that it also applies to subcommands).
+fn ydt(server: &MockServer, name: &str, args: &[&str]) -> (Option<i32>, Vec<Value>) {
+    let config = temp_dir(name);
+    std::fs::create_dir_all(config.join("ydt")).unwrap();
+    std::fs::write(config.join("ydt/config.toml"), "mobile_fallback = false\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", config)
+        .env("XDG_CACHE_HOME", temp_dir(&format!("{name}_cache")))
+        .output()
+        .unwrap();
+    let errors = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|_| panic!("{line}")))
+        .collect();
+    (output.status.code(), errors)
+}
+
+#[test]
+fn cli_prints_errors_as_json() {
+    let server = MockServer::start_with_headers(&[("Retry-After", "30")], |_| (429, "slow down"));
+    let (code, errors) = ydt(&server, "json_errors", &["--json-errors", "hello"]);
+
+    assert_eq!(code, Some(4));
+    assert_eq!(
+        errors,
+        [json!({
+            "error": {
+                "kind": "rate_limited",
+                "word": "hello",
+                "status": 429,
+                "retry_after_secs": 30,
+                "message": "Rate limited by the server for \"hello\"; try again in 30s",
+            }
+        })]
+    );
+}
+
+#[test]
+fn cli_reports_missing_results_as_json_with_ndjson() {
+    let server = MockServer::start("<html><body></body></html>");
+    let (code, errors) = ydt(&server, "json_no_results", &["--format", "ndjson", "xyzzy"]);
+
+    assert_eq!(code, Some(1));
+    assert_eq!(
+        errors,
+        [json!({
+            "error": { "kind": "no_results", "word": "xyzzy", "message": "No results." }
+        })]
+    );
+}
+
+#[test]
+fn diff_prints_errors_as_json() {
+    let server = MockServer::start_with_headers(&[("Retry-After", "30")], |_| (429, "slow down"));
+    let (code, errors) = ydt(
+        &server,
+        "json_errors_diff",
+        &["diff", "hello", "world", "--json-errors"],
+    );
+
+    assert_eq!(code, Some(4));
+    let kinds: Vec<_> = errors.iter().map(|err| &err["error"]["kind"]).collect();
+    assert_eq!(kinds, ["rate_limited", "rate_limited"]);
+    let words: Vec<_> = errors.iter().map(|err| &err["error"]["word"]).collect();
+    assert_eq!(words, ["hello", "world"]);
+}
+
+#[cfg(feature = "open")]
+#[test]
+fn open_prints_errors_as_json() {
+    let server = MockServer::start_with_headers(&[("Retry-After", "30")], |_| (429, "slow down"));
+    let (code, errors) = ydt(
+        &server,
+        "json_errors_open",
+        &["open", "hello", "--json-errors"],
+    );
+
+    assert_eq!(code, Some(4));
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0]["error"]["kind"], "rate_limited");
+    assert_eq!(errors[0]["error"]["word"], "hello");
+}
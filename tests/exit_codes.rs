@@ -0,0 +1,142 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::{Command, Output};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+
+/// Run the binary with `args`, looking words up on `server` without retries.
+fn ydt(args: &[&str], server: &MockServer, name: &str) -> Output {
+    let dir = temp_dir(&format!("exit_codes_{name}"));
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--retries", "0"])
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .output()
+        .unwrap()
+}
+
+fn answering(status: u16, body: &'static str) -> MockServer {
+    MockServer::start_with(move |_| (status, body))
+}
+
+#[test]
+fn success_prints_only_to_stdout() {
+    let output = ydt(&["hello"], &MockServer::start(HELLO_HTML), "success");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("你好"));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn no_results_exit_with_1_and_keep_stdout_empty() {
+    let server = MockServer::start("<html><body></body></html>");
+    for format in ["plain", "tsv", "ndjson"] {
+        let output = ydt(&["--format", format, "hello"], &server, "no_results");
+
+        assert_eq!(output.status.code(), Some(1), "{format}");
+        assert!(output.stdout.is_empty(), "{format}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        if format == "ndjson" {
+            assert!(
+                stderr.starts_with(r#"{"error":{"kind":"no_results""#),
+                "{stderr}"
+            );
+        } else {
+            assert_eq!(stderr, "No results.\n");
+        }
+    }
+}
+
+#[test]
+fn not_found_exits_with_1() {
+    let output = ydt(&["hello"], &answering(404, ""), "not_found");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn http_failures_exit_with_2() {
+    for status in [400, 503] {
+        let output = ydt(
+            &["hello"],
+            &answering(status, ""),
+            &format!("http_{status}"),
+        );
+
+        assert_eq!(output.status.code(), Some(2), "{status}");
+        assert!(output.stdout.is_empty());
+        assert!(!output.stderr.is_empty());
+    }
+}
+
+#[test]
+fn machine_formats_print_errors_to_stderr() {
+    for format in ["tsv", "ndjson"] {
+        let output = ydt(
+            &["--format", format, "--header", "hello"],
+            &answering(503, ""),
+            &format!("error_{format}"),
+        );
+
+        assert_eq!(output.status.code(), Some(2), "{format}");
+        assert!(output.stdout.is_empty(), "{format}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("503"), "{stderr}");
+    }
+}
+
+#[test]
+fn network_failures_exit_with_2() {
+    let dir = temp_dir("exit_codes_network");
+    let url = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/result", listener.local_addr().unwrap())
+    };
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--retries", "0", "hello"])
+        .env("YDT_BASE_URL", url)
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn usage_errors_exit_with_3() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["--bogus", "hello"], &server, "usage");
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+    assert!(server.pending_requests().is_empty());
+}
+
+#[test]
+fn help_and_version_exit_with_0() {
+    for flag in ["--help", "--version"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .arg(flag)
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(0), "{flag}");
+        assert!(!output.stdout.is_empty(), "{flag}");
+    }
+}
+
+#[test]
+fn blocked_requests_exit_with_4() {
+    for status in [403, 429] {
+        let output = ydt(
+            &["hello"],
+            &answering(status, ""),
+            &format!("blocked_{status}"),
+        );
+        assert_eq!(output.status.code(), Some(4), "{status}");
+    }
+}
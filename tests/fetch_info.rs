@@ -0,0 +1,163 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+use std::time::Duration;
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::{
+    CharsetSource, FetchInfo, Fetcher, PageSource, TranslationOptions, UserAgentKind, YdtClient,
+    YdtError,
+};
+
+/// Fetcher that takes a while to answer, to make the elapsed time observable.
+struct SlowFetcher(MockFetcher);
+
+impl Fetcher for SlowFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        std::thread::sleep(Duration::from_millis(20));
+        self.0.fetch(word)
+    }
+}
+
+#[test]
+fn custom_fetcher_reports_defaults_and_elapsed_time() {
+    let client = YdtClient::with_fetcher(SlowFetcher(MockFetcher::new().page("hello", HELLO_HTML)));
+    let (translation, info) = client.translate_detailed("hello").unwrap();
+
+    assert_eq!(translation, client.translate("hello").unwrap());
+    let FetchInfo {
+        status,
+        elapsed,
+        user_agent,
+        final_url,
+        from_cache,
+        source,
+        redirects,
+        charset,
+        charset_source,
+        content_encoding,
+        time_to_headers,
+        request_id,
+    } = info;
+    assert_eq!(status, StatusCode::OK);
+    assert!(elapsed >= Duration::from_millis(20), "{elapsed:?}");
+    assert_eq!(user_agent, None);
+    assert_eq!(final_url, None);
+    assert!(!from_cache);
+    assert_eq!(source, PageSource::Desktop);
+    assert_eq!(redirects, 0);
+    assert_eq!(charset, "UTF-8");
+    assert_eq!(charset_source, CharsetSource::Utf8);
+    assert_eq!(content_encoding, None);
+    assert_eq!(time_to_headers, None);
+    assert_eq!(request_id, None);
+}
+
+#[test]
+fn request_id_is_echoed_back() {
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .translation_options(TranslationOptions {
+            request_id: Some("req-42".to_string()),
+            ..TranslationOptions::default()
+        })
+        .build();
+
+    let (_, info) = client.translate_detailed("hello").unwrap();
+    assert_eq!(info.request_id.as_deref(), Some("req-42"));
+}
+
+#[test]
+fn request_id_of_the_call_takes_precedence() {
+    let client = TranslationOptions {
+        request_id: Some("client".to_string()),
+        ..TranslationOptions::default()
+    };
+    let call = TranslationOptions {
+        request_id: Some("call".to_string()),
+        ..TranslationOptions::default()
+    };
+    assert_eq!(
+        client.clone().merge(call).request_id.as_deref(),
+        Some("call")
+    );
+    assert_eq!(
+        client
+            .merge(TranslationOptions::default())
+            .request_id
+            .as_deref(),
+        Some("client")
+    );
+}
+
+#[test]
+fn errors_are_returned_without_info() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().status("gone", StatusCode::GONE));
+    let err = client.translate_detailed("gone").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            YdtError::HttpStatus {
+                status: StatusCode::GONE,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn http_fetcher_reports_final_url_after_redirect() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/old") {
+            (301, "/result?word=hello&lang=en")
+        } else {
+            (200, HELLO_HTML)
+        }
+    });
+    let client = YdtClient::builder()
+        .base_url(format!("{}/old", server.url))
+        .build();
+    let (translation, info) = client.translate_detailed("hello").unwrap();
+
+    assert_eq!(translation.word, "hello");
+    assert_eq!(info.status, StatusCode::OK);
+    assert_eq!(info.user_agent, Some(UserAgentKind::Project));
+    assert_eq!(
+        info.final_url.unwrap().as_str(),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+    assert_eq!(info.redirects, 1);
+    assert!(!info.from_cache);
+    assert!(info.elapsed > Duration::ZERO);
+    assert!(info
+        .time_to_headers
+        .is_some_and(|time| time <= info.elapsed));
+}
+
+#[test]
+fn verbose_flag_prints_fetch_info_to_stderr() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--verbose", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env(
+            "XDG_CONFIG_HOME",
+            std::env::temp_dir().join("ydt-fetch-info-none"),
+        )
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("你好"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("status: 200 OK"), "{stderr}");
+    assert!(stderr.contains("user agent: project"), "{stderr}");
+    assert!(
+        stderr.contains(&format!("url: {}/result?word=hello", server.url)),
+        "{stderr}"
+    );
+}
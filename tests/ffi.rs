@@ -0,0 +1,93 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use common::{MockServer, HELLO_HTML};
+
+// Declared as a C host would, so that calls go through the C ABI.
+extern "C" {
+    fn ydt_translate(word: *const c_char) -> *mut c_char;
+    fn ydt_last_error() -> *mut c_char;
+    fn ydt_free_string(s: *mut c_char);
+}
+
+/// Copy and release a string returned by `ydt`, or `None` for NULL.
+fn take(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+    unsafe { ydt_free_string(s) };
+    Some(owned)
+}
+
+fn translate(word: &[u8]) -> Option<String> {
+    let word = CString::new(word).unwrap();
+    take(unsafe { ydt_translate(word.as_ptr()) })
+}
+
+fn last_error() -> Option<serde_json::Value> {
+    take(unsafe { ydt_last_error() }).map(|json| serde_json::from_str(&json).unwrap())
+}
+
+// One test, as the client is configured from the process environment.
+#[test]
+fn translations_and_errors_are_returned_as_json() {
+    let server = MockServer::start_with(|request| {
+        if request.target.contains("hello") {
+            (200, HELLO_HTML)
+        } else {
+            (404, "")
+        }
+    });
+    std::env::set_var("YDT_BASE_URL", format!("{}/result", server.url));
+    std::env::set_var("YDT_MOBILE_URL", format!("{}/dict", server.url));
+
+    let json = translate(b"hello").unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["word"], "hello");
+    assert_eq!(value["entries"][0]["pos"], "int.");
+    assert_eq!(last_error(), None);
+
+    assert_eq!(translate(b"missing"), None);
+    let error = last_error().unwrap();
+    // Both the result page and the mobile fallback answer 404.
+    assert_eq!(error["kind"], "all_sources_failed");
+    assert_eq!(error["word"], "missing");
+    let message = error["message"].as_str().unwrap();
+    assert!(message.contains("404 Not Found"), "{message}");
+    // Reading the error does not clear it.
+    assert_eq!(last_error(), Some(error));
+
+    translate(b"hello").unwrap();
+    assert_eq!(last_error(), None);
+}
+
+#[test]
+fn invalid_arguments_are_errors() {
+    assert!(unsafe { ydt_translate(ptr::null()) }.is_null());
+    let error = last_error().unwrap();
+    assert_eq!(error["kind"], "invalid_argument");
+    assert_eq!(error["message"], "word is NULL");
+
+    assert_eq!(translate(b"caf\xe9"), None);
+    assert_eq!(last_error().unwrap()["message"], "word is not valid UTF-8");
+}
+
+#[test]
+fn errors_are_kept_per_thread() {
+    assert!(unsafe { ydt_translate(ptr::null()) }.is_null());
+
+    let other = std::thread::spawn(last_error).join().unwrap();
+
+    assert_eq!(other, None);
+    assert!(last_error().is_some());
+}
+
+#[test]
+fn freeing_null_is_a_no_op() {
+    unsafe { ydt_free_string(ptr::null_mut()) };
+}
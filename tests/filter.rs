@@ -0,0 +1,123 @@
+mod common;
+
+use common::RUN_HTML;
+use ydt::{parse_translation, CollinsSense, Example, Translation};
+
+fn run() -> Translation {
+    parse_translation("run", RUN_HTML).unwrap()
+}
+
+fn parts_of_speech(translation: &Translation) -> Vec<&str> {
+    translation
+        .entries
+        .iter()
+        .map(|entry| entry.pos.as_str())
+        .collect()
+}
+
+#[test]
+fn cjk_substrings_select_senses() {
+    let translation = run();
+    let filtered = translation.filtered(&["经营"]);
+    assert_eq!(parts_of_speech(&filtered), ["v."]);
+    assert_eq!(filtered.phonetics, translation.phonetics);
+
+    let filtered = translation.filtered(&["液态", "短途"]);
+    assert_eq!(parts_of_speech(&filtered), ["n.", "adj."]);
+}
+
+#[test]
+fn ascii_letters_match_in_any_case() {
+    let translation = run();
+    assert_eq!(
+        parts_of_speech(&translation.filtered(&["HONG KONG"])),
+        ["【名】"]
+    );
+    assert_eq!(
+        parts_of_speech(&translation.filtered(&["running-TRACK"])),
+        ["【名】"]
+    );
+}
+
+#[test]
+fn no_filters_keep_everything_and_no_match_keeps_nothing() {
+    let translation = run();
+    assert_eq!(translation.filtered(&[]), translation);
+    let filtered = translation.filtered(&["飞翔"]);
+    assert!(filtered.entries.is_empty());
+    assert!(!filtered.phonetics.is_empty());
+}
+
+#[test]
+fn collins_examples_are_filtered_under_their_sense() {
+    let sense = CollinsSense {
+        number: 1,
+        pos: "VERB".to_string(),
+        en_def: "When you run, you move quickly.".to_string(),
+        zh_def: "跑".to_string(),
+        examples: vec![
+            Example {
+                en: "I ran to the station.".to_string(),
+                zh: "我跑到了车站。".to_string(),
+            },
+            Example {
+                en: "She runs a shop.".to_string(),
+                zh: "她经营一家商店。".to_string(),
+            },
+        ],
+    };
+    let translation = Translation {
+        word: "run".to_string(),
+        collins: vec![sense.clone()],
+        ..Translation::default()
+    };
+
+    let filtered = translation.filtered(&["商店"]);
+    assert_eq!(filtered.collins[0].examples, sense.examples[1..]);
+    assert_eq!(translation.filtered(&["QUICKLY"]).collins, [sense]);
+    assert!(translation.filtered(&["飞翔"]).collins.is_empty());
+}
+
+#[cfg(feature = "http")]
+mod cli {
+    use std::process::{Command, Output};
+
+    use super::common::{temp_dir, MockServer, RUN_HTML};
+
+    fn ydt(args: &[&str], name: &str) -> Output {
+        let server = MockServer::start(RUN_HTML);
+        Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--format", "plain", "run"])
+            .args(args)
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir(name))
+            .env("XDG_CACHE_HOME", temp_dir(&format!("{name}_cache")))
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn filters_are_combined_with_or() {
+        let output = ydt(&["--filter", "经营", "--filter", "液态"], "filter_or");
+
+        assert_eq!(output.status.code(), Some(0), "{output:?}");
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "英 /rʌn/ 美 /rʌn/\n\
+             v.: 跑，奔跑；赶忙，急速去；竞选；（使）运转，（使）运行；经营，管理；开车送（某人）；流淌，流动\n\
+             adj.: 熔化的；液态的\n"
+        );
+    }
+
+    #[test]
+    fn unmatched_filter_reports_the_unfiltered_count() {
+        let output = ydt(&["--filter", "飞翔"], "filter_none");
+
+        assert_eq!(output.status.code(), Some(1), "{output:?}");
+        assert!(output.stdout.is_empty());
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "(no senses match filter; 4 without it)\n"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use ydt::{
+    parse_translation, parse_translation_with_selectors, parse_translation_with_warnings,
+    render_pretty, render_translation, ParseWarning, RenderOptions, Sections, SelectorConfig,
+    Translation,
+};
+
+const TIME_HTML: &str = include_str!("fixtures/time.html");
+const UBIQUITOUS_HTML: &str = include_str!("fixtures/ubiquitous.html");
+
+fn rated(stars: &str) -> String {
+    format!(
+        r#"<div class="word-head"><div class="title">time<span class="star {stars}"></span></div></div>
+{}"#,
+        TIME_HTML.split_once("</div>\n</div>\n").unwrap().1
+    )
+}
+
+#[test]
+fn stars_are_parsed() {
+    let (translation, warnings) = parse_translation_with_warnings("time", TIME_HTML).unwrap();
+    assert_eq!(translation.frequency, Some(5));
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn missing_rating_is_none() {
+    assert_eq!(
+        parse_translation("ubiquitous", UBIQUITOUS_HTML)
+            .unwrap()
+            .frequency,
+        None
+    );
+    // The full page has a rating element without stars.
+    let take = include_str!("fixtures/take_full.html");
+    assert_eq!(parse_translation("take", take).unwrap().frequency, None);
+}
+
+#[test]
+fn unexpected_counts_are_clamped_with_a_warning() {
+    for (class, clamped, stars) in [("star7", 5, 7), ("star0", 1, 0)] {
+        let (translation, warnings) =
+            parse_translation_with_warnings("time", &rated(class)).unwrap();
+        assert_eq!(translation.frequency, Some(clamped), "{class}");
+        assert_eq!(warnings, [ParseWarning::FrequencyOutOfRange { stars }]);
+    }
+    assert_eq!(
+        ParseWarning::FrequencyOutOfRange { stars: 7 }.to_string(),
+        "frequency rating of 7 stars is not between 1 and 5"
+    );
+}
+
+#[test]
+fn rating_selector_is_configurable() {
+    let html = TIME_HTML.replace("class=\"star star5\"", "class=\"rank star3\"");
+    assert_eq!(parse_translation("time", &html).unwrap().frequency, None);
+    let selectors = SelectorConfig {
+        frequency: "span.rank".to_string(),
+        ..SelectorConfig::default()
+    }
+    .compile()
+    .unwrap();
+    let (translation, _) = parse_translation_with_selectors("time", &html, &selectors).unwrap();
+    assert_eq!(translation.frequency, Some(3));
+}
+
+#[test]
+fn pretty_output_shows_stars_after_the_phonetics() {
+    let mut translation = parse_translation("time", TIME_HTML).unwrap();
+    translation.frequency = Some(3);
+    let all = RenderOptions::default();
+    let rendered = render_pretty(&translation, &all, 80);
+    assert_eq!(rendered.lines().next(), Some("英 /taɪm/ 美 /taɪm/ ★★★☆☆"));
+
+    // Other sections and plain output are left alone.
+    let phonetics = RenderOptions {
+        sections: Sections::PhoneticsOnly,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        render_pretty(&translation, &phonetics, 80),
+        "英 /taɪm/ 美 /taɪm/"
+    );
+    assert!(!render_translation(&translation, &all).contains('★'));
+}
+
+#[test]
+fn stars_stand_alone_without_phonetics() {
+    let translation = Translation {
+        phonetics: Vec::new(),
+        frequency: Some(1),
+        ..parse_translation("time", TIME_HTML).unwrap()
+    };
+    let rendered = render_pretty(&translation, &RenderOptions::default(), 80);
+    assert_eq!(rendered.lines().next(), Some("★☆☆☆☆"));
+
+    let empty = Translation {
+        frequency: Some(4),
+        ..Translation::default()
+    };
+    assert_eq!(
+        render_pretty(&empty, &RenderOptions::default(), 80),
+        "No results."
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::process::Command;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{parse_translation, render_lemma_notice};
+
+const RAN_HTML: &str = include_str!("fixtures/ran.html");
+const CHILDREN_HTML: &str = include_str!("fixtures/children.html");
+
+#[test]
+fn past_tense_points_to_the_lemma() {
+    let translation = parse_translation("ran", RAN_HTML).unwrap();
+
+    assert_eq!(translation.headword.as_deref(), Some("run"));
+    assert_eq!(translation.lemma_note.as_deref(), Some("过去式"));
+    assert_eq!(translation.entries.len(), 2);
+    assert_eq!(render_lemma_notice(&translation).unwrap(), "→ run (过去式)");
+}
+
+#[test]
+fn plural_points_to_the_lemma() {
+    let translation = parse_translation("children", CHILDREN_HTML).unwrap();
+
+    assert_eq!(translation.headword.as_deref(), Some("child"));
+    assert_eq!(translation.lemma_note.as_deref(), Some("复数"));
+    assert_eq!(render_lemma_notice(&translation).unwrap(), "→ child (复数)");
+}
+
+#[test]
+fn matching_headword_has_no_note() {
+    let translation = parse_translation("Run", RAN_HTML).unwrap();
+
+    assert_eq!(translation.headword.as_deref(), Some("run"));
+    assert_eq!(translation.lemma_note, None);
+    assert_eq!(render_lemma_notice(&translation), None);
+}
+
+#[test]
+fn page_without_title_has_no_headword() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert_eq!(translation.headword, None);
+    assert_eq!(render_lemma_notice(&translation), None);
+}
+
+#[test]
+fn cli_prints_the_notice_to_stderr_when_piped() {
+    let server = MockServer::start(RAN_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-phonetic", "ran"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", common::temp_dir("headword-config"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "v.: 跑，奔跑；经营，管理\nn.: 跑步；一段时期\n"
+    );
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "→ run (过去式)\n"
+    );
+}
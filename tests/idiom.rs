@@ -0,0 +1,123 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    parse_translation, parse_translation_with_warnings, render_pretty, render_translation,
+    translation_to_json, Idiom, ParseWarning, RenderOptions, Translation,
+};
+
+const HUASHETIANZU_HTML: &str = include_str!("fixtures/huashetianzu.html");
+
+fn huashetianzu() -> Translation {
+    parse_translation("画蛇添足", HUASHETIANZU_HTML).unwrap()
+}
+
+#[test]
+fn idiom_block_is_parsed() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("画蛇添足", HUASHETIANZU_HTML).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(
+        translation.idiom,
+        Some(Idiom {
+            pinyin: "huà shé tiān zú".to_string(),
+            explanation: "画蛇时给蛇添上脚。比喻做了多余的事，非但无益，反而不合适。".to_string(),
+            source: "《战国策·齐策二》：“蛇固无足，子安能为之足。”".to_string(),
+            example: "文章已经写得很好了，再加这一段就是画蛇添足。".to_string(),
+        })
+    );
+    // The English candidates are still parsed.
+    assert_eq!(translation.entries.len(), 2);
+}
+
+#[test]
+fn other_chinese_words_are_unaffected() {
+    let xuexi = include_str!("fixtures/xuexi_full.html");
+    let (translation, warnings) = parse_translation_with_warnings("学习", xuexi).unwrap();
+    assert_eq!(translation.idiom, None);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        include_str!("fixtures/xuexi_full.txt").trim_end()
+    );
+}
+
+#[test]
+fn explanation_precedes_the_candidates() {
+    assert_eq!(
+        render_translation(&huashetianzu(), &RenderOptions::default()),
+        "huà shé tiān zú
+释义 画蛇时给蛇添上脚。比喻做了多余的事，非但无益，反而不合适。
+出处 《战国策·齐策二》：“蛇固无足，子安能为之足。”
+例句 文章已经写得很好了，再加这一段就是画蛇添足。
+
+draw a snake and add feet to it
+overdo it"
+    );
+
+    let rendered = render_pretty(&huashetianzu(), &RenderOptions::default(), 30);
+    assert!(
+        rendered.starts_with(
+            "huà shé tiān zú
+释义 画蛇时给蛇添上脚。比喻做
+     了多余的事，非但无益，反
+     而不合适。"
+        ),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn block_without_explanation_is_dropped_with_a_warning() {
+    let html = HUASHETIANZU_HTML.replace("idiom-explain", "idiom-meaning");
+    let (translation, warnings) = parse_translation_with_warnings("画蛇添足", &html).unwrap();
+    assert_eq!(translation.idiom, None);
+    assert_eq!(warnings, [ParseWarning::IdiomExplanationMissing]);
+}
+
+#[test]
+fn idiom_alone_is_a_result() {
+    let translation = Translation {
+        entries: Vec::new(),
+        ..huashetianzu()
+    };
+    assert!(!translation.is_empty());
+    assert_eq!(
+        translation_to_json(&translation)["idiom"]["pinyin"],
+        "huà shé tiān zú"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_the_explanation() {
+    let server = MockServer::start(HUASHETIANZU_HTML);
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--no-wrap"])
+            .args(args)
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir("idiom"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let stdout = run(&["--format", "plain", "画蛇添足"]);
+    assert!(
+        stdout.starts_with("huà shé tiān zú\n释义 画蛇时给蛇添上脚。"),
+        "{stdout}"
+    );
+    // Piped output keeps the definitions at fixed lines.
+    let stdout = run(&["画蛇添足"]);
+    assert!(
+        stdout.starts_with("\ndraw a snake and add feet to it\noverdo it\n\nhuà shé tiān zú\n"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,142 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::{
+    parse_translation, parse_translation_with_options, render_one_line_labeled, render_pretty,
+    render_translation, Labels, LookupStrategy, RenderOptions, SelectorConfig, Translation,
+    TranslationOptions,
+};
+
+const HA_BOTH_HTML: &str = include_str!("fixtures/ha_both.html");
+const HUASHETIANZU_HTML: &str = include_str!("fixtures/huashetianzu.html");
+
+/// `ha` read in both directions, and an idiom: every kind of label.
+fn translations() -> [Translation; 2] {
+    let options = TranslationOptions {
+        strategy: Some(LookupStrategy::Both),
+        ..TranslationOptions::default()
+    };
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let (ha, _) = parse_translation_with_options("ha", HA_BOTH_HTML, &selectors, &options).unwrap();
+    let idiom = parse_translation("画蛇添足", HUASHETIANZU_HTML).unwrap();
+    [ha, idiom]
+}
+
+fn render(labels: Labels) -> String {
+    let options = RenderOptions {
+        labels,
+        ..RenderOptions::default()
+    };
+    translations()
+        .iter()
+        .map(|translation| render_translation(translation, &options))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[test]
+fn chinese_labels_snapshot() {
+    assert_eq!(
+        render(Labels::Chinese),
+        include_str!("fixtures/labels.zh.txt").trim_end()
+    );
+}
+
+#[test]
+fn english_labels_snapshot() {
+    assert_eq!(
+        render(Labels::English),
+        include_str!("fixtures/labels.en.txt").trim_end()
+    );
+}
+
+#[test]
+fn labels_are_the_only_difference() {
+    let [ha, idiom] = translations();
+    let chinese = render(Labels::Chinese);
+    let english = render(Labels::English);
+    assert_eq!(chinese.lines().count(), english.lines().count());
+    assert_eq!(render(Labels::default()), chinese);
+
+    // The results themselves are left as found.
+    for translation in [&ha, &idiom] {
+        for entry in &translation.entries {
+            assert!(english.contains(&entry.meaning), "{}", entry.meaning);
+        }
+    }
+    assert!(english.contains(&idiom.idiom.unwrap().explanation));
+}
+
+#[test]
+fn pretty_and_one_line_output_use_the_labels() {
+    let hello = parse_translation("hello", include_str!("fixtures/hello.html")).unwrap();
+    let options = RenderOptions {
+        labels: Labels::English,
+        ..RenderOptions::default()
+    };
+    assert!(render_pretty(&hello, &options, 80).starts_with("UK /həˈləʊ/ US /həˈloʊ/\n"));
+    assert!(render_one_line_labeled(&hello, 80, Labels::English)
+        .starts_with("hello UK/həˈləʊ/ US/həˈloʊ/ "));
+    assert!(render_one_line_labeled(&hello, 80, Labels::Chinese)
+        .starts_with("hello 英/həˈləʊ/ 美/həˈloʊ/ "));
+}
+
+#[test]
+fn unrecognized_phonetic_labels_are_kept() {
+    let mut hello = parse_translation("hello", include_str!("fixtures/hello.html")).unwrap();
+    hello.phonetics[0].label = "澳".to_string();
+    let options = RenderOptions {
+        labels: Labels::English,
+        ..RenderOptions::default()
+    };
+    assert!(render_translation(&hello, &options).starts_with("澳 /həˈləʊ/ US /həˈloʊ/\n"));
+}
+
+#[test]
+fn locales_choose_the_labels() {
+    for (locale, labels) in [
+        ("en_US.UTF-8", Some(Labels::English)),
+        ("fr_FR", Some(Labels::English)),
+        ("ja_JP.eucJP", Some(Labels::English)),
+        ("zh_CN.UTF-8", Some(Labels::Chinese)),
+        ("zh_HK", Some(Labels::Chinese)),
+        ("zh", Some(Labels::Chinese)),
+        ("C", None),
+        ("C.UTF-8", None),
+        ("POSIX", None),
+        ("", None),
+    ] {
+        assert_eq!(Labels::from_locale(locale), labels, "{locale}");
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_labels_in_english_when_asked() {
+    let server = MockServer::start(HELLO_HTML);
+    let run = |args: &[&str], lang: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .arg("--no-mobile-fallback")
+            .args(args)
+            .arg("hello")
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir("labels"))
+            .env("XDG_CACHE_HOME", temp_dir("labels_cache"))
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env("LANG", lang)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(run(&["--english-labels"], "zh_CN.UTF-8").starts_with("UK /həˈləʊ/ US /həˈloʊ/\n"));
+    // Piped output keeps the page's labels whatever the locale.
+    assert!(run(&[], "en_US.UTF-8").starts_with("英 /həˈləʊ/ 美 /həˈloʊ/\n"));
+}
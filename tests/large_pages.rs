@@ -0,0 +1,42 @@
+//! Full-size result pages, also used by the parser benchmarks, pinned so that
+//! parser optimizations cannot change results.
+
+use ydt::{parse_translation_from_html, parse_translation_with_warnings, Layout};
+
+const TAKE_HTML: &str = include_str!("fixtures/take_full.html");
+const XUEXI_HTML: &str = include_str!("fixtures/xuexi_full.html");
+
+#[test]
+fn english_page_renders_as_before() {
+    let rendered = parse_translation_from_html("take", TAKE_HTML).unwrap();
+    assert_eq!(rendered, include_str!("fixtures/take_full.txt").trim_end());
+}
+
+#[test]
+fn chinese_page_renders_as_before() {
+    let rendered = parse_translation_from_html("学习", XUEXI_HTML).unwrap();
+    assert_eq!(rendered, include_str!("fixtures/xuexi_full.txt").trim_end());
+}
+
+#[test]
+fn english_page_is_fully_parsed() {
+    let (translation, warnings) = parse_translation_with_warnings("takes", TAKE_HTML).unwrap();
+
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(translation.phonetics.len(), 2);
+    assert_eq!(translation.entries.len(), 36);
+    assert_eq!(translation.headword.as_deref(), Some("take"));
+    assert!(translation.lemma_note.is_some());
+    assert_eq!(translation.suggestions, ["takes", "taken"]);
+}
+
+#[test]
+fn chinese_page_is_fully_parsed() {
+    let (translation, warnings) = parse_translation_with_warnings("学习", XUEXI_HTML).unwrap();
+
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+    assert_eq!(translation.entries.len(), 48);
+    assert_eq!(translation.headword.as_deref(), Some("学习"));
+}
@@ -0,0 +1,58 @@
+use ydt::{parse_translation, parse_translation_with_warnings, Layout};
+
+const C_LANGUAGE_HTML: &str = include_str!("fixtures/c_language.html");
+const USB_CE_HTML: &str = include_str!("fixtures/usb_ce_layout.html");
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+
+#[test]
+fn mixed_script_query_falls_back_to_english_layout() {
+    let translation = parse_translation("C语言", C_LANGUAGE_HTML).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(translation.entries.len(), 1);
+    assert_eq!(translation.entries[0].pos, "n.");
+    assert_eq!(translation.entries[0].meaning, "C language");
+}
+
+#[test]
+fn ascii_query_falls_back_to_chinese_layout() {
+    let (translation, warnings) = parse_translation_with_warnings("usb", USB_CE_HTML).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+    let meanings: Vec<_> = translation
+        .entries
+        .iter()
+        .map(|e| e.meaning.as_str())
+        .collect();
+    assert_eq!(meanings, ["USB interface", "universal serial bus"]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn expected_layout_is_recorded() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+
+    let html = r#"<li class="word-exp-ce mcols-layout"><a class="point">hello</a></li>"#;
+    let translation = parse_translation("你好", html).unwrap();
+    assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+}
+
+#[test]
+fn empty_page_has_no_layout() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("3D打印", "<html></html>").unwrap();
+    assert!(translation.is_empty());
+    assert_eq!(translation.layout, None);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn warnings_come_from_the_expected_layout_when_nothing_matches() {
+    let html = r#"
+        <div class="trans-container"></div>
+        <div class="trans-container"><p>renamed</p></div>
+    "#;
+    let (_, warnings) = parse_translation_with_warnings("hello", html).unwrap();
+    assert_eq!(warnings, [ydt::ParseWarning::DefinitionsMissing]);
+}
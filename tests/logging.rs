@@ -0,0 +1,114 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::sync::{Mutex, Once};
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use log::{Level, Log, Metadata, Record};
+use ydt::{HttpFetcher, YdtClient};
+
+/// Logger keeping every record so tests can inspect them.
+struct CaptureLogger {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("ydt") {
+            let message = record.args().to_string();
+            self.records.lock().unwrap().push((record.level(), message));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+fn install_logger() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+}
+
+/// Records containing `needle`; each test looks up a different word, so tests
+/// running in parallel don't see each other's records.
+fn records_containing(needle: &str) -> Vec<(Level, String)> {
+    LOGGER
+        .records
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, message)| message.contains(needle))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn requests_are_logged_at_debug_level() {
+    install_logger();
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .build();
+    client.translate("hello").unwrap();
+
+    let records = records_containing("word=hello");
+    assert_eq!(records.len(), 2, "{records:?}");
+    assert!(records.iter().all(|(level, _)| *level == Level::Debug));
+    assert!(records[0].1.starts_with("GET "), "{records:?}");
+    assert!(records[1].1.starts_with("200 OK from "), "{records:?}");
+}
+
+#[test]
+fn user_agent_fallback_and_retries_are_warned() {
+    install_logger();
+    let server = MockServer::start_with(|request| {
+        if request.headers["user-agent"].starts_with("ydt/") {
+            (403, "")
+        } else {
+            (503, "")
+        }
+    });
+    let fetcher = HttpFetcher::default()
+        .base_url(format!("{}/result", server.url))
+        .retries(1);
+    YdtClient::with_fetcher(fetcher)
+        .translate("busy")
+        .unwrap_err();
+
+    let warnings: Vec<_> = records_containing("\"busy\"")
+        .into_iter()
+        .filter(|(level, _)| *level == Level::Warn)
+        .map(|(_, message)| message)
+        .collect();
+    assert_eq!(warnings.len(), 3, "{warnings:?}");
+    assert!(warnings[0].contains("403"), "{warnings:?}");
+    assert!(warnings[0].contains("browser user agent"), "{warnings:?}");
+    assert!(warnings[1].contains("retry 1 of 1"), "{warnings:?}");
+    assert!(warnings[2].contains("browser user agent"), "{warnings:?}");
+}
+
+#[test]
+fn empty_pages_are_warned() {
+    install_logger();
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("zzyzx", "<html></html>"));
+    assert!(client.translate("zzyzx").unwrap().is_empty());
+
+    let records = records_containing("\"zzyzx\"");
+    assert_eq!(records.len(), 1, "{records:?}");
+    assert_eq!(records[0].0, Level::Warn);
+    assert!(
+        records[0].1.contains("no phonetics or definitions"),
+        "{records:?}"
+    );
+}
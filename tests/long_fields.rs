@@ -0,0 +1,142 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::fs;
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    parse_translation_with_options, render_one_line, render_translation, render_tsv_row,
+    translation_to_json, ParseWarning, RenderOptions, SelectorConfig, Translation,
+    TranslationOptions, DEFAULT_MAX_FIELD_LEN,
+};
+
+const LONG_HTML: &str = include_str!("fixtures/long_web_trans.html");
+const SENTENCE: &str = "这是一个非常长的网络释义。";
+
+fn parse(max_field_len: Option<usize>) -> (Translation, Vec<ParseWarning>) {
+    let options = TranslationOptions {
+        max_field_len,
+        ..TranslationOptions::default()
+    };
+    let selectors = SelectorConfig::default().compile().unwrap();
+    parse_translation_with_options("lorem ipsum", LONG_HTML, &selectors, &options).unwrap()
+}
+
+#[test]
+fn long_values_are_kept_whole_and_reported() {
+    let (translation, warnings) = parse(None);
+
+    assert_eq!(translation.web_translations[0], SENTENCE.repeat(50));
+    assert_eq!(translation.display_limit, Some(DEFAULT_MAX_FIELD_LEN));
+    assert_eq!(
+        warnings,
+        [ParseWarning::FieldTooLong {
+            field: "web translation".to_string(),
+            len: 650,
+            max: 500,
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "web translation of 650 characters is longer than 500; truncated for display"
+    );
+}
+
+#[test]
+fn text_output_is_truncated() {
+    let (translation, _) = parse(None);
+
+    let rendered = render_translation(&translation, &RenderOptions::default());
+    let expected: String = SENTENCE.repeat(50).chars().take(499).collect();
+    assert_eq!(rendered, format!("(web) {expected}…；占位文本"));
+
+    let row = render_tsv_row("lorem ipsum", &Ok(translation.clone()));
+    assert_eq!(row, format!("lorem ipsum\t\t(web) {expected}…；占位文本"));
+
+    assert_eq!(
+        render_one_line(&translation, 40),
+        render_one_line(&parse(Some(10_000)).0, 40)
+    );
+}
+
+#[test]
+fn json_keeps_the_whole_value_and_flags_the_truncation() {
+    let (translation, _) = parse(None);
+
+    let json = translation_to_json(&translation);
+    assert_eq!(json["web_translations"][0], SENTENCE.repeat(50));
+    assert_eq!(json["truncated_for_display"], true);
+}
+
+#[test]
+fn the_limit_follows_the_options() {
+    let (translation, warnings) = parse(Some(1000));
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.display_limit, None);
+    assert!(translation_to_json(&translation)
+        .get("truncated_for_display")
+        .is_none());
+    assert!(render_translation(&translation, &RenderOptions::default()).ends_with("。；占位文本"));
+
+    let (translation, warnings) = parse(Some(13));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "(web) 这是一个非常长的网络释义…；占位文本"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn config_sets_the_limit() {
+    let config = ydt::config::Config::from_toml("max_field_len = 80").unwrap();
+    assert_eq!(config.max_field_len, Some(80));
+    let err = ydt::config::Config::from_toml("max_field_len = 0").unwrap_err();
+    assert!(err.to_string().contains("`max_field_len`"), "{err}");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_truncates_text_but_not_ndjson() {
+    let server = MockServer::start(LONG_HTML);
+    let config = temp_dir("long_fields");
+    fs::create_dir_all(config.join("ydt")).unwrap();
+    fs::write(config.join("ydt/config.toml"), "max_field_len = 20\n").unwrap();
+    let run = |format: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--format", format, "lorem ipsum"])
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", &config)
+            .env("XDG_CACHE_HOME", temp_dir("long_fields_cache"))
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(
+        run("plain"),
+        "(web) 这是一个非常长的网络释义。这是一个非常…；占位文本\n"
+    );
+    let json: serde_json::Value = serde_json::from_str(&run("ndjson")).unwrap();
+    assert_eq!(json["result"]["web_translations"][0], SENTENCE.repeat(50));
+    assert_eq!(json["result"]["truncated_for_display"], true);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn strict_clients_accept_long_values() {
+    let server = MockServer::start(LONG_HTML);
+    let translation = ydt::YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .strict(true)
+        .build()
+        .translate("lorem ipsum")
+        .unwrap();
+    assert_eq!(translation.display_limit, Some(DEFAULT_MAX_FIELD_LEN));
+}
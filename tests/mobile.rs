@@ -0,0 +1,158 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, MockServer};
+use ydt::{parse_mobile_html, Entry, EntrySource, Layout, PageSource, Phonetic, YdtClient};
+
+const MOBILE_HELLO_HTML: &str = include_str!("fixtures/mobile_hello.html");
+const MOBILE_NIHAO_HTML: &str = include_str!("fixtures/mobile_nihao.html");
+const EMPTY_HTML: &str = "<html><body><p>nothing here</p></body></html>";
+
+fn client_for(server: &MockServer) -> YdtClient {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_url(format!("{}/dict", server.url))
+        .build()
+}
+
+/// Serve `desktop` for the desktop page and the mobile hello page for the mobile one.
+fn desktop_then_mobile(desktop: (u16, &'static str)) -> MockServer {
+    MockServer::start_with(move |request| {
+        if request.target.starts_with("/dict") {
+            (200, MOBILE_HELLO_HTML)
+        } else {
+            desktop
+        }
+    })
+}
+
+#[test]
+fn parses_english_chinese_mobile_page() {
+    let translation = parse_mobile_html("hello", MOBILE_HELLO_HTML).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(
+        translation.phonetics,
+        [
+            Phonetic {
+                label: "英".to_string(),
+                text: "[həˈləʊ]".to_string(),
+            },
+            Phonetic {
+                label: "美".to_string(),
+                text: "[həˈloʊ]".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        translation.entries,
+        [
+            Entry {
+                pos: "int.".to_string(),
+                meaning: "喂；哈罗".to_string(),
+                source: EntrySource::Mobile,
+            },
+            Entry {
+                pos: "n.".to_string(),
+                meaning: "表示问候， 惊奇或唤起注意时的用语".to_string(),
+                source: EntrySource::Mobile,
+            },
+            Entry {
+                pos: String::new(),
+                meaning: "【名】 （Hello）（法）埃洛（人名）".to_string(),
+                source: EntrySource::Mobile,
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_chinese_english_mobile_page() {
+    let translation = parse_mobile_html("你好", MOBILE_NIHAO_HTML).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+    assert!(translation.phonetics.is_empty());
+    let words: Vec<_> = translation
+        .entries
+        .iter()
+        .map(|entry| entry.meaning.as_str())
+        .collect();
+    assert_eq!(words, ["hello", "hi", "how do you do"]);
+}
+
+#[test]
+fn empty_desktop_page_falls_back_to_mobile() {
+    let server = desktop_then_mobile((200, EMPTY_HTML));
+    let (translation, info) = client_for(&server).translate_detailed("hello").unwrap();
+
+    assert_eq!(translation.entries[0].meaning, "喂；哈罗");
+    assert_eq!(info.source, PageSource::Mobile);
+    assert_eq!(
+        info.final_url.unwrap().as_str(),
+        format!("{}/dict?q=hello&le=eng", server.url)
+    );
+    let targets: Vec<_> = server
+        .pending_requests()
+        .into_iter()
+        .map(|request| request.target)
+        .collect();
+    assert_eq!(
+        targets,
+        ["/result?word=hello&lang=en", "/dict?q=hello&le=eng"]
+    );
+}
+
+#[test]
+fn blocked_desktop_page_falls_back_to_mobile() {
+    let server = desktop_then_mobile((500, ""));
+    let translation = client_for(&server).translate("hello").unwrap();
+    assert_eq!(translation.phonetics.len(), 2);
+}
+
+#[test]
+fn desktop_results_are_kept() {
+    let server = MockServer::start(common::HELLO_HTML);
+    let (_, info) = client_for(&server).translate_detailed("hello").unwrap();
+
+    assert_eq!(info.source, PageSource::Desktop);
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[test]
+fn fallback_can_be_disabled() {
+    let server = desktop_then_mobile((200, EMPTY_HTML));
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_url(format!("{}/dict", server.url))
+        .mobile_fallback(false)
+        .build();
+
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[test]
+fn desktop_error_is_kept_when_mobile_is_empty_too() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/dict") {
+            (200, EMPTY_HTML)
+        } else {
+            (503, "")
+        }
+    });
+    let err = client_for(&server).translate("hello").unwrap_err();
+    assert!(err.to_string().contains("503"), "{err}");
+}
+
+#[test]
+fn custom_fetchers_do_not_fall_back() {
+    let server = MockServer::start(MOBILE_HELLO_HTML);
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", EMPTY_HTML))
+        .mobile_url(format!("{}/dict", server.url))
+        .build();
+
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert!(server.pending_requests().is_empty());
+}
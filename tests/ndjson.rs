@@ -0,0 +1,88 @@
+#![cfg(feature = "http")]
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+use ydt::{Fetcher, YdtClient, YdtError};
+
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+
+/// Fetcher that answers slowly and records how many lines were already written
+/// whenever a request starts.
+struct SlowFetcher {
+    written: Arc<Mutex<Vec<String>>>,
+    seen_at_start: Arc<Mutex<Vec<(String, usize)>>>,
+}
+
+impl Fetcher for SlowFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        let written = self.written.lock().unwrap().len();
+        self.seen_at_start
+            .lock()
+            .unwrap()
+            .push((word.to_string(), written));
+        thread::sleep(Duration::from_millis(20));
+        match word {
+            "missing" => Err(YdtError::HttpStatus {
+                word: word.to_string(),
+                url: format!("mock://{word}"),
+                status: StatusCode::NOT_FOUND,
+            }),
+            _ => Ok(HELLO_HTML.to_string()),
+        }
+    }
+}
+
+#[test]
+fn lines_are_emitted_before_later_requests_finish() {
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let seen_at_start = Arc::new(Mutex::new(Vec::new()));
+    let client = YdtClient::with_fetcher(SlowFetcher {
+        written: Arc::clone(&written),
+        seen_at_start: Arc::clone(&seen_at_start),
+    });
+
+    client.translate_each(["hello", "missing", "hi"], |word, result| {
+        written
+            .lock()
+            .unwrap()
+            .push(ydt::render_ndjson_line(word, &result));
+    });
+
+    assert_eq!(
+        *seen_at_start.lock().unwrap(),
+        [
+            ("hello".to_string(), 0),
+            ("missing".to_string(), 1),
+            ("hi".to_string(), 2)
+        ]
+    );
+
+    let lines: Vec<Value> = written
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|line| serde_json::from_str(line).expect("expected valid JSON"))
+        .collect();
+    assert_eq!(lines[0]["word"], "hello");
+    assert_eq!(lines[0]["ok"], true);
+    assert_eq!(lines[0]["result"]["phonetics"][0]["text"], "/həˈləʊ/");
+    assert_eq!(lines[0]["result"]["entries"][1]["pos"], "n.");
+    assert_eq!(lines[1]["word"], "missing");
+    assert_eq!(lines[1]["ok"], false);
+    assert_eq!(
+        lines[1]["error"],
+        "Request for \"missing\" failed with status: 404 Not Found"
+    );
+    assert_eq!(lines[2]["word"], "hi");
+}
+
+#[test]
+fn each_line_is_a_single_line() {
+    let translation = ydt::parse_translation("hello", HELLO_HTML).unwrap();
+    let line = ydt::render_ndjson_line("hello\nworld", &Ok(translation));
+    assert!(!line.contains('\n'));
+}
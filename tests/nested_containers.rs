@@ -0,0 +1,45 @@
+use ydt::{
+    parse_translation_with_options, parse_translation_with_warnings, Entry, EntrySource, Layout,
+    SelectorConfig, TranslationOptions,
+};
+
+const NESTED_CONTAINERS_HTML: &str = include_str!("fixtures/nested_containers.html");
+
+fn entry(pos: &str, meaning: &str) -> Entry {
+    Entry {
+        pos: pos.to_string(),
+        meaning: meaning.to_string(),
+        source: EntrySource::Concise,
+    }
+}
+
+#[test]
+fn nested_containers_are_read_once() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("bank", NESTED_CONTAINERS_HTML).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(translation.phonetics.len(), 2);
+    assert_eq!(
+        translation.entries,
+        [
+            entry("n.", "银行；岸；堤"),
+            entry("v.", "存款；堆积"),
+            entry("n.", "（赌场的）庄家"),
+        ]
+    );
+}
+
+#[test]
+fn duplicates_do_not_count_towards_max_definitions() {
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let options = TranslationOptions {
+        max_definitions: Some(3),
+        ..TranslationOptions::default()
+    };
+    let (translation, _) =
+        parse_translation_with_options("bank", NESTED_CONTAINERS_HTML, &selectors, &options)
+            .unwrap();
+    assert_eq!(translation.entries.len(), 3);
+    assert_eq!(translation.entries[2], entry("n.", "（赌场的）庄家"));
+}
@@ -0,0 +1,194 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+
+use common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+use ydt::{normalize_word, YdtClient, YdtError, DEFAULT_MAX_WORD_LEN};
+
+#[test]
+fn trims_and_collapses_whitespace() {
+    assert_eq!(normalize_word("  hello\n", 100).unwrap(), "hello");
+    assert_eq!(normalize_word("ice \t\n cream", 100).unwrap(), "ice cream");
+    assert_eq!(normalize_word("\u{3000}你好\u{3000}", 100).unwrap(), "你好");
+}
+
+#[test]
+fn folds_text_copied_from_chinese_pages() {
+    let cases = [
+        ("ｈｅｌｌｏ", "hello"),
+        ("ＨＥＬＬＯ　ｗｏｒｌｄ", "HELLO world"),
+        ("ｃ＋＋", "c++"),
+        ("１２３", "123"),
+        ("don\u{2019}t", "don't"),
+        ("don\u{2018}t", "don't"),
+        ("ｄｏｎ＇ｔ", "don't"),
+        ("\u{201c}hello\u{201d}", "\"hello\""),
+        ("hello，", "hello"),
+        ("「hello」", "hello"),
+        ("『ice cream』。", "ice cream"),
+        ("【 run 】", "run"),
+        ("（hello！）", "hello"),
+        ("《画蛇添足》", "画蛇添足"),
+        ("你好，世界。", "你好,世界"),
+        ("hello, world.", "hello, world."),
+        ("\u{2019}tis", "'tis"),
+        ("rock\u{2019}n\u{2019}roll", "rock'n'roll"),
+    ];
+    for (word, expected) in cases {
+        assert_eq!(normalize_word(word, 100).unwrap(), expected, "{word:?}");
+    }
+}
+
+#[test]
+fn drops_invisible_characters_copied_along() {
+    let cases = [
+        ("hel\u{200b}lo", "hello"),
+        ("\u{200b}hello\u{200b}", "hello"),
+        ("ice\u{200c} \u{200d}cream", "ice cream"),
+        ("ice \u{200b} cream", "ice cream"),
+        ("dic\u{ad}tion\u{ad}ary", "dictionary"),
+        ("\u{202a}hello\u{202c}", "hello"),
+        ("\u{200e}你好\u{200f}", "你好"),
+        ("\u{2067}run\u{2069}", "run"),
+        ("\u{feff}「hello」\u{2060}", "hello"),
+    ];
+    for (word, expected) in cases {
+        assert_eq!(normalize_word(word, 100).unwrap(), expected, "{word:?}");
+    }
+    assert!(matches!(
+        normalize_word("\u{200b}\u{ad}", 100),
+        Err(YdtError::EmptyWord)
+    ));
+}
+
+#[test]
+fn rejects_words_of_only_chinese_punctuation() {
+    for word in ["，", "「」", " 。 "] {
+        assert!(
+            matches!(normalize_word(word, 100), Err(YdtError::EmptyWord)),
+            "{word:?}"
+        );
+    }
+}
+
+#[test]
+fn rejects_empty_and_whitespace_only_words() {
+    for word in ["", "   ", "\n\t"] {
+        assert!(
+            matches!(normalize_word(word, 100), Err(YdtError::EmptyWord)),
+            "{word:?}"
+        );
+    }
+}
+
+#[test]
+fn rejects_words_longer_than_the_limit() {
+    let word = "a".repeat(DEFAULT_MAX_WORD_LEN);
+    assert!(normalize_word(&word, DEFAULT_MAX_WORD_LEN).is_ok());
+
+    let err = normalize_word(&format!("{word}a"), DEFAULT_MAX_WORD_LEN).unwrap_err();
+    assert!(
+        matches!(err, YdtError::WordTooLong { len: 101, max: 100 }),
+        "{err:?}"
+    );
+    // The limit counts characters, not bytes.
+    assert!(normalize_word(&"字".repeat(100), 100).is_ok());
+}
+
+#[test]
+fn client_looks_up_the_normalized_word() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    let translation = client.translate("  hello\n").unwrap();
+    assert_eq!(translation.word, "hello");
+}
+
+#[test]
+fn curly_apostrophes_are_requested_like_straight_ones() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build();
+
+    client.translate("don't").unwrap();
+    let straight = server.next_request().target;
+    let translation = client.translate("don\u{2019}t").unwrap();
+    let curly = server.next_request().target;
+
+    assert_eq!(curly, straight);
+    assert_eq!(translation.word, "don't");
+}
+
+#[test]
+fn cli_reports_the_folded_word_when_verbose() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "-v", "ｈｅｌｌｏ，"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("normalize"))
+        .env("XDG_CACHE_HOME", temp_dir("normalize_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("normalized: \"ｈｅｌｌｏ，\" → \"hello\"\n"),
+        "{stderr}"
+    );
+    assert!(server.next_request().target.contains("word=hello&"));
+}
+
+#[test]
+fn cli_finds_words_with_an_embedded_zero_width_space() {
+    // Youdao has no entry for the word with the space left in.
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/result?word=hello&") {
+            (200, HELLO_HTML)
+        } else {
+            (200, "<html><body></body></html>")
+        }
+    });
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "hel\u{200b}lo"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("zero_width"))
+        .env("XDG_CACHE_HOME", temp_dir("zero_width_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("你好"), "{stdout}");
+    assert_eq!(server.next_request().target, "/result?word=hello&lang=en");
+}
+
+#[test]
+fn client_rejects_before_fetching() {
+    // The mock fails every unknown word with 404, so reaching it would change the error.
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .max_word_len(5)
+        .build();
+
+    assert!(matches!(client.translate(" "), Err(YdtError::EmptyWord)));
+    assert!(matches!(
+        client.fetch_html("abcdef"),
+        Err(YdtError::WordTooLong { len: 6, max: 5 })
+    ));
+}
+
+#[test]
+fn cli_reports_empty_word_as_usage_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("  ")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Word to translate is empty"), "{stderr}");
+    assert!(stderr.contains("try '--help'"), "{stderr}");
+}
@@ -0,0 +1,130 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+#[cfg(feature = "http")]
+use ydt::config::Config;
+#[cfg(feature = "http")]
+use ydt::YdtClient;
+use ydt::{
+    build_result_url, is_number, parse_translation, render_translation, RenderOptions, YdtError,
+};
+
+const NUMBER_42_HTML: &str = include_str!("fixtures/number_42.html");
+
+#[test]
+fn numbers_are_recognized() {
+    for (word, expected) in [
+        ("42", true),
+        ("-7", true),
+        ("+7", true),
+        ("3.14", true),
+        (".5", true),
+        ("1,000,000", true),
+        ("1,000.25", true),
+        ("1,00", false),
+        ("1.", false),
+        ("5km", false),
+        ("km/h", false),
+        ("3/4", false),
+        ("1e6", false),
+        ("-", false),
+        ("", false),
+        ("hello", false),
+    ] {
+        assert_eq!(is_number(word), expected, "{word:?}");
+    }
+}
+
+#[test]
+fn units_are_escaped_in_the_result_url() {
+    let url = build_result_url("km/h", "en").unwrap().to_string();
+    assert!(url.contains("word=km%2Fh&"), "{url}");
+    let url = build_result_url("°C", "en").unwrap().to_string();
+    assert!(url.contains("word=%C2%B0C&"), "{url}");
+}
+
+#[test]
+fn conversions_are_parsed_and_rendered() {
+    let translation = parse_translation("42", NUMBER_42_HTML).unwrap();
+    assert!(translation.entries.is_empty());
+    assert!(!translation.is_empty());
+    assert_eq!(translation.conversions, ["四十二", "forty-two", "XLII"]);
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "四十二\nforty-two\nXLII"
+    );
+}
+
+#[test]
+fn number_query_counts_as_not_found() {
+    let err = YdtError::NumberQuery {
+        word: "42".to_string(),
+    };
+    assert!(err.is_not_found());
+    assert_eq!(err.word(), Some("42"));
+    assert_eq!(
+        err.to_string(),
+        "Skipped \"42\": numbers are not translated"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn skip_numbers_is_read_from_config() {
+    let config = Config::from_toml("skip_numbers = true").unwrap();
+    assert_eq!(config.skip_numbers, Some(true));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn numbers_are_looked_up_by_default() {
+    let server = MockServer::start(NUMBER_42_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build();
+    let translation = client.translate("42").unwrap();
+    assert_eq!(translation.conversions.len(), 3);
+    assert!(server.next_request().target.contains("word=42&"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn skip_numbers_sends_no_request() {
+    let server = MockServer::start(NUMBER_42_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .skip_numbers(true)
+        .build();
+    match client.translate("1,000") {
+        Err(YdtError::NumberQuery { word }) => assert_eq!(word, "1,000"),
+        other => panic!("expected NumberQuery, got {other:?}"),
+    }
+    assert!(client.translate("km/h").is_ok());
+    let requests = server.pending_requests();
+    assert_eq!(requests.len(), 1, "{requests:?}");
+    assert!(requests[0].target.contains("word=km%2Fh&"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_explains_empty_number_results() {
+    let server = MockServer::start("<html><body></body></html>");
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--format", "plain", "2024"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("numbers"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "No results: numbers are not translated.\n"
+    );
+}
@@ -0,0 +1,60 @@
+use ydt::{Entry, EntrySource, Phonetic, Translation};
+
+fn hello() -> Translation {
+    Translation {
+        word: "hello".to_string(),
+        phonetics: vec![Phonetic {
+            label: "英".to_string(),
+            text: "/həˈləʊ/".to_string(),
+        }],
+        entries: vec![
+            Entry {
+                pos: "int.".to_string(),
+                meaning: "你好".to_string(),
+                source: EntrySource::Concise,
+            },
+            Entry {
+                pos: "n.".to_string(),
+                meaning: "表示问候".to_string(),
+                source: EntrySource::Concise,
+            },
+        ],
+        ..Translation::default()
+    }
+}
+
+#[test]
+fn renders_everything_on_one_line() {
+    let line = ydt::render_one_line(&hello(), 120);
+    assert_eq!(line, "hello 英/həˈləʊ/ int.你好 | n.表示问候");
+    assert_eq!(ydt::display_width(&line), 38);
+}
+
+#[test]
+fn exact_width_is_not_truncated() {
+    assert_eq!(
+        ydt::render_one_line(&hello(), 38),
+        "hello 英/həˈləʊ/ int.你好 | n.表示问候"
+    );
+}
+
+#[test]
+fn truncates_with_ellipsis_when_one_column_short() {
+    let line = ydt::render_one_line(&hello(), 37);
+    assert_eq!(line, "hello 英/həˈləʊ/ int.你好 | n.表示问…");
+    assert_eq!(ydt::display_width(&line), 37);
+}
+
+#[test]
+fn cut_inside_cjk_run_never_overflows() {
+    // "n.表示" ends at column 34; a budget of 35 columns before the ellipsis leaves
+    // a single column, which cannot hold the double-width "问".
+    let line = ydt::render_one_line(&hello(), 36);
+    assert_eq!(line, "hello 英/həˈləʊ/ int.你好 | n.表示…");
+    assert_eq!(ydt::display_width(&line), 35);
+}
+
+#[test]
+fn zero_width_renders_nothing() {
+    assert_eq!(ydt::render_one_line(&hello(), 0), "");
+}
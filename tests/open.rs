@@ -0,0 +1,219 @@
+#![cfg(feature = "http")]
+
+#[allow(dead_code)]
+#[path = "../src/browser.rs"]
+mod browser;
+mod common;
+
+use std::process::Command;
+
+use browser::{open_word, Launcher, OpenError};
+use common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+use url::Url;
+use ydt::openapi::Credentials;
+use ydt::{parse_translation, translation_to_json, Backend, TranslationOptions, YdtClient};
+
+const HELLLO_HTML: &str = include_str!("fixtures/helllo.html");
+
+/// Launcher recording the URLs it is given instead of opening them.
+#[derive(Default)]
+struct Recorder {
+    opened: Vec<Url>,
+    fail: bool,
+}
+
+impl Launcher for Recorder {
+    fn launch(&mut self, url: &Url) -> Result<(), String> {
+        if self.fail {
+            return Err("no browser".to_string());
+        }
+        self.opened.push(url.clone());
+        Ok(())
+    }
+}
+
+fn client_for(server: &MockServer) -> ydt::YdtClientBuilder {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+}
+
+fn source_url(client: &YdtClient, word: &str, options: &TranslationOptions) -> String {
+    let translation = client.translate_with(word, options).unwrap();
+    translation.source_url.unwrap().to_string()
+}
+
+#[test]
+fn url_has_the_lang_of_the_lookup() {
+    let server = MockServer::start(HELLO_HTML);
+    let default = TranslationOptions::default();
+
+    let client = client_for(&server).build();
+    assert_eq!(
+        source_url(&client, "hello", &default),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+
+    let client = client_for(&server).lang("fr").build();
+    assert_eq!(
+        source_url(&client, "bonjour", &default),
+        format!("{}/result?word=bonjour&lang=fr", server.url)
+    );
+
+    let options = TranslationOptions {
+        lang: Some("ja".to_string()),
+        ..TranslationOptions::default()
+    };
+    assert_eq!(
+        source_url(&client, "ice cream", &options),
+        format!("{}/result?word=ice+cream&lang=ja", server.url)
+    );
+}
+
+#[test]
+fn url_is_the_page_redirected_to() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/old") {
+            (302, "/result?word=hello&lang=en")
+        } else {
+            (200, HELLO_HTML)
+        }
+    });
+    let translation = YdtClient::builder()
+        .base_url(format!("{}/old", server.url))
+        .mobile_fallback(false)
+        .build()
+        .translate("hello")
+        .unwrap();
+
+    assert_eq!(
+        translation.source_url.unwrap().as_str(),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+}
+
+#[test]
+fn url_is_of_the_corrected_word() {
+    let server = MockServer::start_with(|request| {
+        if request.target.contains("helllo") {
+            (200, HELLLO_HTML)
+        } else {
+            (200, HELLO_HTML)
+        }
+    });
+    let translation = client_for(&server)
+        .auto_correct(true)
+        .build()
+        .translate("helllo")
+        .unwrap();
+
+    assert_eq!(translation.corrected_from.as_deref(), Some("helllo"));
+    let url = format!("{}/result?word=hello&lang=en", server.url);
+    assert_eq!(translation_to_json(&translation)["source_url"], url);
+    assert_eq!(translation.source_url.unwrap().as_str(), url);
+}
+
+#[test]
+fn api_results_link_the_result_page() {
+    let server = MockServer::start(r#"{"errorCode": "0", "translation": ["你好"]}"#);
+    let translation = client_for(&server)
+        .api_url(format!("{}/api", server.url))
+        .api_credentials(Credentials::new("app-key", "app-secret"))
+        .backend(Backend::Api)
+        .build()
+        .translate("hello")
+        .unwrap();
+
+    // Not the signed API request.
+    assert_eq!(
+        translation.source_url.unwrap().as_str(),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+}
+
+#[test]
+fn pages_not_fetched_over_http_have_no_url() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert_eq!(translation.source_url, None);
+    assert!(translation_to_json(&translation)
+        .get("source_url")
+        .is_none());
+
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    assert_eq!(client.translate("hello").unwrap().source_url, None);
+}
+
+#[test]
+fn open_launches_the_result_page() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = client_for(&server).build();
+    let mut launcher = Recorder::default();
+
+    let url = open_word(&client, "hello", &mut launcher).unwrap();
+
+    assert_eq!(
+        url.as_str(),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+    assert_eq!(launcher.opened, [url]);
+}
+
+#[test]
+fn open_reports_why_nothing_was_opened() {
+    let mut launcher = Recorder::default();
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    assert!(matches!(
+        open_word(&client, "hello", &mut launcher),
+        Err(OpenError::NoPage)
+    ));
+
+    let client = YdtClient::new();
+    assert!(matches!(
+        open_word(&client, "  ", &mut launcher),
+        Err(OpenError::Lookup(_))
+    ));
+    assert!(launcher.opened.is_empty());
+
+    let server = MockServer::start(HELLO_HTML);
+    let mut launcher = Recorder {
+        fail: true,
+        ..Recorder::default()
+    };
+    let result = open_word(&client_for(&server).build(), "hello", &mut launcher);
+    assert!(matches!(result, Err(OpenError::Launch(msg)) if msg == "no browser"));
+}
+
+#[test]
+fn cli_shows_the_link_under_the_result() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--show-link", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("show_link"))
+        .env("XDG_CACHE_HOME", temp_dir("show_link_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("英 /həˈləʊ/"), "{stdout}");
+    assert!(
+        stdout.ends_with(&format!("\n{}/result?word=hello&lang=en\n", server.url)),
+        "{stdout}"
+    );
+}
+
+#[cfg(not(feature = "open"))]
+#[test]
+fn cli_open_needs_the_feature() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["open", "hello"])
+        .env("XDG_CONFIG_HOME", temp_dir("open"))
+        .env("XDG_CACHE_HOME", temp_dir("open_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("the `open` feature"), "{stderr}");
+}
@@ -0,0 +1,293 @@
+mod common;
+
+#[cfg(feature = "http")]
+use common::MockServer;
+use common::HELLO_HTML;
+use ydt::openapi::{self, parse_response, request_url, sign, truncate_input, Credentials};
+#[cfg(feature = "http")]
+use ydt::{Backend, PageSource, YdtClient};
+use ydt::{Entry, EntrySource, Layout, Phonetic, YdtError};
+
+const HELLO_JSON: &str = include_str!("fixtures/openapi_hello.json");
+const NIHAO_JSON: &str = include_str!("fixtures/openapi_nihao.json");
+
+fn credentials() -> Credentials {
+    Credentials::new("app-key", "app-secret")
+}
+
+#[cfg(feature = "http")]
+fn client_for(server: &MockServer, backend: Backend) -> YdtClient {
+    YdtClient::builder()
+        .api_url(format!("{}/api", server.url))
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .api_credentials(credentials())
+        .backend(backend)
+        .build()
+}
+
+#[cfg(feature = "http")]
+/// Answer API requests with `api` and result page requests with the hello page.
+fn api_then_page(api: &'static str) -> MockServer {
+    MockServer::start_with(move |request| {
+        if request.target.starts_with("/api") {
+            (200, api)
+        } else {
+            (200, HELLO_HTML)
+        }
+    })
+}
+
+#[test]
+fn short_input_is_signed_whole() {
+    assert_eq!(truncate_input(""), "");
+    assert_eq!(truncate_input("hello"), "hello");
+    assert_eq!(
+        truncate_input("exactly twenty chars"),
+        "exactly twenty chars"
+    );
+}
+
+#[test]
+fn long_input_is_truncated_by_characters() {
+    assert_eq!(
+        truncate_input("how are you doing today"),
+        "how are yo23oing today"
+    );
+    assert_eq!(
+        truncate_input("今天天气非常好我们一起去公园散步吧好不好"),
+        "今天天气非常好我们一起去公园散步吧好不好"
+    );
+    assert_eq!(
+        truncate_input("今天天气非常好我们一起去公园散步吧好不好呀"),
+        "今天天气非常好我们一21去公园散步吧好不好呀"
+    );
+}
+
+#[test]
+fn sign_matches_known_digests() {
+    assert_eq!(
+        sign(&credentials(), "hello", "0f1e2d", 1_700_000_000),
+        "abc6ad0167fbfb2da9afd3e2f592555358a9a7b9416ba0d7946a2913abfcae83"
+    );
+    assert_eq!(
+        sign(
+            &credentials(),
+            "how are you doing today",
+            "0f1e2d",
+            1_700_000_000
+        ),
+        "cc48199a2b92c835a52d8d8bbb04d44c3c10b2cb5499d7565ea207ab7f4d1811"
+    );
+}
+
+#[test]
+fn request_url_carries_all_parameters() {
+    let url = request_url(
+        openapi::DEFAULT_API_URL,
+        &credentials(),
+        "hello",
+        "0f1e2d",
+        1_700_000_000,
+    )
+    .unwrap();
+
+    assert_eq!(
+        url.as_str(),
+        "https://openapi.youdao.com/api?q=hello&from=auto&to=auto&appKey=app-key&salt=0f1e2d\
+         &sign=abc6ad0167fbfb2da9afd3e2f592555358a9a7b9416ba0d7946a2913abfcae83\
+         &signType=v3&curtime=1700000000"
+    );
+    assert!(!url.as_str().contains("app-secret"));
+}
+
+#[test]
+fn credentials_debug_hides_the_secret() {
+    let debug = format!("{:?}", credentials());
+    assert!(debug.contains("app-key"), "{debug}");
+    assert!(!debug.contains("app-secret"), "{debug}");
+}
+
+#[test]
+fn parses_english_response() {
+    let translation = parse_response("hello", HELLO_JSON).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert_eq!(
+        translation.phonetics,
+        [
+            Phonetic {
+                label: "英".to_string(),
+                text: "/həˈləʊ/".to_string(),
+            },
+            Phonetic {
+                label: "美".to_string(),
+                text: "/həˈloʊ/".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        translation.entries,
+        [
+            Entry {
+                pos: "int.".to_string(),
+                meaning: "喂；哈罗".to_string(),
+                source: EntrySource::OpenApi,
+            },
+            Entry {
+                pos: "n.".to_string(),
+                meaning: "表示问候， 惊奇或唤起注意时的用语".to_string(),
+                source: EntrySource::OpenApi,
+            },
+            Entry {
+                pos: "网络".to_string(),
+                meaning: "你好；您好；哈啰".to_string(),
+                source: EntrySource::WebTrans,
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_chinese_response() {
+    let translation = parse_response("你好", NIHAO_JSON).unwrap();
+
+    assert_eq!(translation.layout, Some(Layout::ChineseEnglish));
+    assert_eq!(translation.phonetics[0].label, "拼音");
+    assert_eq!(translation.phonetics[0].text, "/nǐ hǎo/");
+    let meanings: Vec<_> = translation
+        .entries
+        .iter()
+        .map(|entry| entry.meaning.as_str())
+        .collect();
+    assert_eq!(meanings, ["hello", "hi", "how do you do"]);
+}
+
+#[test]
+fn machine_translation_is_used_without_dictionary_result() {
+    let json = r#"{"errorCode": "0", "translation": ["你今天过得怎么样"]}"#;
+    let translation = parse_response("how are you doing today", json).unwrap();
+
+    assert!(translation.phonetics.is_empty());
+    assert_eq!(
+        translation.entries,
+        [Entry {
+            pos: String::new(),
+            meaning: "你今天过得怎么样".to_string(),
+            source: EntrySource::OpenApi,
+        }]
+    );
+}
+
+#[test]
+fn empty_response_has_no_results() {
+    let translation = parse_response("qzx", r#"{"errorCode": "0"}"#).unwrap();
+    assert!(translation.is_empty());
+    assert_eq!(translation.layout, None);
+}
+
+#[test]
+fn error_codes_are_mapped() {
+    for (json, expected) in [
+        (r#"{"errorCode": "108"}"#, 108),
+        (r#"{"errorCode": "202", "query": "hello"}"#, 202),
+        (r#"{"errorCode": 411}"#, 411),
+    ] {
+        match parse_response("hello", json).unwrap_err() {
+            YdtError::ApiError { code, msg, .. } => {
+                assert_eq!(code, expected);
+                assert_eq!(msg, openapi::error_message(code));
+            }
+            err => panic!("{json}: {err:?}"),
+        }
+    }
+    assert_eq!(openapi::error_message(108), "invalid app key");
+    assert_eq!(openapi::error_message(99999), "unknown error");
+}
+
+#[test]
+fn malformed_responses_are_reported() {
+    for json in [HELLO_HTML, "{}", r#"{"errorCode": "zero"}"#] {
+        let err = parse_response("hello", json).unwrap_err();
+        assert!(
+            matches!(err, YdtError::InvalidApiResponse { .. }),
+            "{json}: {err:?}"
+        );
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn client_prefers_the_api_when_credentials_are_set() {
+    let server = api_then_page(HELLO_JSON);
+    let (translation, info) = client_for(&server, Backend::Auto)
+        .translate_detailed("hello")
+        .unwrap();
+
+    assert_eq!(translation.entries.last().unwrap().pos, "网络");
+    assert_eq!(info.source, PageSource::Api);
+    let request = server.next_request();
+    assert!(
+        request
+            .target
+            .starts_with("/api?q=hello&from=auto&to=auto&appKey=app-key&salt="),
+        "{}",
+        request.target
+    );
+    assert!(server.pending_requests().is_empty());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn client_scrapes_when_the_api_fails() {
+    let server = api_then_page(r#"{"errorCode": "401"}"#);
+    let (translation, info) = client_for(&server, Backend::Auto)
+        .translate_detailed("hello")
+        .unwrap();
+
+    assert_eq!(info.source, PageSource::Desktop);
+    assert!(translation.entries[0].meaning.contains("你好"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn api_backend_reports_api_errors() {
+    let server = api_then_page(r#"{"errorCode": "401"}"#);
+    let err = client_for(&server, Backend::Api)
+        .translate("hello")
+        .unwrap_err();
+
+    assert!(
+        matches!(err, YdtError::ApiError { code: 401, .. }),
+        "{err:?}"
+    );
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn scrape_backend_ignores_credentials() {
+    let server = api_then_page(HELLO_JSON);
+    let (_, info) = client_for(&server, Backend::Scrape)
+        .translate_detailed("hello")
+        .unwrap();
+
+    assert_eq!(info.source, PageSource::Desktop);
+    assert!(server.next_request().target.starts_with("/result"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn api_backend_requires_credentials() {
+    let client = YdtClient::builder().backend(Backend::Api).build();
+    let err = client.translate("hello").unwrap_err();
+    assert!(matches!(err, YdtError::MissingApiCredentials), "{err:?}");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn backend_names_parse() {
+    assert_eq!("api".parse::<Backend>().unwrap(), Backend::Api);
+    assert_eq!("Scrape".parse::<Backend>().unwrap(), Backend::Scrape);
+    assert!("web".parse::<Backend>().is_err());
+}
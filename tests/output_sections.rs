@@ -0,0 +1,57 @@
+use std::process::Command;
+
+use ydt::{RenderOptions, Sections};
+
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+
+fn render(word: &str, html: &str, sections: Sections) -> String {
+    let translation = ydt::parse_translation(word, html).expect("expected fixture to parse");
+    ydt::render_translation(
+        &translation,
+        &RenderOptions {
+            sections,
+            ..RenderOptions::default()
+        },
+    )
+}
+
+#[test]
+fn default_renders_phonetics_and_definitions() {
+    assert_eq!(
+        render("hello", HELLO_HTML, Sections::All),
+        "英 /həˈləʊ/ 美 /həˈloʊ/\nint.: 你好；喂\nn.: 表示问候"
+    );
+}
+
+#[test]
+fn phonetics_only_renders_phonetics() {
+    assert_eq!(
+        render("hello", HELLO_HTML, Sections::PhoneticsOnly),
+        "英 /həˈləʊ/ 美 /həˈloʊ/"
+    );
+}
+
+#[test]
+fn definitions_only_renders_definitions() {
+    assert_eq!(
+        render("hello", HELLO_HTML, Sections::DefinitionsOnly),
+        "int.: 你好；喂\nn.: 表示问候"
+    );
+}
+
+#[test]
+fn phonetic_only_and_no_phonetic_conflict() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--phonetic-only", "--no-phonetic", "hello"])
+        .output()
+        .expect("expected binary to run");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn phonetics_only_is_empty_without_phonetics() {
+    let html = r#"<li class="word-exp-ce mcols-layout"><a class="point">study</a></li>"#;
+    assert_eq!(render("学习", html, Sections::PhoneticsOnly), "");
+    assert_eq!(render("学习", html, Sections::DefinitionsOnly), "study");
+}
@@ -0,0 +1,169 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::time::{Duration, Instant};
+
+use proptest::prelude::*;
+use ydt::{
+    parse_mobile_html, parse_translation, parse_translation_from_html, MAX_FIELD_LEN,
+    MAX_NESTING_DEPTH, MAX_PARSED_ITEMS,
+};
+
+/// An English and a Chinese word, so that both layouts are tried first.
+const WORDS: [&str; 2] = ["hello", "你好"];
+
+fn parse_all(html: &str) {
+    for word in WORDS {
+        let _ = parse_translation_from_html(word, html);
+        let _ = parse_mobile_html(word, html);
+    }
+}
+
+/// Elements the parsers look for, so that generated pages reach every branch.
+const TAGS: [&str; 12] = [
+    r#"<div class="trans-container">"#,
+    r#"<div class="per-phone">"#,
+    "<span>",
+    r#"<span class="phonetic">"#,
+    r#"<li class="word-exp">"#,
+    r#"<span class="pos">"#,
+    r#"<span class="trans">"#,
+    r#"<li class="word-exp-ce mcols-layout">"#,
+    r#"<a class="point">"#,
+    r#"<div class="typo-rel"><a class="title">"#,
+    r#"<div class="word-head"><div class="title">"#,
+    "<ul><li>",
+];
+
+fn html_ish() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        "[a-z 你好/ˈə.;<>&]{0,20}",
+        Just("&amp;&#x4f60;&#0;".to_string()),
+        Just("\u{fffd}\u{fffd}".to_string()),
+    ];
+    leaf.prop_recursive(6, 64, 4, |inner| {
+        (
+            prop::sample::select(TAGS.to_vec()),
+            prop::collection::vec(inner, 0..4),
+            any::<bool>(),
+        )
+            .prop_map(|(tag, children, close)| {
+                // Unclosed and mismatched tags are left to the HTML parser.
+                let end = if close { "</span></div></li>" } else { "" };
+                format!("{tag}{}{end}", children.concat())
+            })
+    })
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_do_not_panic(bytes in prop::collection::vec(any::<u8>(), 0..2048)) {
+        parse_all(&String::from_utf8_lossy(&bytes));
+    }
+
+    #[test]
+    fn html_like_input_does_not_panic(html in html_ish()) {
+        parse_all(&html);
+    }
+
+    #[test]
+    fn truncated_pages_do_not_panic(cut in 0..include_str!("fixtures/hello.html").len()) {
+        let page = include_str!("fixtures/hello.html");
+        let cut = (0..=cut).rev().find(|&end| page.is_char_boundary(end)).unwrap();
+        parse_all(&page[..cut]);
+    }
+}
+
+/// A definition inside `depth` nested elements.
+fn nested_definition(depth: usize) -> String {
+    format!(
+        r#"<div class="trans-container"></div>{}<div class="trans-container"><li class="word-exp"><span class="pos">n.</span><span class="trans">深</span></li></div>{}"#,
+        "<div>".repeat(depth),
+        "</div>".repeat(depth)
+    )
+}
+
+#[test]
+fn deeply_nested_elements_are_cut_quickly() {
+    let html = nested_definition(200_000);
+    let start = Instant::now();
+
+    let translation = parse_translation("hello", &html).unwrap();
+
+    assert!(translation.entries.is_empty());
+    parse_all(&html);
+    assert!(
+        start.elapsed() < Duration::from_secs(5),
+        "{:?}",
+        start.elapsed()
+    );
+}
+
+#[test]
+fn nesting_within_the_limit_is_parsed() {
+    let html = format!(
+        "<html><head><meta charset=utf-8><link rel=icon><script>if (a<b) {{}}</script><SCRIPT>if (a<div) {{}}</Script></head><body>{}<br><BR><IMG src=x><svg><path/></svg></body></html>",
+        nested_definition(MAX_NESTING_DEPTH - 8)
+    );
+
+    let translation = parse_translation("hello", &html).unwrap();
+
+    assert_eq!(translation.entries[0].meaning, "深");
+}
+
+#[test]
+fn megabyte_attribute_values_are_ignored() {
+    let html = format!(
+        r#"<div class="trans-container" title="{}"></div><div class="trans-container"><li class="word-exp"><span class="pos">n.</span><span class="trans">长</span></li></div>"#,
+        "x".repeat(1024 * 1024)
+    );
+
+    let translation = parse_translation("hello", &html).unwrap();
+
+    assert_eq!(translation.entries[0].meaning, "长");
+}
+
+#[test]
+fn definitions_are_capped() {
+    let entries = (0..MAX_PARSED_ITEMS * 4)
+        .map(|n| {
+            format!(r#"<li class="word-exp"><span class="pos">n.</span><span class="trans">多{n}</span></li>"#)
+        })
+        .collect::<String>();
+    let html = format!(
+        r#"<div class="trans-container"></div><div class="trans-container">{entries}</div>"#
+    );
+
+    let translation = parse_translation("hello", &html).unwrap();
+
+    assert_eq!(translation.entries.len(), MAX_PARSED_ITEMS);
+}
+
+#[test]
+fn chinese_english_candidates_and_suggestions_are_capped() {
+    let candidate = r#"<li class="word-exp-ce mcols-layout"><a class="point">many</a></li>"#;
+    let suggestion = r#"<div class="typo-rel"><a class="title">hallo</a></div>"#;
+    let html = format!(
+        "{}{}",
+        candidate.repeat(MAX_PARSED_ITEMS + 1),
+        suggestion.repeat(MAX_PARSED_ITEMS + 1)
+    );
+
+    let translation = parse_translation("多", &html).unwrap();
+
+    assert_eq!(translation.entries.len(), MAX_PARSED_ITEMS);
+    assert_eq!(translation.suggestions.len(), MAX_PARSED_ITEMS);
+}
+
+#[test]
+fn long_fields_are_cut_at_a_character_boundary() {
+    let meaning = "长".repeat(MAX_FIELD_LEN);
+    let html = format!(
+        r#"<div class="trans-container"></div><div class="trans-container"><li class="word-exp"><span class="pos">n.</span><span class="trans">{meaning}</span></li></div>"#
+    );
+
+    let translation = parse_translation("hello", &html).unwrap();
+
+    let parsed = &translation.entries[0].meaning;
+    assert!(parsed.len() <= MAX_FIELD_LEN && parsed.len() > MAX_FIELD_LEN - 3);
+    assert!(meaning.starts_with(parsed.as_str()));
+}
@@ -0,0 +1,122 @@
+use ydt::{
+    parse_ce_candidates, parse_definitions, parse_phonetics, parse_translation,
+    parse_translation_with_options, Layout, ParsedPage, SelectorConfig, TranslationOptions,
+};
+
+const ENGLISH_CHINESE: &[(&str, &str)] = &[
+    ("hello", include_str!("fixtures/hello.html")),
+    ("run", include_str!("fixtures/run.html")),
+    ("ran", include_str!("fixtures/ran.html")),
+    ("time", include_str!("fixtures/time.html")),
+    ("ubiquitous", include_str!("fixtures/ubiquitous.html")),
+    ("mother-in-law", include_str!("fixtures/mother_in_law.html")),
+    ("NASA", include_str!("fixtures/nasa_abbr.html")),
+    ("NASA", include_str!("fixtures/partial_entries.html")),
+    ("bank", include_str!("fixtures/nested_containers.html")),
+    ("C语言", include_str!("fixtures/c_language.html")),
+];
+
+const CHINESE_ENGLISH: &[(&str, &str)] = &[
+    ("USB", include_str!("fixtures/usb_ce_layout.html")),
+    ("画蛇添足", include_str!("fixtures/huashetianzu.html")),
+];
+
+#[test]
+fn sections_match_the_full_parse_of_english_pages() {
+    for &(word, html) in ENGLISH_CHINESE {
+        let translation = parse_translation(word, html).unwrap();
+        assert_eq!(translation.layout, Some(Layout::EnglishChinese), "{word}");
+        assert_eq!(
+            parse_phonetics(html).unwrap(),
+            translation.phonetics,
+            "{word}"
+        );
+        assert_eq!(
+            parse_definitions(html).unwrap(),
+            translation.entries,
+            "{word}"
+        );
+    }
+}
+
+#[test]
+fn candidates_match_the_full_parse_of_chinese_pages() {
+    for &(word, html) in CHINESE_ENGLISH {
+        let translation = parse_translation(word, html).unwrap();
+        assert_eq!(translation.layout, Some(Layout::ChineseEnglish), "{word}");
+        assert_eq!(
+            parse_ce_candidates(html).unwrap(),
+            translation.entries,
+            "{word}"
+        );
+    }
+}
+
+#[test]
+fn one_parsed_page_gives_every_section() {
+    let html = include_str!("fixtures/hello.html");
+    let page = ParsedPage::parse(html);
+    assert_eq!(page.phonetics(), parse_phonetics(html).unwrap());
+    assert_eq!(page.definitions(), parse_definitions(html).unwrap());
+    assert!(page.ce_candidates().is_empty());
+}
+
+#[test]
+fn custom_selectors_are_used() {
+    let html = r#"
+        <div class="trans-container"></div>
+        <div class="trans-container">
+          <li class="entry"><span class="pos">int.</span><span class="trans">你好</span></li>
+        </div>
+    "#;
+    let selectors = SelectorConfig {
+        word_exp: "li.entry".to_string(),
+        ..SelectorConfig::default()
+    }
+    .compile()
+    .unwrap();
+    assert!(ParsedPage::parse(html).definitions().is_empty());
+    let page = ParsedPage::with_selectors(html, &selectors);
+    assert_eq!(page.definitions()[0].meaning, "你好");
+}
+
+#[test]
+fn sections_are_extracted_once() {
+    let page = ParsedPage::parse(include_str!("fixtures/take_collins.html"));
+    let definitions = page.definitions();
+    let examples = page.examples();
+    assert!(!definitions.is_empty());
+    assert!(!examples.is_empty());
+    assert!(std::ptr::eq(page.definitions(), definitions));
+    assert!(std::ptr::eq(page.examples(), examples));
+    assert_eq!(page.phonetics(), page.phonetics());
+}
+
+#[test]
+fn examples_are_those_of_the_collins_senses() {
+    let html = include_str!("fixtures/take_collins.html");
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let options = TranslationOptions {
+        include_collins: Some(true),
+        ..TranslationOptions::default()
+    };
+    let (translation, _) =
+        parse_translation_with_options("take", html, &selectors, &options).unwrap();
+    let examples = translation
+        .collins
+        .into_iter()
+        .flat_map(|sense| sense.examples)
+        .collect::<Vec<_>>();
+    assert_eq!(ParsedPage::parse(html).examples(), examples);
+}
+
+#[test]
+fn suggestions_match_the_full_parse() {
+    let html = include_str!("fixtures/helllo.html");
+    let translation = parse_translation("helllo", html).unwrap();
+    assert!(!translation.suggestions.is_empty());
+    assert_eq!(
+        ParsedPage::parse(html).suggestions(),
+        translation.suggestions
+    );
+}
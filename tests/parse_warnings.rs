@@ -0,0 +1,115 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, HELLO_HTML};
+use ydt::{parse_translation_with_warnings, ParseWarning, YdtClient, YdtError};
+
+const NO_WORD_EXP_HTML: &str = include_str!("fixtures/degraded_no_word_exp.html");
+const PHONETIC_HTML: &str = include_str!("fixtures/degraded_phonetic.html");
+const ENTRY_HTML: &str = include_str!("fixtures/degraded_entry.html");
+
+#[test]
+fn recognized_page_has_no_warnings() {
+    let (_, warnings) = parse_translation_with_warnings("hello", HELLO_HTML).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn renamed_definition_class_is_reported() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("hello", NO_WORD_EXP_HTML).unwrap();
+
+    assert_eq!(translation.phonetics.len(), 1);
+    assert!(translation.entries.is_empty());
+    assert_eq!(warnings, [ParseWarning::DefinitionsMissing]);
+    assert_eq!(
+        warnings[0].to_string(),
+        "found div.trans-container but no li.word-exp inside"
+    );
+}
+
+#[test]
+fn broken_phonetic_blocks_are_reported() {
+    let (translation, warnings) = parse_translation_with_warnings("hello", PHONETIC_HTML).unwrap();
+
+    assert_eq!(translation.phonetics.len(), 1);
+    assert_eq!(translation.phonetics[0].label, "美");
+    assert_eq!(
+        warnings,
+        [
+            ParseWarning::PhoneticTextMissing {
+                label: "英".to_string()
+            },
+            ParseWarning::PhoneticLabelMissing,
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "phonetic label 英 present but span.phonetic missing"
+    );
+}
+
+#[test]
+fn incomplete_definition_is_reported() {
+    let (translation, warnings) = parse_translation_with_warnings("hello", ENTRY_HTML).unwrap();
+
+    // The text outside span.pos stands in for the missing span.trans.
+    assert_eq!(translation.entries.len(), 2);
+    assert_eq!(translation.entries[1].meaning, "表示问候");
+    assert_eq!(warnings, [ParseWarning::DefinitionIncomplete]);
+}
+
+#[test]
+fn candidate_without_word_is_reported() {
+    let html = r#"<li class="word-exp-ce mcols-layout"><span>你好</span></li>"#;
+    let (translation, warnings) = parse_translation_with_warnings("你好", html).unwrap();
+
+    assert!(translation.is_empty());
+    assert_eq!(warnings, [ParseWarning::CandidateTextMissing]);
+}
+
+#[test]
+fn strict_client_rejects_partially_recognized_pages() {
+    let fetcher = MockFetcher::new()
+        .page("hello", HELLO_HTML)
+        .page("broken", ENTRY_HTML);
+    let client = YdtClient::builder().fetcher(fetcher).strict(true).build();
+
+    assert!(client.translate("hello").is_ok());
+    let err = client.translate("broken").unwrap_err();
+    assert!(
+        matches!(&err, YdtError::UnrecognizedLayout { warnings, .. } if warnings == &[ParseWarning::DefinitionIncomplete]),
+        "{err:?}"
+    );
+    assert_eq!(
+        err.to_string(),
+        "Unrecognized result page layout for \"broken\": found li.word-exp with span.pos but no span.trans"
+    );
+}
+
+#[test]
+fn lenient_client_returns_partial_results() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("broken", ENTRY_HTML));
+    assert_eq!(client.translate("broken").unwrap().entries.len(), 2);
+}
+
+#[test]
+fn strict_flag_fails_the_lookup() {
+    let server = common::MockServer::start(ENTRY_HTML);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--strict", "--no-mobile-fallback", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", common::temp_dir("strict-config"))
+        .env("XDG_CACHE_HOME", common::temp_dir("strict-cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unrecognized result page layout"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,86 @@
+use ydt::{
+    parse_translation_with_warnings, render_pretty, render_translation, Entry, EntrySource,
+    ParseWarning, RenderOptions, Sections,
+};
+
+const PARTIAL_ENTRIES_HTML: &str = include_str!("fixtures/partial_entries.html");
+
+fn entry(pos: &str, meaning: &str) -> Entry {
+    Entry {
+        pos: pos.to_string(),
+        meaning: meaning.to_string(),
+        source: EntrySource::Concise,
+    }
+}
+
+#[test]
+fn entries_missing_pos_or_trans_are_kept() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("NASA", PARTIAL_ENTRIES_HTML).unwrap();
+    assert_eq!(
+        translation.entries,
+        [
+            entry("abbr.", "美国国家航空航天局"),
+            entry(
+                "",
+                "（美国）国家航空和宇宙航行局（National Aeronautics and Space Administration）"
+            ),
+            entry("n.", "美国宇航局"),
+        ]
+    );
+    assert_eq!(
+        warnings,
+        [
+            ParseWarning::DefinitionIncomplete,
+            ParseWarning::DefinitionEmpty
+        ]
+    );
+    assert_eq!(
+        warnings[1].to_string(),
+        "found li.word-exp with no definition text"
+    );
+}
+
+#[test]
+fn text_outside_any_span_is_the_gloss() {
+    let html = r#"
+<div class="trans-container"></div>
+<div class="trans-container">
+  <li class="word-exp"><span class="pos">n.</span> 超文本传输协议</li>
+  <li class="word-exp">超文本传输协议（HyperText Transfer Protocol）</li>
+  <li class="word-exp"><span class="pos">v.</span></li>
+</div>
+"#;
+    let (translation, warnings) = parse_translation_with_warnings("HTTP", html).unwrap();
+    assert_eq!(
+        translation.entries,
+        [
+            entry("n.", "超文本传输协议"),
+            entry("", "超文本传输协议（HyperText Transfer Protocol）"),
+        ]
+    );
+    assert_eq!(
+        warnings,
+        [
+            ParseWarning::DefinitionIncomplete,
+            ParseWarning::DefinitionEmpty
+        ]
+    );
+}
+
+#[test]
+fn entries_without_pos_render_as_the_gloss() {
+    let (translation, _) = parse_translation_with_warnings("NASA", PARTIAL_ENTRIES_HTML).unwrap();
+    let options = RenderOptions {
+        sections: Sections::DefinitionsOnly,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        render_translation(&translation, &options),
+        "abbr.: 美国国家航空航天局\n（美国）国家航空和宇宙航行局（National Aeronautics and Space Administration）\nn.: 美国宇航局"
+    );
+    assert_eq!(
+        render_pretty(&translation, &options, 80),
+        "abbr. 美国国家航空航天局\n      （美国）国家航空和宇宙航行局（National Aeronautics and Space\n      Administration）\nn.    美国宇航局"
+    );
+}
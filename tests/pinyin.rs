@@ -0,0 +1,104 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    looks_like_pinyin, parse_translation, parse_translation_with_warnings, render_pretty,
+    render_translation, render_tsv_row, Candidate, Layout, RenderOptions,
+};
+
+const XUEXI_PINYIN_HTML: &str = include_str!("fixtures/xuexi_pinyin.html");
+
+#[test]
+fn pinyin_queries_are_recognized() {
+    for text in [
+        "xuexi", "xue xi", "XueXi", "xi'an", "zhongguo", "nihao", "change",
+    ] {
+        assert!(looks_like_pinyin(text), "{text}");
+    }
+    for text in ["hello", "ma", "xue3xi2", "学习", "strength", "", "zzz"] {
+        assert!(!looks_like_pinyin(text), "{text}");
+    }
+}
+
+#[test]
+fn candidates_are_parsed() {
+    let (translation, warnings) =
+        parse_translation_with_warnings("xuexi", XUEXI_PINYIN_HTML).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(translation.layout, Some(Layout::Pinyin));
+    assert!(translation.entries.is_empty());
+    assert_eq!(
+        translation.candidates,
+        [
+            Candidate {
+                hanzi: "学习".to_string(),
+                pinyin: "xué xí".to_string(),
+                gloss: "study; learn".to_string(),
+            },
+            Candidate {
+                hanzi: "学系".to_string(),
+                pinyin: "xué xì".to_string(),
+                gloss: "department (of a university)".to_string(),
+            },
+            Candidate {
+                hanzi: "血洗".to_string(),
+                pinyin: "xuè xǐ".to_string(),
+                gloss: String::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn candidates_are_only_read_for_pinyin_queries() {
+    let translation = parse_translation("hello", XUEXI_PINYIN_HTML).unwrap();
+    assert!(translation.is_empty());
+    assert_eq!(translation.layout, None);
+
+    // English results take precedence over the pinyin reading of a word.
+    let hello = include_str!("fixtures/hello.html");
+    let translation = parse_translation("nihao", hello).unwrap();
+    assert_eq!(translation.layout, Some(Layout::EnglishChinese));
+    assert!(translation.candidates.is_empty());
+}
+
+#[test]
+fn candidates_render_one_per_line() {
+    let translation = parse_translation("xuexi", XUEXI_PINYIN_HTML).unwrap();
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "学习 xué xí study; learn\n学系 xué xì department (of a university)\n血洗 xuè xǐ"
+    );
+    assert_eq!(
+        render_pretty(&translation, &RenderOptions::default(), 30),
+        "学习 xué xí study; learn
+学系 xué xì department (of a
+            university)
+血洗 xuè xǐ"
+    );
+    assert_eq!(
+        render_tsv_row("xuexi", &Ok(translation)),
+        "xuexi\t\t学习 xué xí study; learn; 学系 xué xì department (of a university); 血洗 xuè xǐ"
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_candidates() {
+    let server = MockServer::start(XUEXI_PINYIN_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "xuexi"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("pinyin"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\n学习 xué xí study; learn\n"), "{stdout}");
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::{Command, Output};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+
+const RAN_HTML: &str = include_str!("fixtures/ran.html");
+
+/// Run the binary with `args` and stdin and stdout piped, so neither is a
+/// terminal.
+fn ydt(args: &[&str], html: &'static str) -> Output {
+    let server = MockServer::start(html);
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("--no-mobile-fallback")
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("piped_output"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn phonetics_are_on_the_first_line() {
+    assert_eq!(
+        stdout(ydt(&["hello"], HELLO_HTML)),
+        include_str!("fixtures/hello.piped.txt")
+    );
+}
+
+#[test]
+fn headword_notice_goes_to_stderr() {
+    let output = ydt(&["ran"], RAN_HTML);
+    assert_eq!(
+        String::from_utf8(output.stderr.clone()).unwrap(),
+        "→ run (过去式)\n"
+    );
+    assert_eq!(stdout(output), include_str!("fixtures/ran.piped.txt"));
+}
+
+#[test]
+fn first_line_is_empty_without_phonetics() {
+    let html = r#"<li class="word-exp-ce mcols-layout"><a class="point">study</a></li>"#;
+    assert_eq!(stdout(ydt(&["学习"], html)), "\nstudy\n");
+}
+
+#[test]
+fn long_definitions_are_not_wrapped() {
+    let out = stdout(ydt(&["--width", "20", "hello"], HELLO_HTML));
+    assert_eq!(out, include_str!("fixtures/hello.piped.txt"));
+    assert!(!out.contains('\x1b'));
+}
+
+#[test]
+fn explicit_formats_keep_their_layout() {
+    let out = stdout(ydt(&["--format", "plain", "ran"], RAN_HTML));
+    assert!(out.starts_with("→ run (过去式)\n英 "), "{out}");
+
+    let out = stdout(ydt(&["--one-line", "ran"], RAN_HTML));
+    assert_eq!(out.lines().count(), 1, "{out}");
+}
+
+#[test]
+fn batches_use_the_stable_layout_without_progress() {
+    let server = MockServer::start(RAN_HTML);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--concurrency", "1"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("piped_output_batch"))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    {
+        use std::io::Write;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"ran\nran\n").unwrap();
+    }
+    let output = child.wait_with_output().unwrap();
+
+    let block = format!("ran\n{}", include_str!("fixtures/ran.piped.txt"));
+    assert_eq!(stdout(output.clone()), format!("{block}\n{block}"));
+    assert!(!String::from_utf8(output.stderr).unwrap().contains('\r'));
+}
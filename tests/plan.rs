@@ -0,0 +1,182 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+use std::time::Duration;
+
+use common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+use ydt::openapi::Credentials;
+use ydt::{
+    Backend, TranslationOptions, UaStrategy, YdtClient, YdtError, DEFAULT_ACCEPT_LANGUAGE,
+    DEFAULT_DEADLINE, DEFAULT_TIMEOUT,
+};
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn default_plan_tries_desktop_then_mobile() {
+    let plan = YdtClient::new()
+        .plan("  hello ", &TranslationOptions::default())
+        .unwrap();
+
+    assert_eq!(plan.word, "hello");
+    let sources: Vec<_> = plan.requests.iter().map(|r| r.source.as_str()).collect();
+    assert_eq!(sources, ["desktop", "mobile"]);
+    let desktop = &plan.requests[0];
+    assert_eq!(
+        desktop.url.as_deref(),
+        Some("https://www.youdao.com/result?word=hello&lang=en")
+    );
+    assert!(header(&desktop.headers, "user-agent")
+        .unwrap()
+        .starts_with("ydt/"));
+    assert_eq!(
+        header(&desktop.headers, "accept-language"),
+        Some(DEFAULT_ACCEPT_LANGUAGE)
+    );
+    assert!(plan.fallback_user_agent.unwrap().starts_with("Mozilla/"));
+    assert_eq!(plan.timeout, DEFAULT_TIMEOUT);
+    assert_eq!(plan.deadline, Some(DEFAULT_DEADLINE));
+    assert_eq!(plan.retries, 0);
+    assert_eq!(plan.proxy, None);
+}
+
+#[test]
+fn plan_follows_the_client_settings() {
+    let client = YdtClient::builder()
+        .base_url("http://127.0.0.1:9/result")
+        .mobile_fallback(false)
+        .lang("fr")
+        .user_agent("probe/1.0")
+        .accept_language("")
+        .proxy("http://proxy.example:3128")
+        .timeout(Duration::from_secs(3))
+        .deadline(Duration::ZERO)
+        .retries(2)
+        .convert_traditional(true)
+        .build();
+    let plan = client.plan("學習", &TranslationOptions::default()).unwrap();
+
+    assert_eq!(plan.word, "学习");
+    assert_eq!(plan.requests.len(), 1);
+    assert_eq!(
+        plan.requests[0].url.as_deref(),
+        Some("http://127.0.0.1:9/result?word=%E5%AD%A6%E4%B9%A0&lang=fr")
+    );
+    assert_eq!(
+        header(&plan.requests[0].headers, "user-agent"),
+        Some("probe/1.0")
+    );
+    assert_eq!(header(&plan.requests[0].headers, "accept-language"), None);
+    assert_eq!(plan.fallback_user_agent, None);
+    assert_eq!(plan.timeout, Duration::from_secs(3));
+    assert_eq!(plan.deadline, None);
+    assert_eq!(plan.retries, 2);
+    assert_eq!(plan.proxy.as_deref(), Some("http://proxy.example:3128"));
+}
+
+#[test]
+fn options_override_the_lang() {
+    let options = TranslationOptions {
+        lang: Some("ja".to_string()),
+        ..TranslationOptions::default()
+    };
+    let plan = YdtClient::builder()
+        .mobile_fallback(false)
+        .ua_strategy(UaStrategy::BrowserOnly)
+        .build()
+        .plan("sake", &options)
+        .unwrap();
+    assert_eq!(
+        plan.requests[0].url.as_deref(),
+        Some("https://www.youdao.com/result?word=sake&lang=ja")
+    );
+    assert!(header(&plan.requests[0].headers, "user-agent")
+        .unwrap()
+        .starts_with("Mozilla/"));
+}
+
+#[test]
+fn sources_without_requests_have_no_url() {
+    let plan = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .mobile_fallback(false)
+        .build()
+        .plan("hello", &TranslationOptions::default())
+        .unwrap();
+    assert_eq!(plan.requests.len(), 1);
+    assert_eq!(plan.requests[0].source, "desktop");
+    assert_eq!(plan.requests[0].url, None);
+    assert!(plan.requests[0].headers.is_empty());
+}
+
+#[test]
+fn api_is_tried_first_when_configured() {
+    let plan = YdtClient::builder()
+        .api_url("http://127.0.0.1:9/api")
+        .api_credentials(Credentials::new("app-key", "app-secret"))
+        .backend(Backend::Auto)
+        .mobile_fallback(false)
+        .build()
+        .plan("hello", &TranslationOptions::default())
+        .unwrap();
+    let sources: Vec<_> = plan.requests.iter().map(|r| r.source.as_str()).collect();
+    assert_eq!(sources, ["api", "desktop"]);
+    assert!(plan.requests[0].optional);
+    assert!(plan.requests[0]
+        .url
+        .as_deref()
+        .unwrap()
+        .starts_with("http://127.0.0.1:9/api?"));
+    assert!(!plan.requests[1].optional);
+}
+
+#[test]
+fn rejected_words_cannot_be_planned() {
+    let err = YdtClient::new()
+        .plan("   ", &TranslationOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, YdtError::EmptyWord), "{err:?}");
+}
+
+#[test]
+fn cli_dry_run_sends_nothing() {
+    let server = MockServer::start(HELLO_HTML);
+    let base_url = format!("{}/result", server.url);
+    let run = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--dry-run"])
+            .args(extra)
+            .arg("hello")
+            .env("YDT_BASE_URL", &base_url)
+            .env("XDG_CONFIG_HOME", temp_dir("plan"))
+            .env("XDG_CACHE_HOME", temp_dir("plan_cache"))
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with(&format!(
+        "word: hello\n1. desktop: GET {base_url}?word=hello&lang=en\n   user-agent: ydt/"
+    )));
+    assert!(stdout.contains("\ndeadline: 15s\nretries: 0\nproxy: none\n"));
+
+    let output = run(&["--json"]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        plan["requests"][0]["url"],
+        format!("{base_url}?word=hello&lang=en")
+    );
+    assert_eq!(plan["timeout_secs"], 10.0);
+
+    assert!(server.pending_requests().is_empty());
+}
@@ -0,0 +1,50 @@
+use ydt::{RenderOptions, Sections};
+
+const RUN_HTML: &str = include_str!("fixtures/run.html");
+
+fn pretty(width: usize) -> String {
+    let translation = ydt::parse_translation("run", RUN_HTML).expect("expected fixture to parse");
+    ydt::render_pretty(&translation, &RenderOptions::default(), width)
+}
+
+#[test]
+fn golden_width_80() {
+    assert_eq!(pretty(80), include_str!("fixtures/run.w80.txt").trim_end());
+}
+
+#[test]
+fn golden_width_40() {
+    assert_eq!(pretty(40), include_str!("fixtures/run.w40.txt").trim_end());
+}
+
+#[test]
+fn lines_never_exceed_width() {
+    for width in [20, 40, 80] {
+        for line in pretty(width).lines().skip(1) {
+            assert!(
+                ydt::display_width(line) <= width,
+                "{line:?} exceeds {width}"
+            );
+        }
+    }
+}
+
+#[test]
+fn definitions_only_omits_phonetics() {
+    let translation = ydt::parse_translation("run", RUN_HTML).expect("expected fixture to parse");
+    let options = RenderOptions {
+        sections: Sections::DefinitionsOnly,
+        ..RenderOptions::default()
+    };
+    let out = ydt::render_pretty(&translation, &options, 80);
+    assert!(out.starts_with("v.     跑，奔跑"));
+}
+
+#[test]
+fn wraps_latin_text_at_spaces() {
+    assert_eq!(
+        ydt::wrap_to_width("the quick brown fox", 10),
+        ["the quick", "brown fox"]
+    );
+    assert_eq!(ydt::wrap_to_width("abcdefgh", 3), ["abc", "def", "gh"]);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::{ProgressEvent, YdtClient};
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Completed(String, usize, usize, usize),
+    Finished(usize, usize),
+}
+
+#[test]
+fn emits_events_in_order_with_counts() {
+    let client = YdtClient::with_fetcher(
+        MockFetcher::new()
+            .page("hello", HELLO_HTML)
+            .page("hi", HELLO_HTML)
+            .status("blocked", StatusCode::FORBIDDEN),
+    );
+    let mut events = Vec::new();
+
+    let results = client.translate_many_with_progress(&["hello", "blocked", "hi"], 2, |event| {
+        events.push(match event {
+            ProgressEvent::Completed {
+                word,
+                completed,
+                failed,
+                total,
+            } => Event::Completed(word.to_string(), completed, failed, total),
+            ProgressEvent::Finished { completed, failed } => Event::Finished(completed, failed),
+        });
+    });
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        events,
+        [
+            Event::Completed("hello".to_string(), 1, 0, 3),
+            Event::Completed("blocked".to_string(), 2, 1, 3),
+            Event::Completed("hi".to_string(), 3, 1, 3),
+            Event::Finished(3, 1),
+        ]
+    );
+}
+
+#[test]
+fn empty_batch_only_finishes() {
+    let client = YdtClient::with_fetcher(MockFetcher::new());
+    let mut events = Vec::new();
+
+    client.translate_many_with_progress(&[] as &[&str], 2, |event| {
+        events.push(format!("{event:?}"));
+    });
+
+    assert_eq!(events, ["Finished { completed: 0, failed: 0 }"]);
+}
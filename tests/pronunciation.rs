@@ -0,0 +1,122 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockServer, RecordedRequest, HELLO_HTML};
+use ydt::{Accent, PronunciationItem, YdtClient, YdtError};
+
+const UK_AUDIO: &str = "ID3 uk recording";
+const US_AUDIO: &str = "ID3 us recording";
+
+fn client_for(server: &MockServer) -> YdtClient {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .audio_url(format!("{}/dictvoice", server.url))
+        .mobile_fallback(false)
+        .build()
+}
+
+/// Serve `page` as the result page and the recordings in each accent.
+fn respond(page: &'static str) -> impl Fn(&RecordedRequest) -> (u16, &'static str) {
+    move |request| {
+        if request.target.starts_with("/result") {
+            (200, page)
+        } else if request.target.ends_with("type=1") {
+            (200, UK_AUDIO)
+        } else {
+            (200, US_AUDIO)
+        }
+    }
+}
+
+#[test]
+fn gathers_phonetics_and_recordings_of_both_accents() {
+    let server = MockServer::start_with(respond(HELLO_HTML));
+    let pack = client_for(&server).pronunciation_pack(" hello ").unwrap();
+
+    assert_eq!(pack.word, "hello");
+    assert!(pack.is_complete(), "{:?}", pack.errors);
+    assert_eq!(pack.uk.phonetic.as_deref(), Some("/həˈləʊ/"));
+    assert_eq!(pack.us.phonetic.as_deref(), Some("/həˈloʊ/"));
+    assert_eq!(pack.uk.audio.as_deref(), Some(UK_AUDIO.as_bytes()));
+    assert_eq!(
+        pack.get(Accent::Us).audio.as_deref(),
+        Some(US_AUDIO.as_bytes())
+    );
+
+    let mut targets: Vec<_> = server
+        .pending_requests()
+        .into_iter()
+        .map(|request| request.target)
+        .collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        [
+            "/dictvoice?audio=hello&type=1",
+            "/dictvoice?audio=hello&type=2",
+            "/result?word=hello&lang=en",
+        ]
+    );
+}
+
+#[test]
+fn missing_recordings_are_reported_per_accent() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/result") {
+            (200, HELLO_HTML)
+        } else if request.target.ends_with("type=1") {
+            (404, "")
+        } else {
+            (200, "")
+        }
+    });
+    let pack = client_for(&server).pronunciation_pack("hello").unwrap();
+
+    assert_eq!(pack.uk.phonetic.as_deref(), Some("/həˈləʊ/"));
+    assert_eq!(pack.uk.audio, None);
+    // An empty recording is missing without being an error.
+    assert_eq!(pack.us.audio, None);
+    assert_eq!(pack.errors.len(), 1, "{:?}", pack.errors);
+    let (item, err) = &pack.errors[0];
+    assert_eq!(*item, PronunciationItem::Audio(Accent::Uk));
+    assert_eq!(item.to_string(), "uk audio");
+    assert!(matches!(err, YdtError::HttpStatus { .. }), "{err:?}");
+}
+
+#[test]
+fn a_page_without_phonetics_is_not_an_error() {
+    let server = MockServer::start_with(respond(include_str!("fixtures/xuexi_full.html")));
+    let pack = client_for(&server).pronunciation_pack("学习").unwrap();
+
+    assert!(pack.is_complete(), "{:?}", pack.errors);
+    assert_eq!(pack.uk.phonetic, None);
+    assert_eq!(pack.us.phonetic, None);
+    assert_eq!(pack.uk.audio.as_deref(), Some(UK_AUDIO.as_bytes()));
+}
+
+#[test]
+fn a_failed_page_keeps_the_recordings() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/result") {
+            (500, "")
+        } else {
+            (200, US_AUDIO)
+        }
+    });
+    let pack = client_for(&server).pronunciation_pack("hello").unwrap();
+
+    assert_eq!(pack.us.phonetic, None);
+    assert_eq!(pack.us.audio.as_deref(), Some(US_AUDIO.as_bytes()));
+    let items: Vec<_> = pack.errors.iter().map(|(item, _)| *item).collect();
+    assert_eq!(items, [PronunciationItem::Phonetics]);
+}
+
+#[test]
+fn a_rejected_word_fails_the_whole_pack() {
+    let server = MockServer::start_with(respond(HELLO_HTML));
+    let result = client_for(&server).pronunciation_pack("  ");
+
+    assert!(matches!(result, Err(YdtError::EmptyWord)));
+    assert!(server.pending_requests().is_empty());
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "http")]
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ydt::{Fetcher, YdtClient, YdtError};
+
+const INTERVAL: Duration = Duration::from_millis(40);
+// Recording happens just after the limiter releases a request.
+const SLACK: Duration = Duration::from_millis(1);
+
+/// Fetcher recording when each request was sent.
+#[derive(Default)]
+struct RecordingFetcher {
+    calls: Mutex<Vec<Instant>>,
+}
+
+impl Fetcher for RecordingFetcher {
+    fn fetch(&self, _word: &str) -> Result<String, YdtError> {
+        self.calls.lock().unwrap().push(Instant::now());
+        Ok(String::new())
+    }
+}
+
+fn sorted_calls(fetcher: &RecordingFetcher) -> Vec<Instant> {
+    let mut calls = fetcher.calls.lock().unwrap().clone();
+    calls.sort();
+    calls
+}
+
+#[test]
+fn spaces_sequential_requests() {
+    let fetcher = Arc::new(RecordingFetcher::default());
+    let client = YdtClient::builder()
+        .fetcher(Arc::clone(&fetcher))
+        .min_request_interval(INTERVAL)
+        .build();
+
+    for word in ["a", "b", "c"] {
+        client.translate(word).unwrap();
+    }
+
+    let calls = sorted_calls(&fetcher);
+    assert!(calls[1] - calls[0] >= INTERVAL - SLACK);
+    assert!(calls[2] - calls[0] >= INTERVAL * 2 - SLACK);
+}
+
+#[test]
+fn limit_is_global_across_threads() {
+    let fetcher = Arc::new(RecordingFetcher::default());
+    let client = YdtClient::builder()
+        .fetcher(Arc::clone(&fetcher))
+        .min_request_interval(INTERVAL)
+        .build();
+
+    client.translate_many(&["a", "b", "c", "d"], 4);
+
+    let calls = sorted_calls(&fetcher);
+    assert_eq!(calls.len(), 4);
+    assert!(calls[3] - calls[0] >= INTERVAL * 3 - SLACK);
+}
+
+#[test]
+fn reports_waits_to_callback() {
+    let waits = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&waits);
+    let client = YdtClient::builder()
+        .fetcher(RecordingFetcher::default())
+        .min_request_interval(INTERVAL)
+        .on_rate_limit_wait(move |wait| recorded.lock().unwrap().push(wait))
+        .build();
+
+    client.translate("a").unwrap();
+    client.translate("b").unwrap();
+
+    let waits = waits.lock().unwrap();
+    assert_eq!(waits.len(), 1);
+    assert!(waits[0] > Duration::ZERO && waits[0] <= INTERVAL);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::{YdtClient, YdtError};
+
+#[test]
+fn returns_the_page_unparsed() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("hello", HELLO_HTML));
+    assert_eq!(client.fetch_html("hello").unwrap(), HELLO_HTML);
+}
+
+#[test]
+fn error_statuses_are_reported() {
+    let client = YdtClient::with_fetcher(MockFetcher::new());
+    let err = client.fetch_html("missing").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            YdtError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn invalid_utf8_is_replaced() {
+    let server = MockServer::start_bytes(b"<p>caf\xe9 \xff</p>");
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .build();
+    assert_eq!(
+        client.fetch_html("cafe").unwrap(),
+        "<p>caf\u{fffd} \u{fffd}</p>"
+    );
+}
+
+#[test]
+fn raw_html_flag_prints_the_body() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--raw-html", "hello"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env(
+            "XDG_CONFIG_HOME",
+            std::env::temp_dir().join("ydt-raw-html-none"),
+        )
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), HELLO_HTML);
+}
@@ -0,0 +1,73 @@
+//! Recorded result pages against their expected output; see `ydt::test_util`.
+
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::fs;
+#[cfg(feature = "http")]
+use std::process::Command;
+#[cfg(feature = "http")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockFetcher, MockServer, HELLO_HTML};
+#[cfg(feature = "http")]
+use ydt::YdtClient;
+
+#[test]
+fn recorded_pages_render_as_before() {
+    let checked = ydt::test_util::check_recorded_pages(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/recorded"
+    ));
+    assert!(checked >= 5, "only {checked} recorded pages");
+}
+
+#[test]
+#[should_panic(expected = "missing")]
+fn pages_without_expected_output_fail() {
+    let dir = std::env::temp_dir().join(format!("ydt-recorded-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("hello.html"), "<html></html>").unwrap();
+    ydt::test_util::check_recorded_pages(&dir);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn fetched_pages_are_passed_to_the_callback() {
+    let pages = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&pages);
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .on_page(move |word, html| seen.lock().unwrap().push((word.to_string(), html.len())))
+        .build();
+
+    client.translate(" hello ").unwrap();
+    assert!(client.translate("missing").is_err());
+    assert_eq!(
+        *pages.lock().unwrap(),
+        [("hello".to_string(), HELLO_HTML.len())]
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_records_fetched_pages() {
+    let server = MockServer::start(HELLO_HTML);
+    let dir = temp_dir("record").join("pages");
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--record"])
+        .arg(&dir)
+        .arg("good-bye")
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("record_config"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("good_bye.html")).unwrap(),
+        HELLO_HTML
+    );
+}
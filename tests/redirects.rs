@@ -0,0 +1,166 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{YdtClient, YdtError};
+
+fn client_for(server: &MockServer, path: &str) -> ydt::YdtClientBuilder {
+    YdtClient::builder()
+        .base_url(format!("{}{path}", server.url))
+        .mobile_fallback(false)
+}
+
+/// Redirect `/a` to `/b` and `/b` to the result page.
+fn chain() -> MockServer {
+    MockServer::start_with(|request| {
+        if request.target.starts_with("/a") {
+            (302, "/b")
+        } else if request.target.starts_with("/b") {
+            (302, "/result?word=hello&lang=en")
+        } else {
+            (200, HELLO_HTML)
+        }
+    })
+}
+
+fn unexpected_redirect(err: YdtError) -> String {
+    match err {
+        YdtError::UnexpectedRedirect { to, .. } => to,
+        err => panic!("{err:?}"),
+    }
+}
+
+#[test]
+fn redirect_chain_is_followed_and_counted() {
+    let server = chain();
+    let (_, info) = client_for(&server, "/a")
+        .build()
+        .translate_detailed("hello")
+        .unwrap();
+
+    assert_eq!(info.redirects, 2);
+    assert_eq!(
+        info.final_url.unwrap().as_str(),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+}
+
+#[test]
+fn concurrent_requests_count_their_own_redirects() {
+    let redirecting = chain();
+    let direct = MockServer::start(HELLO_HTML);
+    // Clients with the default settings share their HTTP clients.
+    let redirected = client_for(&redirecting, "/a").build();
+    let not_redirected = client_for(&direct, "/result").build();
+
+    std::thread::scope(|scope| {
+        for (client, expected) in [(&redirected, 2), (&not_redirected, 0)] {
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    let (_, info) = client.translate_detailed("hello").unwrap();
+                    assert_eq!(info.redirects, expected);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn redirects_beyond_the_limit_are_rejected() {
+    let server = chain();
+    let err = client_for(&server, "/a")
+        .max_redirects(1)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    assert_eq!(
+        unexpected_redirect(err),
+        format!("{}/result?word=hello&lang=en", server.url)
+    );
+}
+
+#[test]
+fn no_redirects_rejects_the_first_one() {
+    let server = chain();
+    let err = client_for(&server, "/a")
+        .no_redirects()
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    assert_eq!(unexpected_redirect(err), format!("{}/b", server.url));
+}
+
+#[test]
+fn redirect_loop_is_rejected() {
+    let server = MockServer::start_with(|request| {
+        if request.target.starts_with("/a") {
+            (302, "/b")
+        } else {
+            (302, "/a")
+        }
+    });
+    let err = client_for(&server, "/a")
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    let to = unexpected_redirect(err);
+    assert_eq!(to, format!("{}/b", server.url));
+}
+
+#[test]
+fn redirect_to_another_host_is_rejected() {
+    let target = MockServer::start(HELLO_HTML);
+    let port = target.url.rsplit(':').next().unwrap().to_string();
+    let elsewhere: &'static str = format!("http://localhost:{port}/result").leak();
+    let server = MockServer::start_with(move |_| (302, elsewhere));
+
+    let err = client_for(&server, "/result")
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    assert_eq!(
+        unexpected_redirect(err),
+        format!("http://localhost:{port}/result")
+    );
+    assert!(target.pending_requests().is_empty());
+}
+
+#[test]
+fn cookies_are_sent_back_on_later_requests() {
+    let server = MockServer::start_with_headers(
+        &[("Set-Cookie", "OUTFOX_SEARCH_USER_ID=abc; Path=/")],
+        |_| (200, HELLO_HTML),
+    );
+    let client = client_for(&server, "/result").cookies(true).build();
+
+    client.translate("hello").unwrap();
+    client.translate("hello").unwrap();
+
+    assert_eq!(server.next_request().headers.get("cookie"), None);
+    assert_eq!(
+        server.next_request().headers["cookie"],
+        "OUTFOX_SEARCH_USER_ID=abc"
+    );
+}
+
+#[test]
+fn cookies_are_not_kept_by_default() {
+    let server = MockServer::start_with_headers(
+        &[("Set-Cookie", "OUTFOX_SEARCH_USER_ID=abc; Path=/")],
+        |_| (200, HELLO_HTML),
+    );
+    let client = client_for(&server, "/result").build();
+
+    client.translate("hello").unwrap();
+    client.translate("hello").unwrap();
+
+    assert!(server
+        .pending_requests()
+        .iter()
+        .all(|request| !request.headers.contains_key("cookie")));
+}
@@ -0,0 +1,134 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use ydt::{
+    parse_translation, parse_translation_with_options, render_translation, translation_to_json,
+    RelatedGroup, RelatedWord, RenderOptions, SelectorConfig, Selectors, TranslationOptions,
+};
+
+const CREATE_RELATED_HTML: &str = include_str!("fixtures/create_related.html");
+
+fn selectors() -> Selectors {
+    SelectorConfig::default().compile().unwrap()
+}
+
+fn related() -> TranslationOptions {
+    TranslationOptions {
+        include_related: Some(true),
+        ..TranslationOptions::default()
+    }
+}
+
+fn word(word: &str, gloss: &str) -> RelatedWord {
+    RelatedWord {
+        word: word.to_string(),
+        gloss: gloss.to_string(),
+    }
+}
+
+#[test]
+fn groups_are_parsed_by_part_of_speech() {
+    let (translation, warnings) =
+        parse_translation_with_options("create", CREATE_RELATED_HTML, &selectors(), &related())
+            .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    // The root line has no part of speech and is left out.
+    assert_eq!(
+        translation.related,
+        [
+            RelatedGroup {
+                pos: "adj.".to_string(),
+                words: vec![word("creative", "创造性的")],
+            },
+            RelatedGroup {
+                pos: "adv.".to_string(),
+                words: vec![word("creatively", "创造性地")],
+            },
+            RelatedGroup {
+                pos: "n.".to_string(),
+                words: vec![
+                    word("creation", "创造，创作；创作物，产物"),
+                    word("creativity", "创造力；创造性"),
+                    word("creator", ""),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn glosses_end_at_the_next_word() {
+    let html = r#"
+<div class="trans-container"></div>
+<div class="trans-container">
+  <li class="word-exp"><span class="pos">vt.</span><span class="trans">创造</span></li>
+</div>
+<div class="rel_word"><div class="wordGroup">
+  <span class="contentTitle">n.</span>
+  <a class="search-js">creation</a> <span>创造</span>
+  <a class="search-js">creator</a> 创造者
+</div></div>
+"#;
+    let (translation, _) =
+        parse_translation_with_options("create", html, &selectors(), &related()).unwrap();
+    assert_eq!(
+        translation.related[0].words,
+        [word("creation", "创造"), word("creator", "创造者")]
+    );
+}
+
+#[test]
+fn missing_section_is_empty() {
+    let hello = include_str!("fixtures/hello.html");
+    let (translation, warnings) =
+        parse_translation_with_options("hello", hello, &selectors(), &related()).unwrap();
+    assert!(translation.related.is_empty());
+    assert!(warnings.is_empty(), "{warnings:?}");
+
+    // Only parsed when requested.
+    let translation = parse_translation("create", CREATE_RELATED_HTML).unwrap();
+    assert!(translation.related.is_empty());
+}
+
+#[test]
+fn related_words_follow_the_definitions() {
+    let (translation, _) =
+        parse_translation_with_options("create", CREATE_RELATED_HTML, &selectors(), &related())
+            .unwrap();
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "英 /kriˈeɪt/ 美 /kriˈeɪt/
+vt.: 创造，创作；引起，造成
+
+adj. creative 创造性的
+adv. creatively 创造性地
+n.   creation 创造，创作；创作物，产物
+     creativity 创造力；创造性
+     creator"
+    );
+
+    let json = translation_to_json(&translation);
+    assert_eq!(json["related"][2]["words"][0]["word"], "creation");
+    assert_eq!(json["related"][0]["pos"], "adj.");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_shows_related_words() {
+    let server = MockServer::start(CREATE_RELATED_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--no-wrap", "--related", "create"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("related"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("adv. creatively 创造性地\n"), "{stdout}");
+}
@@ -0,0 +1,134 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer};
+use serde_json::json;
+use ydt::{
+    parse_translation, render_one_line, render_pretty, render_translation, render_tsv_row,
+    translation_to_json, RenderOptions, ResultKind,
+};
+
+const HELLO_HTML: &str = include_str!("fixtures/hello.html");
+const WEB_ONLY_HTML: &str = include_str!("fixtures/web_only.html");
+const HELLLO_HTML: &str = include_str!("fixtures/helllo.html");
+const EMPTY_HTML: &str = "<html><body></body></html>";
+
+#[test]
+fn dictionary_results_take_precedence() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert_eq!(translation.result_kind(), ResultKind::Full);
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "英 /həˈləʊ/ 美 /həˈloʊ/\nint.: 你好；喂\nn.: 表示问候"
+    );
+    assert_eq!(translation_to_json(&translation)["kind"], "full");
+}
+
+#[test]
+fn web_translations_are_marked_and_shown_before_suggestions() {
+    let translation = parse_translation("vibe coding", WEB_ONLY_HTML).unwrap();
+    assert_eq!(translation.result_kind(), ResultKind::WebOnly);
+    assert!(!translation.is_empty());
+    assert_eq!(translation.web_translations, ["氛围编程", "凭感觉编程"]);
+    assert_eq!(translation.suggestions, ["vibe"]);
+
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "(web) 氛围编程；凭感觉编程"
+    );
+    assert_eq!(
+        render_one_line(&translation, 80),
+        "vibe coding (web) 氛围编程；凭感觉编程"
+    );
+    assert_eq!(
+        render_pretty(&translation, &RenderOptions::default(), 20),
+        "(web) 氛围编程；凭感\n      觉编程"
+    );
+    assert_eq!(
+        render_tsv_row("vibe coding", &Ok(translation.clone())),
+        "vibe coding\t\t(web) 氛围编程；凭感觉编程"
+    );
+    assert_eq!(
+        translation_to_json(&translation),
+        json!({
+            "word": "vibe coding",
+            "kind": "web_only",
+            "phonetics": [],
+            "entries": [],
+            "web_translations": ["氛围编程", "凭感觉编程"],
+            "suggestions": ["vibe"],
+        })
+    );
+}
+
+#[test]
+fn web_translations_are_hidden_behind_dictionary_results() {
+    let mut translation = parse_translation("hello", HELLO_HTML).unwrap();
+    translation.web_translations = vec!["哈喽".to_string()];
+    assert_eq!(translation.result_kind(), ResultKind::Full);
+    assert!(!render_translation(&translation, &RenderOptions::default()).contains("(web)"));
+    assert!(!render_one_line(&translation, 200).contains("(web)"));
+}
+
+#[test]
+fn suggestions_replace_no_results() {
+    let translation = parse_translation("helllo", HELLLO_HTML).unwrap();
+    assert_eq!(translation.result_kind(), ResultKind::SuggestionsOnly);
+    assert!(translation.is_empty());
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "Did you mean: hello, hellos?"
+    );
+    assert_eq!(
+        render_one_line(&translation, 80),
+        "Did you mean: hello, hellos?"
+    );
+    assert_eq!(
+        translation_to_json(&translation),
+        json!({
+            "word": "helllo",
+            "kind": "suggestions_only",
+            "phonetics": [],
+            "entries": [],
+            "suggestions": ["hello", "hellos"],
+        })
+    );
+}
+
+#[test]
+fn empty_pages_render_no_results() {
+    let translation = parse_translation("xyzzy", EMPTY_HTML).unwrap();
+    assert_eq!(translation.result_kind(), ResultKind::Empty);
+    assert_eq!(
+        render_translation(&translation, &RenderOptions::default()),
+        "No results."
+    );
+    assert_eq!(
+        render_pretty(&translation, &RenderOptions::default(), 80),
+        "No results."
+    );
+    assert_eq!(translation_to_json(&translation)["kind"], "empty");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_prints_web_only_results_with_a_marker() {
+    let server = MockServer::start(WEB_ONLY_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--format", "plain", "vibe coding"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("result_kind"))
+        .env("XDG_CACHE_HOME", temp_dir("result_kind_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "(web) 氛围编程；凭感觉编程\n"
+    );
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use ydt::{YdtClient, YdtClientBuilder, YdtError};
+
+/// Answer the first `limited` requests with `429 Too Many Requests` and
+/// `retry_after`, if any, then the result page.
+fn throttling(retry_after: Option<String>, limited: usize) -> MockServer {
+    let headers: &'static [_] = match retry_after {
+        Some(value) => Vec::leak(vec![("Retry-After", &*String::leak(value))]),
+        None => &[],
+    };
+    let requests = AtomicUsize::new(0);
+    MockServer::start_with_headers(headers, move |_| {
+        if requests.fetch_add(1, Ordering::SeqCst) < limited {
+            (429, "")
+        } else {
+            (200, HELLO_HTML)
+        }
+    })
+}
+
+fn client_for(server: &MockServer) -> YdtClientBuilder {
+    YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .user_agent("ydt-test")
+        .mobile_fallback(false)
+}
+
+fn retry_after(err: YdtError) -> Option<Duration> {
+    match err {
+        YdtError::RateLimited { retry_after, .. } => retry_after,
+        err => panic!("{err:?}"),
+    }
+}
+
+#[test]
+fn short_retry_after_in_seconds_is_waited_out() {
+    let server = throttling(Some("1".to_string()), 1);
+    let start = Instant::now();
+
+    let translation = client_for(&server)
+        .retries(1)
+        .build()
+        .translate("hello")
+        .unwrap();
+
+    assert!(!translation.is_empty());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.pending_requests().len(), 2);
+}
+
+#[test]
+fn retry_after_date_in_the_past_is_retried_right_away() {
+    let date = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(60));
+    let server = throttling(Some(date), 1);
+
+    let result = client_for(&server).retries(1).build().translate("hello");
+
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(server.pending_requests().len(), 2);
+}
+
+#[test]
+fn long_retry_after_in_seconds_fails_without_retrying() {
+    let server = throttling(Some("120".to_string()), usize::MAX);
+
+    let err = client_for(&server)
+        .retries(3)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Rate limited by the server for \"hello\"; try again in 120s"
+    );
+    assert_eq!(retry_after(err), Some(Duration::from_secs(120)));
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[test]
+fn retry_after_date_is_converted_to_a_duration() {
+    let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(3600));
+    let server = throttling(Some(date), usize::MAX);
+
+    let err = client_for(&server)
+        .retries(1)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    let wait = retry_after(err).unwrap();
+    assert!(
+        wait > Duration::from_secs(3590) && wait <= Duration::from_secs(3600),
+        "{wait:?}"
+    );
+    assert_eq!(server.pending_requests().len(), 1);
+}
+
+#[test]
+fn missing_retry_after_is_reported() {
+    let server = throttling(None, usize::MAX);
+
+    let err = client_for(&server).build().translate("hello").unwrap_err();
+
+    assert_eq!(err.to_string(), "Rate limited by the server for \"hello\"");
+    assert_eq!(retry_after(err), None);
+}
+
+#[test]
+fn cooldown_file_stops_later_clients_from_sending_requests() {
+    let path = temp_dir("cooldown_library").join("state").join("cooldown");
+    let server = throttling(Some("120".to_string()), usize::MAX);
+
+    client_for(&server)
+        .cooldown_path(&path)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+    assert_eq!(server.pending_requests().len(), 1);
+    assert!(path.exists());
+
+    let err = client_for(&server)
+        .cooldown_path(&path)
+        .build()
+        .translate("hello")
+        .unwrap_err();
+
+    let wait = retry_after(err).unwrap();
+    assert!(wait > Duration::from_secs(110), "{wait:?}");
+    assert!(server.pending_requests().is_empty());
+}
+
+#[test]
+fn expired_cooldown_is_ignored() {
+    let path = temp_dir("cooldown_expired").join("cooldown");
+    std::fs::write(&path, "1\n").unwrap();
+    let server = MockServer::start(HELLO_HTML);
+
+    let result = client_for(&server)
+        .cooldown_path(&path)
+        .build()
+        .translate("hello");
+
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn cli_fails_fast_during_cooldown() {
+    let dir = temp_dir("cooldown_cli");
+    let server = throttling(Some("120".to_string()), usize::MAX);
+    let run = || {
+        Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "hello"])
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", &dir)
+            .env("XDG_CACHE_HOME", dir.join("cache"))
+            .output()
+            .unwrap()
+    };
+
+    let output = run();
+    assert_eq!(output.status.code(), Some(4));
+    assert!(dir.join("cache/ydt/cooldown").exists());
+    assert!(!server.pending_requests().is_empty());
+
+    let output = run();
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Rate limited by the server"), "{stderr}");
+    assert!(server.pending_requests().is_empty());
+}
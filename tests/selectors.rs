@@ -0,0 +1,114 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{parse_translation_with_selectors, SelectorConfig, YdtClient, YdtError};
+
+// Same page as hello.html, with the definitions' class renamed.
+const RENAMED_HTML: &str = include_str!("fixtures/degraded_no_word_exp.html");
+
+#[test]
+fn built_in_selectors_are_valid_css() {
+    let defaults = SelectorConfig::default();
+    let all = [
+        &defaults.trans_container,
+        &defaults.phone,
+        &defaults.phone_label,
+        &defaults.phonetic,
+        &defaults.word_exp,
+        &defaults.pos,
+        &defaults.trans,
+        &defaults.word_exp_ce,
+        &defaults.point,
+    ];
+    for css in all {
+        assert!(scraper::Selector::parse(css).is_ok(), "{css}");
+    }
+}
+
+#[test]
+fn default_config_matches_the_built_in_parser() {
+    let selectors = SelectorConfig::default().compile().unwrap();
+    let (translation, warnings) =
+        parse_translation_with_selectors("hello", HELLO_HTML, &selectors).unwrap();
+
+    assert_eq!(
+        translation,
+        ydt::parse_translation("hello", HELLO_HTML).unwrap()
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn renamed_class_is_fixed_by_config_file() {
+    let fetcher = MockFetcher::new().page("hello", RENAMED_HTML);
+    let shared = std::sync::Arc::new(fetcher);
+
+    let default_client = YdtClient::with_fetcher(shared.clone());
+    assert!(default_client
+        .translate("hello")
+        .unwrap()
+        .entries
+        .is_empty());
+
+    let config = Config::from_toml(
+        r#"
+        [selectors]
+        word_exp = "li.word-exp-new"
+        "#,
+    )
+    .unwrap();
+    let client = YdtClient::builder().fetcher(shared).config(&config).build();
+    let translation = client.translate("hello").unwrap();
+
+    assert_eq!(translation.entries.len(), 1);
+    assert_eq!(translation.entries[0].pos, "int.");
+    assert_eq!(translation.entries[0].meaning, "你好；喂");
+    assert_eq!(translation.phonetics.len(), 1);
+}
+
+#[test]
+fn unset_selectors_keep_their_defaults() {
+    let config = Config::from_toml("[selectors]\npos = \"em.pos\"").unwrap();
+    assert_eq!(
+        config.selectors,
+        Some(SelectorConfig {
+            pos: "em.pos".to_string(),
+            ..SelectorConfig::default()
+        })
+    );
+}
+
+#[test]
+fn unknown_selector_key_is_named() {
+    let err = Config::from_toml("[selectors]\nwordexp = \"li\"").unwrap_err();
+    assert!(err.to_string().contains("`selectors.wordexp`"), "{err}");
+}
+
+#[test]
+fn invalid_selector_is_reported_on_lookup() {
+    let selectors = SelectorConfig {
+        trans: "span..trans".to_string(),
+        ..SelectorConfig::default()
+    };
+    let err = selectors.compile().unwrap_err();
+    assert!(
+        matches!(&err, YdtError::ParseCssSelector { css, message } if css == "span..trans" && !message.is_empty()),
+        "{err:?}"
+    );
+
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .selectors(selectors)
+        .build();
+    for _ in 0..2 {
+        let err = client.translate("hello").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to parse CSS selector `span..trans`: "),
+            "{err}"
+        );
+    }
+}
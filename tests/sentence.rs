@@ -0,0 +1,195 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use ydt::{
+    looks_like_sentence, parse_sentence_translation, YdtClient, YdtError, DEFAULT_MAX_WORD_LEN,
+    DEFAULT_SENTENCE_THRESHOLD,
+};
+
+const EN_ZH_HTML: &str = include_str!("fixtures/sentence_en_zh.html");
+const ZH_EN_HTML: &str = include_str!("fixtures/sentence_zh_en.html");
+
+fn ydt(args: &[&str], server: &MockServer) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(args)
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env(
+            "XDG_CONFIG_HOME",
+            std::env::temp_dir().join("ydt-sentence-none"),
+        )
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn parses_english_to_chinese() {
+    assert_eq!(
+        parse_sentence_translation(EN_ZH_HTML).as_deref(),
+        Some("你今天过得怎么样")
+    );
+}
+
+#[test]
+fn parses_chinese_to_english() {
+    assert_eq!(
+        parse_sentence_translation(ZH_EN_HTML).as_deref(),
+        Some("The weather is very nice today.")
+    );
+}
+
+#[test]
+fn dictionary_pages_have_no_sentence() {
+    assert_eq!(parse_sentence_translation(HELLO_HTML), None);
+}
+
+#[test]
+fn threshold_leaves_phrases_to_the_dictionary() {
+    for phrase in [
+        "take off",
+        "look forward to",
+        "ice cream",
+        "etc.",
+        "人工智能",
+    ] {
+        assert!(
+            !looks_like_sentence(phrase, DEFAULT_SENTENCE_THRESHOLD),
+            "{phrase}"
+        );
+    }
+    for sentence in [
+        "how are you doing today",
+        "Take off.",
+        "今天的天气非常好",
+        "你好吗？",
+    ] {
+        assert!(
+            looks_like_sentence(sentence, DEFAULT_SENTENCE_THRESHOLD),
+            "{sentence}"
+        );
+    }
+    assert!(looks_like_sentence("take off", 2));
+    assert!(!looks_like_sentence("how are you doing today", 0));
+}
+
+#[test]
+fn client_translates_sentences() {
+    let client = YdtClient::with_fetcher(
+        MockFetcher::new()
+            .page("how are you doing today", EN_ZH_HTML)
+            .page("今天天气非常好", ZH_EN_HTML),
+    );
+
+    assert_eq!(
+        client
+            .translate_sentence("  how are you   doing today ")
+            .unwrap()
+            .as_deref(),
+        Some("你今天过得怎么样")
+    );
+    assert_eq!(
+        client
+            .translate_sentence("今天天气非常好")
+            .unwrap()
+            .as_deref(),
+        Some("The weather is very nice today.")
+    );
+}
+
+#[test]
+fn long_input_is_translated_as_a_sentence() {
+    let server = MockServer::start(EN_ZH_HTML);
+    let output = ydt(&["how are you doing today"], &server);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "你今天过得怎么样\n"
+    );
+}
+
+/// A sentence of 131 characters, longer than any word.
+const LONG_SENTENCE: &str = "the quick brown fox jumps over the lazy dog while the \
+    farmer watches from the porch and wonders whether it will rain before evening";
+
+#[test]
+fn sentences_may_be_longer_than_words() {
+    assert!(LONG_SENTENCE.chars().count() > DEFAULT_MAX_WORD_LEN);
+    let client = YdtClient::with_fetcher(MockFetcher::new().page(LONG_SENTENCE, EN_ZH_HTML));
+
+    assert_eq!(
+        client.translate_sentence(LONG_SENTENCE).unwrap().as_deref(),
+        Some("你今天过得怎么样")
+    );
+    assert!(matches!(
+        client.translate(LONG_SENTENCE),
+        Err(YdtError::WordTooLong { max: 100, .. })
+    ));
+
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .max_sentence_len(100)
+        .build();
+    assert!(matches!(
+        client.translate_sentence(LONG_SENTENCE),
+        Err(YdtError::WordTooLong { max: 100, .. })
+    ));
+}
+
+#[test]
+fn cli_translates_sentences_longer_than_words() {
+    let server = MockServer::start(EN_ZH_HTML);
+    for args in [&["--sentence", LONG_SENTENCE][..], &[LONG_SENTENCE]] {
+        let output = ydt(args, &server);
+
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "你今天过得怎么样\n"
+        );
+    }
+}
+
+#[test]
+fn cli_rejects_long_words() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&[&"a".repeat(DEFAULT_MAX_WORD_LEN + 1)], &server);
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("101"), "{stderr}");
+    assert!(server.pending_requests().is_empty());
+}
+
+#[test]
+fn sentence_flag_forces_translation() {
+    let server = MockServer::start(EN_ZH_HTML);
+    let output = ydt(&["--sentence", "hi there"], &server);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "你今天过得怎么样\n"
+    );
+}
+
+#[test]
+fn phrases_are_still_looked_up() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["take off"], &server);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("你好"));
+}
+
+#[test]
+fn missing_translation_exits_with_no_results() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = ydt(&["--sentence", "how are you doing today"], &server);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
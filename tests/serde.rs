@@ -0,0 +1,217 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::HELLO_HTML;
+use serde_json::json;
+use ydt::{
+    parse_translation, Candidate, CollinsSense, Direction, Entry, EntrySource, Example, Idiom,
+    Layout, ParseWarning, Phonetic, RelatedGroup, RelatedWord, Translation,
+};
+
+fn full_translation() -> Translation {
+    Translation {
+        word: "runing".to_string(),
+        phonetics: vec![Phonetic {
+            label: "英".to_string(),
+            text: "/ˈrʌnɪŋ/".to_string(),
+        }],
+        entries: vec![
+            Entry {
+                pos: "n.".to_string(),
+                meaning: "跑步".to_string(),
+                source: EntrySource::Concise,
+            },
+            Entry {
+                pos: String::new(),
+                meaning: "running".to_string(),
+                source: EntrySource::Mobile,
+            },
+        ],
+        other_directions: Vec::new(),
+        candidates: vec![Candidate {
+            hanzi: "润".to_string(),
+            pinyin: "rùn".to_string(),
+            gloss: String::new(),
+        }],
+        conversions: vec!["forty-two".to_string()],
+        web_translations: vec!["跑步的".to_string()],
+        layout: Some(Layout::EnglishChinese),
+        direction: Some(Direction::EnToZh),
+        headword: Some("run".to_string()),
+        lemma_note: Some("现在分词".to_string()),
+        frequency: Some(4),
+        idiom: Some(Idiom {
+            pinyin: String::new(),
+            explanation: "比喻做了多余的事".to_string(),
+            source: "《战国策》".to_string(),
+            example: String::new(),
+        }),
+        collins: vec![CollinsSense {
+            number: 1,
+            pos: "VERB".to_string(),
+            en_def: "When you run, you move quickly.".to_string(),
+            zh_def: String::new(),
+            examples: vec![Example {
+                en: "I ran to the station.".to_string(),
+                zh: "我跑到了车站。".to_string(),
+            }],
+        }],
+        related: vec![RelatedGroup {
+            pos: "n.".to_string(),
+            words: vec![RelatedWord {
+                word: "runner".to_string(),
+                gloss: "跑步者".to_string(),
+            }],
+        }],
+        suggestions: vec!["running".to_string()],
+        converted_from: None,
+        corrected_from: Some("runing".to_string()),
+        source_url: Some(
+            "https://www.youdao.com/result?word=running&lang=en"
+                .parse()
+                .unwrap(),
+        ),
+        display_limit: None,
+    }
+}
+
+#[test]
+fn parsed_page_matches_the_golden_json() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+
+    let json = serde_json::to_string_pretty(&translation).unwrap();
+
+    assert_eq!(json, include_str!("fixtures/hello.json").trim_end());
+}
+
+#[test]
+fn golden_json_deserializes_to_the_parsed_page() {
+    let translation: Translation =
+        serde_json::from_str(include_str!("fixtures/hello.json")).unwrap();
+
+    assert_eq!(translation, parse_translation("hello", HELLO_HTML).unwrap());
+}
+
+#[test]
+fn every_field_round_trips() {
+    let translation = full_translation();
+
+    let json = serde_json::to_string(&translation).unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<Translation>(&json).unwrap(),
+        translation
+    );
+}
+
+#[test]
+fn field_names_are_stable() {
+    assert_eq!(
+        serde_json::to_value(full_translation()).unwrap(),
+        json!({
+            "word": "runing",
+            "phonetics": [{ "label": "英", "text": "/ˈrʌnɪŋ/" }],
+            "entries": [
+                { "pos": "n.", "meaning": "跑步", "source": "concise" },
+                { "meaning": "running", "source": "mobile" },
+            ],
+            "candidates": [{ "hanzi": "润", "pinyin": "rùn" }],
+            "conversions": ["forty-two"],
+            "web_translations": ["跑步的"],
+            "layout": "english_chinese",
+            "direction": "en_to_zh",
+            "headword": "run",
+            "lemma_note": "现在分词",
+            "frequency": 4,
+            "idiom": { "explanation": "比喻做了多余的事", "source": "《战国策》" },
+            "collins": [{
+                "number": 1,
+                "pos": "VERB",
+                "en_def": "When you run, you move quickly.",
+                "examples": [{ "en": "I ran to the station.", "zh": "我跑到了车站。" }],
+            }],
+            "related": [{ "pos": "n.", "words": [{ "word": "runner", "gloss": "跑步者" }] }],
+            "suggestions": ["running"],
+            "corrected_from": "runing",
+            "source_url": "https://www.youdao.com/result?word=running&lang=en",
+        })
+    );
+}
+
+#[test]
+fn empty_sections_are_left_out() {
+    let translation = Translation {
+        word: "xyzzy".to_string(),
+        ..Translation::default()
+    };
+
+    let json = serde_json::to_string(&translation).unwrap();
+
+    assert_eq!(json, r#"{"word":"xyzzy"}"#);
+    assert_eq!(
+        serde_json::from_str::<Translation>(&json).unwrap(),
+        translation
+    );
+}
+
+#[test]
+fn parse_warnings_round_trip() {
+    let warnings = vec![
+        ParseWarning::PhoneticTextMissing {
+            label: "美".to_string(),
+        },
+        ParseWarning::DefinitionsMissing,
+    ];
+
+    let json = serde_json::to_value(&warnings).unwrap();
+
+    assert_eq!(
+        json,
+        json!([{ "phonetic_text_missing": { "label": "美" } }, "definitions_missing"])
+    );
+    assert_eq!(
+        serde_json::from_value::<Vec<ParseWarning>>(json).unwrap(),
+        warnings
+    );
+}
+
+#[test]
+fn error_reports_round_trip() {
+    let report = ydt::YdtError::RateLimited {
+        word: "hello".to_string(),
+        retry_after: None,
+    }
+    .to_owned_report();
+
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "kind": "rate_limited",
+            "message": "Rate limited by the server for \"hello\"",
+            "word": "hello",
+            "status": 429,
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<ydt::ErrorReport>(json).unwrap(),
+        report
+    );
+    assert_eq!(
+        serde_json::to_string(&ydt::YdtError::EmptyWord.to_owned_report()).unwrap(),
+        r#"{"kind":"empty_word","message":"Word to translate is empty"}"#
+    );
+}
+
+#[test]
+fn build_info_serializes_like_its_json() {
+    let info = ydt::build_info();
+
+    assert!(info.features.contains(&"serde"), "{:?}", info.features);
+    assert_eq!(
+        serde_json::to_value(&info).unwrap(),
+        ydt::build_info_to_json(&info)
+    );
+}
@@ -0,0 +1,299 @@
+#![cfg(feature = "server")]
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use serde_json::Value;
+use ydt::server::Server;
+use ydt::{build_info, build_info_to_json, Fetcher, StatusCode, YdtClient, YdtError};
+
+const HELLLO_HTML: &str = include_str!("fixtures/helllo.html");
+
+const HEL_JSON: &str = r#"{
+  "result": {"msg": "success", "code": 200},
+  "data": {"entries": [{"explain": "int. 喂；哈罗", "entry": "hello"}, {"entry": "helo"}]}
+}"#;
+
+/// Start a server for `client` on a free port, returning its URL.
+fn serve(client: YdtClient) -> String {
+    let server = Server::bind("127.0.0.1:0", client).unwrap();
+    let url = format!("http://{}", server.local_addr());
+    thread::spawn(move || server.run());
+    url
+}
+
+fn mock_client() -> YdtClient {
+    YdtClient::with_fetcher(
+        MockFetcher::new()
+            .page("hello", HELLO_HTML)
+            .page("helllo", HELLLO_HTML)
+            .status("teapot", StatusCode::IM_A_TEAPOT)
+            .status("busy", StatusCode::TOO_MANY_REQUESTS)
+            .status("broken", StatusCode::INTERNAL_SERVER_ERROR),
+    )
+}
+
+/// Status, `Retry-After` header and JSON body of a GET request.
+fn get(url: &str) -> (u16, Option<String>, Value) {
+    let response = reqwest::blocking::get(url).unwrap();
+    let status = response.status().as_u16();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/json; charset=utf-8"
+    );
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = serde_json::from_str(&response.text().unwrap()).unwrap();
+    (status, retry_after, body)
+}
+
+#[test]
+fn translate_returns_json() {
+    let url = serve(mock_client());
+
+    let (status, _, body) = get(&format!("{url}/translate?word=hello&lang=en"));
+
+    assert_eq!(status, 200);
+    assert_eq!(body["word"], "hello");
+    assert_eq!(body["phonetics"][0]["text"], "/həˈləʊ/");
+    assert_eq!(body["entries"][0]["pos"], "int.");
+    assert_eq!(body["entries"][0]["meaning"], "你好；喂");
+}
+
+#[test]
+fn translate_passes_lang_on() {
+    let youdao = MockServer::start(HELLO_HTML);
+    let url = serve(
+        YdtClient::builder()
+            .base_url(format!("{}/result", youdao.url))
+            .mobile_fallback(false)
+            .build(),
+    );
+
+    let (status, _, body) = get(&format!("{url}/translate?word=hello&lang=fr"));
+    assert_eq!(status, 200);
+    assert_eq!(body["word"], "hello");
+    assert_eq!(youdao.next_request().target, "/result?word=hello&lang=fr");
+
+    let (status, _, _) = get(&format!("{url}/translate?word=hello"));
+    assert_eq!(status, 200);
+    assert_eq!(youdao.next_request().target, "/result?word=hello&lang=en");
+}
+
+#[test]
+fn errors_map_to_http_statuses() {
+    let url = serve(mock_client());
+
+    let (status, _, body) = get(&format!("{url}/translate?word=missing"));
+    assert_eq!(status, 404);
+    assert_eq!(body["kind"], "http_status");
+    assert_eq!(body["word"], "missing");
+    assert_eq!(body["status"], 404);
+
+    let (status, _, body) = get(&format!("{url}/translate?word=helllo"));
+    assert_eq!(status, 404);
+    assert_eq!(body["kind"], "no_results");
+    assert_eq!(body["suggestions"], serde_json::json!(["hello", "hellos"]));
+
+    let (status, retry_after, _) = get(&format!("{url}/translate?word=busy"));
+    assert_eq!(status, 429);
+    assert_eq!(retry_after, None);
+
+    for word in ["broken", "teapot"] {
+        let (status, _, body) = get(&format!("{url}/translate?word={word}"));
+        assert_eq!(status, 502, "{word}");
+        assert_eq!(body["kind"], "http_status");
+    }
+}
+
+#[test]
+fn invalid_requests_are_rejected() {
+    let url = serve(mock_client());
+
+    let (status, _, body) = get(&format!("{url}/translate"));
+    assert_eq!(status, 400);
+    assert_eq!(body["kind"], "invalid_argument");
+
+    let (status, _, body) = get(&format!("{url}/translate?word=%20"));
+    assert_eq!(status, 400);
+    assert_eq!(body["kind"], "empty_word");
+
+    let (status, _, body) = get(&format!("{url}/suggest?q=hel&limit=0"));
+    assert_eq!(status, 400);
+    assert_eq!(body["message"], "Invalid limit: 0");
+
+    let (status, _, _) = get(&format!("{url}/nothing"));
+    assert_eq!(status, 404);
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("{url}/translate?word=hello"))
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+/// Fetcher rate-limited by the server, which asked to wait 30 seconds.
+struct RateLimitedFetcher;
+
+impl Fetcher for RateLimitedFetcher {
+    fn fetch(&self, word: &str) -> Result<String, YdtError> {
+        Err(YdtError::RateLimited {
+            word: word.to_string(),
+            retry_after: Some(Duration::from_secs(30)),
+        })
+    }
+}
+
+#[test]
+fn retry_after_is_passed_through() {
+    let url = serve(YdtClient::with_fetcher(RateLimitedFetcher));
+
+    let (status, retry_after, body) = get(&format!("{url}/translate?word=hello"));
+
+    assert_eq!(status, 429);
+    assert_eq!(retry_after.as_deref(), Some("30"));
+    assert_eq!(body["kind"], "rate_limited");
+}
+
+#[test]
+fn suggest_returns_completions() {
+    let upstream = MockServer::start(HEL_JSON);
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .suggest_url(format!("{}/suggest", upstream.url))
+        .build();
+    let url = serve(client);
+
+    let (status, _, body) = get(&format!("{url}/suggest?q=hel&limit=5"));
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        serde_json::json!([
+            { "word": "hello", "gloss": "int. 喂；哈罗" },
+            { "word": "helo", "gloss": "" },
+        ])
+    );
+    let request = upstream.next_request();
+    assert_eq!(request.target, "/suggest?q=hel&num=5&doctype=json&le=en");
+}
+
+#[test]
+fn healthz_answers_ok() {
+    let url = serve(mock_client());
+
+    let (status, _, body) = get(&format!("{url}/healthz"));
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        serde_json::json!({ "status": "ok", "build": build_info_to_json(&build_info()) })
+    );
+    assert!(body["build"]["features"]
+        .as_array()
+        .unwrap()
+        .contains(&"server".into()));
+}
+
+#[test]
+fn request_id_is_echoed_back() {
+    let url = serve(mock_client());
+    let http = reqwest::blocking::Client::new();
+
+    let response = http
+        .get(format!("{url}/translate?word=teapot"))
+        .header("X-Request-Id", "req-42")
+        .send()
+        .unwrap();
+    assert_eq!(response.headers()["x-request-id"], "req-42");
+    let body: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+    assert_eq!(body["kind"], "http_status");
+    assert_eq!(body["request_id"], "req-42");
+
+    let response = http
+        .get(format!("{url}/translate?word=hello"))
+        .header("X-Request-Id", "req-43")
+        .send()
+        .unwrap();
+    assert_eq!(response.headers()["x-request-id"], "req-43");
+    let body: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+    assert_eq!(body.get("request_id"), None);
+}
+
+#[test]
+fn request_id_is_generated_when_missing_or_invalid() {
+    let url = serve(mock_client());
+    let http = reqwest::blocking::Client::new();
+    let is_uuid = |id: &str| {
+        id.len() == 36
+            && id.split('-').map(str::len).eq([8, 4, 4, 4, 12])
+            && id.chars().all(|ch| ch == '-' || ch.is_ascii_hexdigit())
+    };
+
+    let response = http.get(format!("{url}/healthz")).send().unwrap();
+    let first = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(is_uuid(&first), "{first}");
+
+    let response = http
+        .get(format!("{url}/nowhere"))
+        .header("X-Request-Id", "x".repeat(200))
+        .send()
+        .unwrap();
+    let second = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(is_uuid(&second), "{second}");
+    assert_ne!(first, second);
+    let body: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+    assert_eq!(body["request_id"], second);
+}
+
+/// Fetcher that holds each request for a while, recording how many were in
+/// flight at once.
+#[derive(Default)]
+struct SlowFetcher {
+    in_flight: AtomicUsize,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl Fetcher for SlowFetcher {
+    fn fetch(&self, _word: &str) -> Result<String, YdtError> {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(200));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(HELLO_HTML.to_string())
+    }
+}
+
+#[test]
+fn requests_are_handled_concurrently() {
+    let fetcher = SlowFetcher::default();
+    let max_in_flight = Arc::clone(&fetcher.max_in_flight);
+    let url = serve(YdtClient::with_fetcher(fetcher));
+
+    let requests: Vec<_> = (0..4)
+        .map(|_| {
+            let url = format!("{url}/translate?word=hello");
+            thread::spawn(move || get(&url))
+        })
+        .collect();
+    for request in requests {
+        let (status, _, body) = request.join().unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body["word"], "hello");
+    }
+
+    assert!(max_in_flight.load(Ordering::SeqCst) > 1);
+}
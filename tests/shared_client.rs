@@ -0,0 +1,107 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use common::HELLO_HTML;
+use ydt::YdtClient;
+
+/// Local HTTP server keeping connections open, counting the connections and
+/// requests it receives.
+struct KeepAliveServer {
+    url: String,
+    connections: Arc<AtomicUsize>,
+    requests: Arc<AtomicUsize>,
+}
+
+impl KeepAliveServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let requests = Arc::new(AtomicUsize::new(0));
+        let (accepted, served) = (Arc::clone(&connections), Arc::clone(&requests));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let served = Arc::clone(&served);
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    loop {
+                        // Request line and headers, up to the blank line.
+                        let mut lines = 0;
+                        loop {
+                            line.clear();
+                            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                                return;
+                            }
+                            if line.trim().is_empty() && lines > 0 {
+                                break;
+                            }
+                            lines += 1;
+                        }
+                        served.fetch_add(1, Ordering::SeqCst);
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{HELLO_HTML}",
+                            HELLO_HTML.len()
+                        );
+                        if stream.write_all(response.as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        Self {
+            url,
+            connections,
+            requests,
+        }
+    }
+}
+
+fn lookup(server: &KeepAliveServer, client: ydt::YdtClientBuilder) {
+    let translation = client
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build()
+        .translate("hello")
+        .unwrap();
+    assert!(!translation.is_empty());
+}
+
+#[test]
+fn clients_with_default_settings_reuse_connections() {
+    let server = KeepAliveServer::start();
+
+    for _ in 0..3 {
+        lookup(&server, YdtClient::builder());
+    }
+
+    assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+    assert_eq!(server.connections.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn clients_with_custom_settings_use_their_own_connections() {
+    let server = KeepAliveServer::start();
+
+    for _ in 0..2 {
+        lookup(
+            &server,
+            YdtClient::builder().timeout(Duration::from_secs(5)),
+        );
+    }
+    lookup(&server, YdtClient::builder().user_agent("ydt-test"));
+
+    assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+    assert_eq!(server.connections.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{MockFetcher, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::{Entry, EntrySource, PageSource, Translation, TranslationSource, YdtClient, YdtError};
+
+/// A source that records its calls and answers with a fixed outcome.
+struct MockSource {
+    name: &'static str,
+    meaning: Option<&'static str>,
+    fail: bool,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockSource {
+    fn new(name: &'static str, calls: &Arc<Mutex<Vec<String>>>) -> Self {
+        Self {
+            name,
+            meaning: None,
+            fail: false,
+            calls: Arc::clone(calls),
+        }
+    }
+
+    fn meaning(mut self, meaning: &'static str) -> Self {
+        self.meaning = Some(meaning);
+        self
+    }
+
+    fn failing(mut self) -> Self {
+        self.fail = true;
+        self
+    }
+}
+
+impl TranslationSource for MockSource {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn lookup(&self, word: &str, lang: &str) -> Result<Translation, YdtError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}:{word}:{lang}", self.name));
+        if self.fail {
+            return Err(YdtError::HttpStatus {
+                word: word.to_string(),
+                url: format!("mock://{}/{word}", self.name),
+                status: StatusCode::SERVICE_UNAVAILABLE,
+            });
+        }
+        let mut translation = Translation {
+            word: word.to_string(),
+            ..Translation::default()
+        };
+        if let Some(meaning) = self.meaning {
+            translation.entries.push(Entry {
+                pos: String::new(),
+                meaning: meaning.to_string(),
+                source: EntrySource::Concise,
+            });
+        }
+        Ok(translation)
+    }
+}
+
+fn calls() -> Arc<Mutex<Vec<String>>> {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+#[test]
+fn sources_are_tried_in_order_after_the_built_ins() {
+    let calls = calls();
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .lang("ja")
+        .source(MockSource::new("first", &calls))
+        .source(MockSource::new("second", &calls).meaning("你好"))
+        .build();
+
+    let (translation, info) = client.translate_detailed(" hello ").unwrap();
+
+    assert_eq!(translation.entries[0].meaning, "你好");
+    assert_eq!(info.source, PageSource::Custom);
+    assert_eq!(info.final_url, None);
+    assert_eq!(
+        *calls.lock().unwrap(),
+        ["first:hello:ja", "second:hello:ja"]
+    );
+}
+
+#[test]
+fn lookup_stops_at_the_first_source_with_results() {
+    let calls = calls();
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", HELLO_HTML))
+        .source(MockSource::new("first", &calls).meaning("你好"))
+        .build();
+
+    let (_, info) = client.translate_detailed("hello").unwrap();
+
+    assert_eq!(info.source, PageSource::Desktop);
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn empty_result_of_the_first_source_is_returned() {
+    let calls = calls();
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new().page("hello", "<html></html>"))
+        .source(MockSource::new("glossary", &calls).failing())
+        .build();
+
+    assert!(client.translate("hello").unwrap().is_empty());
+    assert_eq!(calls.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn errors_of_every_source_are_collected() {
+    let calls = calls();
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .source(MockSource::new("glossary", &calls).failing())
+        .build();
+
+    let err = client.translate("hello").unwrap_err();
+
+    let YdtError::AllSourcesFailed { errors, .. } = &err else {
+        panic!("{err:?}");
+    };
+    let names: Vec<_> = errors.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["desktop", "glossary"]);
+    assert_eq!(
+        err.to_string(),
+        "All sources failed for \"hello\": desktop: Request for \"hello\" failed with status: \
+         404 Not Found; glossary: Request for \"hello\" failed with status: 503 Service Unavailable"
+    );
+}
+
+#[test]
+fn error_of_the_first_source_wins_over_later_empty_results() {
+    let calls = calls();
+    let client = YdtClient::builder()
+        .fetcher(MockFetcher::new())
+        .source(MockSource::new("glossary", &calls))
+        .build();
+
+    let err = client.translate("hello").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            YdtError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
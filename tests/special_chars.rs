@@ -0,0 +1,85 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{build_result_url, normalize_word, parse_translation, YdtClient, YdtError};
+
+const MOTHER_IN_LAW_HTML: &str = include_str!("fixtures/mother_in_law.html");
+const DONT_HTML: &str = include_str!("fixtures/dont.html");
+
+fn query(word: &str) -> String {
+    build_result_url(word, "en")
+        .unwrap()
+        .query()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn url_significant_characters_are_encoded() {
+    assert_eq!(query("don't"), "word=don%27t&lang=en");
+    assert_eq!(query("AT&T"), "word=AT%26T&lang=en");
+    assert_eq!(query("C#"), "word=C%23&lang=en");
+    assert_eq!(query("mother-in-law"), "word=mother-in-law&lang=en");
+    assert_eq!(query("ice cream"), "word=ice+cream&lang=en");
+    assert_eq!(query("a+b=c?"), "word=a%2Bb%3Dc%3F&lang=en");
+    assert_eq!(query("50%"), "word=50%25&lang=en");
+}
+
+#[test]
+fn url_uses_the_default_endpoint() {
+    let url = build_result_url("hello", "en").unwrap();
+    assert_eq!(
+        url.as_str(),
+        "https://www.youdao.com/result?word=hello&lang=en"
+    );
+}
+
+#[test]
+fn encoded_word_reaches_the_server_intact() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .build();
+    client.fetch_html("AT&T #1").unwrap();
+
+    assert_eq!(
+        server.next_request().target,
+        "/result?word=AT%26T+%231&lang=en"
+    );
+}
+
+#[test]
+fn hyphenated_compound_is_parsed() {
+    let translation = parse_translation("mother-in-law", MOTHER_IN_LAW_HTML).unwrap();
+    assert_eq!(translation.phonetics.len(), 2);
+    assert_eq!(translation.entries[0].pos, "n.");
+    assert_eq!(translation.entries[0].meaning, "岳母；婆婆");
+}
+
+#[test]
+fn contraction_is_parsed() {
+    let translation = parse_translation("don't", DONT_HTML).unwrap();
+    assert_eq!(translation.phonetics[0].text, "/dəʊnt/");
+    let pos: Vec<_> = translation.entries.iter().map(|e| e.pos.as_str()).collect();
+    assert_eq!(pos, ["abbr.", "n."]);
+}
+
+#[test]
+fn control_characters_are_rejected() {
+    for word in ["a\u{0}b", "bell\u{7}", "esc\u{1b}[0m"] {
+        assert!(
+            matches!(
+                normalize_word(word, 100),
+                Err(YdtError::UnsupportedCharacter { .. })
+            ),
+            "{word:?}"
+        );
+    }
+    let err = normalize_word("a\u{0}b", 100).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Word to translate \"a\\0b\" contains unsupported character '\\0'"
+    );
+}
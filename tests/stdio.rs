@@ -0,0 +1,169 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+
+use common::{temp_dir, MockServer, HELLO_HTML};
+use serde_json::{json, Value};
+
+const HEL_JSON: &str = r#"{"data": {"entries": [{"explain": "int. 喂；哈罗", "entry": "hello"}]}}"#;
+
+fn server() -> MockServer {
+    MockServer::start_with(|request| {
+        if request.target.starts_with("/suggest") {
+            (200, HEL_JSON)
+        } else if request.target.contains("word=hello") {
+            (200, HELLO_HTML)
+        } else {
+            (404, "")
+        }
+    })
+}
+
+fn spawn(server: &MockServer, name: &str) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--stdio", "--no-mobile-fallback"])
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("YDT_SUGGEST_URL", format!("{}/suggest", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir(&format!("{name}-config")))
+        .env("XDG_CACHE_HOME", temp_dir(&format!("{name}-cache")))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+fn requests_are_answered_by_id_until_eof() {
+    let server = server();
+    let mut child = spawn(&server, "stdio-eof");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            concat!(
+                r#"{"id":1,"op":"translate","word":"hello"}"#,
+                "\n",
+                r#"{"id":2,"op":"suggest","prefix":"hel","limit":3}"#,
+                "\n",
+                r#"{"id":"three","op":"translate","word":"missing"}"#,
+                "\n\n",
+                r#"{"id":4,"op":"translate"}"#,
+                "\n",
+                "not json\n",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let responses: HashMap<String, Value> = stdout
+        .lines()
+        .map(|line| {
+            let response: Value = serde_json::from_str(line).unwrap();
+            (response["id"].to_string(), response)
+        })
+        .collect();
+    assert_eq!(responses.len(), 5, "{stdout}");
+
+    let hello = &responses["1"];
+    assert_eq!(hello["ok"], true);
+    assert_eq!(hello["result"]["word"], "hello");
+    assert_eq!(hello["result"]["entries"][0]["pos"], "int.");
+
+    assert_eq!(
+        responses["2"],
+        json!({
+            "id": 2,
+            "ok": true,
+            "result": [{ "word": "hello", "gloss": "int. 喂；哈罗" }],
+        })
+    );
+
+    let missing = &responses["\"three\""];
+    assert_eq!(missing["ok"], false);
+    assert_eq!(missing["error"]["kind"], "http_status");
+
+    assert_eq!(
+        responses["4"],
+        json!({
+            "id": 4,
+            "ok": false,
+            "error": { "kind": "invalid_request", "message": "Missing string field `word`" },
+        })
+    );
+    assert_eq!(responses["null"]["error"]["kind"], "invalid_request");
+}
+
+#[test]
+fn process_stays_up_between_requests_and_quits_on_request() {
+    let server = server();
+    let mut child = spawn(&server, "stdio-quit");
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    for id in 1..=2 {
+        writeln!(stdin, r#"{{"id":{id},"op":"translate","word":"hello"}}"#).unwrap();
+        stdin.flush().unwrap();
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["id"], id);
+        assert_eq!(response["ok"], true);
+    }
+
+    // stdin is still open; `quit` alone ends the process.
+    writeln!(stdin, r#"{{"op":"quit"}}"#).unwrap();
+    stdin.flush().unwrap();
+    assert!(child.wait().unwrap().success());
+    let mut rest = String::new();
+    stdout.read_line(&mut rest).unwrap();
+    assert_eq!(rest, "");
+}
+
+#[test]
+fn lines_that_are_not_utf8_are_answered_and_skipped() {
+    let server = server();
+    let mut child = spawn(&server, "stdio-utf8");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"{\"id\":1,\"op\":\"translate\",\"word\":\"\xff\"}\n{\"id\":2,\"op\":\"translate\",\"word\":\"hello\"}\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let responses: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 2, "{stdout}");
+    assert_eq!(responses[0]["id"], Value::Null);
+    assert_eq!(responses[0]["error"]["kind"], "invalid_request");
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["ok"], true);
+}
+
+#[test]
+fn stdio_rejects_a_word() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--stdio", "hello"])
+        .env("XDG_CONFIG_HOME", temp_dir("stdio-word-config"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("'--stdio' cannot be used with '[WORD]'"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{Suggestion, YdtClient, YdtError};
+
+const HEL_JSON: &str = r#"{
+  "result": {"msg": "success", "code": 200},
+  "data": {
+    "entries": [
+      {"explain": "int. 喂；哈罗", "entry": "hello"},
+      {"explain": "n. 地狱", "entry": "hell"},
+      {"entry": "helo"}
+    ],
+    "query": "hel",
+    "language": "en",
+    "type": "dict"
+  }
+}"#;
+
+fn client_for(server: &MockServer) -> YdtClient {
+    YdtClient::builder()
+        .suggest_url(format!("{}/suggest", server.url))
+        .build()
+}
+
+#[test]
+fn parses_suggestions() {
+    let server = MockServer::start(HEL_JSON);
+    let suggestions = client_for(&server).suggest("hel", 5).unwrap();
+
+    assert_eq!(
+        suggestions,
+        [
+            Suggestion {
+                word: "hello".to_string(),
+                gloss: "int. 喂；哈罗".to_string(),
+            },
+            Suggestion {
+                word: "hell".to_string(),
+                gloss: "n. 地狱".to_string(),
+            },
+            Suggestion {
+                word: "helo".to_string(),
+                gloss: String::new(),
+            },
+        ]
+    );
+    let request = server.next_request();
+    assert_eq!(request.target, "/suggest?q=hel&num=5&doctype=json&le=en");
+}
+
+#[test]
+fn results_are_capped_at_the_limit() {
+    let server = MockServer::start(HEL_JSON);
+    let suggestions = client_for(&server).suggest("hel", 2).unwrap();
+    assert_eq!(suggestions.len(), 2);
+}
+
+#[test]
+fn empty_responses_have_no_suggestions() {
+    for body in [
+        r#"{"result": {"msg": "not found", "code": 404}}"#,
+        r#"{"data": {"query": "qzx"}}"#,
+        r#"{"data": {"entries": []}}"#,
+    ] {
+        let server = MockServer::start(body);
+        assert_eq!(client_for(&server).suggest("qzx", 5).unwrap(), [], "{body}");
+    }
+}
+
+#[test]
+fn malformed_payloads_are_reported() {
+    for body in [
+        HELLO_HTML,
+        "[]",
+        r#"{"data": {"entries": "hello"}}"#,
+        r#"{"data": {"entries": [{"explain": "n. 地狱"}]}}"#,
+    ] {
+        let server = MockServer::start(body);
+        let err = client_for(&server).suggest("hel", 5).unwrap_err();
+        assert!(
+            matches!(err, YdtError::InvalidSuggestPayload(_)),
+            "{body}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn non_ascii_prefixes_are_encoded() {
+    let server =
+        MockServer::start(r#"{"data": {"entries": [{"entry": "你好", "explain": "hello"}]}}"#);
+    let suggestions = client_for(&server).suggest(" 你 ", 3).unwrap();
+
+    assert_eq!(suggestions[0].word, "你好");
+    assert_eq!(
+        server.next_request().target,
+        "/suggest?q=%E4%BD%A0&num=3&doctype=json&le=en"
+    );
+}
+
+#[test]
+fn suggest_command_lists_candidates() {
+    let server = MockServer::start(HEL_JSON);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["suggest", "--limit", "2", "hel"])
+        .env("YDT_SUGGEST_URL", format!("{}/suggest", server.url))
+        .env(
+            "XDG_CONFIG_HOME",
+            std::env::temp_dir().join("ydt-suggest-none"),
+        )
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "hello  int. 喂；哈罗\nhell   n. 地狱\n"
+    );
+    assert_eq!(
+        server.next_request().target,
+        "/suggest?q=hel&num=2&doctype=json&le=en"
+    );
+}
+
+#[test]
+fn suggest_command_prints_errors_as_json() {
+    let server = MockServer::start_with(|_| (404, "not found"));
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["suggest", "--json-errors", "--no-cookie-file", "hel"])
+        .env("YDT_SUGGEST_URL", format!("{}/suggest", server.url))
+        .env("XDG_CONFIG_HOME", common::temp_dir("suggest-json-errors"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let error: serde_json::Value = serde_json::from_str(&stderr).unwrap();
+    assert_eq!(error["error"]["kind"], "http_status", "{stderr}");
+    assert_eq!(error["error"]["word"], "hel", "{stderr}");
+    assert_eq!(error["error"]["status"], 404, "{stderr}");
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::process::Command;
+use std::sync::Arc;
+
+use common::{CountingFetcher, MockFetcher, MockServer, HELLO_HTML};
+use ydt::{parse_translation, YdtClient};
+
+const HELLLO_HTML: &str = include_str!("fixtures/helllo.html");
+
+#[test]
+fn suggestions_are_parsed() {
+    let translation = parse_translation("helllo", HELLLO_HTML).unwrap();
+
+    assert!(translation.is_empty());
+    assert_eq!(translation.suggestions, ["hello", "hellos"]);
+    assert_eq!(translation.corrected_from, None);
+}
+
+#[test]
+fn regular_page_has_no_suggestions() {
+    let translation = parse_translation("hello", HELLO_HTML).unwrap();
+    assert!(translation.suggestions.is_empty());
+}
+
+#[test]
+fn suggestions_are_returned_without_auto_correct() {
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("helllo", HELLLO_HTML));
+    let translation = client.translate("helllo").unwrap();
+
+    assert_eq!(translation.word, "helllo");
+    assert!(translation.is_empty());
+    assert_eq!(translation.suggestions, ["hello", "hellos"]);
+}
+
+#[test]
+fn auto_correct_looks_up_the_first_suggestion() {
+    let fetcher = MockFetcher::new()
+        .page("helllo", HELLLO_HTML)
+        .page("hello", HELLO_HTML);
+    let client = YdtClient::builder()
+        .fetcher(fetcher)
+        .auto_correct(true)
+        .build();
+    let (translation, html) = client.translate_with_html("helllo").unwrap();
+
+    assert_eq!(translation.word, "hello");
+    assert_eq!(translation.corrected_from.as_deref(), Some("helllo"));
+    assert_eq!(translation.entries[0].meaning, "你好；喂");
+    assert_eq!(html, HELLO_HTML);
+}
+
+#[test]
+fn auto_correct_follows_at_most_one_suggestion() {
+    // "hello" is answered with another suggestions page, which must not be followed.
+    let fetcher = Arc::new(CountingFetcher::new(
+        MockFetcher::new()
+            .page("helllo", HELLLO_HTML)
+            .page("hello", HELLLO_HTML),
+    ));
+    let client = YdtClient::builder()
+        .fetcher(fetcher.clone())
+        .auto_correct(true)
+        .build();
+    let translation = client.translate("helllo").unwrap();
+
+    assert_eq!(fetcher.fetches(), 2);
+    assert!(translation.is_empty());
+    assert_eq!(translation.word, "hello");
+    assert_eq!(translation.corrected_from.as_deref(), Some("helllo"));
+}
+
+#[test]
+fn cli_prints_suggestions_and_exits_with_no_results() {
+    let server = MockServer::start(HELLLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .arg("helllo")
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", common::temp_dir("suggestions-config"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Did you mean: hello, hellos?\n"
+    );
+}
@@ -0,0 +1,64 @@
+use ydt::parse_translation;
+use ydt::text::normalize;
+
+#[test]
+fn text_is_normalized() {
+    for (text, normalized) in [
+        ("hello", "hello"),
+        ("  你好；喂 \n", "你好；喂"),
+        ("int.\n      表示问候", "int. 表示问候"),
+        ("a\t\t b", "a b"),
+        ("a\u{a0}b", "a b"),
+        ("a\u{202f}\u{a0} b", "a b"),
+        ("你好\u{3000}世界", "你好 世界"),
+        ("\u{3000}学习\u{3000}", "学习"),
+        ("zero\u{200b}width", "zerowidth"),
+        ("\u{feff}bom", "bom"),
+        ("soft\u{ad}hyphen", "softhyphen"),
+        ("\u{202e}right\u{202c} \u{200f}to left", "right to left"),
+        ("\u{2066}isolate\u{2069}", "isolate"),
+        ("a&nbsp;b", "a b"),
+        ("a&#160;b", "a b"),
+        ("a&#xA0;b", "a b"),
+        ("&lt;n.&gt; &amp; &quot;v.&quot;", "<n.> & \"v.\""),
+        ("cafe\u{301}", "café"),
+        // Full-width letters are kept: only canonical equivalents are composed.
+        ("ＡＢＣ", "ＡＢＣ"),
+        ("AT&T", "AT&T"),
+        ("a & b", "a & b"),
+        ("&unknown;", "&unknown;"),
+        ("&#xZZ;", "&#xZZ;"),
+        ("&nbsp", "&nbsp"),
+        ("", ""),
+        (" \u{a0}\u{3000}\u{200b} ", ""),
+    ] {
+        assert_eq!(normalize(text), normalized, "{text:?}");
+    }
+}
+
+#[test]
+fn normalizing_is_idempotent() {
+    for text in ["  a&amp;b ", "x\u{a0}\u{3000}y", "e\u{301}\u{200d}"] {
+        let once = normalize(text);
+        assert_eq!(normalize(&once), once, "{text:?}");
+    }
+}
+
+#[test]
+fn scraped_fields_are_normalized() {
+    let html = "
+<div class=\"trans-container\">
+  <div class=\"per-phone\"><span>英</span><span class=\"phonetic\">/həˈləʊ/\u{200b}</span></div>
+</div>
+<div class=\"trans-container\">
+  <li class=\"word-exp\">
+    <span class=\"pos\">int.</span>
+    <span class=\"trans\">你好；&amp;nbsp;喂
+      <b>（用于问候）</b></span>
+  </li>
+</div>
+";
+    let translation = parse_translation("hello", html).unwrap();
+    assert_eq!(translation.phonetics[0].text, "/həˈləʊ/");
+    assert_eq!(translation.entries[0].meaning, "你好； 喂 （用于问候）");
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::fs;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{temp_dir, CountingFetcher, MockFetcher, MockServer, HELLO_HTML};
+use static_assertions::assert_impl_all;
+use ydt::{
+    HttpFetcher, RenderOptions, SelectorConfig, Selectors, Translation, YdtClient,
+    YdtClientBuilder, YdtError,
+};
+
+assert_impl_all!(YdtClient: Send, Sync);
+assert_impl_all!(YdtClientBuilder: Send, Sync);
+assert_impl_all!(HttpFetcher: Send, Sync);
+assert_impl_all!(Selectors: Send, Sync);
+assert_impl_all!(Translation: Send, Sync);
+assert_impl_all!(RenderOptions: Send, Sync);
+assert_impl_all!(YdtError: Send, Sync);
+
+const THREADS: usize = 32;
+const ROUNDS: usize = 3;
+
+/// Words served by [`fetcher`]; the last one is missing.
+const WORDS: [&str; 6] = ["hello", "run", "ran", "children", "画蛇添足", "xyzzy"];
+
+fn fetcher() -> MockFetcher {
+    MockFetcher::new()
+        .page("hello", HELLO_HTML)
+        .page("run", include_str!("fixtures/run.html"))
+        .page("ran", include_str!("fixtures/ran.html"))
+        .page("children", include_str!("fixtures/children.html"))
+        .page("画蛇添足", include_str!("fixtures/huashetianzu.html"))
+}
+
+type Outcome = Result<Translation, String>;
+
+/// Look every word up `ROUNDS` times from each of `THREADS` threads started
+/// together, returning what each thread got in the order of [`WORDS`].
+fn look_up_together(client: &YdtClient) -> Vec<Vec<Outcome>> {
+    let start = Barrier::new(THREADS);
+    thread::scope(|scope| {
+        let threads: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let start = &start;
+                scope.spawn(move || {
+                    start.wait();
+                    let mut outcomes = vec![None; WORDS.len()];
+                    // Each thread starts at a different word.
+                    for n in 0..ROUNDS * WORDS.len() {
+                        let index = (i + n) % WORDS.len();
+                        let outcome = client.translate(WORDS[index]).map_err(|e| e.to_string());
+                        if let Some(previous) = &outcomes[index] {
+                            assert_eq!(previous, &outcome, "{}", WORDS[index]);
+                        }
+                        outcomes[index] = Some(outcome);
+                    }
+                    outcomes.into_iter().map(Option::unwrap).collect()
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect()
+    })
+}
+
+#[test]
+fn concurrent_lookups_agree_with_sequential_ones() {
+    let interval = Duration::from_micros(200);
+    let counting = Arc::new(CountingFetcher::new(fetcher()));
+    let shared = YdtClient::builder()
+        .fetcher(Arc::clone(&counting))
+        .min_request_interval(interval)
+        .build();
+    let custom = YdtClient::builder()
+        .fetcher(fetcher())
+        .selectors(SelectorConfig::default())
+        .build();
+
+    let started = Instant::now();
+    let results = look_up_together(&shared);
+    let elapsed = started.elapsed();
+    let custom_results = look_up_together(&custom);
+
+    // The first lookups raced to compile the shared selectors; a client
+    // used from one thread now gives the same answers.
+    let expected: Vec<Outcome> = {
+        let client = YdtClient::with_fetcher(fetcher());
+        WORDS
+            .iter()
+            .map(|word| client.translate(word).map_err(|e| e.to_string()))
+            .collect()
+    };
+    assert!(expected[..5].iter().all(Result::is_ok), "{expected:?}");
+    assert!(expected[5].is_err());
+    for outcomes in results.iter().chain(&custom_results) {
+        assert_eq!(outcomes, &expected);
+    }
+
+    // The minimum interval held across threads.
+    let fetches = counting.fetches();
+    assert_eq!(fetches, THREADS * ROUNDS * WORDS.len());
+    assert!(
+        elapsed >= interval * (fetches as u32 - 1),
+        "{fetches} requests in {elapsed:?}"
+    );
+}
+
+#[test]
+fn lookups_finishing_together_save_cookies_once_at_a_time() {
+    let dir = temp_dir("thread_safety_cookies");
+    let path = dir.join("cookies.json");
+    let server = MockServer::start_with_headers(
+        &[("Set-Cookie", "visitor=abc123; Max-Age=3600; Path=/")],
+        |_| (200, HELLO_HTML),
+    );
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .cookie_store_path(&path)
+        .build();
+
+    let start = Barrier::new(THREADS);
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                start.wait();
+                client.translate("hello").unwrap();
+            });
+        }
+    });
+
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("abc123"), "{saved}");
+    let files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, ["cookies.json"]);
+}
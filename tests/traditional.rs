@@ -0,0 +1,104 @@
+#[cfg(feature = "http")]
+mod common;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockFetcher, MockServer};
+#[cfg(feature = "http")]
+use ydt::{render_conversion_notice, YdtClient};
+use ydt::{to_simplified, to_traditional};
+
+#[cfg(feature = "http")]
+const XUEXI_HTML: &str = include_str!("fixtures/xuexi_full.html");
+
+/// Common Traditional characters, each with its own Simplified form.
+const COMMON: &str = "學習國語說話們個來時會對這過開關長問間門見現為與當從動經書買賣東車馬鳥魚\
+    龍風飛電讀寫聽氣愛歡樂難興實體頭覺認識記謝請讓還進運達遠邊義業機樹權區醫華萬價錢銀鐵錯陽陰\
+    隊雙雞離雲靜題類顏願館飯點齊歲歸漢湯滿無燈獨環產畫盡眾確禮種筆節簡紅級紙細組結給綠網線練\
+    總舊藝藥處號蟲裝規視親觀計設證試詩該課調談論講議護變貴費資輕轉辦農連選郵鄉針錄閱隨險響頁\
+    順領顧顯飲養驗驚黃";
+
+#[test]
+fn common_characters_round_trip() {
+    for traditional in COMMON.chars().filter(|ch| !ch.is_whitespace()) {
+        let traditional = traditional.to_string();
+        let simplified = to_simplified(&traditional);
+        assert_ne!(simplified, traditional, "{traditional} is not converted");
+        assert_eq!(to_traditional(&simplified), traditional);
+    }
+}
+
+#[test]
+fn words_convert_character_by_character() {
+    assert_eq!(to_simplified("學習"), "学习");
+    assert_eq!(to_simplified("電腦"), "电脑");
+    // Simplified, shared and unknown characters are kept, so mixed input works.
+    assert_eq!(to_simplified("学習"), "学习");
+    assert_eq!(to_simplified("中文 C語言"), "中文 C语言");
+    assert_eq!(to_simplified("hello"), "hello");
+    assert_eq!(to_traditional("学习中文"), "學習中文");
+}
+
+#[test]
+fn shared_simplified_forms_convert_back_to_the_first() {
+    assert_eq!(to_simplified("頭髮"), "头发");
+    assert_eq!(to_simplified("發現"), "发现");
+    assert_eq!(to_traditional("发"), "發");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn client_looks_up_the_simplified_form() {
+    let fetcher = || MockFetcher::new().page("学习", XUEXI_HTML);
+
+    let client = YdtClient::builder()
+        .fetcher(fetcher())
+        .convert_traditional(true)
+        .build();
+    let translation = client.translate("學習").unwrap();
+    assert_eq!(translation.word, "学习");
+    assert_eq!(translation.converted_from.as_deref(), Some("學習"));
+    assert_eq!(
+        render_conversion_notice(&translation).as_deref(),
+        Some("學習 → 学习")
+    );
+
+    // Words already in Simplified Chinese are not marked.
+    let translation = client.translate("学习").unwrap();
+    assert_eq!(translation.converted_from, None);
+    assert_eq!(render_conversion_notice(&translation), None);
+
+    // Off by default.
+    let client = YdtClient::builder().fetcher(fetcher()).build();
+    assert!(client.translate("學習").is_err());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_converts_unless_told_not_to() {
+    let server = MockServer::start(XUEXI_HTML);
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_ydt"))
+            .args(["--no-mobile-fallback", "--format", "plain"])
+            .args(args)
+            .env("YDT_BASE_URL", format!("{}/result", server.url))
+            .env("XDG_CONFIG_HOME", temp_dir("traditional"))
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["學習"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("學習 → 学习\n"), "{stdout}");
+    let request = server.next_request();
+    assert!(request.target.contains("%E5%AD%A6%E4%B9%A0"), "{request:?}");
+
+    let output = run(&["--no-convert", "學習"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("學習 → 学习"), "{stdout}");
+    let request = server.next_request();
+    assert!(request.target.contains("%E5%AD%B8%E7%BF%92"), "{request:?}");
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+use std::thread;
+
+use common::{CountingFetcher, MockFetcher, HELLO_HTML};
+use ydt::config::Config;
+use ydt::{render_translation, RenderOptions, Translation, YdtClient, YdtError};
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Allocator counting the allocations of each thread.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of allocations made by `f` on this thread.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(value);
+    after - before
+}
+
+fn counting() -> Arc<CountingFetcher> {
+    Arc::new(CountingFetcher::new(
+        MockFetcher::new()
+            .page("hello", HELLO_HTML)
+            .page("run", include_str!("fixtures/run.html"))
+            .page("take", include_str!("fixtures/take_full.html")),
+    ))
+}
+
+#[test]
+fn repeated_lookups_share_one_translation() {
+    let fetcher = counting();
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    let first = client.translate_cached("hello").unwrap();
+    let second = client.translate_cached("  hello ").unwrap();
+
+    assert_eq!(fetcher.fetches(), 1);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(*first, client.translate("hello").unwrap());
+    assert_eq!(
+        render_translation(&first, &RenderOptions::default()),
+        render_translation(&second, &RenderOptions::default())
+    );
+}
+
+#[test]
+fn handles_on_other_threads_see_the_same_translation() {
+    let client = YdtClient::with_fetcher(counting());
+    let ours = client.translate_cached("hello").unwrap();
+
+    let theirs = thread::scope(|scope| {
+        scope
+            .spawn(|| client.translate_cached("hello").unwrap())
+            .join()
+            .unwrap()
+    });
+
+    assert!(Arc::ptr_eq(&ours, &theirs));
+    assert_eq!(ours.entries, theirs.entries);
+}
+
+#[test]
+fn translate_still_looks_up_every_time() {
+    let fetcher = counting();
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    client.translate_cached("hello").unwrap();
+    client.translate("hello").unwrap();
+    client.translate("hello").unwrap();
+
+    assert_eq!(fetcher.fetches(), 3);
+}
+
+#[test]
+fn failures_are_not_kept() {
+    let fetcher = counting();
+    let client = YdtClient::with_fetcher(Arc::clone(&fetcher));
+
+    assert!(client.translate_cached("xyzzy").is_err());
+    assert!(client.translate_cached("xyzzy").is_err());
+    assert!(matches!(
+        client.translate_cached("  "),
+        Err(YdtError::EmptyWord)
+    ));
+
+    assert_eq!(fetcher.fetches(), 2);
+}
+
+#[test]
+fn the_first_cached_is_dropped_when_full() {
+    let fetcher = counting();
+    let client = YdtClient::builder()
+        .fetcher(Arc::clone(&fetcher))
+        .cache_capacity(1)
+        .build();
+
+    for word in ["hello", "hello", "run", "run", "hello"] {
+        client.translate_cached(word).unwrap();
+    }
+    assert_eq!(fetcher.fetches(), 3);
+
+    client.clear_cache();
+    client.translate_cached("hello").unwrap();
+    assert_eq!(fetcher.fetches(), 4);
+}
+
+#[test]
+fn capacity_zero_keeps_nothing() {
+    let fetcher = counting();
+    let client = YdtClient::builder()
+        .fetcher(Arc::clone(&fetcher))
+        .cache_capacity(0)
+        .build();
+
+    let first = client.translate_cached("hello").unwrap();
+    let second = client.translate_cached("hello").unwrap();
+
+    assert_eq!(fetcher.fetches(), 2);
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert_eq!(first, second);
+}
+
+#[test]
+fn capacity_is_read_from_config() {
+    let fetcher = counting();
+    let config = Config::from_toml("cache_capacity = 0").unwrap();
+    let client = YdtClient::builder()
+        .config(&config)
+        .fetcher(Arc::clone(&fetcher))
+        .build();
+
+    client.translate_cached("hello").unwrap();
+    client.translate_cached("hello").unwrap();
+
+    assert_eq!(fetcher.fetches(), 2);
+}
+
+#[test]
+fn hits_allocate_less_than_a_copy() {
+    let client = YdtClient::with_fetcher(counting());
+    let cached = client.translate_cached("take").unwrap();
+
+    let hit = allocations(|| client.translate_cached("take").unwrap());
+    let copy = allocations(|| Translation::clone(&cached));
+
+    // Normalizing the word is all that allocates.
+    assert!(hit <= 2, "{hit}");
+    assert!(copy > 10 * hit, "{copy} against {hit}");
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, MockServer, HELLO_HTML};
+use ydt::{Accent, TranslationOptions, YdtClient};
+
+const TAKE_COLLINS_HTML: &str = include_str!("fixtures/take_collins.html");
+
+fn client(options: TranslationOptions) -> YdtClient {
+    YdtClient::builder()
+        .fetcher(MockFetcher::new().page("take", TAKE_COLLINS_HTML))
+        .translation_options(options)
+        .build()
+}
+
+#[test]
+fn call_options_take_precedence_over_the_client() {
+    let client = client(TranslationOptions {
+        accent: Some(Accent::Uk),
+        include_collins: Some(true),
+        ..TranslationOptions::default()
+    });
+    assert_eq!(client.translate("take").unwrap().collins.len(), 3);
+
+    let call = TranslationOptions {
+        include_collins: Some(false),
+        ..TranslationOptions::default()
+    };
+    let translation = client.translate_with("take", &call).unwrap();
+    assert!(translation.collins.is_empty());
+    // Fields left unset fall back to the client's options.
+    assert_eq!(translation.phonetics.len(), 1);
+    assert_eq!(translation.phonetics[0].label, "英");
+}
+
+#[test]
+fn client_options_take_precedence_over_the_defaults() {
+    let client = client(TranslationOptions {
+        max_definitions: Some(1),
+        ..TranslationOptions::default()
+    });
+    let translation = client
+        .translate_with("take", &TranslationOptions::default())
+        .unwrap();
+    assert_eq!(translation.entries.len(), 1);
+    assert_eq!(translation.entries[0].pos, "v.");
+}
+
+#[test]
+fn unset_options_use_the_defaults() {
+    let translation = client(TranslationOptions::default())
+        .translate_with("take", &TranslationOptions::default())
+        .unwrap();
+    assert_eq!(translation.phonetics.len(), 2);
+    assert_eq!(translation.entries.len(), 2);
+    assert!(translation.collins.is_empty());
+    assert!(translation.related.is_empty());
+}
+
+#[test]
+fn examples_can_be_left_out() {
+    let options = TranslationOptions {
+        include_collins: Some(true),
+        include_examples: Some(false),
+        ..TranslationOptions::default()
+    };
+    let translation = client(TranslationOptions::default())
+        .translate_with("take", &options)
+        .unwrap();
+    assert_eq!(translation.collins.len(), 3);
+    assert!(translation
+        .collins
+        .iter()
+        .all(|sense| sense.examples.is_empty()));
+}
+
+#[test]
+fn definitions_are_grouped_by_part_of_speech() {
+    let html = r#"
+<div class="trans-container"></div>
+<div class="trans-container">
+  <li class="word-exp"><span class="pos">v.</span><span class="trans">跑</span></li>
+  <li class="word-exp"><span class="pos">n.</span><span class="trans">跑步</span></li>
+  <li class="word-exp"><span class="pos">v.</span><span class="trans">经营</span></li>
+</div>
+"#;
+    let client = YdtClient::with_fetcher(MockFetcher::new().page("run", html));
+    let options = TranslationOptions {
+        group_by_pos: Some(true),
+        ..TranslationOptions::default()
+    };
+    let translation = client.translate_with("run", &options).unwrap();
+    let entries = translation
+        .entries
+        .iter()
+        .map(|entry| (entry.pos.as_str(), entry.meaning.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(entries, [("v.", "跑；经营"), ("n.", "跑步")]);
+    assert_eq!(client.translate("run").unwrap().entries.len(), 3);
+}
+
+#[test]
+fn lang_is_sent_per_call() {
+    let server = MockServer::start(HELLO_HTML);
+    let client = YdtClient::builder()
+        .base_url(format!("{}/result", server.url))
+        .mobile_fallback(false)
+        .build();
+
+    client.translate("hello").unwrap();
+    assert!(server.next_request().target.contains("lang=en"));
+
+    let options = TranslationOptions {
+        lang: Some("fr".to_string()),
+        ..TranslationOptions::default()
+    };
+    client.translate_with("hello", &options).unwrap();
+    assert!(server.next_request().target.contains("lang=fr"));
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use common::{MockFetcher, HELLO_HTML};
+use reqwest::StatusCode;
+use ydt::{Entry, EntrySource, Translation, YdtClient};
+
+#[test]
+fn formats_mixed_batch_one_row_per_word() {
+    let client = YdtClient::with_fetcher(
+        MockFetcher::new()
+            .page("hello", HELLO_HTML)
+            .page(
+                "学习",
+                r#"<li class="word-exp-ce mcols-layout"><a class="point">study</a></li>"#,
+            )
+            .status("blocked", StatusCode::FORBIDDEN),
+    );
+
+    let rows: Vec<String> = ["hello", "blocked", "学习"]
+        .iter()
+        .map(|word| ydt::render_tsv_row(word, &client.translate(word)))
+        .collect();
+
+    assert_eq!(
+        rows,
+        [
+            "hello\t英 /həˈləʊ/ 美 /həˈloʊ/\tint.: 你好；喂; n.: 表示问候",
+            "blocked\t\tERROR: Request for \"blocked\" failed with status: 403 Forbidden",
+            "学习\t\tstudy",
+        ]
+    );
+    assert_eq!(ydt::TSV_HEADER.split('\t').count(), 3);
+}
+
+#[test]
+fn sanitizes_tabs_and_newlines_inside_fields() {
+    let translation = Translation {
+        word: "tab\tword".to_string(),
+        phonetics: Vec::new(),
+        entries: vec![Entry {
+            pos: "n.".to_string(),
+            meaning: "first\tline\nsecond line".to_string(),
+            source: EntrySource::Concise,
+        }],
+        ..Translation::default()
+    };
+
+    let row = ydt::render_tsv_row("tab\tword", &Ok(translation));
+    assert_eq!(row, "tab word\t\tn.: first line second line");
+    assert_eq!(row.matches('\t').count(), 2);
+}
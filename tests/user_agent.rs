@@ -0,0 +1,96 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, HELLO_HTML};
+use ydt::{Fetcher, HttpFetcher, UaStrategy, UserAgentKind, YdtError};
+
+fn fetcher(server: &MockServer) -> HttpFetcher {
+    HttpFetcher::default().base_url(format!("{}/result", server.url))
+}
+
+fn user_agents(server: &MockServer) -> Vec<String> {
+    server
+        .pending_requests()
+        .into_iter()
+        .map(|request| request.headers["user-agent"].clone())
+        .collect()
+}
+
+#[test]
+fn project_user_agent_is_sent_first() {
+    let server = MockServer::start(HELLO_HTML);
+    let (_, info) = fetcher(&server).fetch_with_info("hello").unwrap();
+
+    assert_eq!(info.user_agent, Some(UserAgentKind::Project));
+    let sent = user_agents(&server);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].starts_with("ydt/"), "{sent:?}");
+}
+
+#[test]
+fn rejected_project_user_agent_falls_back_to_browser() {
+    let server = MockServer::start_with(|request| {
+        if request.headers["user-agent"].starts_with("ydt/") {
+            (403, "")
+        } else {
+            (200, HELLO_HTML)
+        }
+    });
+    let (html, info) = fetcher(&server).fetch_with_info("hello").unwrap();
+
+    assert_eq!(html, HELLO_HTML);
+    assert_eq!(info.user_agent, Some(UserAgentKind::Browser));
+    let sent = user_agents(&server);
+    assert_eq!(sent.len(), 2);
+    assert!(sent[0].starts_with("ydt/"), "{sent:?}");
+    assert!(sent[1].starts_with("Mozilla/"), "{sent:?}");
+}
+
+#[test]
+fn rate_limits_are_not_retried_with_the_browser_user_agent() {
+    let server = MockServer::start_with_headers(&[("Retry-After", "30")], |_| (429, ""));
+    let err = fetcher(&server).fetch_with_info("hello").unwrap_err();
+
+    assert!(
+        matches!(
+            err,
+            YdtError::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            } if retry_after == Duration::from_secs(30)
+        ),
+        "{err:?}"
+    );
+    let sent = user_agents(&server);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].starts_with("ydt/"), "{sent:?}");
+}
+
+#[test]
+fn browser_only_sends_a_single_browser_request() {
+    let server = MockServer::start(HELLO_HTML);
+    let (_, info) = fetcher(&server)
+        .ua_strategy(UaStrategy::BrowserOnly)
+        .fetch_with_info("hello")
+        .unwrap();
+
+    assert_eq!(info.user_agent, Some(UserAgentKind::Browser));
+    let sent = user_agents(&server);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].starts_with("Mozilla/"), "{sent:?}");
+}
+
+#[test]
+fn custom_user_agent_has_no_fallback() {
+    let server = MockServer::start_with(|_| (403, ""));
+    let err = fetcher(&server)
+        .user_agent("my-tool/1.0")
+        .fetch_with_info("hello")
+        .unwrap_err();
+
+    assert!(err.to_string().contains("403"), "{err}");
+    assert_eq!(user_agents(&server), ["my-tool/1.0"]);
+}
@@ -0,0 +1,14 @@
+//! Parsing in a browser: `wasm-pack test --headless --firefox --no-default-features`.
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn parses_a_result_page() {
+    let out =
+        ydt::parse_translation_from_html("hello", include_str!("fixtures/hello.html")).unwrap();
+
+    assert_eq!(out, "英 /həˈləʊ/ 美 /həˈloʊ/\nint.: 你好；喂\nn.: 表示问候");
+}
@@ -0,0 +1,158 @@
+// The clipboard watching logic of the binary, driven by scripted contents.
+#[allow(dead_code)]
+#[path = "../src/watch.rs"]
+mod watch;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use watch::{ClipboardSource, Watcher};
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Clipboard contents set by the test.
+#[derive(Clone)]
+struct Scripted(Rc<RefCell<Result<String, String>>>);
+
+impl Scripted {
+    fn set(&self, text: &str) {
+        *self.0.borrow_mut() = Ok(text.to_string());
+    }
+
+    fn fail(&self, message: &str) {
+        *self.0.borrow_mut() = Err(message.to_string());
+    }
+}
+
+impl Default for Scripted {
+    fn default() -> Self {
+        Self(Rc::new(RefCell::new(Ok(String::new()))))
+    }
+}
+
+impl ClipboardSource for Scripted {
+    fn text(&mut self) -> Result<String, String> {
+        self.0.borrow().clone()
+    }
+}
+
+fn start(initial: &str, max_len: usize) -> (Scripted, Watcher<Scripted>) {
+    let clipboard = Scripted::default();
+    clipboard.set(initial);
+    let watcher = Watcher::start(clipboard.clone(), max_len, DEBOUNCE).unwrap();
+    (clipboard, watcher)
+}
+
+/// Poll at `start + millis`.
+fn poll(watcher: &mut Watcher<Scripted>, start: Instant, millis: u64) -> Option<String> {
+    watcher.poll(start + Duration::from_millis(millis)).unwrap()
+}
+
+#[test]
+fn initial_contents_are_not_looked_up() {
+    let (_, mut watcher) = start("hello", 50);
+    let now = Instant::now();
+    assert_eq!(poll(&mut watcher, now, 0), None);
+    assert_eq!(poll(&mut watcher, now, 1000), None);
+}
+
+#[test]
+fn new_text_is_looked_up_once_it_settles() {
+    let (clipboard, mut watcher) = start("", 50);
+    let now = Instant::now();
+    clipboard.set("  serendipity\n");
+    assert_eq!(poll(&mut watcher, now, 0), None);
+    assert_eq!(poll(&mut watcher, now, 250), None);
+    assert_eq!(poll(&mut watcher, now, 500).as_deref(), Some("serendipity"));
+    assert_eq!(poll(&mut watcher, now, 750), None);
+}
+
+#[test]
+fn rapid_changes_are_debounced() {
+    let (clipboard, mut watcher) = start("", 50);
+    let now = Instant::now();
+    for (millis, text) in [(0, "ser"), (250, "serend"), (500, "serendip")] {
+        clipboard.set(text);
+        assert_eq!(poll(&mut watcher, now, millis), None, "{text}");
+    }
+    clipboard.set("serendipity");
+    assert_eq!(poll(&mut watcher, now, 750), None);
+    assert_eq!(
+        poll(&mut watcher, now, 1250).as_deref(),
+        Some("serendipity")
+    );
+}
+
+#[test]
+fn repeats_are_skipped() {
+    let (clipboard, mut watcher) = start("", 50);
+    let now = Instant::now();
+    clipboard.set("hello");
+    poll(&mut watcher, now, 0);
+    assert_eq!(poll(&mut watcher, now, 500).as_deref(), Some("hello"));
+
+    // Copied again, or with different line breaks.
+    clipboard.set("hello\n");
+    poll(&mut watcher, now, 1000);
+    assert_eq!(poll(&mut watcher, now, 1500), None);
+
+    clipboard.set("in  spite\nof");
+    poll(&mut watcher, now, 2000);
+    assert_eq!(
+        poll(&mut watcher, now, 2500).as_deref(),
+        Some("in spite of")
+    );
+    clipboard.set("in spite of");
+    poll(&mut watcher, now, 3000);
+    assert_eq!(poll(&mut watcher, now, 3500), None);
+}
+
+#[test]
+fn text_copied_again_after_other_text_is_looked_up() {
+    let (clipboard, mut watcher) = start("", 50);
+    let now = Instant::now();
+    for (millis, text) in [(0, "hello"), (1000, "world"), (2000, "hello")] {
+        clipboard.set(text);
+        poll(&mut watcher, now, millis);
+        assert_eq!(poll(&mut watcher, now, millis + 500).as_deref(), Some(text));
+    }
+}
+
+#[test]
+fn long_and_empty_text_is_skipped() {
+    let (clipboard, mut watcher) = start("hello", 10);
+    let now = Instant::now();
+    clipboard.set("far too long to look up");
+    poll(&mut watcher, now, 0);
+    assert_eq!(poll(&mut watcher, now, 500), None);
+
+    clipboard.set(" \n ");
+    poll(&mut watcher, now, 1000);
+    assert_eq!(poll(&mut watcher, now, 1500), None);
+
+    // The length counts characters, not bytes.
+    clipboard.set("一二三四五六七八九十");
+    poll(&mut watcher, now, 2000);
+    assert_eq!(
+        poll(&mut watcher, now, 2500).as_deref(),
+        Some("一二三四五六七八九十")
+    );
+}
+
+#[test]
+fn read_failures_are_reported() {
+    let clipboard = Scripted::default();
+    clipboard.fail("no clipboard");
+    assert_eq!(
+        Watcher::start(clipboard.clone(), 50, DEBOUNCE)
+            .err()
+            .as_deref(),
+        Some("no clipboard")
+    );
+
+    clipboard.set("");
+    let mut watcher = Watcher::start(clipboard.clone(), 50, DEBOUNCE).unwrap();
+    clipboard.fail("no clipboard");
+    assert!(watcher.poll(Instant::now()).is_err());
+}
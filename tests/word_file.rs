@@ -0,0 +1,131 @@
+#[cfg(feature = "http")]
+mod common;
+#[allow(dead_code)]
+#[path = "../src/word_file.rs"]
+mod word_file;
+
+#[cfg(feature = "http")]
+use std::process::Command;
+
+#[cfg(feature = "http")]
+use common::{temp_dir, MockServer, HELLO_HTML};
+use word_file::{parse, LineWarning, Query};
+
+fn query(word: &str, lang: Option<&str>) -> Query {
+    Query {
+        word: word.to_string(),
+        lang: lang.map(str::to_string),
+    }
+}
+
+#[test]
+fn comments_and_blank_lines_are_skipped() {
+    let list = parse("# vocabulary\n\nhello\n   \n  # indented comment\nworld\n");
+    assert_eq!(list.queries, [query("hello", None), query("world", None)]);
+    assert_eq!(list.lines, [0, 1]);
+    assert!(list.warnings.is_empty());
+}
+
+#[test]
+fn inline_comments_need_whitespace_before_them() {
+    let list = parse("run # irregular\nC#\nF# #language\nice cream\t# two words\n");
+    let words: Vec<_> = list
+        .queries
+        .iter()
+        .map(|query| query.word.as_str())
+        .collect();
+    assert_eq!(words, ["run", "C#", "F#", "ice cream"]);
+}
+
+#[test]
+fn language_overrides_are_read() {
+    let list = parse("amour @fr\nsake @JA  # rice wine\nhello\n");
+    assert_eq!(
+        list.queries,
+        [
+            query("amour", Some("fr")),
+            query("sake", Some("ja")),
+            query("hello", None),
+        ]
+    );
+    assert_eq!(list.queries[0].to_string(), "amour @fr");
+    assert_eq!(list.queries[2].to_string(), "hello");
+}
+
+#[test]
+fn malformed_overrides_skip_only_their_line() {
+    let list = parse("hello\nbonjour @fr!\nmerci @\nguten tag @german\nworld\n");
+    assert_eq!(list.queries, [query("hello", None), query("world", None)]);
+    let lines: Vec<_> = list.warnings.iter().map(|warning| warning.line).collect();
+    assert_eq!(lines, [2, 3, 4]);
+    assert_eq!(
+        list.warnings[0],
+        LineWarning {
+            line: 2,
+            message:
+                "malformed language override `@fr!`, expected two or three letters such as `@fr`"
+                    .to_string(),
+        }
+    );
+}
+
+#[test]
+fn duplicates_are_looked_up_once() {
+    let list = parse("hello\nworld\nhello  # again\namour @fr\namour\namour @fr\n");
+    assert_eq!(
+        list.queries,
+        [
+            query("hello", None),
+            query("world", None),
+            query("amour", Some("fr")),
+            query("amour", None),
+        ]
+    );
+    assert_eq!(list.lines, [0, 1, 0, 2, 3, 2]);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn cli_looks_up_each_word_of_the_file_once() {
+    let server = MockServer::start(HELLO_HTML);
+    let output = Command::new(env!("CARGO_BIN_EXE_ydt"))
+        .args(["--no-mobile-fallback", "--format", "plain", "--from"])
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/words.txt"
+        ))
+        .env("YDT_BASE_URL", format!("{}/result", server.url))
+        .env("XDG_CONFIG_HOME", temp_dir("word_file"))
+        .env("XDG_CACHE_HOME", temp_dir("word_file_cache"))
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let headers: Vec<_> = stdout
+        .split("\n\n")
+        .map(|block| block.lines().next().unwrap())
+        .collect();
+    assert_eq!(headers, ["hello", "run", "amour @fr", "hello", "C#"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("words.txt:7: malformed language override `@fr!`"),
+        "{stderr}"
+    );
+
+    let mut targets: Vec<_> = server
+        .pending_requests()
+        .into_iter()
+        .map(|request| request.target)
+        .collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        [
+            "/result?word=C%23&lang=en",
+            "/result?word=amour&lang=fr",
+            "/result?word=hello&lang=en",
+            "/result?word=run&lang=en",
+        ]
+    );
+}
@@ -0,0 +1,221 @@
+//! Python bindings for `ydt`, built with [maturin](https://www.maturin.rs/).
+//!
+//! ```python
+//! import ydt
+//!
+//! ydt.translate("hello")["entries"][0]
+//! # {'pos': 'int.', 'meaning': '你好；喂'}
+//!
+//! client = ydt.Client(timeout=5, proxy="http://127.0.0.1:8080")
+//! client.translate_many(["hello", "world"])
+//! ```
+//!
+//! Errors are raised as `ydt.YdtError`, or one of its subclasses
+//! `NetworkError`, `NotFound` and `Blocked`. Lookups release the GIL.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use ydt::{Fetcher, Layout, StatusCode, Translation, YdtClient, DEFAULT_CONCURRENCY};
+
+create_exception!(ydt, YdtError, PyException, "A lookup failed.");
+create_exception!(
+    ydt,
+    NetworkError,
+    YdtError,
+    "No response was received, e.g. the connection failed or timed out."
+);
+create_exception!(ydt, NotFound, YdtError, "Youdao has no page for the word.");
+create_exception!(
+    ydt,
+    Blocked,
+    YdtError,
+    "Youdao refused the request or rate-limited it; try again later."
+);
+
+/// The Python exception for `err`.
+fn to_py_err(err: ydt::YdtError) -> PyErr {
+    let message = err.to_string();
+    let blocked = matches!(
+        err.status_code(),
+        Some(StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS)
+    ) || matches!(err, ydt::YdtError::CircuitOpen { .. });
+    if err.is_not_found() {
+        NotFound::new_err(message)
+    } else if err.is_network() {
+        NetworkError::new_err(message)
+    } else if blocked {
+        Blocked::new_err(message)
+    } else {
+        YdtError::new_err(message)
+    }
+}
+
+/// `translation` as a dict with the fields of `ydt::Translation`.
+fn to_dict<'py>(py: Python<'py>, translation: &Translation) -> PyResult<Bound<'py, PyDict>> {
+    let phonetics = PyList::empty(py);
+    for phonetic in &translation.phonetics {
+        let item = PyDict::new(py);
+        item.set_item("label", &phonetic.label)?;
+        item.set_item("text", &phonetic.text)?;
+        phonetics.append(item)?;
+    }
+    let entries = PyList::empty(py);
+    for entry in &translation.entries {
+        let item = PyDict::new(py);
+        item.set_item("pos", &entry.pos)?;
+        item.set_item("meaning", &entry.meaning)?;
+        entries.append(item)?;
+    }
+    let layout = translation.layout.map(|layout| match layout {
+        Layout::EnglishChinese => "english_chinese",
+        Layout::ChineseEnglish => "chinese_english",
+        Layout::Pinyin => "pinyin",
+    });
+
+    let dict = PyDict::new(py);
+    dict.set_item("word", &translation.word)?;
+    dict.set_item("phonetics", phonetics)?;
+    dict.set_item("entries", entries)?;
+    dict.set_item("layout", layout)?;
+    dict.set_item("headword", &translation.headword)?;
+    dict.set_item("lemma_note", &translation.lemma_note)?;
+    dict.set_item("frequency", translation.frequency)?;
+    dict.set_item("suggestions", &translation.suggestions)?;
+    Ok(dict)
+}
+
+/// Serves pages given from Python; other words fail with `404 Not Found`.
+struct PagesFetcher {
+    pages: HashMap<String, String>,
+    statuses: HashMap<String, u16>,
+}
+
+impl Fetcher for PagesFetcher {
+    fn fetch(&self, word: &str) -> Result<String, ydt::YdtError> {
+        if let Some(html) = self.pages.get(word) {
+            return Ok(html.clone());
+        }
+        let status = self.statuses.get(word).copied().unwrap_or(404);
+        Err(ydt::YdtError::HttpStatus {
+            word: word.to_string(),
+            url: format!("mock://{word}"),
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::NOT_FOUND),
+        })
+    }
+}
+
+/// A Youdao client; reuse one for many lookups.
+///
+/// `timeout` is in seconds. `cache_dir` keeps cookies and the rate-limit
+/// cooldown between runs, like `~/.cache/ydt` for the command line.
+#[pyclass(frozen, module = "ydt")]
+struct Client {
+    inner: YdtClient,
+}
+
+#[pymethods]
+impl Client {
+    #[new]
+    #[pyo3(signature = (*, timeout=None, proxy=None, cache_dir=None))]
+    fn new(
+        timeout: Option<f64>,
+        proxy: Option<String>,
+        cache_dir: Option<PathBuf>,
+    ) -> PyResult<Self> {
+        let mut builder = YdtClient::builder();
+        if let Some(timeout) = timeout {
+            let timeout = Duration::try_from_secs_f64(timeout)
+                .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?;
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(dir) = cache_dir {
+            builder = builder
+                .cookie_store_path(dir.join("cookies.json"))
+                .cooldown_path(dir.join("cooldown"));
+        }
+        Ok(Self {
+            inner: builder.build(),
+        })
+    }
+
+    /// A client serving `pages` (word to HTML) without network access, for
+    /// tests. Words in `statuses` fail with that HTTP status, others with 404.
+    #[staticmethod]
+    #[pyo3(signature = (pages, statuses=None))]
+    fn _mock(pages: HashMap<String, String>, statuses: Option<HashMap<String, u16>>) -> Self {
+        Self {
+            inner: YdtClient::with_fetcher(PagesFetcher {
+                pages,
+                statuses: statuses.unwrap_or_default(),
+            }),
+        }
+    }
+
+    /// Look `word` up and return the translation as a dict.
+    fn translate<'py>(&self, py: Python<'py>, word: &str) -> PyResult<Bound<'py, PyDict>> {
+        let translation = py
+            .detach(|| self.inner.translate(word))
+            .map_err(to_py_err)?;
+        to_dict(py, &translation)
+    }
+
+    /// Look `words` up in parallel. Returns a list in the same order, holding
+    /// a dict for each word found and the exception for each lookup that
+    /// failed.
+    #[pyo3(signature = (words, concurrency=DEFAULT_CONCURRENCY))]
+    fn translate_many<'py>(
+        &self,
+        py: Python<'py>,
+        words: Vec<String>,
+        concurrency: usize,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let results = py.detach(|| self.inner.translate_many(&words, concurrency));
+        let list = PyList::empty(py);
+        for result in results {
+            match result {
+                Ok(translation) => list.append(to_dict(py, &translation)?)?,
+                Err(err) => list.append(to_py_err(err).into_value(py))?,
+            }
+        }
+        Ok(list)
+    }
+}
+
+/// Look `word` up with default settings and return the translation as a dict.
+#[pyfunction]
+fn translate<'py>(py: Python<'py>, word: &str) -> PyResult<Bound<'py, PyDict>> {
+    let translation = py.detach(|| ydt::translate(word)).map_err(to_py_err)?;
+    to_dict(py, &translation)
+}
+
+/// Look `words` up with default settings; see `Client.translate_many`.
+#[pyfunction]
+fn translate_many<'py>(py: Python<'py>, words: Vec<String>) -> PyResult<Bound<'py, PyList>> {
+    Client {
+        inner: YdtClient::new(),
+    }
+    .translate_many(py, words, DEFAULT_CONCURRENCY)
+}
+
+#[pymodule]
+#[pyo3(name = "ydt")]
+fn ydt_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("YdtError", py.get_type::<YdtError>())?;
+    m.add("NetworkError", py.get_type::<NetworkError>())?;
+    m.add("NotFound", py.get_type::<NotFound>())?;
+    m.add("Blocked", py.get_type::<Blocked>())?;
+    m.add_class::<Client>()?;
+    m.add_function(wrap_pyfunction!(translate, m)?)?;
+    m.add_function(wrap_pyfunction!(translate_many, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+"""Tests for the Python bindings, run with `maturin develop && pytest`.
+
+Pages come from `Client._mock`, so no requests are made.
+"""
+
+import threading
+from pathlib import Path
+
+import pytest
+
+import ydt
+
+HELLO_HTML = (Path(__file__).parents[2] / "tests" / "fixtures" / "hello.html").read_text()
+
+
+@pytest.fixture
+def client():
+    return ydt.Client._mock(
+        {"hello": HELLO_HTML},
+        statuses={"blocked": 403, "busy": 503},
+    )
+
+
+def test_translate_returns_a_dict(client):
+    translation = client.translate("hello")
+
+    assert translation["word"] == "hello"
+    assert translation["phonetics"][0] == {"label": "英", "text": "/həˈləʊ/"}
+    assert translation["entries"][0] == {"pos": "int.", "meaning": "你好；喂"}
+    assert translation["layout"] == "english_chinese"
+    assert translation["suggestions"] == []
+
+
+def test_words_are_normalized(client):
+    assert client.translate("  hello \n")["word"] == "hello"
+
+
+def test_errors_map_to_exception_classes(client):
+    with pytest.raises(ydt.NotFound, match="404 Not Found"):
+        client.translate("missing")
+    with pytest.raises(ydt.Blocked, match="403 Forbidden"):
+        client.translate("blocked")
+    with pytest.raises(ydt.YdtError, match="503 Service Unavailable") as excinfo:
+        client.translate("busy")
+    assert type(excinfo.value) is ydt.YdtError
+    with pytest.raises(ydt.YdtError, match="empty"):
+        client.translate(" ")
+
+
+def test_exceptions_share_a_base_class():
+    for error in (ydt.NetworkError, ydt.NotFound, ydt.Blocked):
+        assert issubclass(error, ydt.YdtError)
+        assert issubclass(error, Exception)
+
+
+def test_network_errors():
+    # Nothing listens on port 9 (discard) of localhost.
+    client = ydt.Client(timeout=2, proxy="http://127.0.0.1:9")
+
+    with pytest.raises(ydt.NetworkError):
+        client.translate("hello")
+
+
+def test_translate_many_keeps_the_order_and_returns_errors(client):
+    results = client.translate_many(["missing", "hello", "blocked"], concurrency=2)
+
+    assert isinstance(results[0], ydt.NotFound)
+    assert results[1]["word"] == "hello"
+    assert isinstance(results[2], ydt.Blocked)
+
+
+def test_clients_can_be_shared_between_threads(client):
+    results = []
+    threads = [
+        threading.Thread(target=lambda: results.append(client.translate("hello")))
+        for _ in range(4)
+    ]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert len(results) == 4
+
+
+def test_client_options_are_validated(tmp_path):
+    ydt.Client(timeout=1.5, cache_dir=tmp_path)
+    with pytest.raises(ValueError):
+        ydt.Client(timeout=-1)
+    with pytest.raises(TypeError):
+        ydt.Client(5)